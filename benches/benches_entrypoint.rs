@@ -5,4 +5,7 @@ use criterion::criterion_main;
 criterion_main! {
     benchmarks::multiple_fields::multiple_fields,
     benchmarks::single_buffer::single_buffer,
+    benchmarks::into_vec::into_vec_group,
+    benchmarks::buf_reader::buf_reader,
+    benchmarks::lazy::lazy,
 }
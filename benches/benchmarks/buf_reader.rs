@@ -0,0 +1,69 @@
+use criterion::{black_box, Bencher, Criterion, criterion_group};
+use binext::{BinaryBufReader, BinaryRead, BinaryWrite};
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+const RECORDS: usize = 256;
+
+/// A 12 KiB record, straddling the 8 KiB buffer of BufReader.
+#[allow(unused)]
+struct Awkward {
+    id: u64,
+    samples: [u32; 3070],
+}
+
+fn records_file() -> PathBuf {
+    let path = std::env::temp_dir().join("binext_bench_buf_reader.bin");
+    let mut file = File::create(&path).unwrap();
+
+    for id in 0..RECORDS as u64 {
+        file.write_binary(&Awkward { id, samples: [id as u32; 3070] }).unwrap();
+    }
+
+    path
+}
+
+fn default_buf_reader(b: &mut Bencher) {
+    let path = records_file();
+
+    b.iter(|| {
+        let mut reader = BufReader::new(File::open(&path).unwrap());
+
+        for _ in 0..RECORDS {
+            black_box(reader.read_binary::<Awkward>().unwrap());
+        }
+    });
+}
+
+fn binary_buf_reader(b: &mut Bencher) {
+    let path = records_file();
+
+    b.iter(|| {
+        let mut reader = BinaryBufReader::<Awkward, _>::new(File::open(&path).unwrap());
+
+        for _ in 0..RECORDS {
+            black_box(reader.read_record().unwrap());
+        }
+    });
+}
+
+fn binary_buf_reader_bytes(b: &mut Bencher) {
+    let path = records_file();
+
+    b.iter(|| {
+        let mut reader = BinaryBufReader::<Awkward, _>::new(File::open(&path).unwrap());
+
+        for _ in 0..RECORDS {
+            black_box(reader.record_bytes().unwrap());
+        }
+    });
+}
+
+fn bench_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("12 KiB records from a file");
+
+    group.bench_function("BufReader", default_buf_reader);
+    group.bench_function("BinaryBufReader", binary_buf_reader);
+    group.bench_function("BinaryBufReader record_bytes", binary_buf_reader_bytes);
+}
+
+criterion_group!(buf_reader, bench_group);
@@ -0,0 +1,57 @@
+use criterion::{black_box, Bencher, Criterion, criterion_group};
+use binext::{write_into_vec, BinaryWrite};
+
+const RECORDS: usize = 4096;
+
+#[allow(unused)]
+#[derive(Clone, Copy)]
+struct Small {
+    id: u32,
+    value: f32,
+    timestamp: u64,
+}
+
+fn records() -> Vec<Small> {
+    (0..RECORDS as u32)
+        .map(|id| Small { id, value: id as f32 * 0.5, timestamp: id as u64 * 1000 })
+        .collect()
+}
+
+fn write_trait(b: &mut Bencher) {
+    let records = records();
+    let mut buf = Vec::with_capacity(RECORDS * std::mem::size_of::<Small>());
+
+    b.iter(|| {
+        buf.clear();
+
+        for record in &records {
+            buf.write_binary(black_box(record)).unwrap();
+        }
+
+        black_box(&buf);
+    });
+}
+
+fn into_vec(b: &mut Bencher) {
+    let records = records();
+    let mut buf = Vec::with_capacity(RECORDS * std::mem::size_of::<Small>());
+
+    b.iter(|| {
+        buf.clear();
+
+        for record in &records {
+            write_into_vec(&mut buf, black_box(record));
+        }
+
+        black_box(&buf);
+    });
+}
+
+fn bench_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Small records into a Vec");
+
+    group.bench_function("write_binary", write_trait);
+    group.bench_function("write_into_vec", into_vec);
+}
+
+criterion_group!(into_vec_group, bench_group);
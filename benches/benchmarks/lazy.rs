@@ -0,0 +1,73 @@
+use criterion::{black_box, Bencher, Criterion, criterion_group};
+use binext::{BinaryRead, BinaryWrite, LazyStruct};
+use std::{io::Cursor, mem::{offset_of, size_of}};
+
+const RECORDS: usize = 4096;
+
+/// A 1 KiB record, of which the filter only looks at two fields.
+#[allow(unused)]
+struct Trade {
+    id: u64,
+    venue: u32,
+    quantity: u32,
+    details: [u64; 126],
+}
+
+fn records() -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(RECORDS * size_of::<Trade>());
+
+    for id in 0..RECORDS as u64 {
+        let trade = Trade { id, venue: id as u32 % 16, quantity: id as u32 % 1000, details: [id; 126] };
+        bytes.write_binary(&trade).unwrap();
+    }
+
+    bytes
+}
+
+fn read_binary(b: &mut Bencher) {
+    let bytes = records();
+
+    b.iter(|| {
+        let mut cursor = Cursor::new(black_box(&bytes));
+        let mut kept = 0;
+
+        for _ in 0..RECORDS {
+            let trade = cursor.read_binary::<Trade>().unwrap();
+
+            if trade.venue == 3 && trade.quantity > 900 {
+                kept += black_box(trade).id;
+            }
+        }
+
+        kept
+    });
+}
+
+fn lazy_struct(b: &mut Bencher) {
+    let bytes = records();
+
+    b.iter(|| {
+        let mut kept = 0;
+
+        for record in black_box(&bytes).chunks_exact(size_of::<Trade>()) {
+            let lazy = LazyStruct::<Trade>::new(record).unwrap();
+
+            if lazy.get::<u32>(offset_of!(Trade, venue)).unwrap() == 3
+                && lazy.get::<u32>(offset_of!(Trade, quantity)).unwrap() > 900
+            {
+                kept += black_box(lazy.materialize()).id;
+            }
+        }
+
+        kept
+    });
+}
+
+fn bench_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Filtering 1 KiB records on two fields");
+
+    group.bench_function("read_binary", read_binary);
+    group.bench_function("LazyStruct", lazy_struct);
+}
+
+criterion_group!(lazy, bench_group);
@@ -1,13 +1,16 @@
 use rand::Fill;
 
+pub mod buf_reader;
+pub mod into_vec;
+pub mod lazy;
 pub mod multiple_fields;
 pub mod single_buffer;
 
-pub(self) trait FromArray<T, Args, const SIZE: usize> {
+trait FromArray<T, Args, const SIZE: usize> {
     fn from_array(array: [T; SIZE], args: Args) -> Self;
 }
 
-pub(self) fn fill<I, T, Args, const SIZE: usize>(args: Args) -> I
+fn fill<I, T, Args, const SIZE: usize>(args: Args) -> I
 where
     I: FromArray<T, Args, SIZE>,
     T: Default + Copy,
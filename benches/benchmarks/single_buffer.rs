@@ -1,9 +1,9 @@
-use std::io::{Cursor, Read, Write};
-use criterion::{Bencher, black_box, Criterion, criterion_group};
-use rand::Rng;
+use std::io::Cursor;
+use criterion::{Bencher, Criterion, criterion_group};
 use binext::{BinaryRead, BinaryWrite};
 use super::{FromArray, fill};
 
+#[allow(unused)]
 struct Buf8192 {
     buffer: [char; 8192]
 }
@@ -26,6 +26,7 @@ pub fn buf_8192(b: &mut Bencher) {
     })
 }
 
+#[allow(unused)]
 struct Buf65535 {
     buffer: [char; 65535]
 }
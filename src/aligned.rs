@@ -0,0 +1,244 @@
+use std::{alloc::{self, Layout}, io::{self, Read}, marker::PhantomData, mem::{align_of, size_of, MaybeUninit}, ops::{Deref, DerefMut}, ptr::NonNull, slice};
+use crate::{seek::{records_to_bytes, whole_records}, Validate, ValidationError};
+
+/// A zeroed heap buffer of `size_of::<T>()` bytes, aligned for a `T`, returned by
+/// [aligned_buffer].
+///
+/// It derefs to a byte slice, to be filled with `read_exact` or any other way, and is then
+/// turned into a boxed `T` in place with [into_validated] or [assume_init], without copying.
+///
+/// [aligned_buffer]: aligned_buffer
+/// [into_validated]: AlignedBuffer::into_validated
+/// [assume_init]: AlignedBuffer::assume_init
+pub struct AlignedBuffer<T> {
+    inner: Box<MaybeUninit<T>>,
+}
+
+impl<T> AlignedBuffer<T> {
+    /// Checks that the bytes form a valid `T` and returns it, boxed.
+    pub fn into_validated(self) -> Result<Box<T>, ValidationError>
+    where
+        T: Validate
+    {
+        T::validate_bytes(&self)?;
+
+        // SAFETY: the bytes have just been validated.
+        Ok(unsafe { self.assume_init() })
+    }
+
+    /// Returns the `T` held in the bytes, boxed, without checking them.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must form a valid `T`, which [read_binary] assumes of any data too, so this is
+    /// sound whenever reading them with it would be.
+    ///
+    /// [read_binary]: crate::BinaryRead::read_binary
+    pub unsafe fn assume_init(self) -> Box<T> {
+        // SAFETY: the caller guarantees the bytes form a valid T.
+        unsafe { self.inner.assume_init() }
+    }
+}
+
+impl<T> Deref for AlignedBuffer<T> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the buffer was zeroed, so every one of its bytes is initialized.
+        unsafe { slice::from_raw_parts(self.inner.as_ptr() as *const u8, size_of::<T>()) }
+    }
+}
+
+impl<T> DerefMut for AlignedBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the buffer was zeroed, so every one of its bytes is initialized.
+        unsafe { slice::from_raw_parts_mut(self.inner.as_mut_ptr() as *mut u8, size_of::<T>()) }
+    }
+}
+
+/// Returns a zeroed heap buffer of `size_of::<T>()` bytes aligned to `align_of::<T>()`, to read
+/// a `T` into, for custom read loops.
+///
+/// This is what [read_binary_boxed] reads into. A plain `Box<[u8]>` can't hold such a buffer,
+/// since it would be freed with the alignment of a byte, so it's returned as an
+/// [AlignedBuffer], which turns into a `Box<T>` once filled.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::aligned_buffer;
+/// use std::io::{self, Cursor, Read};
+///
+/// fn main() -> io::Result<()> {
+///     let mut buffer = aligned_buffer::<[u32; 2]>();
+///     Cursor::new([1u8, 0, 0, 0, 2, 0, 0, 0]).read_exact(&mut buffer)?;
+///
+///     assert_eq!(*buffer.into_validated()?, [u32::from_le(1), u32::from_le(2)]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [read_binary_boxed]: crate::BinaryRead::read_binary_boxed
+/// [AlignedBuffer]: AlignedBuffer
+pub fn aligned_buffer<T>() -> AlignedBuffer<T> {
+    AlignedBuffer { inner: Box::new_zeroed() }
+}
+
+/// An owned, zero-initialized byte buffer holding whole records of `T`, aligned for a `T`.
+///
+/// Bytes kept in a `Vec<u8>` have the alignment of a byte, so they can't be viewed as records
+/// in place. This buffer is allocated with the alignment of `T` instead, and is filled like any
+/// byte slice, with `read_exact` or [read_from], to then be viewed as a slice of records with
+/// [as_records] without copying.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{AlignedBytes, BinaryWrite};
+/// use std::io::{self, Cursor, Read};
+///
+/// fn main() -> io::Result<()> {
+///     let mut bytes = Vec::new();
+///     [3u32, 5, 8].iter().try_for_each(|n| bytes.write_binary(n))?;
+///
+///     let mut buffer = AlignedBytes::<u32>::with_records(3);
+///     Cursor::new(bytes).read_exact(&mut buffer)?;
+///
+///     assert_eq!(buffer.as_records()?, [3, 5, 8]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [read_from]: AlignedBytes::read_from
+/// [as_records]: AlignedBytes::as_records
+pub struct AlignedBytes<T> {
+    ptr: NonNull<u8>,
+    records: usize,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: the buffer owns its bytes, and hands out records of T as a slice of T would.
+unsafe impl<T: Send> Send for AlignedBytes<T> {}
+// SAFETY: shared access only reads the bytes, like a shared slice of T.
+unsafe impl<T: Sync> Sync for AlignedBytes<T> {}
+
+impl<T> AlignedBytes<T> {
+    /// Allocates a zeroed buffer for `records` records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the buffer overflows an `isize`, like `Vec` does.
+    pub fn with_records(records: usize) -> Self {
+        let layout = Self::layout(records);
+
+        let ptr = match layout.size() {
+            0 => NonNull::<T>::dangling().cast(),
+            // SAFETY: the layout has a non-zero size.
+            _ => NonNull::new(unsafe { alloc::alloc_zeroed(layout) })
+                .unwrap_or_else(|| alloc::handle_alloc_error(layout))
+        };
+
+        Self {
+            ptr,
+            records,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Copies `bytes` into a new aligned buffer.
+    ///
+    /// The bytes must hold whole records, otherwise an `InvalidData` error carrying a
+    /// [Misaligned] is returned, and zero sized types are rejected with an `InvalidInput` error.
+    ///
+    /// [Misaligned]: crate::Misaligned
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut buffer = Self::with_records(whole_records::<T>(bytes.len() as u64)? as usize);
+        buffer.copy_from_slice(bytes);
+        Ok(buffer)
+    }
+
+    /// Reads `records` records from `reader` into a new aligned buffer.
+    pub fn read_from<R: Read + ?Sized>(reader: &mut R, records: usize) -> io::Result<Self> {
+        records_to_bytes::<T>(records)?;
+
+        let mut buffer = Self::with_records(records);
+        reader.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Returns the number of records the buffer holds.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Checks that the bytes form valid records and returns them as a slice.
+    pub fn as_records(&self) -> Result<&[T], ValidationError>
+    where
+        T: Validate
+    {
+        self.chunks_exact(size_of::<T>().max(1))
+            .enumerate()
+            .try_for_each(|(index, record)| T::validate_bytes(record).map_err(|e| ValidationError {
+                offset: index * size_of::<T>() + e.offset,
+                ..e
+            }))?;
+
+        // SAFETY: every record has just been validated.
+        Ok(unsafe { self.as_records_unchecked() })
+    }
+
+    /// Returns the records held in the bytes as a slice, without checking them.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must form valid records of `T`, which [read_binary] assumes of any data too,
+    /// so this is sound whenever reading them with it would be.
+    ///
+    /// [read_binary]: crate::BinaryRead::read_binary
+    pub unsafe fn as_records_unchecked(&self) -> &[T] {
+        // SAFETY: the pointer is aligned for T and holds `records` records, which the caller
+        // guarantees to be valid.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr() as *const T, self.records) }
+    }
+
+    fn layout(records: usize) -> Layout {
+        size_of::<T>().checked_mul(records)
+            .and_then(|size| Layout::from_size_align(size, align_of::<T>()).ok())
+            .expect("capacity overflow")
+    }
+}
+
+impl<T> Deref for AlignedBytes<T> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the allocation holds this many initialized bytes.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.records * size_of::<T>()) }
+    }
+}
+
+impl<T> DerefMut for AlignedBytes<T> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the allocation holds this many initialized bytes, borrowed uniquely.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.records * size_of::<T>()) }
+    }
+}
+
+impl<T> Clone for AlignedBytes<T> {
+    fn clone(&self) -> Self {
+        let mut buffer = Self::with_records(self.records);
+        buffer.copy_from_slice(self);
+        buffer
+    }
+}
+
+impl<T> Drop for AlignedBytes<T> {
+    fn drop(&mut self) {
+        let layout = Self::layout(self.records);
+
+        if layout.size() != 0 {
+            // SAFETY: the pointer was allocated with this same layout.
+            unsafe { alloc::dealloc(self.ptr.as_ptr(), layout) }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::{error::Error, fmt, mem::size_of};
+use crate::{bytes, Validate, ValidationError};
+
+/// Error returned by the allocation free writers when the destination can't hold the record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BufferTooSmall {
+    /// Bytes the record needs.
+    pub needed: usize,
+    /// Bytes left in the destination.
+    pub available: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "record of {} bytes doesn't fit in the {} bytes left", self.needed, self.available)
+    }
+}
+
+impl Error for BufferTooSmall {}
+
+/// Converts an array of bytes into a `T` of the same size, checking they form a valid `T`.
+///
+/// This is the allocation free counterpart of [read_binary_validated], meant for FFI functions
+/// returning structs as fixed byte buffers. An `N` other than the size of `T` fails to compile.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{from_array, to_array, validate};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+///     z: i32
+/// }
+///
+/// validate!(Point { x: i32, y: i32, z: i32 });
+///
+/// let bytes = to_array::<_, 12>(&Point { x: 1, y: 2, z: 3 });
+/// assert_eq!(from_array::<Point, 12>(bytes).unwrap(), Point { x: 1, y: 2, z: 3 });
+/// ```
+///
+/// A mismatched size doesn't compile:
+///
+/// ```rust,compile_fail,E0080
+/// let point = binext::from_array::<u64, 4>([0; 4]);
+/// ```
+///
+/// [read_binary_validated]: crate::BinaryRead::read_binary_validated
+pub fn from_array<T: Validate, const N: usize>(array: [u8; N]) -> Result<T, ValidationError> {
+    const { assert!(N == size_of::<T>(), "the array size doesn't match the size of the type") };
+
+    T::validate_bytes(&array)?;
+
+    // SAFETY: the array has the size of T, and its bytes have been checked to form a valid T.
+    Ok(unsafe { std::ptr::read_unaligned(array.as_ptr() as *const T) })
+}
+
+/// Converts a `T` into an array of its bytes.
+///
+/// An `N` other than the size of `T` fails to compile. See [from_array] for an example.
+///
+/// [from_array]: from_array
+pub fn to_array<T, const N: usize>(item: &T) -> [u8; N] {
+    const { assert!(N == size_of::<T>(), "the array size doesn't match the size of the type") };
+
+    bytes::as_bytes(item).try_into().unwrap()
+}
+
+/// Appends the bytes of `item` to `vec`, reserving room for them if needed.
+///
+/// This is the same as `vec.write_binary(item)`, but copies the bytes straight into the spare
+/// capacity of the vector without going through [Write], so there is no `io::Result` to handle.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::write_into_vec;
+///
+/// let mut buffer = Vec::with_capacity(3 * 4);
+///
+/// for value in [1u32, 2, 3] {
+///     write_into_vec(&mut buffer, &value);
+/// }
+///
+/// assert_eq!(buffer, [1u32, 2, 3].map(u32::to_ne_bytes).concat());
+/// ```
+///
+/// [Write]: std::io::Write
+pub fn write_into_vec<T>(vec: &mut Vec<u8>, item: &T) {
+    vec.extend_from_slice(bytes::as_bytes(item));
+}
+
+/// Copies the bytes of `item` to the start of `dst`, returning how many were written.
+///
+/// Neither this nor [read_binary_from_slice_into] allocate, so together with a fixed buffer on
+/// the stack they cover targets without an allocator. If `dst` is shorter than `T`, nothing is
+/// written and a [BufferTooSmall] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{read_binary_from_slice_into, write_binary_to_slice};
+///
+/// let mut buffer = [0u8; 256];
+/// let mut used = 0;
+///
+/// for value in [1u32, 2, 3] {
+///     used += write_binary_to_slice(&mut buffer[used..], &value).unwrap();
+/// }
+///
+/// let mut values = [0u32; 3];
+/// assert_eq!(read_binary_from_slice_into(&buffer[..used], &mut values).unwrap(), 12);
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+///
+/// [read_binary_from_slice_into]: read_binary_from_slice_into
+/// [BufferTooSmall]: BufferTooSmall
+pub fn write_binary_to_slice<T>(dst: &mut [u8], item: &T) -> Result<usize, BufferTooSmall> {
+    let bytes = bytes::as_bytes(item);
+    let available = dst.len();
+
+    dst.get_mut(..bytes.len())
+        .ok_or(BufferTooSmall { needed: bytes.len(), available })?
+        .copy_from_slice(bytes);
+
+    Ok(bytes.len())
+}
+
+/// Reads a `T` from the start of `src` into `dst`, checking its bytes form a valid `T`, and
+/// returns how many bytes were read.
+///
+/// Any bytes past the record are left alone, so consecutive records can be read by advancing
+/// `src` by the returned length. A `src` shorter than `T` is reported as a [ValidationError],
+/// and on failure `dst` is left untouched. See [write_binary_to_slice] for an example.
+///
+/// [ValidationError]: crate::ValidationError
+/// [write_binary_to_slice]: write_binary_to_slice
+pub fn read_binary_from_slice_into<T: Validate>(src: &[u8], dst: &mut T) -> Result<usize, ValidationError> {
+    let bytes = src.get(..size_of::<T>())
+        .ok_or_else(|| ValidationError::new(src.len(), "source is shorter than the record"))?;
+
+    T::validate_bytes(bytes)?;
+
+    // SAFETY: the bytes have the size of T and have been checked to form a valid T.
+    *dst = unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) };
+    Ok(bytes.len())
+}
+
+/// Appends the bytes of `item` to a fixed capacity `heapless::Vec`, like [write_into_vec] but
+/// without an allocator.
+///
+/// If the record doesn't fit in the remaining capacity, nothing is appended and a
+/// [BufferTooSmall] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::write_into_heapless_vec;
+///
+/// let mut buffer = heapless::Vec::<u8, 8>::new();
+///
+/// write_into_heapless_vec(&mut buffer, &1u32).unwrap();
+/// write_into_heapless_vec(&mut buffer, &2u32).unwrap();
+/// assert!(write_into_heapless_vec(&mut buffer, &3u32).is_err());
+///
+/// assert_eq!(buffer[..], [1u32, 2].map(u32::to_ne_bytes).concat());
+/// ```
+///
+/// [write_into_vec]: write_into_vec
+/// [BufferTooSmall]: BufferTooSmall
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub fn write_into_heapless_vec<T, const N: usize>(vec: &mut heapless::Vec<u8, N>, item: &T) -> Result<(), BufferTooSmall> {
+    let bytes = bytes::as_bytes(item);
+
+    vec.extend_from_slice(bytes)
+        .map_err(|_| BufferTooSmall { needed: bytes.len(), available: N - vec.len() })
+}
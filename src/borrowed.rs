@@ -0,0 +1,83 @@
+use std::{borrow::Cow, io::{self, Cursor}, mem::{align_of, size_of}};
+
+/// The BinaryReadSlice trait reads structures from sources backed by bytes in memory, borrowing
+/// them in place when possible instead of copying them.
+///
+/// It's implemented for byte slices, which advance past the records read like when used as a
+/// [Read], and for cursors over them.
+///
+/// [Read]: std::io::Read
+pub trait BinaryReadSlice<'a> {
+    /// Reads a structure, borrowing it from the underlying bytes if they're aligned for `T` at
+    /// the current position, and copying it otherwise.
+    ///
+    /// Like with [read_binary], the bytes are assumed to form a valid `T`. Sources with fewer
+    /// bytes left than a `T` fail with an `UnexpectedEof` error, without advancing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryReadSlice;
+    /// use std::{borrow::Cow, io};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let values = [1u32, 2, 3];
+    ///     let bytes = unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, 12) };
+    ///
+    ///     // The bytes of an array of u32 are aligned for u32.
+    ///     let mut aligned = bytes;
+    ///     assert!(matches!(aligned.read_binary_auto::<u32>()?, Cow::Borrowed(&1)));
+    ///
+    ///     // One byte in, they aren't anymore.
+    ///     let mut misaligned = &bytes[1..];
+    ///     assert!(matches!(misaligned.read_binary_auto::<u16>()?, Cow::Owned(_)));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [read_binary]: crate::BinaryRead::read_binary
+    fn read_binary_auto<T: Clone>(&mut self) -> io::Result<Cow<'a, T>>;
+}
+
+/// Reads a `T` from the start of `bytes`, returning it along with the number of bytes it takes.
+fn read_auto<T: Clone>(bytes: &[u8]) -> io::Result<(Cow<'_, T>, usize)> {
+    if bytes.len() < size_of::<T>() {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("{} bytes left for a {} bytes structure", bytes.len(), size_of::<T>())
+        ));
+    }
+
+    let ptr = bytes.as_ptr() as *const T;
+
+    let item = match ptr as usize % align_of::<T>() {
+        // SAFETY: the pointer is aligned and in bounds, and the bytes are assumed to form a T,
+        // like read_binary does.
+        0 => Cow::Borrowed(unsafe { &*ptr }),
+        // SAFETY: the bytes are in bounds and are assumed to form a T.
+        _ => Cow::Owned(unsafe { ptr.read_unaligned() })
+    };
+
+    Ok((item, size_of::<T>()))
+}
+
+impl<'a> BinaryReadSlice<'a> for &'a [u8] {
+    fn read_binary_auto<T: Clone>(&mut self) -> io::Result<Cow<'a, T>> {
+        let (item, len) = read_auto(self)?;
+        *self = &self[len..];
+
+        Ok(item)
+    }
+}
+
+impl<'a> BinaryReadSlice<'a> for Cursor<&'a [u8]> {
+    fn read_binary_auto<T: Clone>(&mut self) -> io::Result<Cow<'a, T>> {
+        let bytes = *self.get_ref();
+        let position = (self.position() as usize).min(bytes.len());
+
+        let (item, len) = read_auto(&bytes[position..])?;
+        self.set_position((position + len) as u64);
+
+        Ok(item)
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::{self, Write};
+
+const DEFAULT_MAX_PENDING: usize = 1024 * 1024;
+
+/// A writer for non-blocking sinks, keeping the bytes the sink doesn't accept yet instead of
+/// failing with `WouldBlock`.
+///
+/// A write either queues all of its bytes or none of them: it writes as much as the sink takes
+/// and keeps the rest pending, so [write_binary] never leaves a record half written. Pending
+/// bytes are sent first on the next write, or by calling [try_flush_pending] once the sink is
+/// ready again. Once bytes are queued the write succeeds, and an error the sink fails with
+/// meanwhile, other than `WouldBlock`, is returned by the next call instead, so retrying never
+/// queues the same bytes twice.
+///
+/// Pending bytes are kept in memory, up to 1 MiB unless set with [with_max_pending]. A write
+/// that would exceed it fails with `WouldBlock` without queuing anything, applying the
+/// backpressure upstream, unless nothing is pending, so a record larger than the limit can
+/// still be written.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryWrite, BufferingBinaryWriter};
+/// use std::io::{self, Write};
+///
+/// /// Accepts a single byte per call, and only every other call.
+/// struct Slow {
+///     data: Vec<u8>,
+///     ready: bool
+/// }
+///
+/// impl Write for Slow {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.ready = !self.ready;
+///
+///         if !self.ready {
+///             return Err(io::ErrorKind::WouldBlock.into());
+///         }
+///
+///         self.data.push(buf[0]);
+///         Ok(1)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = BufferingBinaryWriter::new(Slow { data: Vec::new(), ready: false });
+///     writer.write_binary(&0x0102u16)?;
+///
+///     while !writer.try_flush_pending()? {}
+///
+///     assert_eq!(writer.into_inner().data, 0x0102u16.to_ne_bytes());
+///     Ok(())
+/// }
+/// ```
+///
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [try_flush_pending]: BufferingBinaryWriter::try_flush_pending
+/// [with_max_pending]: BufferingBinaryWriter::with_max_pending
+pub struct BufferingBinaryWriter<W> {
+    inner: W,
+    buffer: Vec<u8>,
+    max_pending: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> BufferingBinaryWriter<W> {
+    /// Creates a new writer over `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            max_pending: DEFAULT_MAX_PENDING,
+            error: None,
+        }
+    }
+
+    /// Sets how many bytes can be pending before writes fail with `WouldBlock`.
+    pub fn with_max_pending(mut self, max: usize) -> Self {
+        self.max_pending = max;
+        self
+    }
+
+    /// Writes as many pending bytes as the sink accepts, returning whether all of them have
+    /// been written.
+    ///
+    /// If the sink failed during an earlier write, that error is returned first.
+    pub fn try_flush_pending(&mut self) -> io::Result<bool> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        let mut written = 0;
+
+        let result = loop {
+            if written == self.buffer.len() {
+                break Ok(());
+            }
+
+            match self.inner.write(&self.buffer[written..]) {
+                Ok(0) => break Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => written += len,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+                Err(e) => break Err(e)
+            }
+        };
+
+        self.buffer.drain(..written);
+        result.map(|()| self.buffer.is_empty())
+    }
+
+    /// Returns the number of bytes waiting to be written.
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one. Pending bytes are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for BufferingBinaryWriter<W> {
+    /// Writes all of `buf`, keeping what the sink doesn't accept yet as pending, or none of it
+    /// if that would exceed the pending limit.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.buffer.is_empty() && self.buffer.len() + buf.len() > self.max_pending {
+            self.try_flush_pending()?;
+
+            if !self.buffer.is_empty() && self.buffer.len() + buf.len() > self.max_pending {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+        } else if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        self.buffer.extend_from_slice(buf);
+
+        if let Err(e) = self.try_flush_pending() {
+            self.error = Some(e);
+        }
+
+        Ok(buf.len())
+    }
+
+    /// Writes the pending bytes and flushes the sink, failing with `WouldBlock` if it doesn't
+    /// accept all of them.
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.try_flush_pending()? {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,26 @@
+use std::{mem::size_of, slice};
+
+/// Views the memory of `item` as bytes.
+pub(crate) fn as_bytes<T>(item: &T) -> &[u8] {
+    // SAFETY: the pointer is valid for size_of::<T>() bytes during the lifetime of the borrow.
+    unsafe {
+        slice::from_raw_parts(item as *const T as *const u8, size_of::<T>())
+    }
+}
+
+/// Views the memory of a slice as bytes.
+pub(crate) fn slice_as_bytes<T>(items: &[T]) -> &[u8] {
+    // SAFETY: the slice spans exactly size_of_val(items) bytes.
+    unsafe {
+        slice::from_raw_parts(items.as_ptr() as *const u8, std::mem::size_of_val(items))
+    }
+}
+
+/// Views the memory of a slice as mutable bytes, so it can be filled by a reader.
+pub(crate) fn slice_as_bytes_mut<T>(items: &mut [T]) -> &mut [u8] {
+    // SAFETY: the slice spans exactly size_of_val(items) bytes, and the elements are already
+    // initialized, so are their bytes.
+    unsafe {
+        slice::from_raw_parts_mut(items.as_mut_ptr() as *mut u8, std::mem::size_of_val(items))
+    }
+}
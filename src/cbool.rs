@@ -0,0 +1,130 @@
+use std::fmt;
+use crate::{Validate, ValidationError, Zeroable};
+
+/// A boolean stored as an integer of type `T`, mirroring C code that uses `int` or other integer
+/// types as booleans.
+///
+/// Modeling such a field as `bool` breaks the layout, and modeling it as a plain integer loses
+/// the intent. This wrapper has exactly the layout of `T`, and converts to `bool` following C
+/// semantics: zero is `false` and any other value is `true`. With `STRICT` set, [Validate]
+/// rejects values other than `0` and `1` instead, so [read_binary_validated] can enforce them.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, CBool32};
+/// use std::io::{self, Cursor};
+///
+/// #[repr(C)]
+/// struct Options {
+///     // int enabled;
+///     enabled: CBool32,
+///     level: u32
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut bytes = [0u8; 8];
+///     bytes[..4].copy_from_slice(&2u32.to_ne_bytes());
+///
+///     let options = Cursor::new(bytes).read_binary::<Options>()?;
+///     assert!(options.enabled.get());
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [Validate]: Validate
+/// [read_binary_validated]: crate::BinaryRead::read_binary_validated
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct CBool<T, const STRICT: bool = false>(T);
+
+/// A C boolean stored in a `u8`.
+pub type CBool8 = CBool<u8>;
+/// A C boolean stored in a `u16`.
+pub type CBool16 = CBool<u16>;
+/// A C boolean stored in a `u32`, the size of `int` on most platforms.
+pub type CBool32 = CBool<u32>;
+
+impl<T: Copy + PartialEq + From<u8>, const STRICT: bool> CBool<T, STRICT> {
+    /// Creates a C boolean, storing `1` for `true` and `0` for `false`.
+    pub fn new(value: bool) -> Self {
+        Self(T::from(value as u8))
+    }
+
+    /// Creates a C boolean from its raw integer value.
+    pub fn from_raw(raw: T) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw integer value.
+    pub fn raw(self) -> T {
+        self.0
+    }
+
+    /// Returns the boolean value, any non zero value is `true`.
+    pub fn get(self) -> bool {
+        self.0 != T::from(0)
+    }
+
+    /// Returns the boolean value if the raw value is `0` or `1`, `None` otherwise.
+    pub fn get_strict(self) -> Option<bool> {
+        match self.0 {
+            raw if raw == T::from(0) => Some(false),
+            raw if raw == T::from(1) => Some(true),
+            _ => None
+        }
+    }
+}
+
+impl<T: Copy + PartialEq + From<u8>, const STRICT: bool> Default for CBool<T, STRICT> {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl<T: Copy + PartialEq + From<u8>, const STRICT: bool> From<bool> for CBool<T, STRICT> {
+    fn from(value: bool) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Copy + PartialEq + From<u8>, const STRICT: bool> From<CBool<T, STRICT>> for bool {
+    fn from(value: CBool<T, STRICT>) -> Self {
+        value.get()
+    }
+}
+
+impl<T: Copy + PartialEq + From<u8>, const STRICT: bool> PartialEq for CBool<T, STRICT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: Copy + PartialEq + From<u8>, const STRICT: bool> Eq for CBool<T, STRICT> {}
+
+impl<T: Copy + PartialEq + From<u8> + fmt::Debug, const STRICT: bool> fmt::Debug for CBool<T, STRICT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CBool({}, raw: {:?})", self.get(), self.0)
+    }
+}
+
+impl<T: Copy + PartialEq + From<u8> + Validate, const STRICT: bool> Validate for CBool<T, STRICT> {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        T::validate_bytes(bytes)?;
+
+        if STRICT {
+            // SAFETY: the bytes are a valid T, and have its size.
+            let raw = unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) };
+
+            if Self(raw).get_strict().is_none() {
+                return Err(ValidationError::new(0, "strict C boolean must be 0 or 1"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// SAFETY: zero is `false`.
+unsafe impl<T: Zeroable, const STRICT: bool> Zeroable for CBool<T, STRICT> {}
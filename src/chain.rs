@@ -0,0 +1,417 @@
+use std::{error::Error, fmt, fs::File, io::{self, BufReader, Read, Write}, marker::PhantomData, mem::size_of, path::PathBuf};
+use crate::{bytes, iter::{read_record_bytes, read_up_to}, BinaryRead};
+
+/// Size of the hashes chaining the records.
+const HASH_SIZE: usize = 32;
+
+/// Error carried by the `InvalidData` [io::Error] returned by [ChainedLogReader] when the hash
+/// stored in a record doesn't match the previous record.
+///
+/// [io::Error]: std::io::Error
+/// [ChainedLogReader]: ChainedLogReader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChainBroken {
+    /// Index of the record whose previous hash doesn't match.
+    pub index: u64,
+}
+
+impl fmt::Display for ChainBroken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hash chain broken at record {}", self.index)
+    }
+}
+
+impl Error for ChainBroken {}
+
+/// Error carried by the `InvalidData` [io::Error] returned by [LinkedFileReader] when the hash
+/// stored in the header of a file doesn't match the previous file.
+///
+/// [io::Error]: std::io::Error
+/// [LinkedFileReader]: LinkedFileReader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkBroken {
+    /// Index, in the list of files, of the file whose previous hash doesn't match.
+    pub file: usize,
+}
+
+impl fmt::Display for LinkBroken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hash link broken at file {}", self.file)
+    }
+}
+
+impl Error for LinkBroken {}
+
+/// Panics if a `[u8; 32]` at `offset` doesn't fit within a `T`.
+fn check_offset<T>(offset: usize) {
+    assert!(
+        offset.checked_add(HASH_SIZE).is_some_and(|end| end <= size_of::<T>()),
+        "a 32 bytes hash at offset {offset} doesn't fit in a {} bytes record",
+        size_of::<T>()
+    );
+}
+
+/// A writer for tamper-evident logs of records of `T`, where every record holds the hash of the
+/// previous one.
+///
+/// Records have a `[u8; 32]` field, given by its offset, which the writer overwrites with the
+/// hash of the previous record as written, all zeroes for the first one. Hashing is left to the
+/// `hash` function, usually a cryptographic hash such as SHA-256. Since every byte of a record is
+/// hashed, records should have no padding. The log is checked with [ChainedLogReader].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{ChainedLogReader, ChainedLogWriter};
+/// use std::{hash::{DefaultHasher, Hasher}, io::{self, Cursor}, mem::offset_of};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Entry {
+///     prev: [u8; 32],
+///     amount: u64
+/// }
+///
+/// // Not cryptographically secure, just for the example.
+/// fn hash(bytes: &[u8]) -> [u8; 32] {
+///     let mut hasher = DefaultHasher::new();
+///     hasher.write(bytes);
+///     [hasher.finish().to_ne_bytes(); 4].concat().try_into().unwrap()
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let offset = offset_of!(Entry, prev);
+///     let mut writer = ChainedLogWriter::new(Vec::new(), offset, hash);
+///
+///     writer.write_record(&Entry { prev: [0; 32], amount: 10 })?;
+///     writer.write_record(&Entry { prev: [0; 32], amount: 20 })?;
+///
+///     let amounts = ChainedLogReader::<_, Entry, _>::new(Cursor::new(writer.into_inner()), offset, hash)
+///         .map(|entry| entry.map(|entry| entry.amount))
+///         .collect::<io::Result<Vec<_>>>()?;
+///
+///     assert_eq!(amounts, [10, 20]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [ChainedLogReader]: ChainedLogReader
+pub struct ChainedLogWriter<W, T, H> {
+    inner: W,
+    offset: usize,
+    hash: H,
+    prev: [u8; HASH_SIZE],
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<W: Write, T, H: FnMut(&[u8]) -> [u8; HASH_SIZE]> ChainedLogWriter<W, T, H> {
+    /// Creates a new writer over `inner`, starting a new chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `[u8; 32]` at `offset` doesn't fit within a `T`.
+    pub fn new(inner: W, offset: usize, hash: H) -> Self {
+        check_offset::<T>(offset);
+
+        Self {
+            inner,
+            offset,
+            hash,
+            prev: [0; HASH_SIZE],
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes a record, replacing its previous hash field with the hash of the last record.
+    pub fn write_record(&mut self, item: &T) -> io::Result<()> {
+        let mut record = bytes::as_bytes(item).to_vec();
+        record[self.offset..self.offset + HASH_SIZE].copy_from_slice(&self.prev);
+
+        self.inner.write_all(&record)?;
+        self.prev = (self.hash)(&record);
+
+        Ok(())
+    }
+
+    /// Returns the hash of the last record written, which the next one will hold.
+    pub fn last_hash(&self) -> &[u8; HASH_SIZE] {
+        &self.prev
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A reader checking the chain of a log written by [ChainedLogWriter].
+///
+/// It's an iterator over the records, stopping once the source ends at a record boundary. A
+/// record whose previous hash doesn't match the previous record is yielded as an `InvalidData`
+/// error carrying a [ChainBroken], and the iteration stops.
+///
+/// [ChainedLogWriter]: ChainedLogWriter
+/// [ChainBroken]: ChainBroken
+pub struct ChainedLogReader<R, T, H> {
+    inner: R,
+    offset: usize,
+    hash: H,
+    prev: [u8; HASH_SIZE],
+    index: u64,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T, H: FnMut(&[u8]) -> [u8; HASH_SIZE]> ChainedLogReader<R, T, H> {
+    /// Creates a new reader over `inner`, which must start at the beginning of the chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `[u8; 32]` at `offset` doesn't fit within a `T`.
+    pub fn new(inner: R, offset: usize, hash: H) -> Self {
+        check_offset::<T>(offset);
+
+        Self {
+            inner,
+            offset,
+            hash,
+            prev: [0; HASH_SIZE],
+            index: 0,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_record(&mut self) -> io::Result<Option<T>> {
+        let mut record = vec![0u8; size_of::<T>()];
+
+        if !read_record_bytes(&mut self.inner, &mut record)? {
+            return Ok(None);
+        }
+
+        if record[self.offset..self.offset + HASH_SIZE] != self.prev {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, ChainBroken { index: self.index }));
+        }
+
+        self.prev = (self.hash)(&record);
+        self.index += 1;
+
+        record.as_slice().read_binary().map(Some)
+    }
+}
+
+impl<R: Read, T, H: FnMut(&[u8]) -> [u8; HASH_SIZE]> Iterator for ChainedLogReader<R, T, H> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.next_record().transpose();
+        self.done = !matches!(item, Some(Ok(_)));
+
+        item
+    }
+}
+
+/// An incremental hash over the bytes of the files read by [LinkedFileReader], fed as they're
+/// read, so files are never held in memory whole.
+///
+/// With the `digest` feature, it's implemented for every digest with a 32 bytes output, like
+/// `sha2::Sha256`.
+///
+/// [LinkedFileReader]: LinkedFileReader
+pub trait FileHasher {
+    /// Feeds the next bytes of the file.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the hash of the bytes fed since the last call, and starts over.
+    fn finish_reset(&mut self) -> [u8; HASH_SIZE];
+}
+
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+impl<D> FileHasher for D
+where
+    D: digest::Digest + digest::FixedOutputReset,
+    digest::Output<D>: Into<[u8; HASH_SIZE]>
+{
+    fn update(&mut self, bytes: &[u8]) {
+        digest::Digest::update(self, bytes);
+    }
+
+    fn finish_reset(&mut self) -> [u8; HASH_SIZE] {
+        digest::Digest::finalize_reset(self).into()
+    }
+}
+
+/// A reader over the records of a dataset split in files, where the header of every file holds
+/// the hash of the previous one, checking the links so missing or reordered files are caught.
+///
+/// Each file is a header of type `F` followed by records of `T`. The header has a `[u8; 32]`
+/// field, given by its offset, holding the hash of all the bytes of the previous file, all
+/// zeroes for the first one. Hashing is left to a [FileHasher], usually a cryptographic hash
+/// such as SHA-256.
+///
+/// It's an iterator over the records of all the files, in order. Files are read through a
+/// buffer, record by record, and hashed as they are, so memory use doesn't grow with their
+/// size. The link of a file is checked when it's opened, once the previous one has been read
+/// to the end, before any of its records is yielded; a file whose previous hash doesn't match
+/// is yielded as an `InvalidData` error carrying a [LinkBroken], and the iteration stops. A
+/// file too short for its header or ending in the middle of a record is an error too.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{FileHasher, LinkedFileReader};
+/// use std::{hash::{DefaultHasher, Hasher}, io, mem::offset_of};
+///
+/// #[repr(C)]
+/// struct ShardHeader {
+///     prev: [u8; 32],
+///     shard: u64
+/// }
+///
+/// // Not cryptographically secure, just for the example.
+/// struct Hash(DefaultHasher);
+///
+/// impl FileHasher for Hash {
+///     fn update(&mut self, bytes: &[u8]) {
+///         self.0.write(bytes);
+///     }
+///
+///     fn finish_reset(&mut self) -> [u8; 32] {
+///         let hash = std::mem::take(&mut self.0).finish();
+///         [hash.to_ne_bytes(); 4].concat().try_into().unwrap()
+///     }
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let shards = ["shard0.bin", "shard1.bin", "shard2.bin"];
+///     let hash = Hash(DefaultHasher::new());
+///     let reader = LinkedFileReader::<ShardHeader, u64, _>::new(shards, offset_of!(ShardHeader, prev), hash);
+///
+///     let total = reader.sum::<io::Result<u64>>()?;
+///     println!("{total}");
+///     Ok(())
+/// }
+/// ```
+///
+/// [FileHasher]: FileHasher
+/// [LinkBroken]: LinkBroken
+pub struct LinkedFileReader<F, T, H> {
+    paths: Vec<PathBuf>,
+    offset: usize,
+    hasher: H,
+    prev: [u8; HASH_SIZE],
+    /// Index of the next file to open.
+    file: usize,
+    header: Option<F>,
+    records: Option<BufReader<File>>,
+    record: Vec<u8>,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<F, T, H: FileHasher> LinkedFileReader<F, T, H> {
+    /// Creates a new reader over the files at `paths`, in order, the first one starting the
+    /// chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is zero sized, or if a `[u8; 32]` at `offset` doesn't fit within an `F`.
+    pub fn new<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>, offset: usize, hasher: H) -> Self {
+        check_offset::<F>(offset);
+        assert!(size_of::<T>() > 0, "zero sized records can't be read from a file");
+
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+            offset,
+            hasher,
+            prev: [0; HASH_SIZE],
+            file: 0,
+            header: None,
+            records: None,
+            record: vec![0; size_of::<T>()],
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the header of the file whose records are being read, if one was opened.
+    pub fn header(&self) -> Option<&F> {
+        self.header.as_ref()
+    }
+
+    /// Returns the hash of the last file read to the end, which a file following it should
+    /// hold.
+    pub fn last_hash(&self) -> &[u8; HASH_SIZE] {
+        &self.prev
+    }
+
+    fn next_record(&mut self) -> io::Result<Option<T>> {
+        loop {
+            if let Some(records) = &mut self.records {
+                if read_record_bytes(records, &mut self.record)? {
+                    self.hasher.update(&self.record);
+                    return self.record.as_slice().read_binary().map(Some);
+                }
+
+                self.records = None;
+                self.prev = self.hasher.finish_reset();
+            }
+
+            let Some(path) = self.paths.get(self.file) else {
+                return Ok(None);
+            };
+
+            let mut records = BufReader::new(File::open(path)?);
+            let mut header = vec![0; size_of::<F>()];
+            let len = read_up_to(&mut records, &mut header)?;
+
+            if len < header.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("file {} is {len} bytes long, too short for its header", self.file)
+                ));
+            }
+
+            if header[self.offset..self.offset + HASH_SIZE] != self.prev {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, LinkBroken { file: self.file }));
+            }
+
+            self.hasher.update(&header);
+            self.header = Some(header.as_slice().read_binary()?);
+            self.file += 1;
+            self.records = Some(records);
+        }
+    }
+}
+
+impl<F, T, H: FileHasher> Iterator for LinkedFileReader<F, T, H> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.next_record().transpose();
+        self.done = !matches!(item, Some(Ok(_)));
+
+        item
+    }
+}
@@ -0,0 +1,255 @@
+use std::{error::Error, fmt, ops::{Bound, RangeBounds}};
+use crate::crc::Crc32;
+
+/// Checksum algorithms for fields covering other bytes of the same struct, marked with
+/// `#[checksum = ...]` in [validate].
+///
+/// [validate]: crate::validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Checksum {
+    /// The ones' complement sum of 16 bits words used by IPv4, TCP, UDP and ICMP (RFC 1071),
+    /// stored in 2 bytes. An odd trailing byte is padded with a zero.
+    Internet,
+    /// CRC-16/ARC, usually just called CRC-16, stored in 2 bytes.
+    Crc16Arc,
+    /// CRC-16/MODBUS, as used by Modbus RTU, stored in 2 bytes.
+    Crc16Modbus,
+    /// CRC-16/CCITT-FALSE, stored in 2 bytes.
+    Crc16Ccitt,
+    /// CRC-32 (IEEE), as used by zlib, PNG and Ethernet, stored in 4 bytes.
+    Crc32,
+}
+
+impl Checksum {
+    /// Returns the size in bytes of the checksum.
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Crc32 => 4,
+            _ => 2
+        }
+    }
+
+    /// Computes the checksum of `bytes`.
+    pub fn compute(self, bytes: &[u8]) -> u32 {
+        let mut state = State::new(self);
+        state.update(bytes);
+        state.finish()
+    }
+
+    /// Describes a checksum of the bytes of a struct in `range`, `..` covering the whole struct.
+    pub fn of(self, range: impl RangeBounds<usize>) -> ChecksumOf {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => Some(end + 1),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => None
+        };
+
+        ChecksumOf { algorithm: self, start, end, little_endian: false }
+    }
+}
+
+/// A checksum computed incrementally.
+enum State {
+    Internet { sum: u32, odd: Option<u8> },
+    Crc16 { crc: u16, reflected: bool },
+    Crc32(Crc32),
+}
+
+impl State {
+    fn new(algorithm: Checksum) -> Self {
+        match algorithm {
+            Checksum::Internet => Self::Internet { sum: 0, odd: None },
+            Checksum::Crc16Arc => Self::Crc16 { crc: 0, reflected: true },
+            Checksum::Crc16Modbus => Self::Crc16 { crc: 0xFFFF, reflected: true },
+            Checksum::Crc16Ccitt => Self::Crc16 { crc: 0xFFFF, reflected: false },
+            Checksum::Crc32 => Self::Crc32(Crc32::new())
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Internet { sum, odd } => {
+                let mut bytes = bytes;
+
+                if let (Some(high), Some((&low, rest))) = (*odd, bytes.split_first()) {
+                    *sum = fold(*sum + u32::from(u16::from_be_bytes([high, low])));
+                    *odd = None;
+                    bytes = rest;
+                }
+
+                let mut words = bytes.chunks_exact(2);
+                for word in &mut words {
+                    *sum = fold(*sum + u32::from(u16::from_be_bytes([word[0], word[1]])));
+                }
+
+                if let [last] = words.remainder() {
+                    *odd = Some(*last);
+                }
+            },
+            Self::Crc16 { crc, reflected: true } => {
+                for &byte in bytes {
+                    *crc ^= u16::from(byte);
+                    for _ in 0..8 {
+                        *crc = if *crc & 1 == 1 { (*crc >> 1) ^ 0xA001 } else { *crc >> 1 };
+                    }
+                }
+            },
+            Self::Crc16 { crc, reflected: false } => {
+                for &byte in bytes {
+                    *crc ^= u16::from(byte) << 8;
+                    for _ in 0..8 {
+                        *crc = if *crc & 0x8000 != 0 { (*crc << 1) ^ 0x1021 } else { *crc << 1 };
+                    }
+                }
+            },
+            Self::Crc32(crc) => crc.update(bytes)
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        match *self {
+            Self::Internet { sum, odd } => {
+                let sum = fold(sum + odd.map_or(0, |high| u32::from(high) << 8));
+                u32::from(!(sum as u16))
+            },
+            Self::Crc16 { crc, .. } => u32::from(crc),
+            Self::Crc32(crc) => crc.finish()
+        }
+    }
+}
+
+/// Folds the carries of a ones' complement sum back into its low 16 bits.
+fn fold(sum: u32) -> u32 {
+    let sum = (sum & 0xFFFF) + (sum >> 16);
+    (sum & 0xFFFF) + (sum >> 16)
+}
+
+/// A checksum field covering a range of the bytes of the same struct, made with [Checksum::of].
+///
+/// While computing the checksum the bytes of the field itself, if within the range, are taken
+/// as zero, like most protocols do. The checksum is stored in big endian, network, byte order,
+/// unless [little_endian] is used.
+///
+/// [Checksum::of]: Checksum::of
+/// [little_endian]: ChecksumOf::little_endian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChecksumOf {
+    /// The algorithm of the checksum.
+    pub algorithm: Checksum,
+    /// Offset of the first byte covered.
+    pub start: usize,
+    /// Offset past the last byte covered, `None` for the end of the struct.
+    pub end: Option<usize>,
+    /// Whether the checksum is stored in little endian byte order.
+    pub little_endian: bool,
+}
+
+impl ChecksumOf {
+    /// Stores the checksum in little endian byte order.
+    pub fn little_endian(self) -> Self {
+        Self { little_endian: true, ..self }
+    }
+
+    /// Computes the checksum of `record`, the bytes of the struct, for the field at
+    /// `field_offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range or the field doesn't fit within `record`.
+    pub fn compute(&self, record: &[u8], field_offset: usize) -> u32 {
+        let end = self.end.unwrap_or(record.len());
+        let field_end = field_offset + self.algorithm.size();
+
+        assert!(
+            self.start <= end && end <= record.len() && field_end <= record.len(),
+            "checksum of bytes {}..{end} at offset {field_offset} doesn't fit in a {} bytes record",
+            self.start,
+            record.len()
+        );
+
+        let mut state = State::new(self.algorithm);
+        let (start, zeroed_start) = (self.start, field_offset.clamp(self.start, end));
+        let zeroed_end = field_end.clamp(self.start, end);
+
+        state.update(&record[start..zeroed_start]);
+        state.update(&[0; 4][..zeroed_end - zeroed_start]);
+        state.update(&record[zeroed_end..end]);
+        state.finish()
+    }
+
+    /// Computes the checksum of `record` and stores it in the field at `field_offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range or the field doesn't fit within `record`.
+    pub fn stamp(&self, record: &mut [u8], field_offset: usize) {
+        let checksum = self.compute(record, field_offset);
+        let field = &mut record[field_offset..field_offset + self.algorithm.size()];
+
+        match (self.algorithm.size(), self.little_endian) {
+            (2, false) => field.copy_from_slice(&(checksum as u16).to_be_bytes()),
+            (2, true) => field.copy_from_slice(&(checksum as u16).to_le_bytes()),
+            (_, false) => field.copy_from_slice(&checksum.to_be_bytes()),
+            (_, true) => field.copy_from_slice(&checksum.to_le_bytes())
+        }
+    }
+
+    /// Returns the checksum stored in the field at `field_offset` of `record`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field doesn't fit within `record`.
+    pub fn stored(&self, record: &[u8], field_offset: usize) -> u32 {
+        let field = &record[field_offset..field_offset + self.algorithm.size()];
+
+        match (field, self.little_endian) {
+            (&[a, b], false) => u32::from(u16::from_be_bytes([a, b])),
+            (&[a, b], true) => u32::from(u16::from_le_bytes([a, b])),
+            (&[a, b, c, d], false) => u32::from_be_bytes([a, b, c, d]),
+            (&[a, b, c, d], true) => u32::from_le_bytes([a, b, c, d]),
+            _ => unreachable!("checksums are 2 or 4 bytes")
+        }
+    }
+
+    /// Checks the checksum stored in the field at `field_offset` of `record` against its bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range or the field doesn't fit within `record`.
+    pub fn verify(&self, record: &[u8], field_offset: usize) -> Result<(), ChecksumMismatch> {
+        let (stored, computed) = (self.stored(record, field_offset), self.compute(record, field_offset));
+
+        if stored != computed {
+            return Err(ChecksumMismatch { stored, computed });
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned by [ChecksumOf::verify] when a stored checksum doesn't match the bytes it
+/// covers.
+///
+/// [ChecksumOf::verify]: ChecksumOf::verify
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChecksumMismatch {
+    /// The checksum stored in the field.
+    pub stored: u32,
+    /// The checksum of the covered bytes.
+    pub computed: u32,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stored checksum {:#x} doesn't match the computed {:#x}", self.stored, self.computed)
+    }
+}
+
+impl Error for ChecksumMismatch {}
@@ -0,0 +1,237 @@
+use std::{io::{self, Read, Write}, mem::{size_of, MaybeUninit}, slice};
+use crate::{bytes, BinaryRead};
+
+/// A field of a [Columns] type: its name, offset and size in bytes.
+///
+/// [Columns]: Columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Field {
+    /// Name of the field, its index prefixed by a dot for tuple structs.
+    pub name: &'static str,
+    /// Offset of the field within the type.
+    pub offset: usize,
+    /// Size of the field in bytes.
+    pub size: usize,
+}
+
+/// Types whose fields are known, so arrays of them can be split into one column per field.
+///
+/// Implementations are usually generated with [columns], which takes the offsets and sizes from
+/// the real layout of the type. Fields must be listed in declaration order, and padding bytes
+/// aren't part of any column.
+///
+/// [columns]: crate::columns
+pub trait Columns: Sized {
+    /// The fields of the type.
+    const FIELDS: &'static [Field];
+}
+
+/// Records split into one column of bytes per field, as returned by [split_columns].
+///
+/// [split_columns]: split_columns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSet {
+    len: usize,
+    columns: Vec<(Field, Vec<u8>)>,
+}
+
+impl ColumnSet {
+    /// Creates a set for records of `T` from the bytes of each column, in the order of its
+    /// fields, erroring with `InvalidInput` if they don't hold the same number of values.
+    pub fn from_columns<T: Columns>(columns: Vec<Vec<u8>>) -> io::Result<Self> {
+        if columns.len() != T::FIELDS.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} columns given for {} fields", columns.len(), T::FIELDS.len())
+            ));
+        }
+
+        let len = match T::FIELDS.iter().zip(&columns).find(|(field, _)| field.size > 0) {
+            Some((field, bytes)) => bytes.len() / field.size,
+            None => 0
+        };
+
+        for (field, bytes) in T::FIELDS.iter().zip(&columns) {
+            if bytes.len() != field.size * len {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("column {} doesn't hold {len} values of {} bytes", field.name, field.size)
+                ));
+            }
+        }
+
+        Ok(Self {
+            len,
+            columns: T::FIELDS.iter().copied().zip(columns).collect(),
+        })
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the fields and the bytes of their columns, in declaration order.
+    pub fn columns(&self) -> impl Iterator<Item = (&Field, &[u8])> {
+        self.columns.iter().map(|(field, bytes)| (field, bytes.as_slice()))
+    }
+
+    /// Returns the bytes of the column of the field called `name`.
+    pub fn column(&self, name: &str) -> Option<&[u8]> {
+        self.columns.iter()
+            .find(|(field, _)| field.name == name)
+            .map(|(_, bytes)| bytes.as_slice())
+    }
+
+    /// Returns the column of the field called `name` as values of `U`, or `None` if there is no
+    /// such field or `U` doesn't have its size.
+    pub fn column_as<U>(&self, name: &str) -> Option<Vec<U>> {
+        let (field, bytes) = self.columns.iter().find(|(field, _)| field.name == name)?;
+
+        if field.size != size_of::<U>() {
+            return None;
+        }
+
+        bytes.as_slice().read_binary_vec(self.len).ok()
+    }
+
+    /// Unwraps this set, returning the bytes of each column.
+    pub fn into_columns(self) -> Vec<Vec<u8>> {
+        self.columns.into_iter().map(|(_, bytes)| bytes).collect()
+    }
+}
+
+/// Splits records into one column per field, with the values of each field stored contiguously.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{columns, join_columns, split_columns};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Sample {
+///     timestamp: u64,
+///     value: f32,
+///     sensor: u8
+/// }
+///
+/// columns!(Sample { timestamp: u64, value: f32, sensor: u8 });
+///
+/// let samples = [
+///     Sample { timestamp: 10, value: 0.5, sensor: 1 },
+///     Sample { timestamp: 20, value: 1.5, sensor: 2 },
+/// ];
+///
+/// let columns = split_columns(&samples);
+///
+/// assert_eq!(columns.column_as::<u64>("timestamp").unwrap(), [10, 20]);
+/// assert_eq!(columns.column("sensor").unwrap(), [1, 2]);
+/// assert_eq!(join_columns::<Sample>(&columns).unwrap(), samples);
+/// ```
+pub fn split_columns<T: Columns>(records: &[T]) -> ColumnSet {
+    let columns = T::FIELDS.iter().map(|field| {
+        let mut column = Vec::with_capacity(field.size * records.len());
+
+        for record in records {
+            column.extend_from_slice(&bytes::as_bytes(record)[field.offset..field.offset + field.size]);
+        }
+
+        (*field, column)
+    });
+
+    ColumnSet {
+        len: records.len(),
+        columns: columns.collect(),
+    }
+}
+
+/// Joins columns back into records, zeroing their padding.
+///
+/// The columns must have been split from records of `T`, otherwise an `InvalidInput` error is
+/// returned.
+pub fn join_columns<T: Columns>(columns: &ColumnSet) -> io::Result<Vec<T>> {
+    if columns.columns.iter().map(|(field, _)| field).ne(T::FIELDS) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "columns don't match the fields of the type"));
+    }
+
+    let mut records = Vec::with_capacity(columns.len);
+
+    for index in 0..columns.len {
+        let mut record = MaybeUninit::<T>::zeroed();
+
+        // SAFETY: the memory is zeroed and as large as T.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(record.as_mut_ptr() as *mut u8, size_of::<T>())
+        };
+
+        for (field, column) in &columns.columns {
+            bytes[field.offset..field.offset + field.size]
+                .copy_from_slice(&column[index * field.size..(index + 1) * field.size]);
+        }
+
+        // SAFETY: every field has been copied from the columns, and the padding is zeroed.
+        records.push(unsafe { record.assume_init() });
+    }
+
+    Ok(records)
+}
+
+/// Writes records as columns, the values of each field to its own sink, in the order of the
+/// fields.
+///
+/// An `InvalidInput` error is returned if there isn't one sink per field.
+pub fn write_columns<T: Columns, W: Write>(records: &[T], sinks: &mut [W]) -> io::Result<()> {
+    check_count::<T>(sinks.len())?;
+
+    for (field, sink) in T::FIELDS.iter().zip(sinks) {
+        let mut column = Vec::with_capacity(field.size * records.len());
+
+        for record in records {
+            column.extend_from_slice(&bytes::as_bytes(record)[field.offset..field.offset + field.size]);
+        }
+
+        sink.write_all(&column)?;
+    }
+
+    Ok(())
+}
+
+/// Reads `count` records from columns written by [write_columns], one source per field in the
+/// order of the fields.
+///
+/// An `InvalidInput` error is returned if there isn't one source per field.
+///
+/// [write_columns]: write_columns
+pub fn read_columns<T: Columns, R: Read>(sources: &mut [R], count: usize) -> io::Result<Vec<T>> {
+    check_count::<T>(sources.len())?;
+
+    let mut columns = Vec::with_capacity(sources.len());
+
+    for (field, source) in T::FIELDS.iter().zip(sources) {
+        let len = field.size.checked_mul(count)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "column size overflows a usize"))?;
+
+        columns.push(source.read_binary_vec::<u8>(len)?);
+    }
+
+    join_columns(&ColumnSet {
+        len: count,
+        columns: T::FIELDS.iter().copied().zip(columns).collect(),
+    })
+}
+
+fn check_count<T: Columns>(count: usize) -> io::Result<()> {
+    match count == T::FIELDS.len() {
+        true => Ok(()),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{count} streams given for {} fields", T::FIELDS.len())
+        ))
+    }
+}
@@ -0,0 +1,202 @@
+use std::{fmt, mem::size_of, ptr};
+use crate::bytes;
+
+/// Bytes shown on each side of the first difference in a [DiffReport].
+///
+/// [DiffReport]: DiffReport
+const EXCERPT_CONTEXT: usize = 8;
+
+/// A field of a [BinaryFields] type: its name, type, offset and size, and how to display it.
+///
+/// [BinaryFields]: BinaryFields
+#[derive(Clone, Copy)]
+pub struct FieldInfo {
+    name: &'static str,
+    ty: &'static str,
+    offset: usize,
+    size: usize,
+    debug: fn(&[u8]) -> String,
+}
+
+impl FieldInfo {
+    /// Describes the field `name` of type `F`, spelled `ty`, found at `offset`.
+    ///
+    /// This is used by [binary_fields], which checks the types of the fields.
+    ///
+    /// # Safety
+    ///
+    /// The containing type must hold a `F` at `offset`, since its bytes are decoded as one to
+    /// display it.
+    ///
+    /// [binary_fields]: crate::binary_fields
+    pub const unsafe fn new<F: fmt::Debug>(name: &'static str, ty: &'static str, offset: usize) -> Self {
+        Self {
+            name,
+            ty,
+            offset,
+            size: size_of::<F>(),
+            debug: debug_bytes::<F>,
+        }
+    }
+
+    /// Returns the name of the field, its index prefixed by a dot for tuple structs.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the type of the field, as spelled in its declaration.
+    pub const fn ty(&self) -> &'static str {
+        self.ty
+    }
+
+    /// Returns the offset of the field within the type.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the size of the field in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl fmt::Debug for FieldInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldInfo")
+            .field("name", &self.name)
+            .field("ty", &self.ty)
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+fn debug_bytes<F: fmt::Debug>(bytes: &[u8]) -> String {
+    // SAFETY: FieldInfo::new requires the bytes of this field to hold a F, and they're only
+    // taken from values of the containing type.
+    let value = unsafe { ptr::read_unaligned(bytes.as_ptr() as *const F) };
+    let debug = format!("{value:?}");
+    std::mem::forget(value);
+
+    debug
+}
+
+/// Types whose fields are known along with their types, so two values can be compared field by
+/// field with [binary_eq_report] and [assert_eq_binary].
+///
+/// Implementations are usually generated with [binary_fields]. Fields must be listed in
+/// declaration order, and padding bytes between them aren't compared.
+///
+/// [binary_eq_report]: binary_eq_report
+/// [assert_eq_binary]: crate::assert_eq_binary
+/// [binary_fields]: crate::binary_fields
+pub trait BinaryFields: Sized {
+    /// The fields of the type.
+    const FIELDS: &'static [FieldInfo];
+}
+
+/// Where two values compared with [binary_eq_report] first differ.
+///
+/// Its `Display` implementation is the focused report [assert_eq_binary] panics with: the
+/// differing field and both its values, followed by a hex excerpt of the bytes around the
+/// difference.
+///
+/// [binary_eq_report]: binary_eq_report
+/// [assert_eq_binary]: crate::assert_eq_binary
+#[derive(Debug, Clone)]
+pub struct DiffReport {
+    /// Offset of the first differing byte within the type.
+    pub offset: usize,
+    /// The field holding that byte.
+    pub field: FieldInfo,
+    /// The value of the field on the left, formatted with `Debug`.
+    pub left: String,
+    /// The value of the field on the right, formatted with `Debug`.
+    pub right: String,
+    /// Offset of the first byte of the excerpts.
+    pub excerpt_offset: usize,
+    /// Bytes of the left value around the difference.
+    pub left_excerpt: Vec<u8>,
+    /// Bytes of the right value around the difference.
+    pub right_excerpt: Vec<u8>,
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = &self.field;
+
+        writeln!(
+            f,
+            "first difference at byte {}, in field `{}: {}` (bytes {}..{})",
+            self.offset, field.name, field.ty, field.offset, field.offset + field.size
+        )?;
+        writeln!(f, "  left:  {}", self.left)?;
+        writeln!(f, "  right: {}", self.right)?;
+        writeln!(f, "bytes {}..{}:", self.excerpt_offset, self.excerpt_offset + self.left_excerpt.len())?;
+        writeln!(f, "  left:  {}", hex(&self.left_excerpt))?;
+        writeln!(f, "  right: {}", hex(&self.right_excerpt))?;
+        write!(f, "         {}^^", "   ".repeat(self.offset - self.excerpt_offset))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" ")
+}
+
+/// Compares the bytes of the fields of two values, returning where they first differ, or
+/// `None` if every field holds the same bytes.
+///
+/// Padding between fields is skipped, so values that only differ there compare equal. Fields
+/// are compared byte for byte, so a nested struct is compared with its own padding, and floats
+/// are equal when their bits are, unlike with `PartialEq`. This backs [assert_eq_binary], and
+/// is meant for custom test harnesses reporting failures their own way.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{binary_eq_report, binary_fields};
+///
+/// #[repr(C)]
+/// #[derive(Debug)]
+/// struct Quote {
+///     bid: f64,
+///     ask: f64,
+///     size: u32
+/// }
+///
+/// binary_fields!(Quote { bid: f64, ask: f64, size: u32 });
+///
+/// let a = Quote { bid: 1.5, ask: 1.75, size: 100 };
+/// let b = Quote { bid: 1.5, ask: 1.75, size: 200 };
+///
+/// let report = binary_eq_report(&a, &b).unwrap();
+/// assert_eq!(report.field.name(), "size");
+/// assert_eq!((report.left.as_str(), report.right.as_str()), ("100", "200"));
+///
+/// assert!(binary_eq_report(&a, &a).is_none());
+/// ```
+///
+/// [assert_eq_binary]: crate::assert_eq_binary
+pub fn binary_eq_report<T: BinaryFields>(left: &T, right: &T) -> Option<DiffReport> {
+    let left = bytes::as_bytes(left);
+    let right = bytes::as_bytes(right);
+
+    T::FIELDS.iter().find_map(|field| {
+        let range = field.offset..field.offset + field.size;
+        let (left_field, right_field) = (&left[range.clone()], &right[range]);
+
+        let position = left_field.iter().zip(right_field).position(|(l, r)| l != r)?;
+        let offset = field.offset + position;
+        let excerpt = offset.saturating_sub(EXCERPT_CONTEXT)..(offset + EXCERPT_CONTEXT + 1).min(left.len());
+
+        Some(DiffReport {
+            offset,
+            field: *field,
+            left: (field.debug)(left_field),
+            right: (field.debug)(right_field),
+            excerpt_offset: excerpt.start,
+            left_excerpt: left[excerpt.clone()].to_vec(),
+            right_excerpt: right[excerpt].to_vec(),
+        })
+    })
+}
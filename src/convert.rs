@@ -0,0 +1,58 @@
+use std::io;
+use crate::{iter::read_record, BinaryRead, BinaryWrite};
+
+/// Streams the records of `A` in `reader` to `writer` as records of `B`, mapping each one with
+/// `f`, and returns how many were converted.
+///
+/// This is the building block of file migrations between struct layouts: records are read,
+/// converted and written one at a time, so files of any size are converted in constant memory.
+/// Conversion stops at the end of `reader`, which ending in the middle of a record is reported
+/// as an `UnexpectedEof` error. Records are read like with [read_binary], without validation,
+/// and both sides are used unbuffered, so wrap them in a `BufReader` and `BufWriter`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::convert_records;
+/// use std::{fs::File, io::{self, BufReader, BufWriter, Write}};
+///
+/// #[repr(C)]
+/// struct SampleV1 {
+///     timestamp: u32,
+///     value: f32
+/// }
+///
+/// #[repr(C)]
+/// struct SampleV2 {
+///     timestamp: u64,
+///     value: f64,
+///     sensor: u32
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut reader = BufReader::new(File::open("samples_v1.bin")?);
+///     let mut writer = BufWriter::new(File::create("samples_v2.bin")?);
+///
+///     let converted = convert_records(&mut reader, &mut writer, |old: SampleV1| SampleV2 {
+///         timestamp: u64::from(old.timestamp),
+///         value: f64::from(old.value),
+///         sensor: 0
+///     })?;
+///
+///     writer.flush()?;
+///     println!("migrated {converted} samples");
+///     Ok(())
+/// }
+/// ```
+///
+/// [read_binary]: crate::BinaryRead::read_binary
+pub fn convert_records<A, B>(reader: &mut impl BinaryRead, writer: &mut impl BinaryWrite, mut f: impl FnMut(A) -> B) -> io::Result<usize> {
+    let mut converted = 0;
+
+    while let Some(item) = read_record::<A, _>(reader)? {
+        writer.write_binary(&f(item))?;
+        converted += 1;
+    }
+
+    Ok(converted)
+}
@@ -0,0 +1,107 @@
+use std::io;
+
+/// Lookup table of the reflected CRC-32 polynomial used by zlib, PNG and Ethernet.
+const TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+};
+
+/// Size of a checksum stored within a record.
+pub(crate) const CRC_SIZE: usize = 4;
+
+/// An incremental CRC-32 (IEEE) computation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Crc32(u32);
+
+impl Crc32 {
+    pub(crate) const fn new() -> Self {
+        Self(!0)
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |crc, &byte| TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8));
+    }
+
+    pub(crate) const fn finish(&self) -> u32 {
+        !self.0
+    }
+}
+
+/// Computes the CRC-32 (IEEE) of `bytes`, the checksum used by zlib, PNG and Ethernet.
+///
+/// This is the same checksum this crate stores in [RecordHeader], [FileFooter] and checksummed
+/// [BinaryLog] records, so it can be used to check them by hand or to checksum any other byte
+/// range.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{crc32, BinaryWrite};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut buffer = Vec::new();
+///     buffer.write_binary(&[1u32, 2, 3])?;
+///
+///     let crc = crc32(&buffer);
+///     buffer.extend_from_slice(&crc.to_le_bytes());
+///
+///     assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+///     Ok(())
+/// }
+/// ```
+///
+/// [RecordHeader]: crate::RecordHeader
+/// [FileFooter]: crate::FileFooter
+/// [BinaryLog]: crate::BinaryLog
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finish()
+}
+
+/// Fails with `InvalidInput` if a `u32` checksum at `offset` doesn't fit in a record of `len`
+/// bytes, so it can be checked before any I/O.
+pub(crate) fn check_offset(offset: usize, len: usize) -> io::Result<()> {
+    if offset.checked_add(CRC_SIZE).is_some_and(|end| end <= len) {
+        return Ok(());
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("a 4 bytes checksum at offset {offset} doesn't fit in a {len} bytes record")
+    ))
+}
+
+/// Computes the CRC-32 of the bytes of a record, skipping the `u32` checksum field at `offset`.
+///
+/// # Panics
+///
+/// Panics if a `u32` at `offset` doesn't fit within the record.
+pub(crate) fn crc32_excluding(record: &[u8], offset: usize) -> u32 {
+    assert!(
+        offset.checked_add(CRC_SIZE).is_some_and(|end| end <= record.len()),
+        "a 4 bytes checksum at offset {offset} doesn't fit in a {} bytes record",
+        record.len()
+    );
+
+    let mut crc = Crc32::new();
+    crc.update(&record[..offset]);
+    crc.update(&record[offset + CRC_SIZE..]);
+    crc.finish()
+}
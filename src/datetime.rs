@@ -0,0 +1,258 @@
+use std::{fmt, io::{self, Read, Write}, time::{Duration, SystemTime, UNIX_EPOCH}};
+use crate::{Validate, ValidationError, Zeroable};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// A UTC timestamp with a fixed binary layout, to be stored in records instead of the types of
+/// a time library.
+///
+/// It's made of the seconds since the unix epoch, as an `i64`, and the nanoseconds within that
+/// second, as a `u32` always below one billion, followed by four reserved zero bytes, 16 bytes in
+/// total. The layout is the same whichever conversions are enabled, so files written through
+/// `SystemTime`, the `chrono` feature or the `time` feature are interchangeable. Records read
+/// from untrusted sources should be validated, since [Validate] rejects out of range
+/// nanoseconds.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::BinDateTimeUtc;
+///
+/// let timestamp = BinDateTimeUtc::new(1_700_000_000, 500_000_000).unwrap();
+/// assert_eq!(timestamp.to_string(), "2023-11-14T22:13:20.500000000Z");
+///
+/// assert!(BinDateTimeUtc::new(0, 1_000_000_000).is_none());
+/// ```
+///
+/// [Validate]: crate::Validate
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BinDateTimeUtc {
+    secs: i64,
+    nanos: u32,
+    reserved: u32,
+}
+
+impl BinDateTimeUtc {
+    /// The unix epoch, 1970-01-01T00:00:00Z.
+    pub const UNIX_EPOCH: Self = Self { secs: 0, nanos: 0, reserved: 0 };
+
+    /// Creates a timestamp from the seconds since the unix epoch and the nanoseconds within that
+    /// second, returning `None` if `nanos` isn't below one billion.
+    pub const fn new(secs: i64, nanos: u32) -> Option<Self> {
+        if nanos >= NANOS_PER_SEC {
+            return None;
+        }
+
+        Some(Self { secs, nanos, reserved: 0 })
+    }
+
+    /// Returns the current time, from [SystemTime::now].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system clock is out of the range of an `i64` of seconds.
+    ///
+    /// [SystemTime::now]: std::time::SystemTime::now
+    pub fn now() -> Self {
+        SystemTime::now().try_into().expect("system time out of range")
+    }
+
+    /// Returns the seconds since the unix epoch, negative before it.
+    pub const fn secs(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the nanoseconds within the second.
+    pub const fn nanos(&self) -> u32 {
+        self.nanos
+    }
+}
+
+impl fmt::Display for BinDateTimeUtc {
+    /// Formats the timestamp as RFC 3339, as in `2023-11-14T22:13:20.500000000Z`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.secs.div_euclid(86400);
+        let secs = self.secs.rem_euclid(86400);
+
+        // Converts days since the epoch into a civil date, as described in
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days as i128 + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i128::from(month <= 2);
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:09}Z",
+            secs / 3600, secs / 60 % 60, secs % 60, self.nanos
+        )
+    }
+}
+
+impl TryFrom<SystemTime> for BinDateTimeUtc {
+    type Error = ValidationError;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        let out_of_range = || ValidationError::new(0, "time out of the range of BinDateTimeUtc");
+
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => Ok(Self {
+                secs: after.as_secs().try_into().map_err(|_| out_of_range())?,
+                nanos: after.subsec_nanos(),
+                reserved: 0,
+            }),
+            Err(before) => {
+                let before = before.duration();
+                let secs = i64::try_from(before.as_secs()).map_err(|_| out_of_range())?;
+
+                Ok(match before.subsec_nanos() {
+                    0 => Self { secs: -secs, nanos: 0, reserved: 0 },
+                    nanos => Self {
+                        secs: (-secs).checked_sub(1).ok_or_else(out_of_range)?,
+                        nanos: NANOS_PER_SEC - nanos,
+                        reserved: 0,
+                    }
+                })
+            }
+        }
+    }
+}
+
+impl TryFrom<BinDateTimeUtc> for SystemTime {
+    type Error = ValidationError;
+
+    fn try_from(time: BinDateTimeUtc) -> Result<Self, Self::Error> {
+        let since_epoch = Duration::new(time.secs.unsigned_abs(), 0);
+        let nanos = Duration::from_nanos(time.nanos.into());
+
+        let result = match time.secs {
+            0.. => UNIX_EPOCH.checked_add(since_epoch),
+            _ => UNIX_EPOCH.checked_sub(since_epoch)
+        };
+
+        result.and_then(|time| time.checked_add(nanos))
+            .ok_or_else(|| ValidationError::new(0, "time out of the range of SystemTime"))
+    }
+}
+
+impl Validate for BinDateTimeUtc {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        if bytes.len() != std::mem::size_of::<Self>() {
+            return Err(ValidationError::new(0, "length doesn't match the size of BinDateTimeUtc"));
+        }
+
+        let nanos = u32::from_ne_bytes(bytes[8..12].try_into().unwrap());
+
+        if nanos >= NANOS_PER_SEC {
+            return Err(ValidationError::new(0, "not below one billion").in_field("nanos", 8));
+        }
+
+        Ok(())
+    }
+}
+
+crate::swap_bytes!(BinDateTimeUtc { secs: i64, nanos: u32, reserved: u32 });
+
+// SAFETY: all zeroes is the unix epoch.
+unsafe impl Zeroable for BinDateTimeUtc {}
+
+/// Reads a duration as the seconds, as a little endian `u64`, followed by the nanoseconds within
+/// the last second, as a little endian `u32`.
+pub(crate) fn read_duration<R: Read + ?Sized>(reader: &mut R) -> io::Result<Duration> {
+    let mut bytes = [0; 12];
+    reader.read_exact(&mut bytes)?;
+
+    let secs = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    let nanos = u32::from_le_bytes(bytes[8..].try_into().unwrap());
+
+    if nanos >= NANOS_PER_SEC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("duration of {secs} seconds has {nanos} nanoseconds, not below one billion")
+        ));
+    }
+
+    Ok(Duration::new(secs, nanos))
+}
+
+/// Writes a duration, see [read_duration].
+pub(crate) fn write_duration<W: Write + ?Sized>(writer: &mut W, duration: Duration) -> io::Result<()> {
+    let mut bytes = [0; 12];
+    bytes[..8].copy_from_slice(&duration.as_secs().to_le_bytes());
+    bytes[8..].copy_from_slice(&duration.subsec_nanos().to_le_bytes());
+
+    writer.write_all(&bytes)
+}
+
+/// Reads a time as its duration since the unix epoch, see [read_duration].
+pub(crate) fn read_system_time<R: Read + ?Sized>(reader: &mut R) -> io::Result<SystemTime> {
+    let since_epoch = read_duration(reader)?;
+
+    UNIX_EPOCH.checked_add(since_epoch)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "time out of the range of SystemTime"))
+}
+
+/// Writes a time as its duration since the unix epoch, see [read_duration].
+pub(crate) fn write_system_time<W: Write + ?Sized>(writer: &mut W, time: SystemTime) -> io::Result<()> {
+    let since_epoch = time.duration_since(UNIX_EPOCH).map_err(|_| io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "times before the unix epoch can't be written as a duration since it"
+    ))?;
+
+    write_duration(writer, since_epoch)
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for BinDateTimeUtc {
+    type Error = ValidationError;
+
+    /// Converts a chrono timestamp, failing for leap seconds, which chrono represents with
+    /// nanoseconds above one billion.
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+        Self::new(time.timestamp(), time.timestamp_subsec_nanos())
+            .ok_or_else(|| ValidationError::new(0, "leap seconds can't be represented"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<BinDateTimeUtc> for chrono::DateTime<chrono::Utc> {
+    type Error = ValidationError;
+
+    fn try_from(time: BinDateTimeUtc) -> Result<Self, Self::Error> {
+        chrono::DateTime::from_timestamp(time.secs, time.nanos)
+            .ok_or_else(|| ValidationError::new(0, "time out of the range of chrono"))
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl From<time::OffsetDateTime> for BinDateTimeUtc {
+    fn from(time: time::OffsetDateTime) -> Self {
+        Self {
+            secs: time.unix_timestamp(),
+            nanos: time.nanosecond(),
+            reserved: 0,
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<BinDateTimeUtc> for time::OffsetDateTime {
+    type Error = ValidationError;
+
+    /// Converts the timestamp into a UTC date time.
+    fn try_from(time: BinDateTimeUtc) -> Result<Self, Self::Error> {
+        time::OffsetDateTime::from_unix_timestamp(time.secs)
+            .map(|date| date + time::Duration::nanoseconds(time.nanos.into()))
+            .map_err(|_| ValidationError::new(0, "time out of the range of time"))
+    }
+}
@@ -0,0 +1,196 @@
+use std::{error::Error, fmt, io::{self, Read}, net::TcpStream, time::{Duration, Instant}};
+
+/// Sources whose per-call read timeout can be adjusted, such as sockets.
+///
+/// [DeadlineReader] uses this to shrink the timeout of every underlying `read` call as its
+/// deadline approaches, so a blocked read never outlives the remaining budget.
+///
+/// [DeadlineReader]: DeadlineReader
+pub trait SetReadTimeout {
+    /// Sets the timeout applied to each individual read call, `None` meaning no timeout.
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+impl SetReadTimeout for TcpStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+#[cfg(unix)]
+impl SetReadTimeout for std::os::unix::net::UnixStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        std::os::unix::net::UnixStream::set_read_timeout(self, timeout)
+    }
+}
+
+impl<T: SetReadTimeout + ?Sized> SetReadTimeout for &T {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+/// Error carried by the `TimedOut` [io::Error] returned once a [DeadlineReader] runs out of time.
+///
+/// It can be recovered with `error.get_ref().and_then(|e| e.downcast_ref::<DeadlineExceeded>())`.
+///
+/// [io::Error]: std::io::Error
+/// [DeadlineReader]: DeadlineReader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeadlineExceeded {
+    /// Bytes consumed from the source since the deadline was armed.
+    pub consumed: usize,
+    /// The total budget that was exceeded.
+    pub budget: Duration,
+}
+
+impl fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "read deadline of {:?} exceeded after {} bytes", self.budget, self.consumed)
+    }
+}
+
+impl Error for DeadlineExceeded {}
+
+type SetTimeoutFn<R> = fn(&R, Option<Duration>) -> io::Result<()>;
+
+/// A [Read] adapter enforcing a total wall-clock budget across all the read calls needed to
+/// complete one record.
+///
+/// Socket timeouts apply to each syscall, so a peer trickling one byte at a time can keep
+/// [read_binary] waiting forever without ever tripping them. This reader arms a deadline on the
+/// first read and fails with [TimedOut] once it passes, no matter how many partial reads
+/// happened in between. Call [reset] before each record to give it a fresh budget.
+///
+/// When created with [with_timeouts], the per-call timeout of the source is shrunk to the
+/// remaining budget before every read, so even a completely silent peer is bounded. Otherwise
+/// the deadline is only checked between reads.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{BinaryRead, DeadlineReader};
+/// use std::{io, net::TcpStream, time::Duration};
+///
+/// #[derive(Debug)]
+/// struct MyStruct {
+///     a: u32,
+///     b: u64
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let stream = TcpStream::connect("127.0.0.1:8080")?;
+///     let mut reader = DeadlineReader::with_timeouts(stream, Duration::from_secs(1));
+///
+///     loop {
+///         // Each record must arrive in full within a second.
+///         reader.reset();
+///         let item = reader.read_binary::<MyStruct>()?;
+///         println!("{item:?}");
+///     }
+/// }
+/// ```
+///
+/// [Read]: std::io::Read
+/// [read_binary]: crate::BinaryRead::read_binary
+/// [TimedOut]: std::io::ErrorKind::TimedOut
+/// [reset]: DeadlineReader::reset
+/// [with_timeouts]: DeadlineReader::with_timeouts
+pub struct DeadlineReader<R> {
+    inner: R,
+    budget: Duration,
+    deadline: Option<Instant>,
+    consumed: usize,
+    set_timeout: Option<SetTimeoutFn<R>>,
+}
+
+impl<R> DeadlineReader<R> {
+    /// Creates a reader that checks the deadline between reads of `inner`.
+    pub fn new(inner: R, budget: Duration) -> Self {
+        Self {
+            inner,
+            budget,
+            deadline: None,
+            consumed: 0,
+            set_timeout: None,
+        }
+    }
+
+    /// Creates a reader that also adjusts the per-call timeout of `inner` to the remaining budget.
+    ///
+    /// The timeout of `inner` is left modified when this reader is dropped or unwrapped.
+    pub fn with_timeouts(inner: R, budget: Duration) -> Self
+    where
+        R: SetReadTimeout
+    {
+        Self {
+            set_timeout: Some(R::set_read_timeout),
+            ..Self::new(inner, budget)
+        }
+    }
+
+    /// Disarms the deadline, the next read starts a new budget.
+    pub fn reset(&mut self) {
+        self.deadline = None;
+        self.consumed = 0;
+    }
+
+    /// Bytes consumed since the deadline was last armed.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// The budget given to each record.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn exceeded(&self) -> io::Error {
+        io::Error::new(io::ErrorKind::TimedOut, DeadlineExceeded {
+            consumed: self.consumed,
+            budget: self.budget,
+        })
+    }
+}
+
+impl<R: Read> Read for DeadlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let deadline = *self.deadline.get_or_insert_with(|| Instant::now() + self.budget);
+
+        let remaining = match deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) if !remaining.is_zero() => remaining,
+            _ => return Err(self.exceeded())
+        };
+
+        if let Some(set_timeout) = self.set_timeout {
+            set_timeout(&self.inner, Some(remaining))?;
+        }
+
+        match self.inner.read(buf) {
+            Ok(read) => {
+                self.consumed += read;
+                Ok(read)
+            },
+            // Unix reports an expired socket timeout as WouldBlock, Windows as TimedOut.
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut)
+                && (self.set_timeout.is_some() || Instant::now() >= deadline) => Err(self.exceeded()),
+            Err(e) => Err(e)
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::io::{self, Read, Write};
+use crate::{BinaryRead, BinaryWrite};
+
+/// A writer collapsing runs of identical consecutive records into a single `(count, record)`
+/// pair, where `count` is a `u32`.
+///
+/// This shrinks slowly-changing data, like telemetry, considerably. The last record is kept
+/// until a different one is written, so [finish] must be called once done to write the pending
+/// run, records still pending are lost if the writer is dropped without calling it. The
+/// records can be read back with [DedupReader].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{DedupReader, DedupWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = DedupWriter::new(Vec::new());
+///
+///     for reading in [20u16, 20, 20, 21] {
+///         writer.write_record(reading)?;
+///     }
+///
+///     let buffer = writer.finish()?;
+///     // Two runs of a u32 count and a u16 reading.
+///     assert_eq!(buffer.len(), 12);
+///
+///     let readings = DedupReader::<_, u16>::new(Cursor::new(buffer))
+///         .collect::<io::Result<Vec<_>>>()?;
+///
+///     assert_eq!(readings, [20, 20, 20, 21]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [finish]: DedupWriter::finish
+/// [DedupReader]: DedupReader
+pub struct DedupWriter<W, T> {
+    inner: W,
+    last: Option<T>,
+    count: u32,
+}
+
+impl<W: Write, T: PartialEq> DedupWriter<W, T> {
+    /// Creates a new writer over `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            last: None,
+            count: 0,
+        }
+    }
+
+    /// Writes a record, which is only buffered if it's the same as the previous one.
+    pub fn write_record(&mut self, item: T) -> io::Result<()> {
+        if self.last.as_ref() == Some(&item) && self.count < u32::MAX {
+            self.count += 1;
+            return Ok(());
+        }
+
+        self.write_run()?;
+        self.last = Some(item);
+        self.count = 1;
+
+        Ok(())
+    }
+
+    /// Writes the pending run, if any, and flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_run()?;
+        self.inner.flush()
+    }
+
+    /// Writes the pending run and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    fn write_run(&mut self) -> io::Result<()> {
+        if let Some(last) = self.last.take() {
+            self.inner.write_binary(&self.count)?;
+            self.inner.write_binary(&last)?;
+            self.count = 0;
+        }
+
+        Ok(())
+    }
+}
+
+/// A reader expanding the `(count, record)` pairs written by [DedupWriter] back into records.
+///
+/// It's an iterator yielding each record as many times as it was written, and stops once the
+/// source ends at a pair boundary.
+///
+/// [DedupWriter]: DedupWriter
+pub struct DedupReader<R, T> {
+    inner: R,
+    current: Option<T>,
+    remaining: u32,
+    done: bool,
+}
+
+impl<R: Read, T: Clone> DedupReader<R, T> {
+    /// Creates a new reader over `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            current: None,
+            remaining: 0,
+            done: false,
+        }
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_run(&mut self) -> io::Result<bool> {
+        let count = match crate::iter::read_record::<u32, _>(&mut self.inner)? {
+            Some(0) => return Err(io::Error::new(io::ErrorKind::InvalidData, "run with a count of zero")),
+            Some(count) => count,
+            None => return Ok(false)
+        };
+
+        self.current = Some(self.inner.read_binary()?);
+        self.remaining = count;
+
+        Ok(true)
+    }
+}
+
+impl<R: Read, T: Clone> Iterator for DedupReader<R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.remaining == 0 {
+            match self.next_run() {
+                Ok(true) => {},
+                Ok(false) => {
+                    self.done = true;
+                    return None;
+                },
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        self.remaining -= 1;
+
+        if self.remaining == 0 {
+            self.current.take().map(Ok)
+        } else {
+            self.current.clone().map(Ok)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, None)
+    }
+}
@@ -0,0 +1,175 @@
+use std::{io::{self, Read, Write}, marker::PhantomData, mem::size_of};
+use crate::{bytes, iter::read_record_bytes, BinaryRead};
+
+/// Size of the delta-encoded field.
+const FIELD_SIZE: usize = size_of::<u64>();
+
+/// Panics if a `u64` at `offset` doesn't fit within a `T`.
+fn check_offset<T>(offset: usize) {
+    assert!(
+        offset.checked_add(FIELD_SIZE).is_some_and(|end| end <= size_of::<T>()),
+        "an 8 bytes field at offset {offset} doesn't fit in a {} bytes record",
+        size_of::<T>()
+    );
+}
+
+fn field(record: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(record[offset..offset + FIELD_SIZE].try_into().unwrap())
+}
+
+fn set_field(record: &mut [u8], offset: usize, value: u64) {
+    record[offset..offset + FIELD_SIZE].copy_from_slice(&value.to_ne_bytes());
+}
+
+/// A writer delta-encoding a `u64` or `i64` field of records of `T`.
+///
+/// The field, given by its offset, is written as is for the first record and as its difference
+/// from the previous record for the rest, leaving the other fields untouched. For sorted values
+/// like timestamps the differences are small, so the output compresses much better. Differences
+/// wrap around, so any sequence round trips, and `u64` and `i64` fields are handled alike. The
+/// records are reconstructed with [DeltaReader].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{DeltaReader, DeltaWriter};
+/// use std::{io::{self, Cursor}, mem::offset_of};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Sample {
+///     timestamp: u64,
+///     value: f64
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let offset = offset_of!(Sample, timestamp);
+///     let mut writer = DeltaWriter::new(Vec::new(), offset);
+///
+///     for timestamp in [1_700_000_000, 1_700_000_005, 1_700_000_010] {
+///         writer.write_record(&Sample { timestamp, value: 0.5 })?;
+///     }
+///
+///     let timestamps = DeltaReader::<_, Sample>::new(Cursor::new(writer.into_inner()), offset)
+///         .map(|sample| sample.map(|sample| sample.timestamp))
+///         .collect::<io::Result<Vec<_>>>()?;
+///
+///     assert_eq!(timestamps, [1_700_000_000, 1_700_000_005, 1_700_000_010]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [DeltaReader]: DeltaReader
+pub struct DeltaWriter<W, T> {
+    inner: W,
+    offset: usize,
+    prev: u64,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<W: Write, T> DeltaWriter<W, T> {
+    /// Creates a new writer over `inner`, whose first record will be written in full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `u64` at `offset` doesn't fit within a `T`.
+    pub fn new(inner: W, offset: usize) -> Self {
+        check_offset::<T>(offset);
+
+        Self {
+            inner,
+            offset,
+            prev: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes a record, replacing its field with the difference from the previous record.
+    pub fn write_record(&mut self, item: &T) -> io::Result<()> {
+        let mut record = bytes::as_bytes(item).to_vec();
+        let value = field(&record, self.offset);
+        set_field(&mut record, self.offset, value.wrapping_sub(self.prev));
+
+        self.inner.write_all(&record)?;
+        self.prev = value;
+
+        Ok(())
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A reader reconstructing the records written by [DeltaWriter].
+///
+/// It's an iterator over the records with the absolute values of the field restored, stopping
+/// once the source ends at a record boundary.
+///
+/// [DeltaWriter]: DeltaWriter
+pub struct DeltaReader<R, T> {
+    inner: R,
+    offset: usize,
+    prev: u64,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T> DeltaReader<R, T> {
+    /// Creates a new reader over `inner`, which must start at the first record.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `u64` at `offset` doesn't fit within a `T`.
+    pub fn new(inner: R, offset: usize) -> Self {
+        check_offset::<T>(offset);
+
+        Self {
+            inner,
+            offset,
+            prev: 0,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_record(&mut self) -> io::Result<Option<T>> {
+        let mut record = vec![0u8; size_of::<T>()];
+
+        if !read_record_bytes(&mut self.inner, &mut record)? {
+            return Ok(None);
+        }
+
+        let value = field(&record, self.offset).wrapping_add(self.prev);
+        set_field(&mut record, self.offset, value);
+        self.prev = value;
+
+        record.as_slice().read_binary().map(Some)
+    }
+}
+
+impl<R: Read, T> Iterator for DeltaReader<R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.next_record().transpose();
+        self.done = !matches!(item, Some(Ok(_)));
+
+        item
+    }
+}
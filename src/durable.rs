@@ -0,0 +1,105 @@
+use std::{fs::File, io::{self, BufWriter, Write}, time::Duration};
+
+/// How much of a file to make durable when syncing it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SyncMode {
+    /// Syncs the contents of the file, and only the metadata needed to read them back, like
+    /// its length, with [File::sync_data].
+    ///
+    /// [File::sync_data]: std::fs::File::sync_data
+    #[default]
+    Data,
+    /// Syncs the contents and all the metadata of the file, with [File::sync_all].
+    ///
+    /// [File::sync_all]: std::fs::File::sync_all
+    All,
+}
+
+/// Writers whose data can be made durable, surviving a crash or power failure once synced.
+///
+/// This is implemented for [File] and for buffered writers over durable ones, which are
+/// flushed before syncing the inner writer.
+///
+/// [File]: std::fs::File
+pub trait Durable: Write {
+    /// Flushes any buffered data and syncs it to the storage device.
+    fn sync(&mut self, mode: SyncMode) -> io::Result<()>;
+
+    /// Truncates or extends the underlying file to `len` bytes, used to discard torn records.
+    ///
+    /// Writers that can't be resized return an `Unsupported` error, the default.
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        let _ = len;
+        Err(io::Error::new(io::ErrorKind::Unsupported, "this writer can't be truncated"))
+    }
+
+    /// Flushes any buffered data and returns another handle to the underlying file, which can
+    /// be synced while this writer is in use, or `None` if there's no such handle, the default.
+    fn sync_handle(&mut self) -> io::Result<Option<File>> {
+        Ok(None)
+    }
+}
+
+impl Durable for File {
+    fn sync(&mut self, mode: SyncMode) -> io::Result<()> {
+        match mode {
+            SyncMode::Data => self.sync_data(),
+            SyncMode::All => self.sync_all()
+        }
+    }
+
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        File::set_len(self, len)
+    }
+
+    fn sync_handle(&mut self) -> io::Result<Option<File>> {
+        self.try_clone().map(Some)
+    }
+}
+
+impl<W: Durable> Durable for BufWriter<W> {
+    fn sync(&mut self, mode: SyncMode) -> io::Result<()> {
+        self.flush()?;
+        self.get_mut().sync(mode)
+    }
+
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        self.flush()?;
+        self.get_mut().set_len(len)
+    }
+
+    fn sync_handle(&mut self) -> io::Result<Option<File>> {
+        self.flush()?;
+        self.get_mut().sync_handle()
+    }
+}
+
+impl<W: Durable + ?Sized> Durable for &mut W {
+    fn sync(&mut self, mode: SyncMode) -> io::Result<()> {
+        (**self).sync(mode)
+    }
+
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        (**self).set_len(len)
+    }
+
+    fn sync_handle(&mut self) -> io::Result<Option<File>> {
+        (**self).sync_handle()
+    }
+}
+
+/// When a [BinaryLog] syncs its appends to the storage device, trading latency for safety.
+///
+/// [BinaryLog]: crate::BinaryLog
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DurabilityPolicy {
+    /// Never syncs on its own, leaving it to the operating system or to explicit syncs.
+    #[default]
+    None,
+    /// Syncs after every `n` appends.
+    EveryN(u32),
+    /// Syncs on the first append after the given time has passed since the last sync.
+    Every(Duration),
+    /// Syncs after every append.
+    Always,
+}
@@ -0,0 +1,156 @@
+use std::num::*;
+use crate::ValidationError;
+
+/// Types whose byte order can be reversed field by field, used to read and write them in a
+/// fixed endianness regardless of the host.
+///
+/// Integers and floats have their bytes reversed, single byte types are left as they are, and
+/// arrays swap each element. Structs are implemented with the [swap_bytes] macro.
+///
+/// Reversing the bytes of a valid value doesn't necessarily produce a valid one: a byte
+/// swapped `char` may not be a unicode scalar value anymore. So swapping bytes in the foreign
+/// order into the native one goes through [swap_bytes_checked], which checks the result, and
+/// [read_binary_le]/[read_binary_be] return an `InvalidData` error instead of producing an
+/// invalid value. When the requested order is the native one nothing is swapped, and like with
+/// [read_binary], no checks are made; use [Validate] for those.
+///
+/// [swap_bytes]: crate::swap_bytes
+/// [swap_bytes_checked]: SwapBytes::swap_bytes_checked
+/// [read_binary_le]: crate::BinaryRead::read_binary_le
+/// [read_binary_be]: crate::BinaryRead::read_binary_be
+/// [read_binary]: crate::BinaryRead::read_binary
+/// [Validate]: crate::Validate
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not binary-safe, its byte order can't be swapped",
+    label = "not binary-safe",
+    note = "`String`, `Vec`, `&str` and other types owning or borrowing memory only hold a pointer to it, \
+            which has no byte order to swap; use a fixed size `[u8; N]` instead"
+)]
+pub trait SwapBytes {
+    /// Reverses, in place, the byte order of each field of the `Self` held in `bytes`.
+    ///
+    /// The result isn't required to be a valid `Self`, this is used to produce bytes in a
+    /// foreign byte order.
+    fn swap_bytes(bytes: &mut [u8]);
+
+    /// Reverses, in place, the byte order of each field of the `Self` held in `bytes`, checking
+    /// that the result is a valid `Self`.
+    ///
+    /// This is used to bring bytes in a foreign byte order into the native one.
+    fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), ValidationError> {
+        Self::swap_bytes(bytes);
+        Ok(())
+    }
+}
+
+macro_rules! reverse {
+    ($($ty: ty),*) => {
+        $(
+            impl SwapBytes for $ty {
+                fn swap_bytes(bytes: &mut [u8]) {
+                    bytes.reverse();
+                }
+            }
+        )*
+    };
+}
+
+// Reversing keeps zero and non zero values as they were, so NonZero types need no checks.
+reverse!(
+    u16, u32, u64, u128, usize, i16, i32, i64, i128, isize, f32, f64,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    Option<NonZeroU16>, Option<NonZeroU32>, Option<NonZeroU64>, Option<NonZeroU128>,
+    Option<NonZeroUsize>, Option<NonZeroI16>, Option<NonZeroI32>, Option<NonZeroI64>,
+    Option<NonZeroI128>, Option<NonZeroIsize>
+);
+
+macro_rules! unchanged {
+    ($($ty: ty),*) => {
+        $(
+            impl SwapBytes for $ty {
+                fn swap_bytes(_: &mut [u8]) {}
+            }
+        )*
+    };
+}
+
+unchanged!(u8, i8, bool, NonZeroU8, NonZeroI8, Option<NonZeroU8>, Option<NonZeroI8>, (), std::marker::PhantomPinned);
+
+impl<T: ?Sized> SwapBytes for std::marker::PhantomData<T> {
+    fn swap_bytes(_: &mut [u8]) {}
+}
+
+impl SwapBytes for char {
+    fn swap_bytes(bytes: &mut [u8]) {
+        bytes.reverse();
+    }
+
+    fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), ValidationError> {
+        bytes.reverse();
+
+        let value = u32::from_ne_bytes((&*bytes).try_into().unwrap());
+
+        match char::from_u32(value) {
+            Some(_) => Ok(()),
+            None => Err(ValidationError::new(0, "byte swapped char is not a valid unicode scalar value"))
+        }
+    }
+}
+
+impl<T: SwapBytes, const N: usize> SwapBytes for [T; N] {
+    fn swap_bytes(bytes: &mut [u8]) {
+        if let Some(size) = std::num::NonZeroUsize::new(std::mem::size_of::<T>()) {
+            bytes.chunks_exact_mut(size.get()).for_each(T::swap_bytes);
+        }
+    }
+
+    fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), ValidationError> {
+        let size = match std::mem::size_of::<T>() {
+            0 => return Ok(()),
+            size => size
+        };
+
+        bytes.chunks_exact_mut(size)
+            .enumerate()
+            .try_for_each(|(index, element)| {
+                T::swap_bytes_checked(element).map_err(|e| ValidationError {
+                    offset: index * size + e.offset,
+                    ..e
+                })
+            })
+    }
+}
+
+/// Reads a `T`, swapping its bytes first if `swap` is set.
+pub(crate) fn read_swapped<T: SwapBytes, R: std::io::Read + ?Sized>(reader: &mut R, swap: bool) -> std::io::Result<T> {
+    let mut item = std::mem::MaybeUninit::<T>::zeroed();
+
+    // SAFETY: the memory is zeroed, so every byte of it is initialized.
+    let bytes = crate::bytes::slice_as_bytes_mut(unsafe {
+        std::slice::from_raw_parts_mut(item.as_mut_ptr(), 1)
+    });
+
+    reader.read_exact(bytes)?;
+
+    if swap {
+        T::swap_bytes_checked(bytes)?;
+    }
+
+    // SAFETY: the bytes have been read and, if swapped, checked to be valid.
+    Ok(unsafe { item.assume_init() })
+}
+
+/// Writes a `T`, swapping its bytes first if `swap` is set.
+pub(crate) fn write_swapped<T: SwapBytes, W: std::io::Write + ?Sized>(writer: &mut W, item: &T, swap: bool) -> std::io::Result<()> {
+    let bytes = crate::bytes::as_bytes(item);
+
+    if !swap {
+        return writer.write_all(bytes);
+    }
+
+    let mut swapped = bytes.to_vec();
+    T::swap_bytes(&mut swapped);
+
+    writer.write_all(&swapped)
+}
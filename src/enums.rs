@@ -0,0 +1,76 @@
+use std::{error::Error, fmt, io::{self, Read, Write}};
+
+/// Enums with data-carrying variants written as a discriminant followed by the payload of the
+/// variant, read with [read_binary_enum] and written with [write_binary_enum].
+///
+/// This trait should be implemented with the [binary_enum] macro.
+///
+/// [read_binary_enum]: crate::BinaryRead::read_binary_enum
+/// [write_binary_enum]: crate::BinaryWrite::write_binary_enum
+/// [binary_enum]: crate::binary_enum
+pub trait BinaryEnum: Sized {
+    /// Size of every frame, discriminant included, if the variants are padded to the largest
+    /// one. `None` if each frame takes only the size of its own variant.
+    const FRAME_SIZE: Option<usize>;
+
+    /// Writes the discriminant of the variant followed by its payload.
+    fn write_enum<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Reads a discriminant and the payload of the variant it refers to.
+    ///
+    /// An unknown discriminant is reported as an `InvalidData` error carrying an
+    /// [UnknownVariant].
+    ///
+    /// [UnknownVariant]: UnknownVariant
+    fn read_enum<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+/// Error carried by the `InvalidData` [io::Error] returned when reading a discriminant that
+/// doesn't match any variant of a [BinaryEnum].
+///
+/// [io::Error]: std::io::Error
+/// [BinaryEnum]: BinaryEnum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnknownVariant {
+    /// Name of the enum being read.
+    pub name: &'static str,
+    /// The discriminant that was read.
+    pub tag: u64,
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown discriminant {} for `{}`", self.tag, self.name)
+    }
+}
+
+impl Error for UnknownVariant {}
+
+impl From<UnknownVariant> for io::Error {
+    fn from(error: UnknownVariant) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// Writes `len` zero bytes, padding a variant up to the frame size.
+#[doc(hidden)]
+pub fn write_padding<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    io::copy(&mut io::repeat(0).take(len as u64), writer)?;
+    Ok(())
+}
+
+/// Discards `len` bytes, skipping the padding of a variant up to the frame size.
+#[doc(hidden)]
+pub fn skip_padding<R: Read>(reader: &mut R, len: usize) -> io::Result<()> {
+    let skipped = io::copy(&mut reader.take(len as u64), &mut io::sink())?;
+
+    if skipped < len as u64 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("source ended {skipped} bytes into {len} bytes of padding")
+        ));
+    }
+
+    Ok(())
+}
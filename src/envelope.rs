@@ -0,0 +1,190 @@
+use std::{io::{self, Read, Seek, SeekFrom, Write}, marker::PhantomData};
+use crate::{seek::{record_offset, stream_len, whole_records}, BinDateTimeUtc, BinaryRead, BinaryWrite};
+
+/// A record of `T` wrapped with a sequence number and the time it was written.
+///
+/// The sequence number is a `u64`, followed by a [BinDateTimeUtc] and the payload, with the
+/// layout of a `repr(C)` struct. It's [Zeroable], [Validate] and [SwapBytes] whenever `T` is.
+/// Envelopes are usually written through an [EnvelopedWriter], which numbers and timestamps
+/// them.
+///
+/// [BinDateTimeUtc]: crate::BinDateTimeUtc
+/// [Zeroable]: crate::Zeroable
+/// [Validate]: crate::Validate
+/// [SwapBytes]: crate::SwapBytes
+/// [EnvelopedWriter]: EnvelopedWriter
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Envelope<T> {
+    sequence: u64,
+    written_at: BinDateTimeUtc,
+    payload: T,
+}
+
+impl<T> Envelope<T> {
+    /// Wraps `payload` with the given sequence number and time.
+    pub const fn new(sequence: u64, written_at: BinDateTimeUtc, payload: T) -> Self {
+        Self { sequence, written_at, payload }
+    }
+
+    /// Returns the sequence number.
+    pub const fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the time the record was written.
+    pub const fn written_at(&self) -> BinDateTimeUtc {
+        self.written_at
+    }
+
+    /// Returns a reference to the payload.
+    pub const fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    /// Unwraps the envelope, returning the payload.
+    pub fn into_payload(self) -> T {
+        self.payload
+    }
+}
+
+crate::zeroable!([T: crate::Zeroable] Envelope<T> {
+    sequence: u64,
+    written_at: BinDateTimeUtc,
+    payload: T,
+});
+
+crate::validate!([T: crate::Validate] Envelope<T> {
+    sequence: u64,
+    written_at: BinDateTimeUtc,
+    payload: T,
+});
+
+crate::swap_bytes!([T: crate::SwapBytes] Envelope<T> {
+    sequence: u64,
+    written_at: BinDateTimeUtc,
+    payload: T,
+});
+
+/// A writer wrapping records of `T` in [Envelope]s, numbering them with an increasing sequence
+/// and stamping them with the time they are written.
+///
+/// Sequences start at zero unless set with [with_sequence], or resumed after the last record
+/// of an existing file with [resume_from]. The time comes from [BinDateTimeUtc::now] unless
+/// another clock is set with [with_clock], useful for deterministic output.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinDateTimeUtc, BinaryRead, Envelope, EnvelopedWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = EnvelopedWriter::new(Vec::new())
+///         .with_clock(|| BinDateTimeUtc::UNIX_EPOCH);
+///
+///     writer.write_payload(10u32)?;
+///     writer.write_payload(20u32)?;
+///
+///     let mut reader = Cursor::new(writer.into_inner());
+///     let envelopes = reader.read_binary_vec::<Envelope<u32>>(2)?;
+///
+///     assert_eq!(envelopes[1].sequence(), 1);
+///     assert_eq!(*envelopes[1].payload(), 20);
+///     Ok(())
+/// }
+/// ```
+///
+/// [Envelope]: Envelope
+/// [with_sequence]: EnvelopedWriter::with_sequence
+/// [resume_from]: EnvelopedWriter::resume_from
+/// [BinDateTimeUtc::now]: crate::BinDateTimeUtc::now
+/// [with_clock]: EnvelopedWriter::with_clock
+pub struct EnvelopedWriter<W, T, C = fn() -> BinDateTimeUtc> {
+    inner: W,
+    next: u64,
+    clock: C,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<W: Write, T> EnvelopedWriter<W, T> {
+    /// Creates a new writer over `inner`, starting at sequence zero.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            next: 0,
+            clock: BinDateTimeUtc::now,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W: Write, T, C: FnMut() -> BinDateTimeUtc> EnvelopedWriter<W, T, C> {
+    /// Sets the sequence number of the next record.
+    pub fn with_sequence(mut self, next: u64) -> Self {
+        self.next = next;
+        self
+    }
+
+    /// Continues the sequence after the last envelope of `existing`, usually the file being
+    /// appended to, which is left at the same position.
+    ///
+    /// An empty source starts at sequence zero, and one not holding whole envelopes returns an
+    /// `InvalidData` error carrying a [Misaligned].
+    ///
+    /// [Misaligned]: crate::Misaligned
+    pub fn resume_from<R: Read + Seek>(self, existing: &mut R) -> io::Result<Self> {
+        let next = next_sequence::<T, _>(existing)?;
+        Ok(self.with_sequence(next))
+    }
+
+    /// Sets the clock used to timestamp the records.
+    pub fn with_clock<C2: FnMut() -> BinDateTimeUtc>(self, clock: C2) -> EnvelopedWriter<W, T, C2> {
+        EnvelopedWriter {
+            inner: self.inner,
+            next: self.next,
+            clock,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wraps `payload` in an envelope and writes it, returning its sequence number.
+    pub fn write_payload(&mut self, payload: T) -> io::Result<u64> {
+        let sequence = self.next;
+        self.inner.write_binary(&Envelope::new(sequence, (self.clock)(), payload))?;
+        self.next += 1;
+
+        Ok(sequence)
+    }
+
+    /// Returns the sequence number of the next record.
+    pub fn next_sequence(&self) -> u64 {
+        self.next
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads the sequence number following the last envelope of `source`, restoring its position.
+pub(crate) fn next_sequence<T, R: Read + Seek>(source: &mut R) -> io::Result<u64> {
+    let position = source.stream_position()?;
+    let records = whole_records::<Envelope<T>>(stream_len(source)?)?;
+
+    if records == 0 {
+        return Ok(0);
+    }
+
+    source.seek(SeekFrom::Start(record_offset::<Envelope<T>>(records - 1)?))?;
+    let last = source.read_binary::<u64>();
+    source.seek(SeekFrom::Start(position))?;
+
+    Ok(last? + 1)
+}
@@ -0,0 +1,24 @@
+/// Structs modelling a C flexible array member with a trailing zero-length array field.
+///
+/// C declares variable-length structs as `struct { uint16_t len; uint32_t data[]; }`, which Rust
+/// mirrors with a `data: [u32; 0]` field. The plain [read_binary] path only sees the fixed part of
+/// such a struct, so [read_binary_fam] and [write_binary_fam] use this trait to know where the
+/// array starts and the type of its elements.
+///
+/// This trait should be implemented with the [flexible_array] macro.
+///
+/// # Safety
+///
+/// `FAM_OFFSET` must be the offset of the zero-length array field, which is at most the size of
+/// the struct.
+///
+/// [read_binary]: crate::BinaryRead::read_binary
+/// [read_binary_fam]: crate::BinaryRead::read_binary_fam
+/// [write_binary_fam]: crate::BinaryWrite::write_binary_fam
+/// [flexible_array]: crate::flexible_array
+pub unsafe trait FlexibleArray: Sized {
+    /// Type of the elements of the flexible array.
+    type Element;
+    /// Offset of the flexible array within the struct, where its elements start on the wire.
+    const FAM_OFFSET: usize;
+}
@@ -0,0 +1,222 @@
+use std::{fs::File, io::{self, Read, Write}, mem::ManuallyDrop};
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle, RawHandle};
+
+/// An open descriptor, read and written through a `File` wrapping it, which is only closed on
+/// drop if it's owned.
+struct Descriptor {
+    file: ManuallyDrop<File>,
+    owned: bool,
+}
+
+impl Descriptor {
+    fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        retry_interrupted(|| (&*self.file).read(buf))
+    }
+
+    fn write(&self, buf: &[u8]) -> io::Result<usize> {
+        retry_interrupted(|| (&*self.file).write(buf))
+    }
+}
+
+impl Drop for Descriptor {
+    fn drop(&mut self) {
+        if self.owned {
+            // SAFETY: the file is never used again.
+            unsafe { ManuallyDrop::drop(&mut self.file) }
+        }
+    }
+}
+
+/// Repeats a read or write call for as long as it's interrupted by a signal.
+fn retry_interrupted(mut call: impl FnMut() -> io::Result<usize>) -> io::Result<usize> {
+    loop {
+        match call() {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            result => return result
+        }
+    }
+}
+
+/// A [Read] implementation over a raw file descriptor, or handle on Windows, such as one handed
+/// over by a C library, to read records from it with [read_binary].
+///
+/// Each read is a single `read` system call on the descriptor, repeated if interrupted by a
+/// signal. A reader created with [borrow] leaves the descriptor open when dropped, its owner
+/// being in charge of closing it, while one created with [from_owned] closes it.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, BinaryWrite, FdReader};
+/// use std::{io, os::fd::AsRawFd};
+///
+/// fn main() -> io::Result<()> {
+///     let (pipe_reader, mut pipe_writer) = io::pipe()?;
+///     pipe_writer.write_binary(&42u64)?;
+///
+///     // SAFETY: pipe_reader keeps the descriptor open while the reader lives.
+///     let mut reader = unsafe { FdReader::borrow(pipe_reader.as_raw_fd()) };
+///     assert_eq!(reader.read_binary::<u64>()?, 42);
+///     Ok(())
+/// }
+/// ```
+///
+/// [Read]: std::io::Read
+/// [read_binary]: crate::BinaryRead::read_binary
+/// [borrow]: FdReader::borrow
+/// [from_owned]: FdReader::from_owned
+pub struct FdReader(Descriptor);
+
+/// A [Write] implementation over a raw file descriptor, or handle on Windows, such as one handed
+/// over by a C library, to write records to it with [write_binary].
+///
+/// Each write is a single `write` system call on the descriptor, repeated if interrupted by a
+/// signal. Nothing is buffered, so a record is written with as many system calls as the
+/// descriptor takes to accept it. A writer created with [borrow] leaves the descriptor open
+/// when dropped, its owner being in charge of closing it, while one created with [from_owned]
+/// closes it.
+///
+/// [Write]: std::io::Write
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [borrow]: FdWriter::borrow
+/// [from_owned]: FdWriter::from_owned
+pub struct FdWriter(Descriptor);
+
+#[cfg(unix)]
+impl FdReader {
+    /// Creates a reader over `fd` which doesn't close it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open file descriptor, and stay open and not be reassigned for as long
+    /// as the reader lives. Closing it while the reader still exists, even without using the
+    /// reader, may make later reads target whatever file gets the same number next.
+    pub unsafe fn borrow(fd: RawFd) -> Self {
+        Self(Descriptor {
+            // SAFETY: the caller guarantees fd is open, and the file is never closed.
+            file: ManuallyDrop::new(unsafe { File::from_raw_fd(fd) }),
+            owned: false,
+        })
+    }
+
+    /// Creates a reader taking ownership of `fd`, which is closed when the reader is dropped.
+    pub fn from_owned(fd: OwnedFd) -> Self {
+        Self(Descriptor { file: ManuallyDrop::new(File::from(fd)), owned: true })
+    }
+}
+
+#[cfg(unix)]
+impl FdWriter {
+    /// Creates a writer over `fd` which doesn't close it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open file descriptor, and stay open and not be reassigned for as long
+    /// as the writer lives. Closing it while the writer still exists, even without using the
+    /// writer, may make later writes land in whatever file gets the same number next.
+    pub unsafe fn borrow(fd: RawFd) -> Self {
+        Self(Descriptor {
+            // SAFETY: the caller guarantees fd is open, and the file is never closed.
+            file: ManuallyDrop::new(unsafe { File::from_raw_fd(fd) }),
+            owned: false,
+        })
+    }
+
+    /// Creates a writer taking ownership of `fd`, which is closed when the writer is dropped.
+    pub fn from_owned(fd: OwnedFd) -> Self {
+        Self(Descriptor { file: ManuallyDrop::new(File::from(fd)), owned: true })
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for FdReader {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.file.as_raw_fd()
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for FdWriter {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.file.as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl FdReader {
+    /// Creates a reader over `handle` which doesn't close it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be an open handle, and stay open and not be reassigned for as long as
+    /// the reader lives. Closing it while the reader still exists, even without using the
+    /// reader, may make later reads target whatever object gets the same handle next.
+    pub unsafe fn borrow(handle: RawHandle) -> Self {
+        Self(Descriptor {
+            // SAFETY: the caller guarantees handle is open, and the file is never closed.
+            file: ManuallyDrop::new(unsafe { File::from_raw_handle(handle) }),
+            owned: false,
+        })
+    }
+
+    /// Creates a reader taking ownership of `handle`, which is closed when the reader is dropped.
+    pub fn from_owned(handle: OwnedHandle) -> Self {
+        Self(Descriptor { file: ManuallyDrop::new(File::from(handle)), owned: true })
+    }
+}
+
+#[cfg(windows)]
+impl FdWriter {
+    /// Creates a writer over `handle` which doesn't close it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be an open handle, and stay open and not be reassigned for as long as
+    /// the writer lives. Closing it while the writer still exists, even without using the
+    /// writer, may make later writes land in whatever object gets the same handle next.
+    pub unsafe fn borrow(handle: RawHandle) -> Self {
+        Self(Descriptor {
+            // SAFETY: the caller guarantees handle is open, and the file is never closed.
+            file: ManuallyDrop::new(unsafe { File::from_raw_handle(handle) }),
+            owned: false,
+        })
+    }
+
+    /// Creates a writer taking ownership of `handle`, which is closed when the writer is dropped.
+    pub fn from_owned(handle: OwnedHandle) -> Self {
+        Self(Descriptor { file: ManuallyDrop::new(File::from(handle)), owned: true })
+    }
+}
+
+#[cfg(windows)]
+impl AsRawHandle for FdReader {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.0.file.as_raw_handle()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawHandle for FdWriter {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.0.file.as_raw_handle()
+    }
+}
+
+impl Read for FdReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for FdWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+use std::{fs::{self, File, OpenOptions}, io::{self, Read, Seek, SeekFrom, Write}, marker::PhantomData, mem::size_of, path::{Path, PathBuf}, process, sync::atomic::{AtomicU64, Ordering}};
+use crate::{bytes, crc::Crc32, instrument, seek::{record_offset, record_size, stream_len}, BinaryRead, BinaryWrite};
+
+/// Size of the chunks read at once when verifying a file.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The footer closing a file written by [BinaryFileWriter].
+///
+/// It's 32 bytes with the layout of this `repr(C)` struct, every multi-byte field stored in
+/// little endian byte order, and ends with [MAGIC] so it can be found by seeking from the end
+/// of the file. `crc` is the CRC-32 (IEEE, as in zlib) of every byte before the footer.
+///
+/// [BinaryFileWriter]: BinaryFileWriter
+/// [MAGIC]: FileFooter::MAGIC
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileFooter {
+    /// Number of records in the file.
+    pub record_count: u64,
+    /// Number of bytes of records, everything before the footer.
+    pub payload_bytes: u64,
+    /// CRC-32 of the bytes of records.
+    pub crc: u32,
+    /// Bit flags, see [FLAG_CLEAN].
+    ///
+    /// [FLAG_CLEAN]: FileFooter::FLAG_CLEAN
+    pub flags: u32,
+    /// Always [MAGIC], marking the footer.
+    ///
+    /// [MAGIC]: FileFooter::MAGIC
+    pub magic: [u8; 8],
+}
+
+impl FileFooter {
+    /// The bytes every footer ends with.
+    pub const MAGIC: [u8; 8] = *b"BXFOOT01";
+
+    /// Size of the footer in bytes.
+    pub const SIZE: usize = 32;
+
+    /// Flag telling the file was closed cleanly with [finish].
+    ///
+    /// [finish]: BinaryFileWriter::finish
+    pub const FLAG_CLEAN: u32 = 0x0001;
+
+    /// Returns whether the file was closed cleanly.
+    pub const fn is_clean(&self) -> bool {
+        self.flags & Self::FLAG_CLEAN != 0
+    }
+}
+
+crate::swap_bytes!(FileFooter {
+    record_count: u64,
+    payload_bytes: u64,
+    crc: u32,
+    flags: u32,
+    magic: [u8; 8],
+});
+
+const _: () = assert!(size_of::<FileFooter>() == FileFooter::SIZE);
+
+/// A writer of record files closed with a [FileFooter].
+///
+/// Records are written back to back, like with [write_binary], and [finish] appends the footer
+/// with their count and checksum. A file whose writer was dropped without finishing it, like
+/// after a crash, has no footer. Files are opened with [BinaryFile].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryFile, BinaryFileWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = BinaryFileWriter::new(Vec::new());
+///     writer.write_record(&1.5f64)?;
+///     writer.write_record(&2.5f64)?;
+///
+///     let mut file = BinaryFile::<f64, _>::from_reader_verified(Cursor::new(writer.finish()?))?;
+///
+///     assert!(file.footer().is_some_and(|footer| footer.is_clean()));
+///     assert_eq!(file.len(), 2);
+///     assert_eq!(file.get(1)?, 2.5);
+///     Ok(())
+/// }
+/// ```
+///
+/// [FileFooter]: FileFooter
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [finish]: BinaryFileWriter::finish
+/// [BinaryFile]: BinaryFile
+pub struct BinaryFileWriter<W, T> {
+    inner: W,
+    record_count: u64,
+    crc: Crc32,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<W: Write, T> BinaryFileWriter<W, T> {
+    /// Creates a new writer over `inner`, which should be empty.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            record_count: 0,
+            crc: Crc32::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes a record.
+    pub fn write_record(&mut self, item: &T) -> io::Result<()> {
+        let bytes = bytes::as_bytes(item);
+
+        self.inner.write_all(bytes)?;
+        self.crc.update(bytes);
+        self.record_count += 1;
+
+        Ok(())
+    }
+
+    /// Returns how many records were written.
+    pub fn record_count(&self) -> u64 {
+        self.record_count
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes the footer, closing the file cleanly, and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let footer = FileFooter {
+            record_count: self.record_count,
+            payload_bytes: record_offset::<T>(self.record_count)?,
+            crc: self.crc.finish(),
+            flags: FileFooter::FLAG_CLEAN,
+            magic: FileFooter::MAGIC,
+        };
+
+        self.inner.write_binary_le(&footer)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+/// A record file written by [BinaryFileWriter], opened through its footer.
+///
+/// Opening a file distinguishes three cases:
+///
+/// - The file was closed cleanly: [footer] returns its footer, whose record count and size
+///   are checked against the file.
+/// - The file has no footer, as happens when the writer didn't finish, like after a crash.
+///   This is recoverable: the records are counted from the length of the file, ignoring a
+///   partial record at the end, and [footer] returns `None`.
+/// - The footer doesn't match the file: an `InvalidData` error is returned. [open_verified]
+///   also checks the CRC of all the records, which [open] skips to avoid reading the whole
+///   file.
+///
+/// [BinaryFileWriter]: BinaryFileWriter
+/// [footer]: BinaryFile::footer
+/// [open_verified]: BinaryFile::open_verified
+/// [open]: BinaryFile::open
+pub struct BinaryFile<T, R = File> {
+    inner: R,
+    footer: Option<FileFooter>,
+    len: u64,
+    trailing_bytes: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> BinaryFile<T> {
+    /// Opens the file at `path`, checking its footer against its size if it has one.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Opens the file at `path`, also checking the CRC of its records if it has a footer.
+    pub fn open_verified(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader_verified(File::open(path)?)
+    }
+}
+
+impl<T, R: Read + Seek> BinaryFile<T, R> {
+    /// Opens a file from a reader, checking its footer against its size if it has one.
+    pub fn from_reader(mut inner: R) -> io::Result<Self> {
+        let size = record_size::<T>()?;
+        let len = stream_len(&mut inner)?;
+
+        let footer = match len.checked_sub(FileFooter::SIZE as u64) {
+            Some(start) => {
+                inner.seek(SeekFrom::Start(start))?;
+                Some(inner.read_binary_le::<FileFooter>()?).filter(|footer| footer.magic == FileFooter::MAGIC)
+            },
+            None => None
+        };
+
+        let (records, trailing_bytes) = match footer {
+            Some(footer) => {
+                let payload_bytes = len - FileFooter::SIZE as u64;
+
+                if footer.payload_bytes != payload_bytes || record_offset::<T>(footer.record_count).ok() != Some(payload_bytes) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "footer of {} records in {} bytes doesn't match the {payload_bytes} bytes of the file",
+                            footer.record_count, footer.payload_bytes
+                        )
+                    ));
+                }
+
+                (footer.record_count, 0)
+            },
+            None => (len / size, len % size)
+        };
+
+        inner.seek(SeekFrom::Start(0))?;
+
+        Ok(Self {
+            inner,
+            footer,
+            len: records,
+            trailing_bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Opens a file from a reader, also checking the CRC of its records if it has a footer.
+    pub fn from_reader_verified(inner: R) -> io::Result<Self> {
+        let mut file = Self::from_reader(inner)?;
+
+        if let Some(footer) = file.footer {
+            let crc = file.payload_crc(footer.payload_bytes)?;
+
+            if crc != footer.crc {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("footer checksum {:#010x} doesn't match the records' {crc:#010x}", footer.crc)
+                ));
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// Returns the footer, or `None` if the file wasn't closed cleanly.
+    pub fn footer(&self) -> Option<&FileFooter> {
+        self.footer.as_ref()
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many bytes of a partial record follow the last complete one, always zero
+    /// for files with a footer.
+    pub fn trailing_bytes(&self) -> u64 {
+        self.trailing_bytes
+    }
+
+    /// Reads the record at `index`.
+    pub fn get(&mut self, index: u64) -> io::Result<T> {
+        if index >= self.len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("record {index} out of bounds for {} records", self.len)
+            ));
+        }
+
+        self.inner.seek(SeekFrom::Start(record_offset::<T>(index)?))?;
+        self.inner.read_binary()
+    }
+
+    /// Unwraps this file, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn payload_crc(&mut self, len: u64) -> io::Result<u32> {
+        let mut crc = Crc32::new();
+        let mut chunk = vec![0; CHUNK_SIZE];
+        let mut remaining = len;
+
+        self.inner.seek(SeekFrom::Start(0))?;
+
+        while remaining > 0 {
+            let read = usize::try_from(remaining).map_or(chunk.len(), |remaining| chunk.len().min(remaining));
+            self.inner.read_exact(&mut chunk[..read])?;
+            crc.update(&chunk[..read]);
+            remaining -= read as u64;
+        }
+
+        Ok(crc.finish())
+    }
+}
+
+/// Opens the file at `path`, checks that it starts with `magic` and `version` and reads the
+/// header `T` following them, returning the file positioned right after the header, ready to
+/// read records.
+///
+/// The magic is stored as a `u32` and the version as a `u16`, both in little endian byte order,
+/// and are immediately followed by the header, read like with [read_binary]. A file starting
+/// with another magic or version is reported as an `InvalidData` error, and one too short to hold
+/// them and the header as an `UnexpectedEof` one.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{open_binary_file, BinaryRead, BinaryWrite};
+/// use std::{fs::File, io};
+///
+/// const MAGIC: u32 = u32::from_le_bytes(*b"SMPL");
+///
+/// #[derive(Debug)]
+/// struct Header {
+///     record_count: u32,
+///     sample_rate: u32
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut file = File::create("samples.bin")?;
+///     file.write_binary_le(&MAGIC)?;
+///     file.write_binary_le(&2u16)?;
+///     file.write_binary(&Header { record_count: 1, sample_rate: 44100 })?;
+///     file.write_binary(&0.5f32)?;
+///     drop(file);
+///
+///     let (mut file, header) = open_binary_file::<Header>("samples.bin".as_ref(), MAGIC, 2)?;
+///     assert_eq!(header.sample_rate, 44100);
+///     assert_eq!(file.read_binary::<f32>()?, 0.5);
+///     # std::fs::remove_file("samples.bin")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// [read_binary]: crate::BinaryRead::read_binary
+pub fn open_binary_file<T>(path: &Path, magic: u32, version: u16) -> io::Result<(File, T)> {
+    let mut file = File::open(path)?;
+
+    let found = file.read_binary_le::<u32>()?;
+    if found != magic {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} starts with magic {found:#010x}, expected {magic:#010x}", path.display())
+        ));
+    }
+
+    let found = file.read_binary_le::<u16>()?;
+    if found != version {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} has version {found}, expected {version}", path.display())
+        ));
+    }
+
+    let header = file.read_binary::<T>()?;
+    Ok((file, header))
+}
+
+/// Replaces the file at `path` with `records`, atomically: readers of `path` see either the old
+/// contents or all the new records, never a partial write, even if the process crashes.
+///
+/// The records are written to a new temporary file next to it, named like `path` with the
+/// process id, a counter and `.tmp` appended, so concurrent writers never share one. It's
+/// synced to disk and then renamed over `path`. On unix the directory is
+/// synced too, so the rename itself survives a crash. If anything fails before the rename, the
+/// temporary file is removed and the original file is left untouched.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{write_records_atomic, BinaryRead};
+/// use std::{fs::File, io, path::Path};
+///
+/// fn main() -> io::Result<()> {
+///     let path = Path::new("scores.bin");
+///     write_records_atomic(path, &[1u32, 2, 3])?;
+///     write_records_atomic(path, &[4u32, 5])?;
+///
+///     assert_eq!(File::open(path)?.read_binary_vec::<u32>(2)?, [4, 5]);
+///     # std::fs::remove_file(path)?;
+///     Ok(())
+/// }
+/// ```
+pub fn write_records_atomic<T>(path: &Path, records: &[T]) -> io::Result<()> {
+    let (temp, mut file) = create_temp(path)?;
+
+    let result = instrument::bulk::<T, _>("write_records_atomic", || {
+        let written = file.write_all(bytes::slice_as_bytes(records))
+            .and_then(|_| file.sync_all())
+            .map(|_| records.len());
+        instrument::written::<T, _>(&written);
+
+        written.and_then(|_| fs::rename(&temp, path))
+    });
+
+    if let Err(error) = result {
+        // The temporary file may be gone already, and the original error is the one worth
+        // reporting.
+        let _ = fs::remove_file(&temp);
+        return Err(error);
+    }
+
+    #[cfg(unix)]
+    {
+        let parent = path.parent().filter(|parent| !parent.as_os_str().is_empty());
+        File::open(parent.unwrap_or(Path::new(".")))?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Creates a temporary file next to `path` that no other writer is using, returning its path.
+fn create_temp(path: &Path) -> io::Result<(PathBuf, File)> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    loop {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(format!(".{}.{}.tmp", process::id(), COUNTER.fetch_add(1, Ordering::Relaxed)));
+        let temp = PathBuf::from(temp);
+
+        // A leftover of a crashed process that had the same id is skipped, not overwritten.
+        match OpenOptions::new().write(true).create_new(true).open(&temp) {
+            Ok(file) => return Ok((temp, file)),
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error)
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::{io, mem::size_of};
+use bitflags::Flags;
+use crate::{Validate, ValidationError};
+
+/// What to do with bits that don't belong to any flag when reading a [bitflags] type.
+///
+/// The default is [Retain], which keeps files written by newer versions of a program, which may
+/// define more flags, readable and writable without losing those bits. [Truncate] drops them
+/// silently, and [Reject] makes reading fail instead, which suits formats where an unknown bit
+/// means the data can't be interpreted correctly.
+///
+/// [bitflags]: https://docs.rs/bitflags
+/// [Retain]: UnknownBits::Retain
+/// [Truncate]: UnknownBits::Truncate
+/// [Reject]: UnknownBits::Reject
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnknownBits {
+    /// Keeps the unknown bits, so they are written back unchanged.
+    #[default]
+    Retain,
+    /// Clears the unknown bits.
+    Truncate,
+    /// Fails with an `InvalidData` error.
+    Reject,
+}
+
+impl UnknownBits {
+    /// Converts raw bits into flags according to this policy.
+    pub fn from_bits<F: Flags>(self, bits: F::Bits) -> io::Result<F> {
+        match self {
+            Self::Retain => Ok(F::from_bits_retain(bits)),
+            Self::Truncate => Ok(F::from_bits_truncate(bits)),
+            Self::Reject => F::from_bits(bits).ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidData,
+                UNKNOWN_BITS
+            ))
+        }
+    }
+
+    /// Checks flags already read against this policy, as the `#[unknown_bits = ...]` field
+    /// check of [validate] does.
+    ///
+    /// Only [Reject] can fail. Checking never changes the flags, so [Truncate] accepts unknown
+    /// bits like [Retain], and they are only cleared by [from_bits].
+    ///
+    /// [validate]: crate::validate
+    /// [Reject]: UnknownBits::Reject
+    /// [Truncate]: UnknownBits::Truncate
+    /// [Retain]: UnknownBits::Retain
+    /// [from_bits]: UnknownBits::from_bits
+    pub fn check<F: Flags>(self, flags: &F) -> Result<(), ValidationError> {
+        match self {
+            Self::Reject if F::from_bits(flags.bits()).is_none() => Err(ValidationError::new(0, UNKNOWN_BITS)),
+            _ => Ok(())
+        }
+    }
+}
+
+const UNKNOWN_BITS: &str = "flags contain bits that don't belong to any flag";
+
+/// Validates the bytes of a [bitflags] type as its raw bits, for [validate_flags].
+///
+/// [bitflags]: https://docs.rs/bitflags
+/// [validate_flags]: crate::validate_flags
+pub fn validate_flags<F: Flags>(bytes: &[u8]) -> Result<(), ValidationError>
+where
+    F::Bits: Validate
+{
+    assert_eq!(size_of::<F>(), size_of::<F::Bits>(), "flags type doesn't have the size of its bits");
+    F::Bits::validate_bytes(bytes)
+}
@@ -0,0 +1,52 @@
+use ::half::{bf16, f16};
+use crate::{SwapBytes, Validate, ValidationError, Zeroable};
+
+macro_rules! half_float {
+    ($($ty: ty),*) => {
+        $(
+            impl SwapBytes for $ty {
+                fn swap_bytes(bytes: &mut [u8]) {
+                    bytes.reverse();
+                }
+            }
+
+            impl Validate for $ty {
+                fn validate_bytes(_: &[u8]) -> Result<(), ValidationError> {
+                    Ok(())
+                }
+            }
+
+            // SAFETY: all zeroes is positive zero.
+            unsafe impl Zeroable for $ty {}
+        )*
+    };
+}
+
+half_float!(f16, bf16);
+
+/// Widens a slice of half precision floats into `f32`s, usually right after loading them.
+///
+/// Both [f16] and [bf16] are accepted, and every value, NaNs and infinities included, is
+/// converted exactly.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{as_f32_vec, BinaryRead};
+/// use half::f16;
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut cursor = Cursor::new([0x3C00u16, 0xC000].map(u16::to_ne_bytes).concat());
+///     let features = cursor.read_binary::<[f16; 2]>()?;
+///
+///     assert_eq!(as_f32_vec(&features), [1.0, -2.0]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [f16]: half::f16
+/// [bf16]: half::bf16
+pub fn as_f32_vec<F: Copy + Into<f32>>(values: &[F]) -> Vec<f32> {
+    values.iter().map(|&value| value.into()).collect()
+}
@@ -0,0 +1,215 @@
+use std::{collections::HashMap, io::{self, Cursor}, marker::PhantomData, mem::size_of, time::{Duration, Instant}};
+use crate::{bytes, BinaryRead, BinaryWrite};
+
+/// The header preceding the payload of every fragment made by [fragment_binary].
+///
+/// It's 12 bytes with the layout of this `repr(C)` struct, every field stored in little endian
+/// byte order regardless of the host, so fragments can be exchanged between machines.
+///
+/// [fragment_binary]: fragment_binary
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FragmentHeader {
+    /// Identifier of the message the fragment belongs to, chosen by the sender.
+    pub message_id: u32,
+    /// Index of the fragment within the message.
+    pub index: u16,
+    /// Number of fragments of the message.
+    pub count: u16,
+    /// Length of the payload following the header.
+    pub len: u32,
+}
+
+impl FragmentHeader {
+    /// Size of the header in bytes.
+    pub const SIZE: usize = 12;
+}
+
+crate::swap_bytes!(FragmentHeader { message_id: u32, index: u16, count: u16, len: u32 });
+
+const _: () = assert!(size_of::<FragmentHeader>() == FragmentHeader::SIZE);
+
+/// Splits a record into fragments of at most `max_payload` bytes of payload each, every one
+/// preceded by a [FragmentHeader], to be sent as separate datagrams and put back together by a
+/// [Reassembler].
+///
+/// `max_payload` doesn't include the header, so to fit a datagram of `mtu` bytes pass
+/// `mtu - FragmentHeader::SIZE`. Records needing more than `u16::MAX` fragments, or a
+/// `max_payload` of zero, are reported as `InvalidInput` errors.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{fragment_binary, FragmentHeader, Reassembler};
+/// use std::{io, time::Duration};
+///
+/// fn main() -> io::Result<()> {
+///     let frame = [7u16; 1000];
+///     let fragments = fragment_binary(&frame, 1, 512 - FragmentHeader::SIZE)?;
+///     assert_eq!(fragments.len(), 4);
+///
+///     let mut reassembler = Reassembler::<[u16; 1000]>::new(Duration::from_secs(5));
+///     let mut received = None;
+///
+///     for fragment in fragments.iter().rev() {
+///         received = reassembler.accept(fragment)?.or(received);
+///     }
+///
+///     assert_eq!(received, Some(frame));
+///     Ok(())
+/// }
+/// ```
+///
+/// [FragmentHeader]: FragmentHeader
+/// [Reassembler]: Reassembler
+pub fn fragment_binary<T>(item: &T, message_id: u32, max_payload: usize) -> io::Result<Vec<Vec<u8>>> {
+    if max_payload == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "fragments can't have an empty payload"));
+    }
+
+    let bytes = bytes::as_bytes(item);
+    let count = bytes.len().div_ceil(max_payload).max(1);
+    let count = u16::try_from(count).map_err(|_| io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("a {} bytes record needs {count} fragments of {max_payload} bytes, more than {}", bytes.len(), u16::MAX)
+    ))?;
+
+    let mut chunks = bytes.chunks(max_payload);
+    (0..count)
+        .map(|index| {
+            let payload = chunks.next().unwrap_or_default();
+            let header = FragmentHeader { message_id, index, count, len: payload.len() as u32 };
+
+            let mut fragment = Vec::with_capacity(FragmentHeader::SIZE + payload.len());
+            fragment.write_binary_le(&header)?;
+            fragment.extend_from_slice(payload);
+            Ok(fragment)
+        })
+        .collect()
+}
+
+/// The fragments received so far of a message.
+struct Partial {
+    count: u16,
+    fragments: Vec<Option<Vec<u8>>>,
+    received_bytes: usize,
+    first_seen: Instant,
+}
+
+/// Puts back together records of `T` split by [fragment_binary], from fragments received in
+/// any order.
+///
+/// Fragments are given to [accept] as they arrive, which returns the record once all the
+/// fragments of its message have been received. Duplicated fragments are ignored, and fragments
+/// contradicting the ones already received, or adding up to more bytes than a `T`, are rejected
+/// with an `InvalidData` error without disturbing the rest of their message. Messages missing
+/// fragments are kept until [evict_expired] is called after `timeout` has passed since their
+/// first fragment arrived.
+///
+/// A message is forgotten once complete, so a late duplicate of one of its fragments starts a
+/// new message, which never completes and is eventually evicted.
+///
+/// [fragment_binary]: fragment_binary
+/// [accept]: Reassembler::accept
+/// [evict_expired]: Reassembler::evict_expired
+pub struct Reassembler<T> {
+    timeout: Duration,
+    partials: HashMap<u32, Partial>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Reassembler<T> {
+    /// Creates a new reassembler, keeping incomplete messages for `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            partials: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Accepts a fragment, returning the record of its message if it was the last one missing.
+    ///
+    /// Fragments with a malformed header, or not matching the other fragments of their message,
+    /// are reported as `InvalidData` errors, and so is a complete message whose length isn't the
+    /// size of `T`.
+    pub fn accept(&mut self, fragment: &[u8]) -> io::Result<Option<T>> {
+        let header = fragment.get(..FragmentHeader::SIZE)
+            .map(|header| Cursor::new(header).read_binary_le::<FragmentHeader>())
+            .transpose()?
+            .ok_or_else(|| invalid(format!("fragment of {} bytes is shorter than its header", fragment.len())))?;
+
+        let payload = &fragment[FragmentHeader::SIZE..];
+
+        if header.len as usize != payload.len() || header.index >= header.count {
+            return Err(invalid(format!(
+                "fragment {} of {} declares {} bytes of payload but carries {}",
+                header.index, header.count, header.len, payload.len()
+            )));
+        }
+
+        let partial = self.partials.entry(header.message_id).or_insert_with(|| Partial {
+            count: header.count,
+            fragments: vec![None; header.count as usize],
+            received_bytes: 0,
+            first_seen: Instant::now(),
+        });
+
+        if partial.count != header.count {
+            return Err(invalid(format!(
+                "fragment of message {} says it has {} fragments, not {}",
+                header.message_id, header.count, partial.count
+            )));
+        }
+
+        let slot = &mut partial.fragments[header.index as usize];
+        if slot.is_some() {
+            return Ok(None);
+        }
+
+        if partial.received_bytes + payload.len() > size_of::<T>() {
+            return Err(invalid(format!(
+                "fragments of message {} add up to more than the {} bytes of the record",
+                header.message_id, size_of::<T>()
+            )));
+        }
+
+        *slot = Some(payload.to_vec());
+        partial.received_bytes += payload.len();
+
+        if partial.fragments.iter().any(Option::is_none) {
+            return Ok(None);
+        }
+
+        let partial = self.partials.remove(&header.message_id).unwrap();
+
+        if partial.received_bytes != size_of::<T>() {
+            return Err(invalid(format!(
+                "message {} of {} bytes doesn't hold a {} bytes record",
+                header.message_id, partial.received_bytes, size_of::<T>()
+            )));
+        }
+
+        let bytes = partial.fragments.into_iter().flatten().flatten().collect::<Vec<_>>();
+        Cursor::new(bytes).read_binary().map(Some)
+    }
+
+    /// Drops the messages whose first fragment arrived more than `timeout` ago, returning how
+    /// many were dropped.
+    pub fn evict_expired(&mut self) -> usize {
+        let before = self.partials.len();
+        let timeout = self.timeout;
+        self.partials.retain(|_, partial| partial.first_seen.elapsed() <= timeout);
+
+        before - self.partials.len()
+    }
+
+    /// Returns the number of messages with fragments still missing.
+    pub fn pending(&self) -> usize {
+        self.partials.len()
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
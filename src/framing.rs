@@ -0,0 +1,314 @@
+use std::{io::{self, Read, Write}, mem::size_of};
+use crate::{bytes, crc, BinaryRead, BinaryWrite, Validate};
+
+const DEFAULT_MAX_FRAME_LEN: u32 = 16 * 1024 * 1024;
+
+/// The header preceding every frame written by [FramedWriter].
+///
+/// It's a stable part of the format, meant to be parsed from other languages: 16 bytes with the
+/// layout of this `repr(C)` struct, where every multi-byte field is stored in little endian
+/// byte order regardless of the host. [C_DEFINITION] and [PYTHON_FORMAT] describe it for C
+/// and for Python's `struct` module. In memory the fields hold native values; the conversion
+/// happens when reading and writing.
+///
+/// The payload of `len` bytes follows the header, and `crc` is its CRC-32 (IEEE, as in zlib)
+/// when the [FLAG_CHECKSUM] bit of `flags` is set, zero otherwise. The other flag bits are
+/// reserved and written as zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryWrite, RecordHeader};
+///
+/// let header = RecordHeader::new(7, 4);
+///
+/// let mut buffer = Vec::new();
+/// buffer.write_binary_le(&header).unwrap();
+///
+/// assert_eq!(buffer.len(), RecordHeader::SIZE);
+/// assert_eq!(buffer[..4], RecordHeader::MAGIC);
+/// assert_eq!(buffer[4..6], [7, 0]);
+/// ```
+///
+/// [FramedWriter]: FramedWriter
+/// [C_DEFINITION]: RecordHeader::C_DEFINITION
+/// [PYTHON_FORMAT]: RecordHeader::PYTHON_FORMAT
+/// [FLAG_CHECKSUM]: RecordHeader::FLAG_CHECKSUM
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordHeader {
+    /// Always [MAGIC], marking the start of a frame.
+    ///
+    /// [MAGIC]: RecordHeader::MAGIC
+    pub magic: [u8; 4],
+    /// Application defined kind of the payload.
+    pub tag: u16,
+    /// Bit flags, see [FLAG_CHECKSUM].
+    ///
+    /// [FLAG_CHECKSUM]: RecordHeader::FLAG_CHECKSUM
+    pub flags: u16,
+    /// Length of the payload in bytes.
+    pub len: u32,
+    /// CRC-32 of the payload, if [FLAG_CHECKSUM] is set.
+    ///
+    /// [FLAG_CHECKSUM]: RecordHeader::FLAG_CHECKSUM
+    pub crc: u32,
+}
+
+impl RecordHeader {
+    /// The bytes every header starts with.
+    pub const MAGIC: [u8; 4] = *b"BXF1";
+
+    /// Size of the header in bytes.
+    pub const SIZE: usize = 16;
+
+    /// Flag telling `crc` holds the CRC-32 of the payload.
+    pub const FLAG_CHECKSUM: u16 = 0x0001;
+
+    /// Definition of the header in C. Multi-byte fields are little endian.
+    pub const C_DEFINITION: &'static str = "\
+struct binext_record_header {
+    uint8_t magic[4];  /* \"BXF1\" */
+    uint16_t tag;      /* little endian */
+    uint16_t flags;    /* little endian, bit 0: crc is set */
+    uint32_t len;      /* little endian, payload bytes */
+    uint32_t crc;      /* little endian, CRC-32 of the payload */
+};
+";
+
+    /// Format of the header for Python's `struct` module.
+    pub const PYTHON_FORMAT: &'static str = "<4sHHII";
+
+    /// Creates the header of a payload of `len` bytes without a checksum.
+    pub const fn new(tag: u16, len: u32) -> Self {
+        Self { magic: Self::MAGIC, tag, flags: 0, len, crc: 0 }
+    }
+
+    /// Creates the header of `payload`, with its checksum.
+    pub fn with_checksum(tag: u16, payload: &[u8]) -> io::Result<Self> {
+        Ok(Self {
+            flags: Self::FLAG_CHECKSUM,
+            crc: crc::crc32(payload),
+            ..Self::new(tag, payload_len(payload.len())?)
+        })
+    }
+
+    /// Returns whether the header carries a checksum of the payload.
+    pub const fn has_checksum(&self) -> bool {
+        self.flags & Self::FLAG_CHECKSUM != 0
+    }
+}
+
+crate::swap_bytes!(RecordHeader {
+    magic: [u8; 4],
+    tag: u16,
+    flags: u16,
+    len: u32,
+    crc: u32,
+});
+
+crate::validate!(RecordHeader {
+    #[magic = RecordHeader::MAGIC]
+    magic: [u8; 4],
+    tag: u16,
+    flags: u16,
+    len: u32,
+    crc: u32,
+});
+
+const _: () = assert!(size_of::<RecordHeader>() == RecordHeader::SIZE);
+
+fn payload_len(len: usize) -> io::Result<u32> {
+    u32::try_from(len).map_err(|_| io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("payload of {len} bytes doesn't fit in a frame")
+    ))
+}
+
+/// A writer of tagged frames, each made of a [RecordHeader] and a payload.
+///
+/// Payloads are records written raw, like with [write_binary], or arbitrary bytes, and are
+/// checksummed with CRC-32 unless disabled. Frames are read back with [FramedReader], or by any
+/// other language following the documented header.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{FramedReader, FramedWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = FramedWriter::new(Vec::new());
+///     writer.write_frame(1, &42u64)?;
+///     writer.write_frame_bytes(2, b"hello")?;
+///
+///     let mut reader = FramedReader::new(Cursor::new(writer.into_inner()));
+///     assert_eq!(reader.read_frame::<u64>()?, (1, 42));
+///
+///     let (header, payload) = reader.next_frame()?.unwrap();
+///     assert_eq!((header.tag, payload.as_slice()), (2, &b"hello"[..]));
+///     assert!(reader.next_frame()?.is_none());
+///     Ok(())
+/// }
+/// ```
+///
+/// [RecordHeader]: RecordHeader
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [FramedReader]: FramedReader
+pub struct FramedWriter<W> {
+    inner: W,
+    checksums: bool,
+}
+
+impl<W: Write> FramedWriter<W> {
+    /// Creates a new writer over `inner`, checksumming payloads.
+    pub fn new(inner: W) -> Self {
+        Self { inner, checksums: true }
+    }
+
+    /// Sets whether payloads are checksummed.
+    pub fn with_checksums(mut self, checksums: bool) -> Self {
+        self.checksums = checksums;
+        self
+    }
+
+    /// Writes a frame holding `item`.
+    pub fn write_frame<T>(&mut self, tag: u16, item: &T) -> io::Result<()> {
+        self.write_frame_bytes(tag, bytes::as_bytes(item))
+    }
+
+    /// Writes a frame holding `payload`.
+    pub fn write_frame_bytes(&mut self, tag: u16, payload: &[u8]) -> io::Result<()> {
+        let header = match self.checksums {
+            true => RecordHeader::with_checksum(tag, payload)?,
+            false => RecordHeader::new(tag, payload_len(payload.len())?)
+        };
+
+        self.inner.write_binary_le(&header)?;
+        self.inner.write_all(payload)
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A reader of the frames written by [FramedWriter].
+///
+/// Headers with the wrong magic and payloads not matching their checksum are reported as
+/// `InvalidData` errors. So are frames longer than the maximum length, 16 MiB unless set with
+/// [with_max_frame_len], whose header is read but not their payload, so a corrupt or hostile
+/// length can't force a large allocation.
+///
+/// [FramedWriter]: FramedWriter
+/// [with_max_frame_len]: FramedReader::with_max_frame_len
+pub struct FramedReader<R> {
+    inner: R,
+    max_frame_len: u32,
+}
+
+impl<R: Read> FramedReader<R> {
+    /// Creates a new reader over `inner`.
+    pub fn new(inner: R) -> Self {
+        Self { inner, max_frame_len: DEFAULT_MAX_FRAME_LEN }
+    }
+
+    /// Sets the length of the longest payload accepted by [next_frame].
+    ///
+    /// [next_frame]: FramedReader::next_frame
+    pub fn with_max_frame_len(mut self, max: u32) -> Self {
+        self.max_frame_len = max;
+        self
+    }
+
+    /// Reads the next frame, returning `None` if the source ends right before it.
+    ///
+    /// The payload grows as its bytes arrive, so a source ending before the length given by
+    /// the header fails with `UnexpectedEof` without having allocated all of it.
+    pub fn next_frame(&mut self) -> io::Result<Option<(RecordHeader, Vec<u8>)>> {
+        let Some(header) = self.next_header()? else {
+            return Ok(None);
+        };
+
+        if header.len > self.max_frame_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame of {} bytes exceeds the maximum of {}", header.len, self.max_frame_len)
+            ));
+        }
+
+        let mut payload = Vec::new();
+        (&mut self.inner).take(header.len as u64).read_to_end(&mut payload)?;
+
+        if payload.len() != header.len as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("frame of {} bytes ends after {}", header.len, payload.len())
+            ));
+        }
+
+        check_payload(&header, &payload)?;
+
+        Ok(Some((header, payload)))
+    }
+
+    /// Reads a frame holding a record of `T`, returning its tag along with it.
+    ///
+    /// A payload whose length isn't the size of `T` is skipped and reported as an `InvalidData`
+    /// error.
+    pub fn read_frame<T>(&mut self) -> io::Result<(u16, T)> {
+        let header = self.next_header()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "no frame left"))?;
+
+        if header.len as usize != size_of::<T>() {
+            io::copy(&mut (&mut self.inner).take(header.len as u64), &mut io::sink())?;
+
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame of {} bytes doesn't hold a {} bytes record", header.len, size_of::<T>())
+            ));
+        }
+
+        let item = self.inner.read_binary_boxed::<T>()?;
+        check_payload(&header, bytes::as_bytes(&*item))?;
+
+        Ok((header.tag, *item))
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_header(&mut self) -> io::Result<Option<RecordHeader>> {
+        let mut bytes = [0; RecordHeader::SIZE];
+
+        if !crate::iter::read_record_bytes(&mut self.inner, &mut bytes)? {
+            return Ok(None);
+        }
+
+        RecordHeader::validate_bytes(&bytes)?;
+        bytes.as_slice().read_binary_le().map(Some)
+    }
+}
+
+fn check_payload(header: &RecordHeader, payload: &[u8]) -> io::Result<()> {
+    if header.has_checksum() {
+        let crc = crc::crc32(payload);
+
+        if crc != header.crc {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame checksum {:#010x} doesn't match its payload's {crc:#010x}", header.crc)
+            ));
+        }
+    }
+
+    Ok(())
+}
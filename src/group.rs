@@ -0,0 +1,281 @@
+use std::{fs::File, io::{self, Read, Seek, Write}, sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError}, thread::{self, JoinHandle}, time::{Duration, Instant}};
+use crate::{BinaryLog, DurabilityPolicy, Durable};
+
+/// State shared between the appenders and the commit thread.
+struct Shared<T, F> {
+    state: Mutex<State<T, F>>,
+    /// Wakes the commit thread when a batch starts, fills up or the log is closed.
+    appended: Condvar,
+    /// Wakes the appenders waiting for their batch to be synced.
+    synced: Condvar,
+}
+
+struct State<T, F> {
+    log: BinaryLog<T, F>,
+    /// Number of records covered by the last successful sync.
+    synced: u64,
+    /// When the first append not synced yet happened.
+    batch_start: Option<Instant>,
+    /// The error of a failed sync, after which nothing is reported as durable anymore.
+    failed: Option<(io::ErrorKind, String)>,
+    closed: bool,
+}
+
+impl<T, F> Shared<T, F> {
+    fn lock(&self) -> MutexGuard<'_, State<T, F>> {
+        // The state is consistent between statements, so a panicking appender doesn't corrupt it.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T, F> State<T, F> {
+    fn failure(&self) -> Option<io::Error> {
+        self.failed.as_ref().map(|(kind, message)| io::Error::new(
+            *kind,
+            format!("a previous sync of the log failed: {message}")
+        ))
+    }
+}
+
+/// A [BinaryLog] shared between threads that syncs appends in batches, group commit style.
+///
+/// Syncing after every append caps a log at a few hundred appends per second, the latency of
+/// the storage device. Here appends only write their record and return, while a commit thread
+/// syncs the file once `max_batch` appends are pending or `max_delay` has passed since the first
+/// of them, whichever happens first. Appenders that need their record to be durable use
+/// [append_durable], which waits for the sync covering it, so many of them share each sync.
+///
+/// Files with a [sync_handle], like [File], are synced through it without holding the log, so
+/// appends go on while a sync is in progress, and are synced by the next one. After a sync
+/// fails, nothing can be known about which records reached the storage device, so every later
+/// [append_durable] and [close] fails too.
+///
+/// The log is synced through its [Durable] implementation, so any durable file can back it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{BinaryLog, GroupCommitLog};
+/// use std::{io, thread, time::Duration};
+///
+/// fn main() -> io::Result<()> {
+///     let log = BinaryLog::<u64>::open("payments.bin")?;
+///     let log = GroupCommitLog::new(log, Duration::from_millis(5), 64);
+///
+///     thread::scope(|scope| {
+///         for worker in 0..8 {
+///             let log = &log;
+///             scope.spawn(move || log.append_durable(&worker));
+///         }
+///     });
+///
+///     log.close()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// [BinaryLog]: crate::BinaryLog
+/// [append_durable]: GroupCommitLog::append_durable
+/// [close]: GroupCommitLog::close
+/// [Durable]: crate::Durable
+/// [sync_handle]: crate::Durable::sync_handle
+/// [File]: std::fs::File
+pub struct GroupCommitLog<T, F = File> {
+    shared: Arc<Shared<T, F>>,
+    max_batch: u64,
+    committer: Option<JoinHandle<()>>,
+}
+
+impl<T, F> GroupCommitLog<T, F>
+where
+    T: 'static,
+    F: Read + Write + Seek + Durable + Send + 'static
+{
+    /// Takes over `log`, starting a commit thread that syncs it once `max_batch` appends are
+    /// pending or `max_delay` after the first of them.
+    ///
+    /// The records already in the log are taken as synced, and its own durability policy is
+    /// replaced, since syncing is now up to the commit thread.
+    pub fn new(log: BinaryLog<T, F>, max_delay: Duration, max_batch: u32) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                synced: log.len(),
+                log: log.with_durability(DurabilityPolicy::None),
+                batch_start: None,
+                failed: None,
+                closed: false,
+            }),
+            appended: Condvar::new(),
+            synced: Condvar::new(),
+        });
+
+        let max_batch = u64::from(max_batch.max(1));
+        let committer = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || commit(&shared, max_delay, max_batch))
+        };
+
+        Self { shared, max_batch, committer: Some(committer) }
+    }
+
+    /// Appends a record, returning its index once its bytes are in the file, without waiting
+    /// for them to be synced.
+    pub fn append(&self, item: &T) -> io::Result<u64> {
+        let mut state = self.shared.lock();
+        let index = state.log.append(item)?;
+
+        // The commit thread only needs waking when a batch starts, to time it, and once full.
+        if state.batch_start.is_none() {
+            state.batch_start = Some(Instant::now());
+            self.shared.appended.notify_one();
+        } else if index + 1 - state.synced >= self.max_batch {
+            self.shared.appended.notify_one();
+        }
+
+        Ok(index)
+    }
+
+    /// Appends a record and waits until a sync covering it has completed, returning its index.
+    pub fn append_durable(&self, item: &T) -> io::Result<u64> {
+        let index = self.append(item)?;
+        self.wait_synced(index)?;
+
+        Ok(index)
+    }
+
+    /// Waits until the record at `index`, and every one before it, has been synced.
+    ///
+    /// Fails right away if there's no record at `index`, rather than waiting for one to be
+    /// appended.
+    pub fn wait_synced(&self, index: u64) -> io::Result<()> {
+        let mut state = self.shared.lock();
+        let len = state.log.len();
+
+        if index >= len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("record {index} out of bounds for {len} records")
+            ));
+        }
+
+        loop {
+            if let Some(error) = state.failure() {
+                return Err(error);
+            }
+
+            if state.synced > index {
+                return Ok(());
+            }
+
+            state = self.shared.synced.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Returns the number of records covered by the last sync.
+    pub fn synced(&self) -> u64 {
+        self.shared.lock().synced
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> u64 {
+        self.shared.lock().log.len()
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Syncs the pending appends, stops the commit thread and returns the log.
+    pub fn close(mut self) -> io::Result<BinaryLog<T, F>> {
+        self.stop();
+        let shared = Arc::clone(&self.shared);
+        drop(self);
+
+        let state = Arc::try_unwrap(shared)
+            .unwrap_or_else(|_| unreachable!("the commit thread has been joined"))
+            .state
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        match state.failure() {
+            Some(error) => Err(error),
+            None => Ok(state.log)
+        }
+    }
+}
+
+impl<T, F> GroupCommitLog<T, F> {
+    /// Tells the commit thread to sync the pending appends and waits for it to finish.
+    fn stop(&mut self) {
+        if let Some(committer) = self.committer.take() {
+            self.shared.lock().closed = true;
+            self.shared.appended.notify_one();
+
+            // The thread only panics if syncing the file does, and there's nothing to do then.
+            let _ = committer.join();
+        }
+    }
+}
+
+impl<T, F> Drop for GroupCommitLog<T, F> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Body of the commit thread, syncing the log once per batch until it's closed.
+fn commit<T, F>(shared: &Shared<T, F>, max_delay: Duration, max_batch: u64)
+where
+    F: Read + Write + Seek + Durable
+{
+    let mut state = shared.lock();
+
+    loop {
+        let Some(batch_start) = state.batch_start else {
+            if state.closed {
+                return;
+            }
+
+            state = shared.appended.wait(state).unwrap_or_else(PoisonError::into_inner);
+            continue;
+        };
+
+        let deadline = batch_start + max_delay;
+        let now = Instant::now();
+
+        if state.log.len() - state.synced < max_batch && now < deadline && !state.closed {
+            state = shared.appended.wait_timeout(state, deadline - now)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+            continue;
+        }
+
+        // Appends made while syncing start the next batch.
+        state.batch_start = None;
+
+        if state.failed.is_none() {
+            let len = state.log.len();
+            let unsynced = state.log.unsynced();
+
+            let result = match state.log.sync_handle() {
+                Ok(Some((mut handle, mode))) => {
+                    drop(state);
+                    let result = handle.sync(mode);
+                    state = shared.lock();
+
+                    result.map(|()| state.log.mark_synced(unsynced))
+                },
+                Ok(None) => state.log.sync(),
+                Err(error) => Err(error)
+            };
+
+            match result {
+                Ok(()) => state.synced = len,
+                Err(error) => state.failed = Some((error.kind(), error.to_string()))
+            }
+        }
+
+        shared.synced.notify_all();
+    }
+}
@@ -0,0 +1,137 @@
+use std::io::{self, Read, Write};
+use digest::{Digest, FixedOutputReset, Output};
+
+/// A writer computing the digest of every byte written through it.
+///
+/// The digest is updated incrementally with the bytes the underlying writer accepts, so it's
+/// the one of exactly what ended up in the sink, without reading it again. It can be nested
+/// with other writers, hashing what goes in or out of them depending on the nesting order.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryWrite, HashingWriter};
+/// use sha2::{Digest, Sha256};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = HashingWriter::<_, Sha256>::new(Vec::new());
+///     writer.write_binary(&[1u32, 2, 3])?;
+///
+///     let (buffer, digest) = writer.finalize();
+///     assert_eq!(digest, Sha256::digest(&buffer));
+///     Ok(())
+/// }
+/// ```
+pub struct HashingWriter<W, D> {
+    inner: W,
+    digest: D,
+}
+
+impl<W: Write, D: Digest> HashingWriter<W, D> {
+    /// Creates a new writer over `inner` with a fresh digest.
+    pub fn new(inner: W) -> Self {
+        Self::with_digest(inner, D::new())
+    }
+
+    /// Creates a new writer over `inner` continuing the given digest.
+    pub fn with_digest(inner: W, digest: D) -> Self {
+        Self {
+            inner,
+            digest,
+        }
+    }
+
+    /// Returns the underlying writer along with the digest of everything written.
+    pub fn finalize(self) -> (W, Output<D>) {
+        (self.inner, self.digest.finalize())
+    }
+
+    /// Returns the digest of everything written so far, and starts a new one.
+    pub fn finalize_reset(&mut self) -> Output<D>
+    where
+        D: FixedOutputReset
+    {
+        Digest::finalize_reset(&mut self.digest)
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one and discarding the digest.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write, D: Digest> Write for HashingWriter<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.digest.update(&buf[..written]);
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader computing the digest of every byte read through it.
+///
+/// This is the reading counterpart of [HashingWriter].
+///
+/// [HashingWriter]: HashingWriter
+pub struct HashingReader<R, D> {
+    inner: R,
+    digest: D,
+}
+
+impl<R: Read, D: Digest> HashingReader<R, D> {
+    /// Creates a new reader over `inner` with a fresh digest.
+    pub fn new(inner: R) -> Self {
+        Self::with_digest(inner, D::new())
+    }
+
+    /// Creates a new reader over `inner` continuing the given digest.
+    pub fn with_digest(inner: R, digest: D) -> Self {
+        Self {
+            inner,
+            digest,
+        }
+    }
+
+    /// Returns the underlying reader along with the digest of everything read.
+    pub fn finalize(self) -> (R, Output<D>) {
+        (self.inner, self.digest.finalize())
+    }
+
+    /// Returns the digest of everything read so far, and starts a new one.
+    pub fn finalize_reset(&mut self) -> Output<D>
+    where
+        D: FixedOutputReset
+    {
+        Digest::finalize_reset(&mut self.digest)
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwraps this reader, returning the underlying one and discarding the digest.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, D: Digest> Read for HashingReader<R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.digest.update(&buf[..read]);
+
+        Ok(read)
+    }
+}
@@ -0,0 +1,242 @@
+use std::{io::{self, Read, Seek, SeekFrom, Write}, marker::PhantomData, mem::size_of};
+use crate::{bytes, seek::{record_size, stream_len}, BinaryRead, BinaryWrite};
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// The footer closing a file written by [IndexedWriter], pointing at its offset table.
+///
+/// It's 24 bytes with the layout of this `repr(C)` struct, every multi-byte field stored in
+/// little endian byte order, and always sits at the very end of the file. The offset table it
+/// points at holds the offset of each record, as a little endian `u64`, and ends where the
+/// footer starts.
+///
+/// [IndexedWriter]: IndexedWriter
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexFooter {
+    /// Offset of the offset table from the start of the file.
+    pub table_offset: u64,
+    /// Number of records, and of entries in the offset table.
+    pub record_count: u64,
+    /// Always [MAGIC], marking the footer.
+    ///
+    /// [MAGIC]: IndexFooter::MAGIC
+    pub magic: [u8; 8],
+}
+
+impl IndexFooter {
+    /// The bytes every footer ends with.
+    pub const MAGIC: [u8; 8] = *b"BXINDX01";
+
+    /// Size of the footer in bytes.
+    pub const SIZE: usize = 24;
+}
+
+crate::swap_bytes!(IndexFooter {
+    table_offset: u64,
+    record_count: u64,
+    magic: [u8; 8],
+});
+
+const _: () = assert!(size_of::<IndexFooter>() == IndexFooter::SIZE);
+
+/// A writer of record files followed by an offset table and an [IndexFooter], like the
+/// footers of Parquet files.
+///
+/// Records are written back to back, like with [write_binary], while their offsets are kept,
+/// and [finish] appends the table and the footer. Offsets count from the start of the writer,
+/// so a header written to it with [write_header] is skipped by them. Files are read with
+/// [IndexedReader].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{IndexedReader, IndexedWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = IndexedWriter::new(Vec::new());
+///     writer.write_header(b"events v1")?;
+///
+///     for value in [10u32, 20, 30] {
+///         writer.write_record(&value)?;
+///     }
+///
+///     let mut reader = IndexedReader::<u32, _>::new(Cursor::new(writer.finish()?))?;
+///
+///     assert_eq!(reader.len(), 3);
+///     assert_eq!(reader.offset(0), Some(9));
+///     assert_eq!(reader.read_binary_at(2)?, 30);
+///     Ok(())
+/// }
+/// ```
+///
+/// [IndexFooter]: IndexFooter
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [finish]: IndexedWriter::finish
+/// [write_header]: IndexedWriter::write_header
+/// [IndexedReader]: IndexedReader
+pub struct IndexedWriter<W, T> {
+    inner: W,
+    position: u64,
+    offsets: Vec<u64>,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<W: Write, T> IndexedWriter<W, T> {
+    /// Creates a new writer over `inner`, which should be empty.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            position: 0,
+            offsets: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes bytes that aren't a record, such as a header, which the index skips.
+    pub fn write_header(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.inner.write_all(bytes)?;
+        self.position += bytes.len() as u64;
+
+        Ok(())
+    }
+
+    /// Writes a record, keeping its offset for the table.
+    pub fn write_record(&mut self, item: &T) -> io::Result<()> {
+        let bytes = bytes::as_bytes(item);
+
+        self.inner.write_all(bytes)?;
+        self.offsets.push(self.position);
+        self.position += bytes.len() as u64;
+
+        Ok(())
+    }
+
+    /// Returns how many records were written.
+    pub fn record_count(&self) -> u64 {
+        self.offsets.len() as u64
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes the offset table and the footer, and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut table = Vec::with_capacity(self.offsets.len() * size_of::<u64>());
+        self.offsets.iter().for_each(|offset| table.extend_from_slice(&offset.to_le_bytes()));
+
+        let footer = IndexFooter {
+            table_offset: self.position,
+            record_count: self.offsets.len() as u64,
+            magic: IndexFooter::MAGIC,
+        };
+
+        self.inner.write_all(&table)?;
+        self.inner.write_binary_le(&footer)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+/// A reader giving random access to the records of a file written by [IndexedWriter],
+/// through its offset table.
+///
+/// The footer and the whole table are read when the reader is created, checking that the
+/// table sits right before the footer and that every record it points at ends before the
+/// table. A file that doesn't meet them, or has no footer, is reported as an `InvalidData`
+/// error. See [IndexedWriter] for an example.
+///
+/// [IndexedWriter]: IndexedWriter
+pub struct IndexedReader<T, R> {
+    inner: R,
+    offsets: Vec<u64>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, R: Read + Seek> IndexedReader<T, R> {
+    /// Creates a new reader over `inner`, reading its footer and offset table.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let size = record_size::<T>()?;
+        let len = stream_len(&mut inner)?;
+
+        let footer_start = len.checked_sub(IndexFooter::SIZE as u64)
+            .ok_or_else(|| invalid_data("file too small for an index footer"))?;
+
+        inner.seek(SeekFrom::Start(footer_start))?;
+        let footer = inner.read_binary_le::<IndexFooter>()?;
+
+        if footer.magic != IndexFooter::MAGIC {
+            return Err(invalid_data("missing index footer magic"));
+        }
+
+        let table_len = footer.record_count.checked_mul(size_of::<u64>() as u64);
+
+        if table_len.and_then(|table_len| footer.table_offset.checked_add(table_len)) != Some(footer_start) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "offset table of {} records at {} doesn't end at the footer at {footer_start}",
+                    footer.record_count, footer.table_offset
+                )
+            ));
+        }
+
+        inner.seek(SeekFrom::Start(footer.table_offset))?;
+        let mut table = vec![0; (footer_start - footer.table_offset) as usize];
+        inner.read_exact(&mut table)?;
+
+        let offsets = table.chunks_exact(size_of::<u64>())
+            .map(|entry| u64::from_le_bytes(entry.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        if let Some(index) = offsets.iter().position(|offset| offset.saturating_add(size) > footer.table_offset) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("record {index} at {} overlaps the offset table at {}", offsets[index], footer.table_offset)
+            ));
+        }
+
+        Ok(Self {
+            inner,
+            offsets,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> u64 {
+        self.offsets.len() as u64
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns the offset of the record at `index`, if there's one.
+    pub fn offset(&self, index: u64) -> Option<u64> {
+        usize::try_from(index).ok().and_then(|index| self.offsets.get(index).copied())
+    }
+
+    /// Reads the record at `index`, seeking to its offset.
+    pub fn read_binary_at(&mut self, index: u64) -> io::Result<T> {
+        let offset = self.offset(index).ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("record {index} out of bounds for {} records", self.offsets.len())
+        ))?;
+
+        self.inner.seek(SeekFrom::Start(offset))?;
+        self.inner.read_binary()
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
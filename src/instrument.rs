@@ -0,0 +1,54 @@
+#[cfg(feature = "metrics")]
+use std::{any::type_name, mem::size_of, time::Instant};
+
+/// Counts the records of `T` read, or a read error.
+#[inline(always)]
+#[cfg_attr(not(feature = "metrics"), allow(clippy::extra_unused_type_parameters))]
+pub(crate) fn read<T, E>(outcome: &Result<usize, E>) {
+    #[cfg(feature = "metrics")]
+    count::<T, E>(outcome, "binext_records_read_total", "binext_bytes_read_total", "binext_read_errors_total");
+    #[cfg(not(feature = "metrics"))]
+    let _ = outcome;
+}
+
+/// Counts the records of `T` written, or a write error.
+#[inline(always)]
+#[cfg_attr(not(feature = "metrics"), allow(clippy::extra_unused_type_parameters))]
+pub(crate) fn written<T, E>(outcome: &Result<usize, E>) {
+    #[cfg(feature = "metrics")]
+    count::<T, E>(outcome, "binext_records_written_total", "binext_bytes_written_total", "binext_write_errors_total");
+    #[cfg(not(feature = "metrics"))]
+    let _ = outcome;
+}
+
+/// Runs the bulk `operation` over records of `T`, recording how long it took.
+#[inline(always)]
+#[cfg_attr(not(feature = "metrics"), allow(clippy::extra_unused_type_parameters))]
+pub(crate) fn bulk<T, V>(operation: &'static str, f: impl FnOnce() -> V) -> V {
+    #[cfg(feature = "metrics")]
+    {
+        let start = Instant::now();
+        let result = f();
+
+        ::metrics::histogram!("binext_bulk_duration_seconds", "type" => type_name::<T>(), "operation" => operation)
+            .record(start.elapsed().as_secs_f64());
+
+        result
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = operation;
+        f()
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn count<T, E>(outcome: &Result<usize, E>, records: &'static str, bytes: &'static str, errors: &'static str) {
+    match outcome {
+        Ok(count) => {
+            ::metrics::counter!(records, "type" => type_name::<T>()).increment(*count as u64);
+            ::metrics::counter!(bytes, "type" => type_name::<T>()).increment((count * size_of::<T>()) as u64);
+        },
+        Err(_) => ::metrics::counter!(errors, "type" => type_name::<T>()).increment(1)
+    }
+}
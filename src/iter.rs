@@ -0,0 +1,224 @@
+use std::{io::{self, Read, Seek, SeekFrom}, marker::PhantomData, mem::{size_of, MaybeUninit}};
+use crate::{bytes, seek::records_to_bytes};
+
+/// Reads into `buf` until it's full or the source ends, returning how many bytes were read.
+pub(crate) fn read_up_to<R: Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e)
+        }
+    }
+
+    Ok(filled)
+}
+
+/// Fills `buf` from the source, returning `false` if the source ended right before it.
+///
+/// Ending in the middle of `buf` is reported as an `UnexpectedEof` error.
+pub(crate) fn read_record_bytes<R: Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("source ended {filled} bytes into a {} bytes record", buf.len())
+            )),
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e)
+        }
+    }
+
+    Ok(true)
+}
+
+/// Reads a record of `T`, returning `None` if the source ended right at a record boundary.
+///
+/// Ending in the middle of a record is reported as an `UnexpectedEof` error.
+pub(crate) fn read_record<T, R: Read + ?Sized>(reader: &mut R) -> io::Result<Option<T>> {
+    let mut item = MaybeUninit::<T>::zeroed();
+
+    // SAFETY: the memory is zeroed, so every byte of it is initialized.
+    let buf = bytes::slice_as_bytes_mut(unsafe {
+        std::slice::from_raw_parts_mut(item.as_mut_ptr(), 1)
+    });
+
+    if !read_record_bytes(reader, buf)? {
+        return Ok(None);
+    }
+
+    // SAFETY: every byte of the record has been read from the source.
+    Ok(Some(unsafe { item.assume_init() }))
+}
+
+/// An iterator over the records of a binary source, created by [binary_iter].
+///
+/// It stops once the source ends at a record boundary. If it ends in the middle of a record, or
+/// the source fails, the error is yielded and the iteration stops.
+///
+/// [binary_iter]: crate::BinaryRead::binary_iter
+pub struct BinaryIter<'a, R: ?Sized, T> {
+    reader: &'a mut R,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, R: Read + ?Sized, T> BinaryIter<'a, R, T> {
+    pub(crate) fn new(reader: &'a mut R) -> Self {
+        Self {
+            reader,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Read + ?Sized, T> Iterator for BinaryIter<'_, R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Zero sized records take no bytes, so there is no end of the source to stop at.
+        if self.done || size_of::<T>() == 0 {
+            return None;
+        }
+
+        let item = read_record(self.reader).transpose();
+        self.done = !matches!(item, Some(Ok(_)));
+
+        item
+    }
+}
+
+/// An iterator over the records of a seekable binary source along with the byte offset each one
+/// starts at, created by [binary_iter_with_offset].
+///
+/// [binary_iter_with_offset]: crate::BinaryReadSeek::binary_iter_with_offset
+pub struct BinaryIterWithOffset<'a, R: ?Sized, T> {
+    inner: BinaryIter<'a, R, T>,
+    offset: u64,
+}
+
+impl<'a, R: Read + ?Sized, T> BinaryIterWithOffset<'a, R, T> {
+    pub(crate) fn new(reader: &'a mut R, offset: u64) -> Self {
+        Self {
+            inner: BinaryIter::new(reader),
+            offset,
+        }
+    }
+}
+
+impl<R: Read + ?Sized, T> Iterator for BinaryIterWithOffset<'_, R, T> {
+    type Item = io::Result<(u64, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next()?;
+        let offset = self.offset;
+        self.offset += size_of::<T>() as u64;
+
+        Some(item.map(|item| (offset, item)))
+    }
+}
+
+/// An iterator over the records of a seekable binary source from the last one to the first,
+/// created by [binary_iter_rev].
+///
+/// Every record is read by seeking to its start. If the source fails, the error is yielded and
+/// the iteration stops.
+///
+/// [binary_iter_rev]: crate::BinaryReadSeek::binary_iter_rev
+pub struct BinaryIterRev<'a, R: ?Sized, T> {
+    reader: &'a mut R,
+    remaining: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, R: Read + Seek + ?Sized, T> BinaryIterRev<'a, R, T> {
+    pub(crate) fn new(reader: &'a mut R, records: u64) -> Self {
+        Self {
+            reader,
+            remaining: records,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Read + Seek + ?Sized, T> Iterator for BinaryIterRev<'_, R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+
+        let item = self.reader.seek(SeekFrom::Start(self.remaining * size_of::<T>() as u64))
+            .and_then(|_| read_record(self.reader))
+            .and_then(|item| item.ok_or_else(|| io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "source ended before the record"
+            )));
+
+        if item.is_err() {
+            self.remaining = 0;
+        }
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, usize::try_from(self.remaining).ok())
+    }
+}
+
+/// Extension for iterators of records, collecting them into a binary buffer.
+///
+/// This is the writing counterpart of [binary_iter]: each record is written in order, like with
+/// [write_binary], into a single `Vec<u8>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, CollectBinary};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let records = [1u16, 2, 3];
+///     let bytes = records.iter().collect_binary();
+///
+///     let read = Cursor::new(bytes)
+///         .binary_iter::<u16>()
+///         .collect::<io::Result<Vec<_>>>()?;
+///
+///     assert_eq!(read, records);
+///     Ok(())
+/// }
+/// ```
+///
+/// [binary_iter]: crate::BinaryRead::binary_iter
+/// [write_binary]: crate::BinaryWrite::write_binary
+pub trait CollectBinary: Iterator + Sized {
+    /// Collects an iterator of references to records into their bytes.
+    fn collect_binary<'a, T: 'a>(self) -> Vec<u8>
+    where
+        Self: Iterator<Item = &'a T>
+    {
+        let mut buf = Vec::with_capacity(records_to_bytes::<T>(self.size_hint().0).unwrap_or(0));
+        self.for_each(|item| buf.extend_from_slice(bytes::as_bytes(item)));
+
+        buf
+    }
+
+    /// Collects an iterator of owned records into their bytes.
+    fn collect_binary_owned(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(records_to_bytes::<Self::Item>(self.size_hint().0).unwrap_or(0));
+        self.for_each(|item| buf.extend_from_slice(bytes::as_bytes(&item)));
+
+        buf
+    }
+}
+
+impl<I: Iterator> CollectBinary for I {}
@@ -0,0 +1,35 @@
+use std::mem::{align_of, size_of};
+
+/// Declares the exact size and alignment a type has in its binary format.
+///
+/// Implementations are usually generated with [binary_layout], which also checks at compile time
+/// that the declared values match the real layout of the type, so accidental field changes that
+/// would silently change the on-disk format break the build instead. The check is available as
+/// `T::assert_layout()` too.
+///
+/// [binary_layout]: crate::binary_layout
+pub trait BinaryLayout: Sized {
+    /// Size in bytes of the type.
+    const SIZE: usize;
+    /// Alignment in bytes of the type.
+    const ALIGN: usize;
+}
+
+/// Panics, failing the build when evaluated in a const context, if `T` doesn't have the given
+/// size and alignment.
+///
+/// # Examples
+///
+/// ```rust
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     value: u32
+/// }
+///
+/// const _: () = binext::assert_layout::<Record, 16, 8>();
+/// ```
+pub const fn assert_layout<T, const SIZE: usize, const ALIGN: usize>() {
+    assert!(size_of::<T>() == SIZE, "size of the type doesn't match its declared layout");
+    assert!(align_of::<T>() == ALIGN, "alignment of the type doesn't match its declared layout");
+}
@@ -0,0 +1,126 @@
+use std::{borrow::Cow, io, marker::PhantomData, mem::size_of, ptr};
+use crate::{BufferTooSmall, Validate, ValidationError};
+
+/// The raw bytes of a `T`, owned or borrowed, whose fields are decoded only when asked for.
+///
+/// This is meant for filtering large records, where most are discarded after looking at a
+/// few fields: [get] decodes and validates just the bytes of one field, and records that pass
+/// the filter are decoded in full with [materialize]. Fields are found by their offset, as
+/// given by `offset_of!`, and loaded unaligned, so the bytes can come from anywhere in a
+/// buffer.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{to_array, LazyStruct};
+/// use std::mem::offset_of;
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Order {
+///     id: u64,
+///     price: f64,
+///     quantity: u32,
+///     notes: [u8; 1004]
+/// }
+///
+/// let order = Order { id: 7, price: 9.5, quantity: 3, notes: [0; 1004] };
+/// let bytes = to_array::<_, 1024>(&order);
+///
+/// let lazy = LazyStruct::<Order>::new(&bytes[..]).unwrap();
+///
+/// if lazy.get::<u32>(offset_of!(Order, quantity)).unwrap() > 2 {
+///     assert_eq!(lazy.materialize(), order);
+/// }
+/// ```
+///
+/// [get]: LazyStruct::get
+/// [materialize]: LazyStruct::materialize
+#[derive(Debug, Clone)]
+pub struct LazyStruct<'a, T> {
+    bytes: Cow<'a, [u8]>,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> LazyStruct<'a, T> {
+    /// Wraps the record held at the start of `bytes`, ignoring any bytes past it.
+    ///
+    /// Fails if `bytes` are shorter than a `T`.
+    pub fn new(bytes: impl Into<Cow<'a, [u8]>>) -> Result<Self, BufferTooSmall> {
+        let mut bytes = bytes.into();
+
+        if bytes.len() < size_of::<T>() {
+            return Err(BufferTooSmall { needed: size_of::<T>(), available: bytes.len() });
+        }
+
+        match &mut bytes {
+            Cow::Borrowed(slice) => *slice = &slice[..size_of::<T>()],
+            Cow::Owned(vec) => vec.truncate(size_of::<T>())
+        }
+
+        Ok(Self {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Decodes the field of type `F` found at `offset` of the record, checking only its bytes.
+    ///
+    /// The offset and type aren't checked against `T` itself, so a wrong pair decodes other
+    /// bytes of the record, but never reads past it.
+    pub fn get<F: Validate>(&self, offset: usize) -> Result<F, ValidationError> {
+        let field = offset.checked_add(size_of::<F>())
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or_else(|| ValidationError::new(offset, "field out of bounds"))?;
+
+        F::validate_bytes(field).map_err(|e| ValidationError { offset: offset + e.offset, ..e })?;
+
+        // SAFETY: the bytes are in bounds and have been checked to form a valid F.
+        Ok(unsafe { ptr::read_unaligned(field.as_ptr() as *const F) })
+    }
+
+    /// Decodes the whole record, assuming its bytes form a valid `T` like [read_binary] does.
+    ///
+    /// [read_binary]: crate::BinaryRead::read_binary
+    pub fn materialize(&self) -> T {
+        // SAFETY: the bytes hold a whole T, whose validity is assumed like for any read.
+        unsafe { ptr::read_unaligned(self.bytes.as_ptr() as *const T) }
+    }
+
+    /// Decodes the whole record, checking that its bytes form a valid `T` first.
+    pub fn materialize_validated(&self) -> Result<T, ValidationError>
+    where
+        T: Validate
+    {
+        T::validate_bytes(&self.bytes)?;
+        Ok(self.materialize())
+    }
+
+    /// Returns the bytes of the record.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Takes ownership of the bytes, so the record outlives the buffer it was borrowed from.
+    pub fn into_owned(self) -> LazyStruct<'static, T> {
+        LazyStruct {
+            bytes: Cow::Owned(self.bytes.into_owned()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Reads the bytes of a record into an owned [LazyStruct], as described in
+/// [read_binary_lazy].
+///
+/// [LazyStruct]: LazyStruct
+/// [read_binary_lazy]: crate::BinaryRead::read_binary_lazy
+pub(crate) fn read_lazy<T, R: io::Read + ?Sized>(reader: &mut R) -> io::Result<LazyStruct<'static, T>> {
+    let mut bytes = vec![0; size_of::<T>()];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(LazyStruct {
+        bytes: Cow::Owned(bytes),
+        _marker: PhantomData,
+    })
+}
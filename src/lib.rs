@@ -95,22 +95,239 @@
 //!
 //! ```
 //!
+//! # Types with invalid bit patterns
+//!
+//! Some types can't hold arbitrary bytes: a `bool` must be `0` or `1`, a `char` must be a valid
+//! unicode scalar value and the `NonZero*` integers must never be zero. Reading, for example,
+//! four zero bytes into a struct with a `NonZeroU32` field is undefined behaviour. Such structs
+//! should implement [Validate] and be read with [read_binary_validated], which rejects invalid
+//! bytes with an `InvalidData` error. When zero is a legitimate value, prefer `Option<NonZero*>`,
+//! which accepts every byte pattern.
+//!
+//! # Metrics
+//!
+//! With the `metrics` feature, typed reads and writes are reported to the [metrics] facade,
+//! to whatever recorder the application installs. Without it, none of this is compiled in.
+//!
+//! - `binext_records_read_total`, `binext_bytes_read_total` and `binext_read_errors_total`
+//!   count the records read, and the reads that failed, by [read_binary] and the bulk reads
+//!   built on [read_binary_extend] and [read_binary_extend_partial].
+//! - `binext_records_written_total`, `binext_bytes_written_total` and
+//!   `binext_write_errors_total` count the same for [write_binary] and
+//!   [write_records_atomic].
+//! - `binext_bulk_duration_seconds` is a histogram of how long each bulk operation took.
+//!
+//! Counters are labeled with the `type` of the records, as given by `std::any::type_name`, and
+//! the histogram with the `operation` too, one of `read_binary_extend`,
+//! `read_binary_extend_partial` and `write_records_atomic`.
+//! There are no per-record labels, so there is one series per record type.
+//!
 //! [Read]: std::io::Read
 //! [Write]: std::io::Write
 //! [BinaryRead]: BinaryRead
 //! [BinaryWrite]: BinaryWrite
+//! [Validate]: Validate
+//! [read_binary_validated]: BinaryRead::read_binary_validated
+//! [metrics]: https://docs.rs/metrics
+//! [read_binary]: BinaryRead::read_binary
+//! [read_binary_extend]: BinaryRead::read_binary_extend
+//! [read_binary_extend_partial]: BinaryRead::read_binary_extend_partial
+//! [write_binary]: BinaryWrite::write_binary
+//! [write_records_atomic]: write_records_atomic
 //!
 
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
 #[cfg(test)]
 mod tests;
+mod aligned;
+mod array;
+mod borrowed;
+mod buffering;
+mod bytes;
+mod cbool;
+mod chain;
+mod checksum;
+mod columns;
+#[cfg(feature = "testing")]
+mod compare;
+mod convert;
+mod crc;
+mod datetime;
+mod deadline;
+mod dedup;
+mod delta;
+mod durable;
+mod endian;
+mod envelope;
+mod enums;
+mod fam;
+#[cfg(any(unix, windows))]
+mod fd;
+mod file;
+mod fragment;
+#[cfg(feature = "bitflags")]
+mod flags;
+#[cfg(feature = "half")]
+mod float16;
+mod framing;
+mod group;
+#[cfg(feature = "digest")]
+mod hashing;
+mod indexed;
+mod instrument;
+mod iter;
+mod layout;
+mod lazy;
+#[cfg(feature = "nalgebra")]
+mod linalg;
+mod log;
+mod macros;
+#[cfg(feature = "memmap2")]
+mod mapped;
+#[cfg(feature = "ndarray")]
+mod ndim;
+mod npy;
+mod packed;
+mod page;
+mod patch;
+mod periodic;
+mod report;
+mod scan;
+mod scatter;
+mod seek;
+mod separated;
+#[cfg(feature = "zstd-seekable")]
+mod seekable;
+mod seqlock;
+mod sequence;
+mod slot;
+#[cfg(any(unix, windows))]
+mod snapshot;
+mod stride;
+mod tagged;
+mod take;
+mod tee;
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(feature = "text")]
+mod text;
+mod throttle;
+mod validate;
+mod varint;
+#[cfg(feature = "glam")]
+mod vecmath;
+mod wire;
+mod zeroable;
+
+pub use aligned::{AlignedBuffer, AlignedBytes, aligned_buffer};
+pub use array::{BufferTooSmall, from_array, read_binary_from_slice_into, to_array, write_binary_to_slice, write_into_vec};
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub use array::write_into_heapless_vec;
+pub use borrowed::BinaryReadSlice;
+pub use buffering::BufferingBinaryWriter;
+pub use cbool::{CBool, CBool8, CBool16, CBool32};
+pub use chain::{ChainBroken, ChainedLogReader, ChainedLogWriter, FileHasher, LinkBroken, LinkedFileReader};
+pub use checksum::{Checksum, ChecksumMismatch, ChecksumOf};
+pub use columns::{ColumnSet, Columns, Field, join_columns, read_columns, split_columns, write_columns};
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use compare::{binary_eq_report, BinaryFields, DiffReport, FieldInfo};
+pub use convert::convert_records;
+pub use crc::crc32;
+pub use datetime::BinDateTimeUtc;
+pub use deadline::{DeadlineExceeded, DeadlineReader, SetReadTimeout};
+pub use dedup::{DedupReader, DedupWriter};
+pub use delta::{DeltaReader, DeltaWriter};
+pub use durable::{DurabilityPolicy, Durable, SyncMode};
+pub use endian::SwapBytes;
+pub use envelope::{Envelope, EnvelopedWriter};
+pub use enums::{BinaryEnum, UnknownVariant};
+pub use fam::FlexibleArray;
+#[cfg(any(unix, windows))]
+pub use fd::{FdReader, FdWriter};
+pub use file::{BinaryFile, BinaryFileWriter, FileFooter, open_binary_file, write_records_atomic};
+pub use fragment::{FragmentHeader, Reassembler, fragment_binary};
+#[cfg(feature = "bitflags")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+pub use flags::UnknownBits;
+#[cfg(feature = "bitflags")]
+#[doc(hidden)]
+pub use flags::validate_flags as __validate_flags;
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
+pub use float16::as_f32_vec;
+pub use framing::{FramedReader, FramedWriter, RecordHeader};
+pub use group::GroupCommitLog;
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub use hashing::{HashingReader, HashingWriter};
+pub use indexed::{IndexFooter, IndexedReader, IndexedWriter};
+pub use iter::{BinaryIter, BinaryIterRev, BinaryIterWithOffset, CollectBinary};
+pub use layout::{BinaryLayout, assert_layout};
+pub use lazy::LazyStruct;
+pub use log::{BinaryLog, LogOptions, RecoveryReport};
+#[cfg(feature = "memmap2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+pub use mapped::{MappedIter, MappedRecords, mmap_iter};
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+pub use ndim::MAX_NDIM;
+pub use npy::{NpyField, NpyRecord, NpyType, read_npy, write_npy};
+pub use packed::{Packed, PresenceMismatch};
+pub use page::{Page, SlotIdx};
+pub use patch::{apply_delta, layout_fingerprint, make_delta, Delta, DeltaRange};
+pub use periodic::PeriodicFlushWriter;
+pub use report::{FileReport, HeaderCheck, RecordFileOptions, validate_record_file, validate_record_file_deep, validate_record_file_with};
+pub use scan::{scan_for, scan_reader};
+pub use scatter::ChainReader;
+pub use seek::{BinaryReadSeek, Misaligned};
+pub use separated::{SeparatedReader, SeparatedWriter};
+#[cfg(feature = "zstd-seekable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd-seekable")))]
+pub use seekable::{SeekableReader, SeekableWriter};
+pub use seqlock::SeqLocked;
+pub use sequence::{DisorderPolicy, OutOfSequence, SequenceChecker, SequenceEvent};
+pub use slot::{SlotHandle, SlotWriter};
+#[cfg(any(unix, windows))]
+pub use snapshot::{read_binary_at_offsets, snapshot_records, snapshot_records_verified};
+pub use stride::BinaryBufReader;
+pub use tagged::TaggedStreamReader;
+pub use take::{LimitReached, TakeExact};
+pub use tee::{TeeFailed, TeeWriter};
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use testing::{measure_throughput, MockBinarySink, MockBinarySource, ThroughputReport};
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+pub use text::{BINARY_MARKER, FromTextPairs, InvalidPair};
+pub use throttle::{Clock, SystemClock, ThrottledWriter};
+pub use validate::{Validate, ValidationError, validate_field};
+#[cfg(feature = "glam")]
+#[cfg_attr(docsrs, doc(cfg(feature = "glam")))]
+pub use vecmath::{UnalignedMat2, UnalignedMat4, UnalignedQuat, UnalignedVec4};
+pub use wire::{WireOption, WireTwin};
+pub use zeroable::Zeroable;
 
-use std::{alloc::{alloc, Layout}, io::{self, Write, Read}, mem::size_of, slice};
+#[doc(hidden)]
+pub use enums::{skip_padding as __skip_padding, write_padding as __write_padding};
+#[doc(hidden)]
+pub use packed::condition as __condition;
+
+use std::{collections::{BTreeMap, HashMap}, hash::Hash, io::{self, Write, Read, Seek, SeekFrom}, mem::{size_of, MaybeUninit}, ptr, slice, time::{Duration, SystemTime}};
+
+/// Most bytes [read_self_sized] accepts beyond the size of the struct, 16 MiB, so a corrupt or
+/// hostile size field can't make it allocate an arbitrary amount of them.
+///
+/// [read_self_sized]: BinaryRead::read_self_sized
+pub const MAX_SELF_SIZED_SURPLUS: usize = 16 * 1024 * 1024;
 
 /// The BinaryRead trait allows for reading data structures out of binary sources.
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// struct MyStruct {
 ///     some: char,
 ///     // fields
@@ -139,7 +356,7 @@ pub trait BinaryRead: Read {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// struct MyStruct {
     ///     some: char,
     ///     // fields
@@ -161,26 +378,21 @@ pub trait BinaryRead: Read {
     /// ```
     ///
     fn read_binary_boxed<T>(&mut self) -> io::Result<Box<T>> {
-        Ok(unsafe {
-            // Allocate the memory using the global allocator, so it can be Boxed later.
-            let ptr = alloc(Layout::new::<T>());
-
-            // SAFETY: all needed conditions for this not to be UB are satisfied, see
-            // slice::from_raw_parts_mut to see them.
-            let slice = slice::from_raw_parts_mut(ptr, size_of::<T>());
+        let mut buffer = aligned_buffer::<T>();
 
-            self.read_exact(slice)?;
+        let read = self.read_exact(&mut buffer).map(|_| 1);
+        instrument::read::<T, _>(&read);
+        read?;
 
-            // SAFETY: The pointer has been written to, since it has not been freed, it is still valid.
-            Box::from_raw(ptr as *mut T)
-        })
+        // SAFETY: the bytes have been read from the source, which is all read_binary asks.
+        Ok(unsafe { buffer.assume_init() })
     }
 
     /// Reads from a binary source and converts the bytes into the specified structure.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// struct MyStruct {
     ///     some: char,
     ///     // fields
@@ -205,86 +417,1665 @@ pub trait BinaryRead: Read {
         self.read_binary_boxed()
             .map(|boxed| *boxed)
     }
-}
 
-/// The BinaryRead trait allows for writing data structures into binary [Write] sources.
-///
-/// # Examples
-///
-/// ```rust
-/// #[derive(Default)]
-/// struct MyStruct {
-///     some: char,
-///     // fields
-/// }
-///
-/// use binext::BinaryWrite;
-/// use std::{io, fs};
-///
-/// fn main() -> io::Result<()> {
-///     use std::sync::Mutex;
-///     let mut file = fs::OpenOptions::new()
-///         .write(true)
-///         .create(true)
-///         .truncate(true)
-///         .open("myfile.bin")?;
-///
-///     let instance = MyStruct::default();
-///
-///     // Write the struct into the file, this way, later when read, it can be recovered.
-///     file.write_binary(&instance)?;
-///
-///     Ok(())
-/// }
-/// ```
-///
-/// [Write]: Write
-///
-pub trait BinaryWrite: Write {
-    /// Writes into a binary source the provided struct.
+    /// Reads the bytes of a record into a [LazyStruct], to decode only the fields needed.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// #[derive(Default)]
-    /// struct MyStruct {
-    ///     some: char,
-    ///     // fields
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::{io::{self, Cursor}, mem::offset_of};
+    ///
+    /// #[repr(C)]
+    /// struct Event {
+    ///     kind: u16,
+    ///     payload: [u8; 254]
     /// }
     ///
-    /// use binext::BinaryWrite;
-    /// use std::{io, fs};
+    /// fn main() -> io::Result<()> {
+    ///     let mut bytes = Vec::new();
+    ///     bytes.write_binary(&Event { kind: 3, payload: [0; 254] })?;
+    ///
+    ///     let event = Cursor::new(bytes).read_binary_lazy::<Event>()?;
+    ///     assert_eq!(event.get::<u16>(offset_of!(Event, kind))?, 3);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [LazyStruct]: crate::LazyStruct
+    fn read_binary_lazy<T>(&mut self) -> io::Result<LazyStruct<'static, T>> {
+        lazy::read_lazy(self)
+    }
+
+    /// Reads from a binary source like [read_binary], retrying the reads that fail with a
+    /// transient error.
+    ///
+    /// Flaky sources, like non-blocking sockets, may fail with errors such as `WouldBlock` or
+    /// `TimedOut` in the middle of a record. Errors for which `is_transient` returns `true` are
+    /// retried up to `retries` times in total, keeping the bytes already read, while any other
+    /// error is returned right away. `Interrupted` errors are always retried and don't count.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use binext::BinaryRead;
+    /// use std::{io, net::TcpStream};
     ///
     /// fn main() -> io::Result<()> {
-    ///     use std::sync::Mutex;
-    ///     let mut file = fs::OpenOptions::new()
-    ///         .write(true)
-    ///         .create(true)
-    ///         .truncate(true)
-    ///         .open("myfile.bin")?;
+    ///     let mut stream = TcpStream::connect("127.0.0.1:7000")?;
+    ///     stream.set_nonblocking(true)?;
     ///
-    ///     let instance = MyStruct::default();
+    ///     let header = stream.read_binary_retry::<[u8; 16]>(5, |e| {
+    ///         e.kind() == io::ErrorKind::WouldBlock
+    ///     })?;
     ///
-    ///     // Write the struct into the file, this way, later when read, it can be recovered.
-    ///     file.write_binary(&instance)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [read_binary]: BinaryRead::read_binary
+    fn read_binary_retry<T>(&mut self, retries: usize, is_transient: impl Fn(&io::Error) -> bool) -> io::Result<T> {
+        let mut item = MaybeUninit::<T>::zeroed();
+
+        // SAFETY: the memory is zeroed, so every byte of it is initialized.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(item.as_mut_ptr() as *mut u8, size_of::<T>())
+        };
+
+        let mut filled = 0;
+        let mut attempts = 0;
+
+        while filled < bytes.len() {
+            match self.read(&mut bytes[filled..]) {
+                Ok(0) => return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("source ended {filled} bytes into a {} bytes record", size_of::<T>())
+                )),
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) if attempts < retries && is_transient(&e) => attempts += 1,
+                Err(e) => return Err(e)
+            }
+        }
+
+        // SAFETY: every byte of the record has been read from the source.
+        Ok(unsafe { item.assume_init() })
+    }
+
+    /// Reads from a binary source and converts the bytes into the specified structure, checking
+    /// beforehand that they form a valid instance of it.
+    ///
+    /// Types with validity invariants, like `bool`, `char` or the `NonZero*` integers, can't hold
+    /// arbitrary bytes, and reading an invalid pattern into them with [read_binary] is undefined
+    /// behaviour. This method runs [Validate::validate_bytes] over the bytes read and returns an
+    /// [InvalidData] error instead of producing an invalid value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::{io::{self, Cursor}, num::NonZeroU32};
+    ///
+    /// fn main() {
+    ///     let mut cursor = Cursor::new([0u8; 4]);
+    ///     let error = cursor.read_binary_validated::<NonZeroU32>().unwrap_err();
+    ///
+    ///     assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    /// }
+    /// ```
+    ///
+    /// [read_binary]: BinaryRead::read_binary
+    /// [Validate::validate_bytes]: Validate::validate_bytes
+    /// [InvalidData]: io::ErrorKind::InvalidData
+    fn read_binary_validated<T: Validate>(&mut self) -> io::Result<T> {
+        let mut item = MaybeUninit::<T>::zeroed();
+
+        // SAFETY: the memory is zeroed, so every byte of it is initialized.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(item.as_mut_ptr() as *mut u8, size_of::<T>())
+        };
+
+        self.read_exact(bytes)?;
+        T::validate_bytes(bytes)?;
+
+        // SAFETY: the bytes have been checked to form a valid T.
+        Ok(unsafe { item.assume_init() })
+    }
+
+    /// Reads the `#[repr(C)]` twin of a [WireTwin] type, validating it, and converts it back,
+    /// as written by [write_binary_wire].
+    ///
+    /// A twin failing to validate, or holding a value that doesn't convert back, like an unknown
+    /// enum discriminant, is reported as an `InvalidData` error carrying a [ValidationError].
+    /// See [wire_twin] for an example.
+    ///
+    /// [WireTwin]: crate::WireTwin
+    /// [write_binary_wire]: BinaryWrite::write_binary_wire
+    /// [ValidationError]: crate::ValidationError
+    /// [wire_twin]: crate::wire_twin
+    fn read_binary_wire<T: WireTwin>(&mut self) -> io::Result<T> {
+        let wire = self.read_binary_validated::<T::Wire>()?;
+        Ok(T::from_wire(wire)?)
+    }
+
+    /// Reads a record of `T` holding a CRC-32 of its own bytes in the `u32` field at
+    /// `crc_offset`, as written by [write_binary_self_crc].
+    ///
+    /// The checksum covers every other byte of the record, padding included, and is stored in
+    /// native byte order like the rest of the record. A record whose stored checksum doesn't
+    /// match its bytes is reported as an `InvalidData` error before being turned into a `T`.
+    /// A `u32` at `crc_offset` that doesn't fit within a `T` is reported as an `InvalidInput`
+    /// error before anything is read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::{io::{self, Cursor}, mem::offset_of};
+    ///
+    /// #[repr(C)]
+    /// #[derive(Debug, PartialEq)]
+    /// struct Block {
+    ///     id: u32,
+    ///     crc: u32,
+    ///     data: [u8; 8]
+    /// }
     ///
+    /// fn main() -> io::Result<()> {
+    ///     let block = Block { id: 3, crc: 0, data: *b"payload!" };
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_self_crc(&block, offset_of!(Block, crc))?;
+    ///
+    ///     let read = Cursor::new(&buffer).read_binary_self_crc::<Block>(offset_of!(Block, crc))?;
+    ///     assert_eq!(read.data, block.data);
+    ///
+    ///     buffer[9] ^= 1;
+    ///     assert!(Cursor::new(&buffer).read_binary_self_crc::<Block>(offset_of!(Block, crc)).is_err());
     ///     Ok(())
     /// }
     /// ```
-    fn write_binary<T>(&mut self, item: &T) -> io::Result<()> {
-        let ptr = item as *const T as *mut u8;
+    ///
+    /// [write_binary_self_crc]: BinaryWrite::write_binary_self_crc
+    fn read_binary_self_crc<T>(&mut self, crc_offset: usize) -> io::Result<T> {
+        crc::check_offset(crc_offset, size_of::<T>())?;
+        let mut item = MaybeUninit::<T>::zeroed();
 
-        // SAFETY: all needed conditions for this not to be UB are satisfied, see
-        // slice::from_raw_parts to see them.
-        let buf = unsafe {
-            slice::from_raw_parts(ptr, size_of::<T>())
+        // SAFETY: the memory is zeroed, so every byte of it is initialized.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(item.as_mut_ptr() as *mut u8, size_of::<T>())
         };
 
-        self.write(buf)?;
-        Ok(())
+        self.read_exact(bytes)?;
+
+        let computed = crc::crc32_excluding(bytes, crc_offset);
+        let stored = u32::from_ne_bytes(bytes[crc_offset..crc_offset + crc::CRC_SIZE].try_into().unwrap());
+
+        if stored != computed {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("stored checksum {stored:#010x} doesn't match the record's {computed:#010x}")
+            ));
+        }
+
+        // SAFETY: the bytes have been read from the source and match their checksum.
+        Ok(unsafe { item.assume_init() })
     }
-}
 
-impl<I: Write> BinaryWrite for I {}
+    /// Reads a structure stored in little endian byte order, swapping the bytes of its fields on
+    /// big endian hosts.
+    ///
+    /// If swapping produces an invalid value, like a `char` that isn't a valid unicode scalar
+    /// value, an `InvalidData` error is returned. See [SwapBytes] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut cursor = Cursor::new([2, 1, 0, 0]);
+    ///     assert_eq!(cursor.read_binary_le::<u32>()?, 258);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [SwapBytes]: SwapBytes
+    fn read_binary_le<T: SwapBytes>(&mut self) -> io::Result<T> {
+        endian::read_swapped(self, cfg!(target_endian = "big"))
+    }
 
-impl<I: Read> BinaryRead for I {}
+    /// Reads a structure stored in big endian byte order, swapping the bytes of its fields on
+    /// little endian hosts.
+    ///
+    /// If swapping produces an invalid value, like a `char` that isn't a valid unicode scalar
+    /// value, an `InvalidData` error is returned. See [SwapBytes] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut cursor = Cursor::new([0, 0, 1, 2]);
+    ///     assert_eq!(cursor.read_binary_be::<u32>()?, 258);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [SwapBytes]: SwapBytes
+    fn read_binary_be<T: SwapBytes>(&mut self) -> io::Result<T> {
+        endian::read_swapped(self, cfg!(target_endian = "little"))
+    }
+
+    /// Reads a field stored either as a `u32` or as a `u64`, depending on `is_64`, widening it
+    /// to a `u64`.
+    ///
+    /// This covers formats where a flag in a header selects the width of a length or offset
+    /// field. Since a `#[repr(C)]` struct has a fixed layout, records containing such a field
+    /// are best declared as two structs, one for each width, with the flag choosing which one
+    /// is read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[repr(C)]
+    /// struct Header {
+    ///     flags: u32
+    /// }
+    ///
+    /// const WIDE_LENGTHS: u32 = 1;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&Header { flags: WIDE_LENGTHS })?;
+    ///     buffer.write_binary(&5_000_000_000u64)?;
+    ///
+    ///     let mut cursor = Cursor::new(buffer);
+    ///     let header = cursor.read_binary::<Header>()?;
+    ///     let len = cursor.read_u32_or_u64(header.flags & WIDE_LENGTHS != 0)?;
+    ///
+    ///     assert_eq!(len, 5_000_000_000);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_u32_or_u64(&mut self, is_64: bool) -> io::Result<u64> {
+        match is_64 {
+            true => self.read_binary::<u64>(),
+            false => self.read_binary::<u32>().map(u64::from)
+        }
+    }
+
+    /// Reads from a binary source exactly as many records as fit in `dst`, overwriting them in
+    /// place.
+    ///
+    /// This allows refilling a preallocated buffer each time without allocating. The bytes are
+    /// read straight into the memory of the slice, so if the read fails, its contents are
+    /// unspecified: some leading elements may hold new data and one of them may be a mix of old
+    /// and new bytes. Use [read_binary_slice_partial] to know how many records were filled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u32, 2, 3])?;
+    ///
+    ///     let mut records = vec![0u32; 3];
+    ///     Cursor::new(buffer).read_binary_slice_into(&mut records)?;
+    ///
+    ///     assert_eq!(records, [1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [read_binary_slice_partial]: BinaryRead::read_binary_slice_partial
+    fn read_binary_slice_into<T>(&mut self, dst: &mut [T]) -> io::Result<()> {
+        self.read_exact(bytes::slice_as_bytes_mut(dst))
+    }
+
+    /// Reads records into `dst` until it is full or the source ends, returning how many complete
+    /// records were read.
+    ///
+    /// The first `n` elements of `dst`, `n` being the returned count, are fully overwritten with
+    /// the records read. If the source ended in the middle of a record, the element after them
+    /// holds a mix of old and new bytes, while the rest are untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // Two and a half u32 records.
+    ///     let mut cursor = Cursor::new([1u8; 10]);
+    ///     let mut records = [0u32; 4];
+    ///
+    ///     assert_eq!(cursor.read_binary_slice_partial(&mut records)?, 2);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_slice_partial<T>(&mut self, dst: &mut [T]) -> io::Result<usize> {
+        if size_of::<T>() == 0 {
+            return Ok(dst.len());
+        }
+
+        let filled = iter::read_up_to(self, bytes::slice_as_bytes_mut(dst))?;
+        Ok(filled / size_of::<T>())
+    }
+
+    /// Reads a header record and returns it along with this reader, positioned right after it.
+    ///
+    /// This makes handing the rest of a layered format to a downstream parser a single step.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor, Read};
+    ///
+    /// struct Header {
+    ///     version: u32
+    /// }
+    ///
+    /// fn parse_body(reader: &mut impl Read) -> io::Result<u64> {
+    ///     reader.read_binary::<u64>()
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&Header { version: 2 })?;
+    ///     buffer.write_binary(&128u64)?;
+    ///
+    ///     let mut cursor = Cursor::new(buffer);
+    ///     let (header, body) = cursor.read_binary_split::<Header>()?;
+    ///
+    ///     assert_eq!(header.version, 2);
+    ///     assert_eq!(parse_body(body)?, 128);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_split<H>(&mut self) -> io::Result<(H, &mut Self)>
+    where
+        Self: Sized
+    {
+        let header = self.read_binary()?;
+        Ok((header, self))
+    }
+
+    /// Reads `count` consecutive records of `T` into a `Vec`.
+    ///
+    /// The memory for all the records is allocated upfront, and if it can't be, an
+    /// `InvalidInput` error is returned.
+    ///
+    /// Records are `size_of::<T>()` bytes apart. In Rust the size of a type always includes the
+    /// padding up to its alignment, so it's also the stride between array elements, and records
+    /// are laid out exactly like an array of `T`, as written by [write_binary].
+    ///
+    /// [write_binary]: BinaryWrite::write_binary
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u64, 2, 3])?;
+    ///
+    ///     let records = Cursor::new(buffer).read_binary_vec::<u64>(3)?;
+    ///
+    ///     assert_eq!(records, [1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_vec<T>(&mut self, count: usize) -> io::Result<Vec<T>> {
+        let mut vec = Vec::new();
+        self.read_binary_extend(&mut vec, count)?;
+
+        Ok(vec)
+    }
+
+    /// Reads `count` consecutive records of `T`, appending them to `dst`.
+    ///
+    /// Exactly the additional capacity needed is reserved, so clearing and refilling the same
+    /// vector doesn't allocate once it's large enough. The records are read straight into the
+    /// spare capacity, and the length of `dst` only changes once all of them have been read, so
+    /// on failure its contents are the ones it had before.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut cursor = Cursor::new([1u8, 0, 2, 0, 3, 0]);
+    ///     let mut records = vec![0u16];
+    ///
+    ///     cursor.read_binary_extend(&mut records, 3)?;
+    ///
+    ///     assert_eq!(records, [0, 1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_extend<T>(&mut self, dst: &mut Vec<T>, count: usize) -> io::Result<()> {
+        instrument::bulk::<T, _>("read_binary_extend", || {
+            let spare = reserve_zeroed(dst, count)?;
+            let read = self.read_exact(bytes::slice_as_bytes_mut(spare)).map(|_| count);
+            instrument::read::<T, _>(&read);
+            read?;
+
+            // SAFETY: all the new records have been read from the source.
+            unsafe { dst.set_len(dst.len() + count) };
+            Ok(())
+        })
+    }
+
+    /// Reads records of `T` until `max` of them have been read or the source ends, appending
+    /// them to `dst` and returning how many were read.
+    ///
+    /// Like with [read_binary_extend], only complete records are appended; if the source ended
+    /// in the middle of a record, its bytes are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // Two and a half u32 records.
+    ///     let mut cursor = Cursor::new([1u8; 10]);
+    ///     let mut records = Vec::new();
+    ///
+    ///     assert_eq!(cursor.read_binary_extend_partial::<u32>(&mut records, 64)?, 2);
+    ///     assert_eq!(records.len(), 2);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [read_binary_extend]: BinaryRead::read_binary_extend
+    fn read_binary_extend_partial<T>(&mut self, dst: &mut Vec<T>, max: usize) -> io::Result<usize> {
+        instrument::bulk::<T, _>("read_binary_extend_partial", || {
+            let spare = reserve_zeroed(dst, max)?;
+            let count = match size_of::<T>() {
+                0 => Ok(max),
+                size => iter::read_up_to(self, bytes::slice_as_bytes_mut(spare)).map(|read| read / size)
+            };
+            instrument::read::<T, _>(&count);
+            let count = count?;
+
+            // SAFETY: the first count new records have been read from the source.
+            unsafe { dst.set_len(dst.len() + count) };
+            Ok(count)
+        })
+    }
+
+    /// Reads `count` consecutive records of `T` into a boxed slice.
+    ///
+    /// A boxed slice has no spare capacity, making it a compact choice for read-only record
+    /// arrays.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u64, 2, 3])?;
+    ///
+    ///     let records: Box<[u64]> = Cursor::new(buffer).read_binary_boxed_slice(3)?;
+    ///
+    ///     assert_eq!(*records, [1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_boxed_slice<T>(&mut self, count: usize) -> io::Result<Box<[T]>> {
+        self.read_binary_vec(count)
+            .map(Vec::into_boxed_slice)
+    }
+
+    /// Reads `count` consecutive records of `T` and returns them sorted by the key extracted
+    /// from each one.
+    ///
+    /// The sort is stable, so records with equal keys keep the order they were read in. This is
+    /// handy for loading index tables that are looked up with a binary search.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Entry {
+    ///     key: u32,
+    ///     offset: u32
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&Entry { key: 9, offset: 0 })?;
+    ///     buffer.write_binary(&Entry { key: 4, offset: 8 })?;
+    ///
+    ///     let index = Cursor::new(buffer).read_binary_sorted_by(2, |entry: &Entry| entry.key)?;
+    ///
+    ///     assert_eq!(index, [Entry { key: 4, offset: 8 }, Entry { key: 9, offset: 0 }]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_sorted_by<T, K: Ord>(&mut self, count: usize, key: impl Fn(&T) -> K) -> io::Result<Vec<T>> {
+        let mut records = self.read_binary_vec(count)?;
+        records.sort_by_key(key);
+
+        Ok(records)
+    }
+
+    /// Reads `count` consecutive records of `T` and groups them by the key extracted from each
+    /// one.
+    ///
+    /// Records are put in their bucket as they're read, without collecting them first, and
+    /// within a bucket they keep the order they were read in. On failure the records read so far
+    /// are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Sample {
+    ///     sensor: u16,
+    ///     value: u16
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&Sample { sensor: 1, value: 10 })?;
+    ///     buffer.write_binary(&Sample { sensor: 2, value: 20 })?;
+    ///     buffer.write_binary(&Sample { sensor: 1, value: 11 })?;
+    ///
+    ///     let by_sensor = Cursor::new(buffer).read_binary_grouped_by(3, |s: &Sample| s.sensor)?;
+    ///
+    ///     assert_eq!(by_sensor[&1], [Sample { sensor: 1, value: 10 }, Sample { sensor: 1, value: 11 }]);
+    ///     assert_eq!(by_sensor[&2], [Sample { sensor: 2, value: 20 }]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_grouped_by<T, K: Eq + Hash>(&mut self, count: usize, key: impl Fn(&T) -> K) -> io::Result<HashMap<K, Vec<T>>> {
+        let mut groups = HashMap::<K, Vec<T>>::new();
+
+        for _ in 0..count {
+            let record = self.read_binary()?;
+            groups.entry(key(&record)).or_default().push(record);
+        }
+
+        Ok(groups)
+    }
+
+    /// Reads a struct ending in a flexible array member, followed by `count` elements of it.
+    ///
+    /// Like in C, only the bytes of the struct up to the flexible array are read, and the
+    /// elements are expected right after them. The rest of the struct is zeroed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{flexible_array, BinaryRead};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[repr(C)]
+    /// struct Message {
+    ///     len: u16,
+    ///     data: [u16; 0]
+    /// }
+    ///
+    /// flexible_array!(Message, data: u16);
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut cursor = Cursor::new([2, 0, 7, 0, 9, 0]);
+    ///     let (message, data) = cursor.read_binary_fam::<Message>(2)?;
+    ///
+    ///     assert_eq!(message.len, 2);
+    ///     assert_eq!(data, [7, 9]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_fam<H: FlexibleArray>(&mut self, count: usize) -> io::Result<(H, Vec<H::Element>)> {
+        let mut header = MaybeUninit::<H>::zeroed();
+
+        // SAFETY: the memory is zeroed, and FAM_OFFSET doesn't exceed the size of H.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(header.as_mut_ptr() as *mut u8, H::FAM_OFFSET)
+        };
+
+        self.read_exact(bytes)?;
+        let elements = self.read_binary_vec(count)?;
+
+        // SAFETY: the bytes of the header have been read from the source, the rest are zero.
+        Ok((unsafe { header.assume_init() }, elements))
+    }
+
+    /// Reads a struct whose first field is a `u32` holding its own size in bytes, as written by
+    /// [write_self_sized].
+    ///
+    /// The size field is read first and returned by `size_field`, then the rest of the declared
+    /// bytes are read. Structs declared smaller than `T`, written by an older version of the
+    /// format, are zero-extended, while the bytes declared beyond the size of `T`, appended by a
+    /// newer version, are returned after it. A declared size too small to hold the size field
+    /// itself, or declaring more than [MAX_SELF_SIZED_SURPLUS] bytes beyond `T`, is reported as
+    /// an `InvalidData` error, before reading anything past the size field.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[repr(C)]
+    /// struct OptionsV1 {
+    ///     size: u32,
+    ///     flags: u32
+    /// }
+    ///
+    /// #[repr(C)]
+    /// struct OptionsV2 {
+    ///     size: u32,
+    ///     flags: u32,
+    ///     timeout: u32
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_self_sized(&mut OptionsV1 { size: 0, flags: 3 }, &[], |o| &mut o.size)?;
+    ///
+    ///     let (options, surplus) = Cursor::new(buffer).read_self_sized(|o: &OptionsV2| o.size)?;
+    ///
+    ///     assert_eq!((options.size, options.flags, options.timeout), (8, 3, 0));
+    ///     assert!(surplus.is_empty());
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [write_self_sized]: BinaryWrite::write_self_sized
+    /// [MAX_SELF_SIZED_SURPLUS]: MAX_SELF_SIZED_SURPLUS
+    fn read_self_sized<T>(&mut self, size_field: impl Fn(&T) -> u32) -> io::Result<(T, Vec<u8>)> {
+        const PREFIX: usize = size_of::<u32>();
+
+        if size_of::<T>() < PREFIX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "self sized structs must start with a u32 size field"
+            ));
+        }
+
+        let mut item = MaybeUninit::<T>::zeroed();
+
+        // SAFETY: the memory is zeroed and as large as T.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(item.as_mut_ptr() as *mut u8, size_of::<T>())
+        };
+
+        self.read_exact(&mut bytes[..PREFIX])?;
+
+        // SAFETY: the size field has been read from the source, the rest of the bytes are zero.
+        let declared = size_field(unsafe { item.assume_init_ref() }) as usize;
+
+        if declared < PREFIX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("declared size {declared} can't hold its own u32 size field")
+            ));
+        }
+
+        let surplus = declared.saturating_sub(size_of::<T>());
+
+        if surplus > MAX_SELF_SIZED_SURPLUS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("declared size {declared} exceeds the struct by more than {MAX_SELF_SIZED_SURPLUS} bytes")
+            ));
+        }
+
+        // SAFETY: the memory is zeroed and as large as T.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(item.as_mut_ptr() as *mut u8, size_of::<T>())
+        };
+
+        self.read_exact(&mut bytes[PREFIX..declared.min(size_of::<T>())])?;
+        let surplus = self.read_binary_vec::<u8>(surplus)?;
+
+        // SAFETY: the declared bytes of T have been read from the source, the rest are zero.
+        Ok((unsafe { item.assume_init() }, surplus))
+    }
+
+    /// Reads a record of `T` stored in `actual_len` bytes, as given by the length of the frame
+    /// holding it, so records written by other versions of the format can be read.
+    ///
+    /// Only the first `min(size_of::<T>(), actual_len)` bytes are read into `T`. Records
+    /// shorter than `T`, written before fields were appended to it, are zero-extended, and the
+    /// trailing bytes of longer ones, holding fields this version doesn't know about, are
+    /// skipped. Use [read_binary_forward_compat_strict] to reject shorter records instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[repr(C)]
+    /// struct Point {
+    ///     x: u32,
+    ///     y: u32
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // A newer writer appended a z coordinate to each point.
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u32, 2, 3, 4, 5, 6])?;
+    ///
+    ///     let mut cursor = Cursor::new(buffer);
+    ///     let first = cursor.read_binary_forward_compat::<Point>(12)?;
+    ///     let second = cursor.read_binary_forward_compat::<Point>(12)?;
+    ///
+    ///     assert_eq!((first.x, first.y, second.x, second.y), (1, 2, 4, 5));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [read_binary_forward_compat_strict]: BinaryRead::read_binary_forward_compat_strict
+    fn read_binary_forward_compat<T>(&mut self, actual_len: usize) -> io::Result<T> {
+        let mut item = MaybeUninit::<T>::zeroed();
+        let len = actual_len.min(size_of::<T>());
+
+        // SAFETY: the memory is zeroed, and len doesn't exceed the size of T.
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(item.as_mut_ptr() as *mut u8, len)
+        };
+
+        self.read_exact(bytes)?;
+
+        let excess = (actual_len - len) as u64;
+        let skipped = io::copy(&mut (&mut *self).take(excess), &mut io::sink())?;
+
+        if skipped < excess {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("source ended {skipped} bytes into {excess} bytes of unknown fields")
+            ));
+        }
+
+        // SAFETY: the first len bytes of T have been read from the source, the rest are zero.
+        Ok(unsafe { item.assume_init() })
+    }
+
+    /// Like [read_binary_forward_compat], but returns an `InvalidData` error if `actual_len` is
+    /// smaller than `T`, instead of zero-extending the record.
+    ///
+    /// [read_binary_forward_compat]: BinaryRead::read_binary_forward_compat
+    fn read_binary_forward_compat_strict<T>(&mut self, actual_len: usize) -> io::Result<T> {
+        if actual_len < size_of::<T>() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("record of {actual_len} bytes is shorter than the {} bytes of the type", size_of::<T>())
+            ));
+        }
+
+        self.read_binary_forward_compat(actual_len)
+    }
+
+    /// Reads a record of `T` stored sparsely: a presence bitmap followed by only the fields it
+    /// marks as present, the others being left as in `T::default()`.
+    ///
+    /// `fields` lists the offset and size of each optional field of `T`, and bit `i` of the
+    /// bitmap, counting from the least significant, tells whether the `i`th of them follows.
+    /// The bitmap is a `u32` for up to 32 fields and a `u64` for up to 64, and the present
+    /// fields follow it in the order they're listed. Fields not fitting within `T`, or more
+    /// than 64 of them, are reported as an `InvalidInput` error, and a bitmap marking fields
+    /// past the listed ones as an `InvalidData` error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::{io::{self, Cursor}, mem::offset_of};
+    ///
+    /// #[repr(C)]
+    /// #[derive(Debug, Default, PartialEq)]
+    /// struct Config {
+    ///     timeout: u32,
+    ///     retries: u32
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let fields = [(offset_of!(Config, timeout), 4), (offset_of!(Config, retries), 4)];
+    ///
+    ///     // Only the second field is present.
+    ///     let mut bytes = 0b10u32.to_ne_bytes().to_vec();
+    ///     bytes.extend_from_slice(&5u32.to_ne_bytes());
+    ///
+    ///     let config = Cursor::new(bytes).read_binary_bitmap::<Config>(&fields)?;
+    ///     assert_eq!(config, Config { timeout: 0, retries: 5 });
+    ///     Ok(())
+    /// }
+    /// ```
+    fn read_binary_bitmap<T: Default>(&mut self, fields: &[(usize, usize)]) -> io::Result<T> {
+        if let Some((index, (offset, size))) = fields.iter().enumerate()
+            .find(|(_, (offset, size))| offset.checked_add(*size).is_none_or(|end| end > size_of::<T>()))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("field {index} of {size} bytes at offset {offset} doesn't fit in a {} bytes record", size_of::<T>())
+            ));
+        }
+
+        let present = match fields.len() {
+            0..=32 => u64::from(self.read_binary::<u32>()?),
+            33..=64 => self.read_binary::<u64>()?,
+            count => return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{count} fields don't fit in a presence bitmap of 64 bits")
+            ))
+        };
+
+        if fields.len() < 64 && present >> fields.len() != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("presence bitmap {present:#x} marks fields past the {} known ones", fields.len())
+            ));
+        }
+
+        let mut item = T::default();
+        let bytes = bytes::slice_as_bytes_mut(slice::from_mut(&mut item));
+
+        for (index, &(offset, size)) in fields.iter().enumerate() {
+            if present & (1 << index) != 0 {
+                self.read_exact(&mut bytes[offset..offset + size])?;
+            }
+        }
+
+        Ok(item)
+    }
+
+    /// Reads a record of `T` preceded by its length as a varint, like the length-delimited
+    /// messages of protobuf's `writeDelimitedTo`, as written by [write_binary_delimited].
+    ///
+    /// The varint is the unsigned little endian base 128 encoding used by protobuf. A length
+    /// other than the size of `T` is reported as an `InvalidData` error, after skipping the
+    /// record so the next one can still be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryRead;
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     // 300 bytes of record, whose length takes two bytes.
+    ///     let mut bytes = vec![0xAC, 0x02];
+    ///     bytes.extend_from_slice(&[7; 300]);
+    ///
+    ///     let record = Cursor::new(bytes).read_binary_delimited::<[u8; 300]>()?;
+    ///     assert_eq!(record, [7; 300]);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [write_binary_delimited]: BinaryWrite::write_binary_delimited
+    fn read_binary_delimited<T>(&mut self) -> io::Result<T> {
+        let len = varint::read_varint(self)?;
+
+        if len != size_of::<T>() as u64 {
+            let skipped = io::copy(&mut self.take(len), &mut io::sink())?;
+
+            return Err(io::Error::new(
+                if skipped < len { io::ErrorKind::UnexpectedEof } else { io::ErrorKind::InvalidData },
+                format!("delimited record of {len} bytes doesn't hold a {} bytes record", size_of::<T>())
+            ));
+        }
+
+        self.read_binary()
+    }
+
+    /// Reads a `Duration` written by [write_duration], as 12 bytes: the seconds, as a `u64`,
+    /// followed by the nanoseconds within the last second, as a `u32`, both in little endian
+    /// byte order.
+    ///
+    /// `Duration` has no defined layout, so unlike a record it can't be read with
+    /// [read_binary]. Nanoseconds not below one billion are reported as an `InvalidData` error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::{io::{self, Cursor}, time::Duration};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_duration(Duration::from_millis(1500))?;
+    ///     assert_eq!(buffer, [1, 0, 0, 0, 0, 0, 0, 0, 0, 0x65, 0xCD, 0x1D]);
+    ///
+    ///     assert_eq!(Cursor::new(buffer).read_duration()?, Duration::from_millis(1500));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [write_duration]: BinaryWrite::write_duration
+    /// [read_binary]: BinaryRead::read_binary
+    fn read_duration(&mut self) -> io::Result<Duration> {
+        datetime::read_duration(self)
+    }
+
+    /// Reads a `SystemTime` written by [write_system_time], as its [duration] since the unix
+    /// epoch.
+    ///
+    /// Times the platform can't represent are reported as an `InvalidData` error. For
+    /// timestamps inside records, see [BinDateTimeUtc].
+    ///
+    /// [write_system_time]: BinaryWrite::write_system_time
+    /// [duration]: BinaryRead::read_duration
+    /// [BinDateTimeUtc]: crate::BinDateTimeUtc
+    fn read_system_time(&mut self) -> io::Result<SystemTime> {
+        datetime::read_system_time(self)
+    }
+
+    /// Reads a map written with [write_binary_map]: an entry count as a `u64`, followed by each
+    /// key and its value.
+    ///
+    /// Keys must be strictly increasing, as [write_binary_map] writes them, otherwise an
+    /// `InvalidData` error is returned, so duplicated or reordered entries aren't silently
+    /// merged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::{collections::BTreeMap, io::{self, Cursor}};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let limits = BTreeMap::from([(1u32, 500u64), (7, 20)]);
+    ///
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_map(&limits)?;
+    ///
+    ///     assert_eq!(Cursor::new(buffer).read_binary_map::<u32, u64>()?, limits);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [write_binary_map]: BinaryWrite::write_binary_map
+    fn read_binary_map<K: Ord, V>(&mut self) -> io::Result<BTreeMap<K, V>> {
+        let count = self.read_binary::<u64>()?;
+        let mut map = BTreeMap::new();
+
+        for index in 0..count {
+            let key = self.read_binary::<K>()?;
+            let value = self.read_binary::<V>()?;
+
+            if map.last_key_value().is_some_and(|(last, _)| *last >= key) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("key of entry {index} isn't greater than the previous one")
+                ));
+            }
+
+            map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+
+    /// Reads an enum written with [write_binary_enum], erroring with `InvalidData` if the
+    /// discriminant doesn't match any variant.
+    ///
+    /// See [binary_enum] for an example.
+    ///
+    /// [write_binary_enum]: BinaryWrite::write_binary_enum
+    /// [binary_enum]: crate::binary_enum
+    fn read_binary_enum<T: BinaryEnum>(&mut self) -> io::Result<T>
+    where
+        Self: Sized
+    {
+        T::read_enum(self)
+    }
+
+    /// Reads a record written with [write_binary_packed], reading each conditional field only
+    /// if its condition holds over the fields read before it.
+    ///
+    /// See [packed] for an example.
+    ///
+    /// [write_binary_packed]: BinaryWrite::write_binary_packed
+    /// [packed]: crate::packed
+    fn read_binary_packed<T: Packed>(&mut self) -> io::Result<T>
+    where
+        Self: Sized
+    {
+        T::read_packed(self)
+    }
+
+    /// Reads a [bitflags] type from its raw bits, handling the bits that don't belong to any
+    /// flag according to `unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, UnknownBits};
+    /// use std::io::{self, Cursor};
+    ///
+    /// bitflags::bitflags! {
+    ///     #[derive(Debug, PartialEq)]
+    ///     struct Permissions: u32 {
+    ///         const READ = 1;
+    ///         const WRITE = 2;
+    ///     }
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let bytes = 0b1001u32.to_ne_bytes();
+    ///
+    ///     let flags = Cursor::new(bytes).read_binary_flags::<Permissions>(UnknownBits::Retain)?;
+    ///     assert_eq!(flags.bits(), 0b1001);
+    ///
+    ///     let flags = Cursor::new(bytes).read_binary_flags::<Permissions>(UnknownBits::Truncate)?;
+    ///     assert_eq!(flags, Permissions::READ);
+    ///
+    ///     assert!(Cursor::new(bytes).read_binary_flags::<Permissions>(UnknownBits::Reject).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [bitflags]: https://docs.rs/bitflags
+    #[cfg(feature = "bitflags")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+    fn read_binary_flags<F: bitflags::Flags>(&mut self, unknown: UnknownBits) -> io::Result<F> {
+        unknown.from_bits(self.read_binary::<F::Bits>()?)
+    }
+
+    /// Reads a `T` written either in binary, after a [BINARY_MARKER] byte, or as `key=value`
+    /// text, one pair per line, telling them apart by their first byte.
+    ///
+    /// Text starts from `T::default()` and sets each pair in order with [FromTextPairs], so keys
+    /// can be left out or repeated. Blank lines and lines starting with `#` are skipped, and
+    /// whitespace around keys and values is trimmed. Text that isn't UTF-8 or a line that can't
+    /// be applied fails with an `InvalidData` error, carrying an [InvalidPair] with its line
+    /// number in the latter case. Text is read to the end of the reader, and an empty reader
+    /// yields `T::default()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{text_pairs, BinaryRead, BINARY_MARKER};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[derive(Debug, Default, PartialEq)]
+    /// struct Config {
+    ///     port: u16,
+    ///     verbose: bool
+    /// }
+    ///
+    /// text_pairs!(Config { port: u16, verbose: bool });
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let text = "# server\nport = 8080\nverbose = true\n";
+    ///     let config = Cursor::new(text).read_binary_or_text::<Config>()?;
+    ///     assert_eq!(config, Config { port: 8080, verbose: true });
+    ///
+    ///     let mut binary = vec![BINARY_MARKER];
+    ///     binary.extend_from_slice(&binext::to_array::<_, 4>(&config));
+    ///     assert_eq!(Cursor::new(binary).read_binary_or_text::<Config>()?, config);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [BINARY_MARKER]: crate::BINARY_MARKER
+    /// [FromTextPairs]: crate::FromTextPairs
+    /// [InvalidPair]: crate::InvalidPair
+    #[cfg(feature = "text")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+    fn read_binary_or_text<T: Default + FromTextPairs>(&mut self) -> io::Result<T> {
+        text::read_binary_or_text(self)
+    }
+
+    /// Reads an [ndarray] array written with [write_binary_array], checking its shape.
+    ///
+    /// The number of dimensions must match `D`, unless it's dynamic, and can't exceed
+    /// [MAX_NDIM]. Arrays of more than `max_elements` elements, or whose element size doesn't
+    /// match `T`, are rejected with an `InvalidData` error before allocating them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use ndarray::{array, Array2};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let matrix = array![[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    ///
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_array(&matrix)?;
+    ///     buffer.write_binary_array(&matrix.t())?;
+    ///
+    ///     let mut cursor = Cursor::new(buffer);
+    ///     assert_eq!(cursor.read_binary_array::<f32, _>(1024)?, matrix);
+    ///
+    ///     let transposed: Array2<f32> = cursor.read_binary_array(1024)?;
+    ///     assert_eq!(transposed, matrix.t());
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [ndarray]: https://docs.rs/ndarray
+    /// [write_binary_array]: BinaryWrite::write_binary_array
+    /// [MAX_NDIM]: crate::MAX_NDIM
+    #[cfg(feature = "ndarray")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    fn read_binary_array<T, D: ::ndarray::Dimension>(&mut self, max_elements: usize) -> io::Result<::ndarray::Array<T, D>>
+    where
+        Self: Sized
+    {
+        ndim::read_array(self, max_elements)
+    }
+
+    /// Returns an iterator reading records of `T` until the source ends.
+    ///
+    /// The iterator stops when the source ends at a record boundary. If it ends in the middle of
+    /// a record, an `UnexpectedEof` error is yielded instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryRead, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u16, 2, 3])?;
+    ///
+    ///     let records = Cursor::new(buffer)
+    ///         .binary_iter::<u16>()
+    ///         .collect::<io::Result<Vec<_>>>()?;
+    ///
+    ///     assert_eq!(records, [1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn binary_iter<T>(&mut self) -> BinaryIter<'_, Self, T> {
+        BinaryIter::new(self)
+    }
+}
+
+/// The BinaryRead trait allows for writing data structures into binary [Write] sources.
+///
+/// # Examples
+///
+/// ```rust
+/// #[derive(Default)]
+/// struct MyStruct {
+///     some: char,
+///     // fields
+/// }
+///
+/// use binext::BinaryWrite;
+/// use std::{io, fs};
+///
+/// fn main() -> io::Result<()> {
+///     use std::sync::Mutex;
+///     let mut file = fs::OpenOptions::new()
+///         .write(true)
+///         .create(true)
+///         .truncate(true)
+///         .open("myfile.bin")?;
+///
+///     let instance = MyStruct::default();
+///
+///     // Write the struct into the file, this way, later when read, it can be recovered.
+///     file.write_binary(&instance)?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [Write]: Write
+///
+pub trait BinaryWrite: Write {
+    /// Writes into a binary source the provided struct.
+    ///
+    /// The bytes are written straight from `item`, without copying it anywhere first, so large
+    /// structs can be written from the heap, for example after reading them with
+    /// [read_binary_boxed], without ever being moved to the stack. The same holds for
+    /// [write_binary_fam] and [write_binary_verified], as well as [write_binary_le] and
+    /// [write_binary_be] in the native byte order, while swapping bytes copies the struct into a
+    /// heap buffer first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[derive(Default)]
+    /// struct MyStruct {
+    ///     some: char,
+    ///     // fields
+    /// }
+    ///
+    /// use binext::BinaryWrite;
+    /// use std::{io, fs};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     use std::sync::Mutex;
+    ///     let mut file = fs::OpenOptions::new()
+    ///         .write(true)
+    ///         .create(true)
+    ///         .truncate(true)
+    ///         .open("myfile.bin")?;
+    ///
+    ///     let instance = MyStruct::default();
+    ///
+    ///     // Write the struct into the file, this way, later when read, it can be recovered.
+    ///     file.write_binary(&instance)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [read_binary_boxed]: BinaryRead::read_binary_boxed
+    /// [write_binary_fam]: BinaryWrite::write_binary_fam
+    /// [write_binary_verified]: BinaryWrite::write_binary_verified
+    /// [write_binary_le]: BinaryWrite::write_binary_le
+    /// [write_binary_be]: BinaryWrite::write_binary_be
+    fn write_binary<T>(&mut self, item: &T) -> io::Result<()> {
+        let written = self.write_all(bytes::as_bytes(item)).map(|_| 1);
+        instrument::written::<T, _>(&written);
+        written.map(|_| ())
+    }
+
+    /// Writes the provided struct and makes it durable, flushing any buffered data and syncing
+    /// it to the storage device.
+    ///
+    /// A write only hands the data to the operating system, which may lose it on a crash or
+    /// power failure until it's synced. See [SyncMode] for how much is synced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use binext::{BinaryWrite, SyncMode};
+    /// use std::{fs::File, io::{self, BufWriter}};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut checkpoint = BufWriter::new(File::create("checkpoint.bin")?);
+    ///     checkpoint.write_binary_sync(&[1u64, 2, 3], SyncMode::Data)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [SyncMode]: crate::SyncMode
+    fn write_binary_sync<T>(&mut self, item: &T, mode: SyncMode) -> io::Result<()>
+    where
+        Self: Durable
+    {
+        self.write_binary(item)?;
+        self.sync(mode)
+    }
+
+    /// Writes a record of `T` after computing and storing its checksum fields, declared with
+    /// `#[checksum = ...]` in [validate], so it can be checked by [read_binary_validated].
+    ///
+    /// `item` is left untouched: the checksums are stored in a copy of its bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{validate, BinaryRead, BinaryWrite, Checksum};
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[repr(C)]
+    /// struct Message {
+    ///     kind: u16,
+    ///     checksum: u16,
+    ///     payload: [u8; 12]
+    /// }
+    ///
+    /// validate!(Message {
+    ///     kind: u16,
+    ///     #[checksum = Checksum::Internet.of(..)]
+    ///     checksum: u16,
+    ///     payload: [u8; 12],
+    /// });
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_with_checksums(&Message { kind: 1, checksum: 0, payload: *b"hello world!" })?;
+    ///     assert!(Cursor::new(&buffer).read_binary_validated::<Message>().is_ok());
+    ///
+    ///     buffer[7] = b'?';
+    ///     assert!(Cursor::new(&buffer).read_binary_validated::<Message>().is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [validate]: crate::validate
+    /// [read_binary_validated]: BinaryRead::read_binary_validated
+    fn write_binary_with_checksums<T: Validate>(&mut self, item: &T) -> io::Result<()> {
+        let mut record = bytes::as_bytes(item).to_vec();
+        T::stamp_checksums(&mut record);
+
+        self.write_all(&record)
+    }
+
+    /// Writes a [WireTwin] type as its `#[repr(C)]` twin, to be read with [read_binary_wire].
+    ///
+    /// See [wire_twin] for an example.
+    ///
+    /// [WireTwin]: crate::WireTwin
+    /// [read_binary_wire]: BinaryRead::read_binary_wire
+    /// [wire_twin]: crate::wire_twin
+    fn write_binary_wire<T: WireTwin>(&mut self, item: &T) -> io::Result<()> {
+        self.write_binary(&item.to_wire())
+    }
+
+    /// Writes a record of `T`, storing a CRC-32 of its other bytes in the `u32` field at
+    /// `crc_offset`, to be checked when reading it with [read_binary_self_crc].
+    ///
+    /// `item` is left untouched: the checksum is computed over a copy of its bytes, padding
+    /// included, and written in place of the field in native byte order. A `u32` at
+    /// `crc_offset` that doesn't fit within a `T` is reported as an `InvalidInput` error before
+    /// anything is written.
+    ///
+    /// [read_binary_self_crc]: BinaryRead::read_binary_self_crc
+    fn write_binary_self_crc<T>(&mut self, item: &T, crc_offset: usize) -> io::Result<()> {
+        crc::check_offset(crc_offset, size_of::<T>())?;
+        let mut record = bytes::as_bytes(item).to_vec();
+        let crc = crc::crc32_excluding(&record, crc_offset);
+        record[crc_offset..crc_offset + crc::CRC_SIZE].copy_from_slice(&crc.to_ne_bytes());
+
+        self.write_all(&record)
+    }
+
+    /// Writes the provided struct in little endian byte order, swapping the bytes of its fields
+    /// on big endian hosts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryWrite;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_le(&258u32)?;
+    ///
+    ///     assert_eq!(buffer, [2, 1, 0, 0]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn write_binary_le<T: SwapBytes>(&mut self, item: &T) -> io::Result<()> {
+        endian::write_swapped(self, item, cfg!(target_endian = "big"))
+    }
+
+    /// Writes the provided struct in big endian byte order, swapping the bytes of its fields on
+    /// little endian hosts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryWrite;
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_be(&258u32)?;
+    ///
+    ///     assert_eq!(buffer, [0, 0, 1, 2]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn write_binary_be<T: SwapBytes>(&mut self, item: &T) -> io::Result<()> {
+        endian::write_swapped(self, item, cfg!(target_endian = "little"))
+    }
+
+    /// Writes a struct ending in a flexible array member, followed by its elements.
+    ///
+    /// Like in C, only the bytes of the struct up to the flexible array are written, and the
+    /// elements follow right after them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{flexible_array, BinaryWrite};
+    /// use std::io;
+    ///
+    /// #[repr(C)]
+    /// struct Message {
+    ///     len: u16,
+    ///     data: [u16; 0]
+    /// }
+    ///
+    /// flexible_array!(Message, data: u16);
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary_fam(&Message { len: 2, data: [] }, &[7, 9])?;
+    ///
+    ///     assert_eq!(buffer.len(), 6);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn write_binary_fam<H: FlexibleArray>(&mut self, header: &H, elements: &[H::Element]) -> io::Result<()> {
+        self.write_all(&bytes::as_bytes(header)[..H::FAM_OFFSET])?;
+        self.write_all(bytes::slice_as_bytes(elements))
+    }
+
+    /// Writes a struct whose first field is a `u32` holding its own size in bytes, followed by
+    /// `surplus`, to be read back with [read_self_sized].
+    ///
+    /// The field returned by `size_field` is set to the size of `T` plus the length of
+    /// `surplus` before writing, so extensions not described by `T` can be carried along.
+    /// A total size that doesn't fit in a `u32` is reported as an `InvalidInput` error.
+    ///
+    /// See [read_self_sized] for an example.
+    ///
+    /// [read_self_sized]: BinaryRead::read_self_sized
+    fn write_self_sized<T>(&mut self, item: &mut T, surplus: &[u8], size_field: impl FnOnce(&mut T) -> &mut u32) -> io::Result<()> {
+        let size = size_of::<T>().checked_add(surplus.len())
+            .and_then(|size| u32::try_from(size).ok())
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} bytes of surplus overflow the u32 size field", surplus.len())
+            ))?;
+
+        *size_field(item) = size;
+
+        self.write_all(bytes::as_bytes(item))?;
+        self.write_all(surplus)
+    }
+
+    /// Writes a record preceded by its length as a varint, like protobuf's `writeDelimitedTo`,
+    /// to be read back with [read_binary_delimited] or by other length-delimited readers.
+    ///
+    /// [read_binary_delimited]: BinaryRead::read_binary_delimited
+    fn write_binary_delimited<T>(&mut self, item: &T) -> io::Result<()> {
+        varint::write_varint(self, size_of::<T>() as u64)?;
+        self.write_binary(item)
+    }
+
+    /// Writes a `Duration` as its seconds, as a `u64`, followed by the nanoseconds within the
+    /// last second, as a `u32`, both in little endian byte order, to be read back with
+    /// [read_duration].
+    ///
+    /// [read_duration]: BinaryRead::read_duration
+    fn write_duration(&mut self, duration: Duration) -> io::Result<()> {
+        datetime::write_duration(self, duration)
+    }
+
+    /// Writes a `SystemTime` as its duration since the unix epoch, like [write_duration], to be
+    /// read back with [read_system_time].
+    ///
+    /// Times before the epoch are reported as an `InvalidInput` error; [BinDateTimeUtc] can hold
+    /// them.
+    ///
+    /// [write_duration]: BinaryWrite::write_duration
+    /// [read_system_time]: BinaryRead::read_system_time
+    /// [BinDateTimeUtc]: crate::BinDateTimeUtc
+    fn write_system_time(&mut self, time: SystemTime) -> io::Result<()> {
+        datetime::write_system_time(self, time)
+    }
+
+    /// Writes a map as an entry count, as a `u64`, followed by each key and its value in
+    /// increasing key order, to be read back with [read_binary_map].
+    ///
+    /// Keys and values are written one after the other, without the padding a tuple of both
+    /// could have. Since entries are ordered by key, the same map always produces the same
+    /// bytes; a `HashMap` can be collected into a `BTreeMap` to get this too.
+    ///
+    /// See [read_binary_map] for an example.
+    ///
+    /// [read_binary_map]: BinaryRead::read_binary_map
+    fn write_binary_map<K, V>(&mut self, map: &BTreeMap<K, V>) -> io::Result<()> {
+        self.write_binary(&(map.len() as u64))?;
+
+        for (key, value) in map {
+            self.write_binary(key)?;
+            self.write_binary(value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes an enum as its discriminant followed by the payload of the variant, padded up to
+    /// the frame size if its layout is uniform.
+    ///
+    /// See [binary_enum] for an example.
+    ///
+    /// [binary_enum]: crate::binary_enum
+    fn write_binary_enum<T: BinaryEnum>(&mut self, item: &T) -> io::Result<()>
+    where
+        Self: Sized
+    {
+        item.write_enum(self)
+    }
+
+    /// Writes a record field by field with no padding, leaving out the conditional fields whose
+    /// condition doesn't hold.
+    ///
+    /// A conditional field disagreeing with its condition is reported as an `InvalidInput`
+    /// error carrying a [PresenceMismatch]. See [packed] for an example.
+    ///
+    /// [PresenceMismatch]: crate::PresenceMismatch
+    /// [packed]: crate::packed
+    fn write_binary_packed<T: Packed>(&mut self, item: &T) -> io::Result<()>
+    where
+        Self: Sized
+    {
+        item.write_packed(self)
+    }
+
+    /// Writes the raw bits of a [bitflags] type, unknown bits included.
+    ///
+    /// [bitflags]: https://docs.rs/bitflags
+    #[cfg(feature = "bitflags")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+    fn write_binary_flags<F: bitflags::Flags>(&mut self, flags: &F) -> io::Result<()> {
+        self.write_binary(&flags.bits())
+    }
+
+    /// Writes an [ndarray] array as a header holding the number of dimensions, the length of
+    /// each one and the size of the elements, all as `u64`s, followed by the elements in
+    /// standard (C) order.
+    ///
+    /// Arrays in another order, like Fortran ordered or transposed ones, are written in
+    /// standard order too, so they are read back with the same shape and elements. See
+    /// [read_binary_array] for an example.
+    ///
+    /// [ndarray]: https://docs.rs/ndarray
+    /// [read_binary_array]: BinaryRead::read_binary_array
+    #[cfg(feature = "ndarray")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    fn write_binary_array<S, D>(&mut self, array: &::ndarray::ArrayBase<S, D>) -> io::Result<()>
+    where
+        Self: Sized,
+        S: ::ndarray::Data,
+        D: ::ndarray::Dimension
+    {
+        ndim::write_array(self, array)
+    }
+
+    /// Writes the provided struct, then reads it back and checks it matches the original.
+    ///
+    /// This is the write-verify pattern used with unreliable media such as flash: after writing,
+    /// the sink is flushed, the cursor is moved back to the start of the record and the record is
+    /// read again. If the read value doesn't match `item`, an [InvalidData] error is returned.
+    /// On success the cursor is left right after the written record.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::BinaryWrite;
+    /// use std::io::{self, Cursor};
+    ///
+    /// #[derive(PartialEq)]
+    /// struct MyStruct {
+    ///     a: u32,
+    ///     b: u64
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut cursor = Cursor::new(Vec::new());
+    ///
+    ///     cursor.write_binary_verified(&MyStruct { a: 1, b: 2 })?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [InvalidData]: io::ErrorKind::InvalidData
+    fn write_binary_verified<T: PartialEq>(&mut self, item: &T) -> io::Result<()>
+    where
+        Self: Read + Seek + Sized
+    {
+        self.write_binary(item)?;
+        self.flush()?;
+        self.seek(SeekFrom::Current(-(size_of::<T>() as i64)))?;
+
+        // Compares in place, since reading T by value would move it to the stack.
+        if *self.read_binary_boxed::<T>()? != *item {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "record read back doesn't match the written one"
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<I: Write> BinaryWrite for I {}
+
+impl<I: Read> BinaryRead for I {}
+
+/// Reserves room for `count` more elements in `vec`, returning them zeroed but outside its length.
+fn reserve_zeroed<T>(vec: &mut Vec<T>, count: usize) -> io::Result<&mut [MaybeUninit<T>]> {
+    seek::records_to_bytes::<T>(count)?;
+    vec.try_reserve_exact(count)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let spare = &mut vec.spare_capacity_mut()[..count];
+
+    // SAFETY: MaybeUninit can hold any bytes.
+    unsafe { ptr::write_bytes(spare.as_mut_ptr(), 0, count) };
+    Ok(spare)
+}
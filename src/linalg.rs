@@ -0,0 +1,78 @@
+use ::nalgebra::{Point, Quaternion, SMatrix, Scalar};
+use crate::{NpyType, SwapBytes, Validate, ValidationError, Zeroable};
+
+// Stack allocated matrices are `repr(C)` over a `[[T; R]; C]` in column major order, points are
+// `repr(C)` over their coordinates vector and quaternions over a `Vector4` of `i`, `j`, `k` and
+// `w`, so all of them have the layout of the equivalent array.
+
+impl<T: SwapBytes, const R: usize, const C: usize> SwapBytes for SMatrix<T, R, C> {
+    fn swap_bytes(bytes: &mut [u8]) {
+        <[[T; R]; C]>::swap_bytes(bytes);
+    }
+
+    fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), ValidationError> {
+        <[[T; R]; C]>::swap_bytes_checked(bytes)
+    }
+}
+
+impl<T: Validate, const R: usize, const C: usize> Validate for SMatrix<T, R, C> {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        <[[T; R]; C]>::validate_bytes(bytes)
+    }
+}
+
+// SAFETY: the matrix is a `[[T; R]; C]`, which is zeroable when `T` is.
+unsafe impl<T: Zeroable, const R: usize, const C: usize> Zeroable for SMatrix<T, R, C> {}
+
+impl<T: NpyType, const R: usize, const C: usize> NpyType for SMatrix<T, R, C> {
+    const DESCR: &'static str = T::DESCR;
+    const LEN: Option<usize> = Some(R * C);
+}
+
+impl<T: Scalar + SwapBytes, const D: usize> SwapBytes for Point<T, D> {
+    fn swap_bytes(bytes: &mut [u8]) {
+        <[T; D]>::swap_bytes(bytes);
+    }
+
+    fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), ValidationError> {
+        <[T; D]>::swap_bytes_checked(bytes)
+    }
+}
+
+impl<T: Scalar + Validate, const D: usize> Validate for Point<T, D> {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        <[T; D]>::validate_bytes(bytes)
+    }
+}
+
+// SAFETY: the point is a `[T; D]`, which is zeroable when `T` is.
+unsafe impl<T: Scalar + Zeroable, const D: usize> Zeroable for Point<T, D> {}
+
+impl<T: Scalar + NpyType, const D: usize> NpyType for Point<T, D> {
+    const DESCR: &'static str = T::DESCR;
+    const LEN: Option<usize> = Some(D);
+}
+
+impl<T: SwapBytes> SwapBytes for Quaternion<T> {
+    fn swap_bytes(bytes: &mut [u8]) {
+        <[T; 4]>::swap_bytes(bytes);
+    }
+
+    fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), ValidationError> {
+        <[T; 4]>::swap_bytes_checked(bytes)
+    }
+}
+
+impl<T: Validate> Validate for Quaternion<T> {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        <[T; 4]>::validate_bytes(bytes)
+    }
+}
+
+// SAFETY: the quaternion is a `[T; 4]`, which is zeroable when `T` is.
+unsafe impl<T: Zeroable> Zeroable for Quaternion<T> {}
+
+impl<T: NpyType> NpyType for Quaternion<T> {
+    const DESCR: &'static str = T::DESCR;
+    const LEN: Option<usize> = Some(4);
+}
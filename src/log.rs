@@ -0,0 +1,405 @@
+use std::{fs::{File, OpenOptions}, io::{self, Read, Seek, SeekFrom, Write}, marker::PhantomData, mem::size_of, path::Path, time::Instant};
+use crate::{bytes, crc::crc32, seek::{record_size, stream_len}, BinaryRead, DurabilityPolicy, Durable, Envelope, Misaligned, SyncMode};
+
+/// Options to open a [BinaryLog] with.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{BinaryLog, LogOptions};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let options = LogOptions::new().checksums(true).recover(true);
+///     let log = BinaryLog::<u64>::open_with("counters.bin", options)?;
+///
+///     if let Some(report) = log.recovery() {
+///         println!("discarded {} bytes", report.discarded_bytes);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [BinaryLog]: BinaryLog
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LogOptions {
+    checksums: bool,
+    recover: bool,
+}
+
+impl LogOptions {
+    /// Creates the default options, without checksums nor recovery.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether every record is followed by the CRC-32 of its bytes, as a little endian
+    /// `u32`, which is verified when reading it back and when recovering.
+    ///
+    /// The same setting must be used every time the log is opened.
+    pub fn checksums(mut self, checksums: bool) -> Self {
+        self.checksums = checksums;
+        self
+    }
+
+    /// Sets whether [BinaryLog::recover] runs when the log is opened, so a torn tail is
+    /// truncated instead of failing with a [Misaligned] error.
+    ///
+    /// [BinaryLog::recover]: BinaryLog::recover
+    /// [Misaligned]: crate::Misaligned
+    pub fn recover(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+}
+
+/// What [BinaryLog::recover] kept and discarded.
+///
+/// [BinaryLog::recover]: BinaryLog::recover
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RecoveryReport {
+    /// Records kept in the log.
+    pub records: u64,
+    /// Whole records discarded because they failed their checksum.
+    pub discarded_records: u64,
+    /// Bytes truncated from the end of the file, including the discarded records and any torn
+    /// record after them.
+    pub discarded_bytes: u64,
+}
+
+/// An append-only file of fixed-size records of `T`.
+///
+/// Records are appended at the end and can be read back by index. When they are synced to the
+/// storage device is set with a [DurabilityPolicy], none by default. The file is generic so
+/// any durable, seekable source can back the log.
+///
+/// Records can be checksummed with [LogOptions], and a torn tail left by a crash is truncated
+/// with [recover].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{BinaryLog, DurabilityPolicy};
+/// use std::io;
+///
+/// #[repr(C)]
+/// struct Event {
+///     timestamp: u64,
+///     kind: u32
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut log = BinaryLog::<Event>::open("events.bin")?
+///         .with_durability(DurabilityPolicy::EveryN(64));
+///
+///     let index = log.append(&Event { timestamp: 1_700_000_000, kind: 3 })?;
+///     assert_eq!(log.get(index)?.kind, 3);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [DurabilityPolicy]: crate::DurabilityPolicy
+/// [LogOptions]: LogOptions
+/// [recover]: BinaryLog::recover
+pub struct BinaryLog<T, F = File> {
+    file: F,
+    len: u64,
+    checksums: bool,
+    recovery: Option<RecoveryReport>,
+    policy: DurabilityPolicy,
+    sync_mode: SyncMode,
+    unsynced: u32,
+    last_sync: Instant,
+    _marker: PhantomData<fn(&T) -> T>,
+}
+
+impl<T> BinaryLog<T> {
+    /// Opens the log at `path`, creating it if it doesn't exist.
+    ///
+    /// The file must hold whole records, otherwise an `InvalidData` error carrying a
+    /// [Misaligned] is returned.
+    ///
+    /// [Misaligned]: crate::Misaligned
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with(path, LogOptions::new())
+    }
+
+    /// Opens the log at `path` with the given options, creating it if it doesn't exist.
+    pub fn open_with(path: impl AsRef<Path>, options: LogOptions) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        Self::from_file_with(file, options)
+    }
+}
+
+impl<T, F: Read + Write + Seek + Durable> BinaryLog<T, F> {
+    /// Creates a log over an already opened file, which must hold whole records.
+    pub fn from_file(file: F) -> io::Result<Self> {
+        Self::from_file_with(file, LogOptions::new())
+    }
+
+    /// Creates a log over an already opened file with the given options.
+    ///
+    /// Unless recovery is enabled, the file must hold whole records.
+    pub fn from_file_with(file: F, options: LogOptions) -> io::Result<Self> {
+        record_size::<T>()?;
+
+        let mut log = Self {
+            file,
+            len: 0,
+            checksums: options.checksums,
+            recovery: None,
+            policy: DurabilityPolicy::None,
+            sync_mode: SyncMode::Data,
+            unsynced: 0,
+            last_sync: Instant::now(),
+            _marker: PhantomData,
+        };
+
+        if options.recover {
+            log.recovery = Some(log.recover()?);
+        } else {
+            let bytes = stream_len(&mut log.file)?;
+            let stride = log.stride();
+
+            if bytes % stride != 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, Misaligned {
+                    bytes,
+                    record_size: stride as usize,
+                    remainder: bytes % stride,
+                }));
+            }
+
+            log.len = bytes / stride;
+        }
+
+        Ok(log)
+    }
+
+    /// Sets when appends are synced to the storage device.
+    pub fn with_durability(mut self, policy: DurabilityPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets how much of the file is synced, only its data by default.
+    pub fn with_sync_mode(mut self, mode: SyncMode) -> Self {
+        self.sync_mode = mode;
+        self
+    }
+
+    /// Appends a record, syncing it if the durability policy requires it, and returns its index.
+    pub fn append(&mut self, item: &T) -> io::Result<u64> {
+        self.file.seek(SeekFrom::Start(self.offset(self.len)?))?;
+        let record = bytes::as_bytes(item);
+
+        if self.checksums {
+            let mut buf = Vec::with_capacity(record.len() + 4);
+            buf.extend_from_slice(record);
+            buf.extend_from_slice(&crc32(record).to_le_bytes());
+            self.file.write_all(&buf)?;
+        } else {
+            self.file.write_all(record)?;
+        }
+
+        let index = self.len;
+        self.len += 1;
+        self.unsynced = self.unsynced.saturating_add(1);
+
+        let sync = match self.policy {
+            DurabilityPolicy::None => false,
+            DurabilityPolicy::EveryN(n) => self.unsynced >= n,
+            DurabilityPolicy::Every(period) => self.last_sync.elapsed() >= period,
+            DurabilityPolicy::Always => true
+        };
+
+        if sync {
+            self.sync()?;
+        }
+
+        Ok(index)
+    }
+
+    /// Syncs all the appended records to the storage device.
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.file.sync(self.sync_mode)?;
+        self.unsynced = 0;
+        self.last_sync = Instant::now();
+
+        Ok(())
+    }
+
+    /// Flushes the file and returns a handle to sync it without borrowing the log, along with
+    /// the mode to sync it with, if the file has one.
+    pub(crate) fn sync_handle(&mut self) -> io::Result<Option<(File, SyncMode)>> {
+        Ok(self.file.sync_handle()?.map(|handle| (handle, self.sync_mode)))
+    }
+
+    /// Records a sync done through [sync_handle], which covered `appends` of the unsynced ones.
+    ///
+    /// [sync_handle]: BinaryLog::sync_handle
+    pub(crate) fn mark_synced(&mut self, appends: u32) {
+        self.unsynced = self.unsynced.saturating_sub(appends);
+        self.last_sync = Instant::now();
+    }
+
+    /// Reads the record at `index`.
+    pub fn get(&mut self, index: u64) -> io::Result<T> {
+        if index >= self.len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("record {index} out of bounds for {} records", self.len)
+            ));
+        }
+
+        self.file.seek(SeekFrom::Start(self.offset(index)?))?;
+        let mut buf = vec![0u8; self.stride() as usize];
+        self.file.read_exact(&mut buf)?;
+
+        // The checksum covers the bytes as written, padding included, so it's checked before
+        // they become a `T`, the same way `recover` does.
+        let (mut record, crc) = buf.split_at(size_of::<T>());
+
+        if self.checksums && crc != crc32(record).to_le_bytes() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("record {index} doesn't match its checksum")
+            ));
+        }
+
+        record.read_binary::<T>()
+    }
+
+    /// Truncates the file after the last good record, returning what was kept and discarded.
+    ///
+    /// A torn record at the end, left by a crash in the middle of an append, is found by
+    /// length arithmetic. When the log is checksummed, the records are also scanned backwards
+    /// from the end until one matches its checksum, discarding the ones that don't. Records
+    /// before the last good one aren't checked. The truncation is synced with the log's
+    /// [SyncMode].
+    ///
+    /// [SyncMode]: crate::SyncMode
+    pub fn recover(&mut self) -> io::Result<RecoveryReport> {
+        let stride = self.stride();
+        let bytes = stream_len(&mut self.file)?;
+        let whole = bytes / stride;
+        let mut records = whole;
+
+        if self.checksums {
+            let mut buf = vec![0u8; stride as usize];
+
+            while records > 0 {
+                self.file.seek(SeekFrom::Start(self.offset(records - 1)?))?;
+                self.file.read_exact(&mut buf)?;
+
+                let (record, crc) = buf.split_at(size_of::<T>());
+
+                if crc == crc32(record).to_le_bytes() {
+                    break;
+                }
+
+                records -= 1;
+            }
+        }
+
+        let kept = self.offset(records)?;
+
+        if kept != bytes {
+            self.file.set_len(kept)?;
+            self.file.sync(self.sync_mode)?;
+        }
+
+        self.len = records;
+
+        Ok(RecoveryReport {
+            records,
+            discarded_records: whole - records,
+            discarded_bytes: bytes - kept,
+        })
+    }
+
+    /// Returns the report of the recovery run when the log was opened, if it was enabled.
+    pub fn recovery(&self) -> Option<&RecoveryReport> {
+        self.recovery.as_ref()
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of appends not synced yet.
+    pub fn unsynced(&self) -> u32 {
+        self.unsynced
+    }
+
+    /// Returns a reference to the underlying file.
+    pub fn get_ref(&self) -> &F {
+        &self.file
+    }
+
+    /// Unwraps this log, returning the underlying file.
+    pub fn into_inner(self) -> F {
+        self.file
+    }
+
+    /// Returns the size of a record in the file, including its checksum.
+    fn stride(&self) -> u64 {
+        size_of::<T>() as u64 + if self.checksums { 4 } else { 0 }
+    }
+
+    /// Returns the byte offset of the record at `index`, erroring if it doesn't fit in a `u64`.
+    fn offset(&self, index: u64) -> io::Result<u64> {
+        index.checked_mul(self.stride())
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("offset of record {index} of {} bytes overflows a u64", self.stride())
+            ))
+    }
+}
+
+impl<T, F: Read + Write + Seek + Durable> BinaryLog<Envelope<T>, F> {
+    /// Returns the sequence number following the last envelope, zero if the log is empty.
+    ///
+    /// This reads the last record, so it's usually called once after opening the log to resume
+    /// numbering, like with [EnvelopedWriter::resume_from].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use binext::{BinDateTimeUtc, BinaryLog, Envelope};
+    /// use std::io;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut log = BinaryLog::<Envelope<u64>>::open("orders.bin")?;
+    ///     let mut sequence = log.next_sequence()?;
+    ///
+    ///     for order in [7, 8, 9] {
+    ///         log.append(&Envelope::new(sequence, BinDateTimeUtc::now(), order))?;
+    ///         sequence += 1;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [EnvelopedWriter::resume_from]: crate::EnvelopedWriter::resume_from
+    pub fn next_sequence(&mut self) -> io::Result<u64> {
+        match self.len {
+            0 => Ok(0),
+            len => Ok(self.get(len - 1)?.sequence() + 1)
+        }
+    }
+}
@@ -0,0 +1,1245 @@
+/// Asserts at compile time that a field lives at the given byte offset of a struct.
+///
+/// Protocol specs usually pin the position of specific fields ("flags at byte 12"), and any
+/// reordering or size change of a field before it silently changes the binary format. This macro
+/// breaks the build instead, naming the field and the expected offset, together with a type
+/// mismatch error showing the actual one.
+///
+/// Nested fields are supported too, which is useful when a spec pins offsets within embedded
+/// sub-structures.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::assert_offset;
+///
+/// #[repr(C)]
+/// struct Inner {
+///     kind: u16,
+///     count: u16
+/// }
+///
+/// #[repr(C)]
+/// struct Packet {
+///     magic: u64,
+///     len: u32,
+///     flags: u32,
+///     inner: Inner
+/// }
+///
+/// assert_offset!(Packet, flags, 12);
+/// assert_offset!(Packet, inner.count, 18);
+/// ```
+///
+/// A change upstream of the field fails to compile:
+///
+/// ```rust,compile_fail,E0080
+/// use binext::assert_offset;
+///
+/// #[repr(C)]
+/// struct Packet {
+///     magic: u64,
+///     len: u64,
+///     flags: u32
+/// }
+///
+/// assert_offset!(Packet, flags, 12);
+/// ```
+#[macro_export]
+macro_rules! assert_offset {
+    ($ty: ty, $($field: tt).+, $offset: expr) => {
+        const _: () = {
+            let actual = ::core::mem::offset_of!($ty, $($field).+);
+
+            assert!(
+                actual == $offset,
+                concat!(
+                    "field `", stringify!($($field).+), "` of `", stringify!($ty),
+                    "` is not at offset ", stringify!($offset)
+                )
+            );
+        };
+
+        // Reports the actual offset in the error message when the assertion above fails.
+        const _: [(); $offset] = [(); ::core::mem::offset_of!($ty, $($field).+)];
+    };
+}
+
+/// Implements [BinaryLayout] for a type and asserts at compile time that its real size and
+/// alignment match the declared ones.
+///
+/// The check is also given to the type as an associated `const fn assert_layout()`, so it can
+/// be repeated where the layout matters, as in `const _: () = Record::assert_layout();`.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{binary_layout, BinaryLayout};
+///
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     value: u32
+/// }
+///
+/// binary_layout!(Record, size = 16, align = 8);
+///
+/// const _: () = Record::assert_layout();
+/// assert_eq!(Record::SIZE, 16);
+/// ```
+///
+/// Adding a field changes the size, so it no longer compiles:
+///
+/// ```rust,compile_fail,E0080
+/// use binext::binary_layout;
+///
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     value: u32,
+///     extra: u64
+/// }
+///
+/// binary_layout!(Record, size = 16, align = 8);
+/// ```
+///
+/// [BinaryLayout]: crate::BinaryLayout
+#[macro_export]
+macro_rules! binary_layout {
+    ($ty: ty, size = $size: expr, align = $align: expr) => {
+        impl $crate::BinaryLayout for $ty {
+            const SIZE: usize = $size;
+            const ALIGN: usize = $align;
+        }
+
+        impl $ty {
+            /// Panics, failing the build when evaluated in a const context, if the size or
+            /// alignment of the type don't match its declared layout.
+            #[allow(dead_code)]
+            pub const fn assert_layout() {
+                $crate::assert_layout::<Self, { $size }, { $align }>()
+            }
+        }
+
+        const _: () = <$ty>::assert_layout();
+    };
+}
+
+/// Implements [FlexibleArray] for a struct, given its trailing zero-length array field and the
+/// type of its elements.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{flexible_array, FlexibleArray};
+///
+/// #[repr(C)]
+/// struct Message {
+///     kind: u32,
+///     len: u8,
+///     data: [u8; 0]
+/// }
+///
+/// flexible_array!(Message, data: u8);
+///
+/// // Just like in C, elements start right after the last field, before the trailing padding.
+/// assert_eq!(Message::FAM_OFFSET, 5);
+/// ```
+///
+/// [FlexibleArray]: crate::FlexibleArray
+#[macro_export]
+macro_rules! flexible_array {
+    ($ty: ty, $field: ident: $element: ty) => {
+        const _: () = {
+            // Ensures the field is a zero-length array of the given elements.
+            fn _check(item: &$ty) -> &[$element; 0] {
+                &item.$field
+            }
+        };
+
+        // SAFETY: the offset is the one of the zero-length array, which can't exceed the size.
+        unsafe impl $crate::FlexibleArray for $ty {
+            type Element = $element;
+            const FAM_OFFSET: usize = ::core::mem::offset_of!($ty, $field);
+        }
+    };
+}
+
+/// Expands the fields of a tuple struct, given as `{ [checks] type }` groups, into `index: type
+/// [checks]` entries and hands them to the `@impl` arm of `$callback`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tuple_fields {
+    ($callback: ident $head: tt [$($out: tt)*] [$index: tt $($indices: tt)*] { $checks: tt $field_ty: ty } $($rest: tt)*) => {
+        $crate::__tuple_fields!($callback $head [$($out)* $index: $field_ty $checks,] [$($indices)*] $($rest)*);
+    };
+    ($callback: ident $head: tt [$($out: tt)*] [$($indices: tt)*]) => {
+        $crate::$callback!(@impl $head { $($out)* });
+    };
+}
+
+/// Normalizes the struct declarations accepted by [zeroable], [validate] and [swap_bytes] and
+/// hands them to the `@impl` arm of `$callback`.
+///
+/// [zeroable]: crate::zeroable
+/// [validate]: crate::validate
+/// [swap_bytes]: crate::swap_bytes
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __struct_fields {
+    ($callback: ident $([$($generics: tt)*])? $ty: ident $(<$($arg: tt),*>)? {
+        $($(#[$check: ident = $($value: tt)+])* $field: ident: $field_ty: ty),* $(,)?
+    }) => {
+        $crate::$callback!(@impl ([$($($generics)*)?] $ty [$($($arg),*)?]) {
+            $($field: $field_ty [$($check = [$($value)+]),*],)*
+        });
+    };
+    ($callback: ident $([$($generics: tt)*])? $ty: ident $(<$($arg: tt),*>)? (
+        $($(#[$check: ident = $($value: tt)+])* $field_ty: ty),* $(,)?
+    )) => {
+        $crate::__tuple_fields!(
+            $callback ([$($($generics)*)?] $ty [$($($arg),*)?]) []
+            [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31]
+            $({ [$($check = [$($value)+]),*] $field_ty })*
+        );
+    };
+    ($callback: ident $([$($generics: tt)*])? $ty: ident $(<$($arg: tt),*>)?) => {
+        $crate::$callback!(@impl ([$($($generics)*)?] $ty [$($($arg),*)?]) {});
+    };
+}
+
+/// Returns the name of a field as used in errors, its index prefixed by a dot for tuple structs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __field_name {
+    ($field: ident) => {
+        stringify!($field)
+    };
+    ($field: tt) => {
+        concat!(".", stringify!($field))
+    };
+}
+
+/// Implements [Zeroable] for a struct, checking that every one of its fields is zeroable.
+///
+/// All the fields of the struct must be listed along with their types, a missing field or a
+/// mismatched type is a compile error. A field whose type isn't zeroable fails to compile too,
+/// pointing at that field. Generic structs list their generic parameters between brackets
+/// first, as in `zeroable!([T, const N: usize] Record<T, N> { ... })`; `PhantomData` fields
+/// impose no bounds on their type parameter. Tuple structs list their field types between
+/// parentheses, as in `zeroable!(Rgb(u8, u8, u8))`, and unit structs are given by their name
+/// alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{zeroable, Zeroable};
+///
+/// #[repr(C)]
+/// struct Frame {
+///     len: u32,
+///     data: [u8; 4096]
+/// }
+///
+/// zeroable!(Frame { len: u32, data: [u8; 4096] });
+///
+/// let frame = Frame::zeroed();
+/// assert_eq!(frame.len, 0);
+/// ```
+///
+/// Zero isn't a valid `NonZeroU32`:
+///
+/// ```rust,compile_fail,E0277
+/// use binext::zeroable;
+/// use std::num::NonZeroU32;
+///
+/// struct Record {
+///     id: NonZeroU32
+/// }
+///
+/// zeroable!(Record { id: NonZeroU32 });
+/// ```
+///
+/// Neither in a tuple struct:
+///
+/// ```rust,compile_fail,E0277
+/// use binext::zeroable;
+/// use std::num::NonZeroU32;
+///
+/// struct Id(u16, NonZeroU32);
+///
+/// zeroable!(Id(u16, NonZeroU32));
+/// ```
+///
+/// [Zeroable]: crate::Zeroable
+#[macro_export]
+macro_rules! zeroable {
+    (@impl ([$($generics: tt)*] $ty: ident [$($arg: tt),*]) { $($field: tt: $field_ty: ty [],)* }) => {
+        const _: () = {
+            fn _assert_zeroable<T: $crate::Zeroable>() {}
+
+            fn _check<$($generics)*>(item: &$ty<$($arg),*>) {
+                let $ty { $($field: _),* } = item;
+
+                $(
+                    let _: &$field_ty = &item.$field;
+                    _assert_zeroable::<$field_ty>();
+                )*
+            }
+        };
+
+        // SAFETY: every field of the struct accepts all zeroes.
+        unsafe impl<$($generics)*> $crate::Zeroable for $ty<$($arg),*> {}
+    };
+    ($($input: tt)*) => {
+        $crate::__struct_fields!(zeroable $($input)*);
+    };
+}
+
+/// Implements [Validate] for a struct by validating each one of its fields.
+///
+/// All the fields of the struct must be listed along with their types, a missing field or a
+/// mismatched type is a compile error. Each field is checked with its own [Validate]
+/// implementation, reading it straight from the bytes without constructing the struct, and the
+/// first failure is reported with the name and offset of the field. Generic, tuple and unit
+/// structs are declared like with [zeroable], the fields of tuple structs being named by their
+/// index, as in `.0`. Zero sized fields, like `PhantomData`, take no bytes and are always valid.
+/// Fields can also be marked with:
+///
+/// - `#[magic = value]`, to require the field to be equal to a constant.
+/// - `#[range = range]`, to require the field to be contained in a range.
+/// - `#[nan = false]`, to reject NaN in floating point fields.
+/// - `#[checksum = Checksum::Crc32.of(range)]`, to require the field to hold the checksum of a
+///   range of the bytes of the struct, computed with the field taken as zero. Such fields are
+///   computed and stored by [write_binary_with_checksums], see [ChecksumOf]. A field that
+///   doesn't have the size of its checksum is a compile error.
+/// - `#[unknown_bits = UnknownBits::Reject]`, to reject bits that don't belong to any flag in
+///   [bitflags] fields, whose types implement [Validate] through [validate_flags]. The other
+///   policies accept any bits, see [UnknownBits::check].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{validate, Validate};
+/// use std::num::NonZeroU16;
+///
+/// #[repr(C)]
+/// struct Packet {
+///     magic: u32,
+///     id: NonZeroU16,
+///     priority: u8,
+///     urgent: bool
+/// }
+///
+/// validate!(Packet {
+///     #[magic = 0xCAFEBABE]
+///     magic: u32,
+///     id: NonZeroU16,
+///     #[range = 0..=3]
+///     priority: u8,
+///     urgent: bool
+/// });
+///
+/// let mut bytes = [0u8; 8];
+/// bytes[..4].copy_from_slice(&0xCAFEBABEu32.to_ne_bytes());
+/// bytes[4] = 1;
+/// assert!(Packet::is_valid_bytes(&bytes));
+///
+/// bytes[6] = 4;
+/// let error = Packet::validate_bytes(&bytes).unwrap_err();
+/// assert_eq!(error.field, Some("priority"));
+/// assert_eq!(error.offset, 6);
+/// ```
+///
+/// [Validate]: crate::Validate
+/// [zeroable]: crate::zeroable
+/// [write_binary_with_checksums]: crate::BinaryWrite::write_binary_with_checksums
+/// [ChecksumOf]: crate::ChecksumOf
+/// [bitflags]: https://docs.rs/bitflags
+/// [validate_flags]: crate::validate_flags
+/// [UnknownBits::check]: crate::UnknownBits::check
+#[macro_export]
+macro_rules! validate {
+    (@magic $bytes: ident, $offset: ident, $field: tt: $field_ty: ty = [$($value: tt)+]) => {
+        if $crate::validate!(@read $bytes, $offset, $field_ty) != ($($value)+) {
+            return Err($crate::ValidationError::new(0, concat!("not equal to ", stringify!($($value)+)))
+                .in_field($crate::__field_name!($field), $offset));
+        }
+    };
+    (@range $bytes: ident, $offset: ident, $field: tt: $field_ty: ty = [$($value: tt)+]) => {
+        if !($($value)+).contains(&$crate::validate!(@read $bytes, $offset, $field_ty)) {
+            return Err($crate::ValidationError::new(0, concat!("not in range ", stringify!($($value)+)))
+                .in_field($crate::__field_name!($field), $offset));
+        }
+    };
+    (@nan $bytes: ident, $offset: ident, $field: tt: $field_ty: ty = [$($value: tt)+]) => {
+        if !($($value)+) && $crate::validate!(@read $bytes, $offset, $field_ty).is_nan() {
+            return Err($crate::ValidationError::new(0, "NaN is not allowed")
+                .in_field($crate::__field_name!($field), $offset));
+        }
+    };
+    (@checksum $bytes: ident, $offset: ident, $field: tt: $field_ty: ty = [$($value: tt)+]) => {
+        let checksum: $crate::ChecksumOf = $($value)+;
+
+        if checksum.verify($bytes, $offset).is_err() {
+            return Err($crate::ValidationError::new(0, "checksum doesn't match the covered bytes")
+                .in_field($crate::__field_name!($field), $offset));
+        }
+    };
+    (@unknown_bits $bytes: ident, $offset: ident, $field: tt: $field_ty: ty = [$($value: tt)+]) => {
+        let policy: $crate::UnknownBits = $($value)+;
+
+        if let Err(e) = policy.check(&$crate::validate!(@read $bytes, $offset, $field_ty)) {
+            return Err(e.in_field($crate::__field_name!($field), $offset));
+        }
+    };
+    (@assert checksum $field: tt: $field_ty: ty = [$($algorithm: ident)::+ . of $($rest: tt)+]) => {
+        assert!(
+            ::core::mem::size_of::<$field_ty>() == $($algorithm)::+.size(),
+            concat!("checksum field `", stringify!($field), "` doesn't have the size of its checksum")
+        );
+    };
+    (@assert checksum $field: tt: $field_ty: ty = $value: tt) => {
+        compile_error!(concat!(
+            "the checksum of field `", stringify!($field), "` must be written as `Checksum::<algorithm>.of(range)`"
+        ));
+    };
+    (@assert $check: ident $field: tt: $field_ty: ty = $value: tt) => {};
+    (@stamp checksum $bytes: ident, $offset: expr, [$($value: tt)+]) => {
+        $crate::ChecksumOf::stamp(&($($value)+), $bytes, $offset);
+    };
+    (@stamp $check: ident $bytes: ident, $offset: expr, $value: tt) => {};
+    (@read $bytes: ident, $offset: ident, $field_ty: ty) => {
+        // SAFETY: the field is in bounds and has already been validated.
+        unsafe {
+            ::core::ptr::read_unaligned($bytes[$offset..].as_ptr() as *const $field_ty)
+        }
+    };
+    (@impl ([$($generics: tt)*] $ty: ident [$($arg: tt),*]) {
+        $($field: tt: $field_ty: ty [$($check: ident = $value: tt),*],)*
+    }) => {
+        const _: () = {
+            fn _check<$($generics)*>(item: &$ty<$($arg),*>) {
+                let $ty { $($field: _),* } = item;
+                $(let _: &$field_ty = &item.$field;)*
+            }
+
+            $($($crate::validate!(@assert $check $field: $field_ty = $value);)*)*
+        };
+
+        impl<$($generics)*> $crate::Validate for $ty<$($arg),*> {
+            fn validate_bytes(bytes: &[u8]) -> Result<(), $crate::ValidationError> {
+                if bytes.len() != ::core::mem::size_of::<Self>() {
+                    return Err($crate::ValidationError::new(0, concat!(
+                        "length doesn't match the size of ", stringify!($ty)
+                    )));
+                }
+
+                $(
+                    let offset = ::core::mem::offset_of!(Self, $field);
+                    $crate::validate_field::<$field_ty>(bytes, offset, $crate::__field_name!($field))?;
+                    $($crate::validate!(@$check bytes, offset, $field: $field_ty = $value);)*
+                )*
+
+                Ok(())
+            }
+
+            fn stamp_checksums(bytes: &mut [u8]) {
+                let _ = &bytes;
+                $($(
+                    $crate::validate!(@stamp $check bytes, ::core::mem::offset_of!(Self, $field), $value);
+                )*)*
+            }
+        }
+    };
+    ($($input: tt)*) => {
+        $crate::__struct_fields!(validate $($input)*);
+    };
+}
+
+/// Implements [SwapBytes] for a struct by swapping each one of its fields.
+///
+/// All the fields of the struct must be listed along with their types, a missing field or a
+/// mismatched type is a compile error. Generic, tuple and unit structs are declared like with
+/// [zeroable]. Padding bytes are left untouched.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{swap_bytes, BinaryRead, BinaryWrite};
+/// use std::io::{self, Cursor};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Header {
+///     magic: [u8; 4],
+///     len: u32,
+///     letter: char
+/// }
+///
+/// swap_bytes!(Header { magic: [u8; 4], len: u32, letter: char });
+///
+/// fn main() -> io::Result<()> {
+///     let header = Header { magic: *b"BXT1", len: 258, letter: 'x' };
+///
+///     let mut buffer = Vec::new();
+///     buffer.write_binary_be(&header)?;
+///     assert_eq!(buffer[4..8], [0, 0, 1, 2]);
+///
+///     assert_eq!(Cursor::new(buffer).read_binary_be::<Header>()?, header);
+///     Ok(())
+/// }
+/// ```
+///
+/// [SwapBytes]: crate::SwapBytes
+/// [zeroable]: crate::zeroable
+#[macro_export]
+macro_rules! swap_bytes {
+    (@impl ([$($generics: tt)*] $ty: ident [$($arg: tt),*]) { $($field: tt: $field_ty: ty [],)* }) => {
+        const _: () = {
+            fn _check<$($generics)*>(item: &$ty<$($arg),*>) {
+                let $ty { $($field: _),* } = item;
+                $(let _: &$field_ty = &item.$field;)*
+            }
+        };
+
+        impl<$($generics)*> $crate::SwapBytes for $ty<$($arg),*> {
+            // Structs without fields leave the bytes untouched.
+            #[allow(unused_variables)]
+            fn swap_bytes(bytes: &mut [u8]) {
+                $(
+                    let offset = ::core::mem::offset_of!(Self, $field);
+                    <$field_ty as $crate::SwapBytes>::swap_bytes(
+                        &mut bytes[offset..offset + ::core::mem::size_of::<$field_ty>()]
+                    );
+                )*
+            }
+
+            #[allow(unused_variables)]
+            fn swap_bytes_checked(bytes: &mut [u8]) -> Result<(), $crate::ValidationError> {
+                $(
+                    let offset = ::core::mem::offset_of!(Self, $field);
+                    <$field_ty as $crate::SwapBytes>::swap_bytes_checked(
+                        &mut bytes[offset..offset + ::core::mem::size_of::<$field_ty>()]
+                    ).map_err(|e| e.in_field($crate::__field_name!($field), offset))?;
+                )*
+
+                Ok(())
+            }
+        }
+    };
+    ($($input: tt)*) => {
+        $crate::__struct_fields!(swap_bytes $($input)*);
+    };
+}
+
+/// Implements [Columns] for a struct, taking the offset and size of each field from its layout.
+///
+/// All the fields of the struct must be listed, in declaration order, along with their types;
+/// a missing field or a mismatched type is a compile error. Generic, tuple and unit structs are
+/// declared like with [zeroable].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{columns, Columns};
+///
+/// #[repr(C)]
+/// struct Trade {
+///     price: f64,
+///     quantity: u32,
+///     side: u8
+/// }
+///
+/// columns!(Trade { price: f64, quantity: u32, side: u8 });
+///
+/// let offsets = Trade::FIELDS.iter().map(|field| (field.name, field.offset)).collect::<Vec<_>>();
+/// assert_eq!(offsets, [("price", 0), ("quantity", 8), ("side", 12)]);
+/// ```
+///
+/// [Columns]: crate::Columns
+/// [zeroable]: crate::zeroable
+#[macro_export]
+macro_rules! columns {
+    (@impl ([$($generics: tt)*] $ty: ident [$($arg: tt),*]) { $($field: tt: $field_ty: ty [],)* }) => {
+        const _: () = {
+            fn _check<$($generics)*>(item: &$ty<$($arg),*>) {
+                let $ty { $($field: _),* } = item;
+                $(let _: &$field_ty = &item.$field;)*
+            }
+        };
+
+        impl<$($generics)*> $crate::Columns for $ty<$($arg),*> {
+            const FIELDS: &'static [$crate::Field] = &[$(
+                $crate::Field {
+                    name: $crate::__field_name!($field),
+                    offset: ::core::mem::offset_of!(Self, $field),
+                    size: ::core::mem::size_of::<$field_ty>(),
+                },
+            )*];
+        }
+    };
+    ($($input: tt)*) => {
+        $crate::__struct_fields!(columns $($input)*);
+    };
+}
+
+/// Implements [NpyRecord] for a struct, so it can be stored in `.npy` files with [write_npy].
+///
+/// All the fields of the struct must be listed, in declaration order, along with their types,
+/// which must implement [NpyType]; a missing field or a mismatched type is a compile error.
+/// Generic, tuple and unit structs are declared like with [zeroable].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{npy_record, NpyRecord};
+///
+/// #[repr(C)]
+/// struct Pixel {
+///     position: [u16; 2],
+///     intensity: f32
+/// }
+///
+/// npy_record!(Pixel { position: [u16; 2], intensity: f32 });
+///
+/// assert_eq!(Pixel::FIELDS[0].len, Some(2));
+/// assert_eq!(Pixel::FIELDS[1].offset, 4);
+/// ```
+///
+/// [NpyRecord]: crate::NpyRecord
+/// [NpyType]: crate::NpyType
+/// [write_npy]: crate::write_npy
+/// [zeroable]: crate::zeroable
+#[macro_export]
+macro_rules! npy_record {
+    (@impl ([$($generics: tt)*] $ty: ident [$($arg: tt),*]) { $($field: tt: $field_ty: ty [],)* }) => {
+        const _: () = {
+            fn _check<$($generics)*>(item: &$ty<$($arg),*>) {
+                let $ty { $($field: _),* } = item;
+                $(let _: &$field_ty = &item.$field;)*
+            }
+        };
+
+        impl<$($generics)*> $crate::NpyRecord for $ty<$($arg),*> {
+            const FIELDS: &'static [$crate::NpyField] = &[$(
+                $crate::NpyField {
+                    name: $crate::__field_name!($field),
+                    descr: <$field_ty as $crate::NpyType>::DESCR,
+                    len: <$field_ty as $crate::NpyType>::LEN,
+                    offset: ::core::mem::offset_of!(Self, $field),
+                },
+            )*];
+        }
+    };
+    ($($input: tt)*) => {
+        $crate::__struct_fields!(npy_record $($input)*);
+    };
+}
+
+/// Implements [BinaryEnum] for an enum whose variants carry at most one payload each.
+///
+/// Every variant is listed along with its payload type, if any, and the discriminant written for
+/// it, whose type follows the name of the enum. Payloads are written raw, like with
+/// [write_binary], right after the discriminant. Two layouts are supported:
+///
+/// - `#[layout = compact]`, the default, where each frame takes only the size of its own
+///   variant. Suited for streams.
+/// - `#[layout = uniform]`, where the payloads are padded with zeroes up to the largest one, so
+///   every frame has the same size. Suited for files of fixed-size records.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{binary_enum, BinaryEnum, BinaryRead, BinaryWrite};
+/// use std::io::{self, Cursor};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Ping {
+///     seq: u32
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Message {
+///     Ping(Ping),
+///     Data([u8; 16]),
+///     Close
+/// }
+///
+/// binary_enum!(#[layout = uniform] Message: u8 {
+///     Ping(Ping) = 1,
+///     Data([u8; 16]) = 2,
+///     Close = 3
+/// });
+///
+/// fn main() -> io::Result<()> {
+///     let mut buffer = Vec::new();
+///     buffer.write_binary_enum(&Message::Ping(Ping { seq: 7 }))?;
+///     buffer.write_binary_enum(&Message::Close)?;
+///
+///     assert_eq!(Message::FRAME_SIZE, Some(17));
+///     assert_eq!(buffer.len(), 34);
+///
+///     let mut cursor = Cursor::new(buffer);
+///     assert_eq!(cursor.read_binary_enum::<Message>()?, Message::Ping(Ping { seq: 7 }));
+///     assert_eq!(cursor.read_binary_enum::<Message>()?, Message::Close);
+///     Ok(())
+/// }
+/// ```
+///
+/// [BinaryEnum]: crate::BinaryEnum
+/// [write_binary]: crate::BinaryWrite::write_binary
+#[macro_export]
+macro_rules! binary_enum {
+    (@pattern $payload: ident $variant: ident) => {
+        Self::$variant
+    };
+    (@pattern $payload: ident $variant: ident $payload_ty: ty) => {
+        Self::$variant($payload)
+    };
+    (@write $writer: ident $payload: ident) => {};
+    (@write $writer: ident $payload: ident $payload_ty: ty) => {
+        $crate::BinaryWrite::write_binary::<$payload_ty>($writer, $payload)?
+    };
+    (@read $reader: ident $variant: ident) => {
+        Self::$variant
+    };
+    (@read $reader: ident $variant: ident $payload_ty: ty) => {
+        Self::$variant($crate::BinaryRead::read_binary::<$payload_ty>($reader)?)
+    };
+    (@size) => {
+        0
+    };
+    (@size $payload_ty: ty) => {
+        ::core::mem::size_of::<$payload_ty>()
+    };
+    (@frame [] $tag: ty, $($size: expr),*) => {
+        None
+    };
+    (@frame [compact] $tag: ty, $($size: expr),*) => {
+        None
+    };
+    (@frame [uniform] $tag: ty, $($size: expr),*) => {{
+        let sizes = [$($size),*];
+        let mut largest = 0;
+        let mut i = 0;
+
+        while i < sizes.len() {
+            if sizes[i] > largest {
+                largest = sizes[i];
+            }
+
+            i += 1;
+        }
+
+        Some(::core::mem::size_of::<$tag>() + largest)
+    }};
+    ($(#[layout = $layout: ident])? $ty: ident: $tag: ty {
+        $($variant: ident $(($payload_ty: ty))? = $discriminant: literal),* $(,)?
+    }) => {
+        impl $crate::BinaryEnum for $ty {
+            const FRAME_SIZE: Option<usize> = $crate::binary_enum!(
+                @frame [$($layout)?] $tag, $($crate::binary_enum!(@size $($payload_ty)?)),*
+            );
+
+            fn write_enum<W: ::std::io::Write>(&self, writer: &mut W) -> ::std::io::Result<()> {
+                let size = match self {
+                    $(
+                        $crate::binary_enum!(@pattern payload $variant $($payload_ty)?) => {
+                            $crate::BinaryWrite::write_binary::<$tag>(writer, &$discriminant)?;
+                            $crate::binary_enum!(@write writer payload $($payload_ty)?);
+                            $crate::binary_enum!(@size $($payload_ty)?)
+                        }
+                    )*
+                };
+
+                match Self::FRAME_SIZE {
+                    Some(frame) => $crate::__write_padding(writer, frame - ::core::mem::size_of::<$tag>() - size),
+                    None => Ok(())
+                }
+            }
+
+            fn read_enum<R: ::std::io::Read>(reader: &mut R) -> ::std::io::Result<Self> {
+                let (item, size) = match $crate::BinaryRead::read_binary::<$tag>(reader)? {
+                    $(
+                        $discriminant => (
+                            $crate::binary_enum!(@read reader $variant $($payload_ty)?),
+                            $crate::binary_enum!(@size $($payload_ty)?)
+                        ),
+                    )*
+                    tag => return Err($crate::UnknownVariant {
+                        name: stringify!($ty),
+                        tag: tag as u64,
+                    }.into())
+                };
+
+                if let Some(frame) = Self::FRAME_SIZE {
+                    $crate::__skip_padding(reader, frame - ::core::mem::size_of::<$tag>() - size)?;
+                }
+
+                Ok(item)
+            }
+        }
+    };
+}
+
+/// Implements [Packed] for a struct, written field by field with no padding and with optional
+/// trailing sections.
+///
+/// Fields are listed in the order they are written. The trailing ones may be conditional:
+/// `Option` fields marked with `#[present_if = predicate]`, where the predicate is a closure
+/// over the record. When writing, each conditional field must be `Some` exactly when its
+/// predicate holds, otherwise a [PresenceMismatch] error is returned. When reading, the
+/// predicate is evaluated over the fields read so far, with the later conditional fields still
+/// `None`, and decides whether the field is read.
+///
+/// Since a reader couldn't tell whether a conditional field was written before reading the
+/// next one, every field after a conditional one must be conditional too.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{packed, BinaryRead, BinaryWrite};
+/// use std::io::{self, Cursor};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Frame {
+///     flags: u8,
+///     len: u16,
+///     checksum: Option<u32>,
+///     timestamp: Option<u64>
+/// }
+///
+/// packed!(Frame {
+///     flags: u8,
+///     len: u16,
+///     #[present_if = |frame| frame.flags & 0x01 != 0]
+///     checksum: Option<u32>,
+///     #[present_if = |frame| frame.flags & 0x02 != 0]
+///     timestamp: Option<u64>,
+/// });
+///
+/// fn main() -> io::Result<()> {
+///     let frame = Frame { flags: 0x01, len: 4, checksum: Some(0xdead), timestamp: None };
+///
+///     let mut buffer = Vec::new();
+///     buffer.write_binary_packed(&frame)?;
+///     assert_eq!(buffer.len(), 7);
+///
+///     assert_eq!(Cursor::new(buffer).read_binary_packed::<Frame>()?, frame);
+///     Ok(())
+/// }
+/// ```
+///
+/// A plain field after a conditional one fails to compile:
+///
+/// ```rust,compile_fail
+/// use binext::packed;
+///
+/// struct Frame {
+///     flags: u8,
+///     checksum: Option<u32>,
+///     len: u16
+/// }
+///
+/// packed!(Frame {
+///     flags: u8,
+///     #[present_if = |frame| frame.flags & 0x01 != 0]
+///     checksum: Option<u32>,
+///     len: u16,
+/// });
+/// ```
+///
+/// [Packed]: crate::Packed
+/// [PresenceMismatch]: crate::PresenceMismatch
+#[macro_export]
+macro_rules! packed {
+    ($ty: ident {
+        $($field: ident: $field_ty: ty,)*
+        $(#[present_if = $condition: expr] $optional: ident: Option<$optional_ty: ty>),* $(,)?
+    }) => {
+        impl $crate::Packed for $ty {
+            fn write_packed<W: ::std::io::Write>(&self, writer: &mut W) -> ::std::io::Result<()> {
+                $(
+                    $crate::BinaryWrite::write_binary::<$field_ty>(writer, &self.$field)?;
+                )*
+
+                $(
+                    let present = $crate::__condition::<Self>($condition)(self);
+
+                    match (present, &self.$optional) {
+                        (true, Some(section)) => $crate::BinaryWrite::write_binary::<$optional_ty>(writer, section)?,
+                        (false, None) => {},
+                        _ => return Err($crate::PresenceMismatch {
+                            name: stringify!($ty),
+                            field: stringify!($optional),
+                            expected: present,
+                        }.into())
+                    }
+                )*
+
+                Ok(())
+            }
+
+            fn read_packed<R: ::std::io::Read>(reader: &mut R) -> ::std::io::Result<Self> {
+                #[allow(unused_mut)]
+                let mut item = Self {
+                    $($field: $crate::BinaryRead::read_binary::<$field_ty>(reader)?,)*
+                    $($optional: None,)*
+                };
+
+                $(
+                    if $crate::__condition::<Self>($condition)(&item) {
+                        item.$optional = Some($crate::BinaryRead::read_binary::<$optional_ty>(reader)?);
+                    }
+                )*
+
+                Ok(item)
+            }
+        }
+    };
+    ($ty: ident { $($field: ident: $field_ty: ty),* }) => {
+        $crate::packed!($ty { $($field: $field_ty,)* });
+    };
+}
+
+/// Implements [WireTwin] for a struct that can't be `#[repr(C)]`, generating its `#[repr(C)]`
+/// twin, so it can be written with [write_binary_wire] and read with [read_binary_wire].
+///
+/// The struct is given along with the visibility and name of its twin, and all of its fields
+/// along with their types, a missing field being a compile error. Each field of the twin holds
+/// the [WireTwin] representation of the field of the same name, so fields can be any type
+/// implementing it, including other structs given a twin with this macro, `Option`s and enums
+/// implemented with [wire_enum]. Fields of other types are mapped by a module, as in
+/// `#[with = module] field: Type as WireType`, where the module, in scope at the macro call,
+/// has the functions:
+///
+/// - `fn to_wire(value: &Type) -> WireType`
+/// - `fn from_wire(wire: WireType) -> Result<Type, ValidationError>`
+///
+/// The twin implements [Validate], so it's checked before being converted back, and errors
+/// point at the field of the twin that failed. It's a plain struct, with public fields in
+/// declaration order, so layout assertions and the other macros of this crate apply to it
+/// like to any other. Generic structs aren't supported.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{wire_enum, wire_twin, BinaryRead, BinaryWrite, ValidationError};
+/// use std::{io::{self, Cursor}, mem::size_of, time::Duration};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Mode {
+///     Fast,
+///     Safe
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Settings {
+///     mode: Mode,
+///     retries: Option<u8>,
+///     timeout: Duration
+/// }
+///
+/// mod millis {
+///     use binext::ValidationError;
+///     use std::time::Duration;
+///
+///     pub fn to_wire(value: &Duration) -> u64 {
+///         value.as_millis() as u64
+///     }
+///
+///     pub fn from_wire(wire: u64) -> Result<Duration, ValidationError> {
+///         Ok(Duration::from_millis(wire))
+///     }
+/// }
+///
+/// wire_enum!(Mode: u8 { Fast = 1, Safe = 2 });
+///
+/// wire_twin!(Settings => pub SettingsWire {
+///     mode: Mode,
+///     retries: Option<u8>,
+///     #[with = millis]
+///     timeout: Duration as u64,
+/// });
+///
+/// fn main() -> io::Result<()> {
+///     let settings = Settings { mode: Mode::Safe, retries: Some(3), timeout: Duration::from_secs(2) };
+///
+///     let mut buffer = Vec::new();
+///     buffer.write_binary_wire(&settings)?;
+///     assert_eq!(buffer.len(), size_of::<SettingsWire>());
+///
+///     assert_eq!(Cursor::new(buffer).read_binary_wire::<Settings>()?, settings);
+///     Ok(())
+/// }
+/// ```
+///
+/// [WireTwin]: crate::WireTwin
+/// [write_binary_wire]: crate::BinaryWrite::write_binary_wire
+/// [read_binary_wire]: crate::BinaryRead::read_binary_wire
+/// [wire_enum]: crate::wire_enum
+/// [Validate]: crate::Validate
+#[macro_export]
+macro_rules! wire_twin {
+    (@wire $field_ty: ty) => {
+        <$field_ty as $crate::WireTwin>::Wire
+    };
+    (@wire $field_ty: ty, $wire_ty: ty) => {
+        $wire_ty
+    };
+    (@to $value: expr) => {
+        $crate::WireTwin::to_wire($value)
+    };
+    (@to $value: expr, $with: ident) => {
+        $with::to_wire($value)
+    };
+    (@from $value: expr) => {
+        $crate::WireTwin::from_wire($value)
+    };
+    (@from $value: expr, $with: ident) => {
+        $with::from_wire($value)
+    };
+    ($ty: ident => $vis: vis $wire: ident {
+        $($(#[with = $with: ident])? $field: ident: $field_ty: ty $(as $wire_ty: ty)?),* $(,)?
+    }) => {
+        #[doc = concat!("The `#[repr(C)]` wire representation of [`", stringify!($ty), "`].")]
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        $vis struct $wire {
+            $(pub $field: $crate::wire_twin!(@wire $field_ty $(, $wire_ty)?),)*
+        }
+
+        $crate::validate!($wire {
+            $($field: $crate::wire_twin!(@wire $field_ty $(, $wire_ty)?),)*
+        });
+
+        impl $crate::WireTwin for $ty {
+            type Wire = $wire;
+
+            fn to_wire(&self) -> $wire {
+                $wire {
+                    $($field: $crate::wire_twin!(@to &self.$field $(, $with)?),)*
+                }
+            }
+
+            fn from_wire(wire: $wire) -> Result<Self, $crate::ValidationError> {
+                Ok(Self {
+                    $(
+                        $field: $crate::wire_twin!(@from wire.$field $(, $with)?)
+                            .map_err(|e: $crate::ValidationError| {
+                                e.in_field(stringify!($field), ::core::mem::offset_of!($wire, $field))
+                            })?,
+                    )*
+                })
+            }
+        }
+    };
+}
+
+/// Implements [WireTwin] for a fieldless enum, written as the discriminant listed for each
+/// variant, whose type follows the name of the enum.
+///
+/// A discriminant matching no variant fails to convert back. See [wire_twin] for an example.
+///
+/// [WireTwin]: crate::WireTwin
+/// [wire_twin]: crate::wire_twin
+#[macro_export]
+macro_rules! wire_enum {
+    ($ty: ident: $tag: ty {
+        $($variant: ident = $discriminant: literal),* $(,)?
+    }) => {
+        impl $crate::WireTwin for $ty {
+            type Wire = $tag;
+
+            fn to_wire(&self) -> $tag {
+                match self {
+                    $(Self::$variant => $discriminant,)*
+                }
+            }
+
+            fn from_wire(wire: $tag) -> Result<Self, $crate::ValidationError> {
+                match wire {
+                    $($discriminant => Ok(Self::$variant),)*
+                    _ => Err($crate::ValidationError::new(0, concat!("unknown discriminant for ", stringify!($ty))))
+                }
+            }
+        }
+    };
+}
+
+/// Implements [Validate] for [bitflags] types, accepting any value of their raw bits.
+///
+/// This lets flags be fields of structs implementing [Validate] with [validate], where the
+/// `#[unknown_bits = ...]` check applies an [UnknownBits] policy to them.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{validate, validate_flags, UnknownBits, Validate};
+///
+/// bitflags::bitflags! {
+///     #[derive(Clone, Copy)]
+///     struct Permissions: u32 {
+///         const READ = 1;
+///         const WRITE = 2;
+///     }
+/// }
+///
+/// validate_flags!(Permissions);
+///
+/// #[repr(C)]
+/// struct Entry {
+///     id: u32,
+///     #[allow(unused)]
+///     permissions: Permissions
+/// }
+///
+/// validate!(Entry {
+///     id: u32,
+///     #[unknown_bits = UnknownBits::Reject]
+///     permissions: Permissions,
+/// });
+///
+/// let mut bytes = [0u8; 8];
+/// bytes[4..].copy_from_slice(&3u32.to_ne_bytes());
+/// assert!(Entry::is_valid_bytes(&bytes));
+///
+/// bytes[4..].copy_from_slice(&0b1001u32.to_ne_bytes());
+/// assert_eq!(Entry::validate_bytes(&bytes).unwrap_err().field, Some("permissions"));
+/// ```
+///
+/// [Validate]: crate::Validate
+/// [bitflags]: https://docs.rs/bitflags
+/// [validate]: crate::validate
+/// [UnknownBits]: crate::UnknownBits
+#[cfg(feature = "bitflags")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+#[macro_export]
+macro_rules! validate_flags {
+    ($($ty: ty),+ $(,)?) => {
+        $(
+            impl $crate::Validate for $ty {
+                fn validate_bytes(bytes: &[u8]) -> Result<(), $crate::ValidationError> {
+                    $crate::__validate_flags::<Self>(bytes)
+                }
+            }
+        )+
+    };
+}
+
+/// Implements [FromTextPairs] for a struct, parsing the value of each listed field with its
+/// `FromStr` implementation, under the name of the field.
+///
+/// Keys are matched exactly, and keys of fields that aren't listed are rejected like unknown
+/// ones. See [read_binary_or_text] for an example.
+///
+/// [FromTextPairs]: crate::FromTextPairs
+/// [read_binary_or_text]: crate::BinaryRead::read_binary_or_text
+#[cfg(feature = "text")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text")))]
+#[macro_export]
+macro_rules! text_pairs {
+    ($ty: ident {
+        $($field: ident: $field_ty: ty),* $(,)?
+    }) => {
+        impl $crate::FromTextPairs for $ty {
+            fn set_pair(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value.parse::<$field_ty>().map_err(|e| e.to_string())?;
+                            Ok(())
+                        },
+                    )*
+                    _ => Err(format!(concat!("unknown key for ", stringify!($ty), ": `{}`"), key))
+                }
+            }
+        }
+    };
+}
+
+/// Implements [BinaryFields] for a struct, taking the offset of each field from its layout.
+///
+/// All the fields of the struct must be listed, in declaration order, along with their types,
+/// which must implement `Debug`; a missing field or a mismatched type is a compile error.
+/// Generic, tuple and unit structs are declared like with [zeroable]. See [binary_eq_report]
+/// for an example.
+///
+/// [BinaryFields]: crate::BinaryFields
+/// [zeroable]: crate::zeroable
+/// [binary_eq_report]: crate::binary_eq_report
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+#[macro_export]
+macro_rules! binary_fields {
+    (@impl ([$($generics: tt)*] $ty: ident [$($arg: tt),*]) { $($field: tt: $field_ty: ty [],)* }) => {
+        const _: () = {
+            fn _check<$($generics)*>(item: &$ty<$($arg),*>) {
+                let $ty { $($field: _),* } = item;
+                $(let _: &$field_ty = &item.$field;)*
+            }
+        };
+
+        impl<$($generics)*> $crate::BinaryFields for $ty<$($arg),*> {
+            // SAFETY: the fields are checked above to have the listed types.
+            const FIELDS: &'static [$crate::FieldInfo] = unsafe { &[$(
+                $crate::FieldInfo::new::<$field_ty>(
+                    $crate::__field_name!($field),
+                    stringify!($field_ty),
+                    ::core::mem::offset_of!(Self, $field)
+                ),
+            )*] };
+        }
+    };
+    ($($input: tt)*) => {
+        $crate::__struct_fields!(binary_fields $($input)*);
+    };
+}
+
+/// Asserts that two values of a [BinaryFields] type hold the same bytes in every field, like
+/// `assert_eq!`.
+///
+/// On failure it panics with the report of [binary_eq_report] instead of the `Debug` output of
+/// both values: the first differing field with both its values, and a hex excerpt of the bytes
+/// around the difference. A custom message can follow the values, like with `assert_eq!`.
+///
+/// # Examples
+///
+/// ```rust,should_panic
+/// use binext::{assert_eq_binary, binary_fields};
+///
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     version: u16,
+///     flags: u16,
+///     reserved: [u8; 56]
+/// }
+///
+/// binary_fields!(Header { magic: u32, version: u16, flags: u16, reserved: [u8; 56] });
+///
+/// let expected = Header { magic: 0xCAFE, version: 2, flags: 0, reserved: [0; 56] };
+/// let actual = Header { magic: 0xCAFE, version: 3, flags: 0, reserved: [0; 56] };
+///
+/// // Panics pointing at `version: u16`, with the values 2 and 3.
+/// assert_eq_binary!(actual, expected, "headers of version {} differ", 2);
+/// ```
+///
+/// [BinaryFields]: crate::BinaryFields
+/// [binary_eq_report]: crate::binary_eq_report
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+#[macro_export]
+macro_rules! assert_eq_binary {
+    ($left: expr, $right: expr $(,)?) => {
+        if let Some(report) = $crate::binary_eq_report(&$left, &$right) {
+            panic!("assertion `left == right` failed: values differ in binary\n{report}");
+        }
+    };
+    ($left: expr, $right: expr, $($arg: tt)+) => {
+        if let Some(report) = $crate::binary_eq_report(&$left, &$right) {
+            panic!("assertion `left == right` failed: {}\n{report}", format_args!($($arg)+));
+        }
+    };
+}
@@ -0,0 +1,144 @@
+use std::{fs::File, io, marker::PhantomData, mem::size_of};
+use memmap2::Mmap;
+use crate::seek::whole_records;
+
+/// The records of a memory-mapped file, returned by [mmap_iter].
+///
+/// Records are borrowed straight from the mapping, so nothing is copied and pages are only
+/// read in by the system when a record on them is used. The mapping is released when this is
+/// dropped.
+///
+/// [mmap_iter]: mmap_iter
+pub struct MappedRecords<T> {
+    map: Mmap,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> MappedRecords<T> {
+    /// Returns the number of records in the mapping.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the mapping holds no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the record at `index`, if there's one.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    /// Returns an iterator over the records in the mapping.
+    pub fn iter(&self) -> MappedIter<'_, T> {
+        MappedIter {
+            bytes: &self.map,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MappedRecords<T> {
+    type Item = &'a T;
+    type IntoIter = MappedIter<'a, T>;
+
+    fn into_iter(self) -> MappedIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over the records of a [MappedRecords], borrowing each one from the mapping.
+///
+/// [MappedRecords]: MappedRecords
+pub struct MappedIter<'a, T> {
+    bytes: &'a [u8],
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for MappedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.bytes.len() < size_of::<T>() {
+            return None;
+        }
+
+        let (record, rest) = self.bytes.split_at(size_of::<T>());
+        let ptr = record.as_ptr() as *const T;
+
+        // Mappings start on a page boundary and records are a multiple of their alignment, so
+        // this only fails if the mapping itself is broken.
+        assert!(ptr.is_aligned(), "mapped record at {ptr:p} is not aligned for {}", std::any::type_name::<T>());
+
+        self.bytes = rest;
+
+        // SAFETY: the pointer is in bounds and aligned, and the bytes live as long as the
+        // mapping, which mmap_iter requires to be valid records.
+        Some(unsafe { &*ptr })
+    }
+
+    fn nth(&mut self, n: usize) -> Option<&'a T> {
+        let skip = n.saturating_mul(size_of::<T>()).min(self.bytes.len());
+        self.bytes = &self.bytes[skip..];
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len() / size_of::<T>();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for MappedIter<'_, T> {}
+
+/// Memory-maps a file of records of `T` to iterate over them by reference, without copying.
+///
+/// This is meant for read-only scans of files too large to read into memory. The file must
+/// hold whole records, otherwise an `InvalidData` error carrying a [Misaligned] is returned,
+/// and zero sized types are rejected with an `InvalidInput` error. Every record yielded is
+/// checked to be in bounds and aligned.
+///
+/// # Safety
+///
+/// The mapping reflects the file, so it must not be modified, by this process or any other,
+/// while the records are in use, since their bytes would change under the references. The
+/// bytes must also form valid records of `T`, which [read_binary] assumes of any data too.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::mmap_iter;
+/// use std::{fs::File, io};
+///
+/// #[repr(C)]
+/// struct Tick {
+///     timestamp: u64,
+///     price: f64
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let file = File::open("ticks.bin")?;
+///     // SAFETY: the file isn't modified while it's scanned.
+///     let ticks = unsafe { mmap_iter::<Tick>(&file)? };
+///
+///     let max = ticks.iter().map(|tick| tick.price).fold(f64::MIN, f64::max);
+///     println!("{} ticks, max price {max}", ticks.len());
+///     Ok(())
+/// }
+/// ```
+///
+/// [Misaligned]: crate::Misaligned
+/// [read_binary]: crate::BinaryRead::read_binary
+pub unsafe fn mmap_iter<T>(file: &File) -> io::Result<MappedRecords<T>> {
+    // SAFETY: the caller guarantees the file isn't modified while it's mapped.
+    let map = unsafe { Mmap::map(file)? };
+    let len = whole_records::<T>(map.len() as u64)? as usize;
+
+    Ok(MappedRecords {
+        map,
+        len,
+        _marker: PhantomData,
+    })
+}
@@ -0,0 +1,99 @@
+use std::{io::{self, Read, Write}, mem::size_of};
+use ::ndarray::{Array, ArrayBase, Data, Dimension};
+use crate::{bytes, BinaryRead};
+
+/// Most dimensions accepted by [read_binary_array], so a corrupt header can't make it allocate
+/// an arbitrary amount of them.
+///
+/// [read_binary_array]: crate::BinaryRead::read_binary_array
+pub const MAX_NDIM: usize = 32;
+
+/// Bytes of elements gathered before writing them, when the array isn't contiguous.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Writes the shape header of `array` followed by its elements in standard order.
+pub(crate) fn write_array<W, S, D>(writer: &mut W, array: &ArrayBase<S, D>) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    S: Data,
+    D: Dimension
+{
+    let mut header = Vec::with_capacity((array.ndim() + 2) * size_of::<u64>());
+    header.extend_from_slice(&(array.ndim() as u64).to_ne_bytes());
+
+    for &dim in array.shape() {
+        header.extend_from_slice(&(dim as u64).to_ne_bytes());
+    }
+
+    header.extend_from_slice(&(size_of::<S::Elem>() as u64).to_ne_bytes());
+    writer.write_all(&header)?;
+
+    if let Some(elements) = array.as_slice() {
+        return writer.write_all(bytes::slice_as_bytes(elements));
+    }
+
+    // Arrays in another order, or with gaps between their elements, are written in the order
+    // of their logical iteration, which is always the standard one.
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+
+    for element in array.iter() {
+        chunk.extend_from_slice(bytes::as_bytes(element));
+
+        if chunk.len() >= CHUNK_SIZE {
+            writer.write_all(&chunk)?;
+            chunk.clear();
+        }
+    }
+
+    writer.write_all(&chunk)
+}
+
+/// Reads an array written by [write_array], checking its shape against `D` and the element
+/// count against `max_elements`.
+pub(crate) fn read_array<R, T, D>(reader: &mut R, max_elements: usize) -> io::Result<Array<T, D>>
+where
+    R: Read,
+    D: Dimension
+{
+    let ndim = reader.read_binary::<u64>()?;
+
+    if ndim > MAX_NDIM as u64 {
+        return Err(invalid_data(format!("{ndim} dimensions exceed the limit of {MAX_NDIM}")));
+    }
+
+    if D::NDIM.is_some_and(|expected| expected as u64 != ndim) {
+        return Err(invalid_data(format!("expected {} dimensions, found {ndim}", D::NDIM.unwrap())));
+    }
+
+    let mut dim = D::zeros(ndim as usize);
+    let mut count = 1usize;
+
+    for axis in dim.slice_mut() {
+        let len = reader.read_binary::<u64>()?;
+
+        *axis = usize::try_from(len)
+            .map_err(|_| invalid_data(format!("axis of {len} elements too long for this platform")))?;
+        count = count.saturating_mul(*axis);
+    }
+
+    let element_size = reader.read_binary::<u64>()?;
+
+    if element_size != size_of::<T>() as u64 {
+        return Err(invalid_data(format!(
+            "element size {element_size} doesn't match the {} bytes of the array type",
+            size_of::<T>()
+        )));
+    }
+
+    if count > max_elements {
+        return Err(invalid_data(format!("array of shape {:?} exceeds the limit of {max_elements} elements", dim.slice())));
+    }
+
+    let elements = reader.read_binary_vec::<T>(count)?;
+
+    Array::from_shape_vec(dim, elements).map_err(|e| invalid_data(e.to_string()))
+}
@@ -0,0 +1,394 @@
+use std::{fmt::Write as _, io::{self, Read, Write}, mem::size_of};
+use crate::{bytes, BinaryRead};
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+/// Headers are padded so the data starts at a multiple of this, like numpy does.
+const ALIGN: usize = 64;
+/// Room left in the header for the record count to grow, like numpy does.
+const GROWTH_DIGITS: usize = 21;
+
+/// Types with a numpy dtype, used to describe the fields of [NpyRecord] types.
+///
+/// It's implemented for the integer, float and `bool` primitives and for arrays of them, which
+/// become subarray fields. Formats are in native byte order.
+///
+/// [NpyRecord]: NpyRecord
+pub trait NpyType {
+    /// The numpy format of the type, or of its elements for arrays, such as `<u4`.
+    const DESCR: &'static str;
+    /// The number of elements for arrays, `None` for scalars.
+    const LEN: Option<usize> = None;
+}
+
+macro_rules! npy_types {
+    ($($ty: ty: $code: literal),* $(,)?) => {
+        $(
+            impl NpyType for $ty {
+                const DESCR: &'static str = match $code.as_bytes()[1] {
+                    b'1' => concat!("|", $code),
+                    _ if cfg!(target_endian = "little") => concat!("<", $code),
+                    _ => concat!(">", $code)
+                };
+            }
+
+            impl<const N: usize> NpyType for [$ty; N] {
+                const DESCR: &'static str = <$ty as NpyType>::DESCR;
+                const LEN: Option<usize> = Some(N);
+            }
+        )*
+    };
+}
+
+npy_types! {
+    u8: "u1", u16: "u2", u32: "u4", u64: "u8",
+    i8: "i1", i16: "i2", i32: "i4", i64: "i8",
+    f32: "f4", f64: "f8", bool: "b1",
+}
+
+/// A field of a [NpyRecord] type.
+///
+/// [NpyRecord]: NpyRecord
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NpyField {
+    /// Name of the field, its index prefixed by a dot for tuple structs.
+    pub name: &'static str,
+    /// The numpy format of the field, or of its elements for arrays.
+    pub descr: &'static str,
+    /// The number of elements of array fields, `None` for scalars.
+    pub len: Option<usize>,
+    /// Offset of the field within the type.
+    pub offset: usize,
+}
+
+/// Records that can be stored in `.npy` files as numpy structured arrays.
+///
+/// Implementations are usually generated with [npy_record], which takes the offsets from the
+/// real layout of the type.
+///
+/// [npy_record]: crate::npy_record
+pub trait NpyRecord: Sized {
+    /// The fields of the type, in declaration order.
+    const FIELDS: &'static [NpyField];
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Returns the size of a numpy format, such as `<u4` or `|V7`.
+fn descr_size(descr: &str) -> Option<usize> {
+    descr.trim_start_matches(['<', '>', '|', '=']).get(1..)?.parse().ok()
+}
+
+/// Returns the numpy structured dtype of `T`, with its padding as unnamed void fields.
+fn dtype<T: NpyRecord>() -> String {
+    let mut descr = String::from("[");
+    let mut offset = 0;
+
+    let push = |descr: &mut String, name: &str, format: &str, len: Option<usize>| {
+        if descr.len() > 1 {
+            descr.push_str(", ");
+        }
+
+        match len {
+            Some(len) => write!(descr, "('{name}', '{format}', ({len},))"),
+            None => write!(descr, "('{name}', '{format}')")
+        }.unwrap();
+    };
+
+    for field in T::FIELDS {
+        if field.offset > offset {
+            push(&mut descr, "", &format!("|V{}", field.offset - offset), None);
+        }
+
+        push(&mut descr, field.name, field.descr, field.len);
+        offset = field.offset + descr_size(field.descr).unwrap_or(0) * field.len.unwrap_or(1);
+    }
+
+    if size_of::<T>() > offset {
+        push(&mut descr, "", &format!("|V{}", size_of::<T>() - offset), None);
+    }
+
+    descr.push(']');
+    descr
+}
+
+/// Writes records as a version 1.0 `.npy` file, a one dimensional numpy structured array.
+///
+/// The dtype lists the fields of `T` in native byte order, with the padding between them as
+/// unnamed void fields, which is how numpy itself saves aligned structs, so the file loads with
+/// `numpy.load` with the same names, offsets and item size.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{npy_record, read_npy, write_npy};
+/// use std::io::{self, Cursor};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Reading {
+///     sensor: u16,
+///     value: f64
+/// }
+///
+/// npy_record!(Reading { sensor: u16, value: f64 });
+///
+/// fn main() -> io::Result<()> {
+///     let readings = [Reading { sensor: 1, value: 0.5 }, Reading { sensor: 2, value: 1.5 }];
+///
+///     let mut buffer = Vec::new();
+///     write_npy(&mut buffer, &readings)?;
+///
+///     assert_eq!(read_npy::<Reading>(&mut Cursor::new(buffer))?, readings);
+///     Ok(())
+/// }
+/// ```
+pub fn write_npy<T: NpyRecord>(writer: &mut impl Write, records: &[T]) -> io::Result<()> {
+    let shape = records.len().to_string();
+    let mut header = format!("{{'descr': {}, 'fortran_order': False, 'shape': ({shape},), }}", dtype::<T>());
+
+    header.extend(std::iter::repeat_n(' ', GROWTH_DIGITS.saturating_sub(shape.len())));
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.extend(std::iter::repeat_n(' ', ALIGN - unpadded % ALIGN));
+    header.push('\n');
+
+    let len = u16::try_from(header.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "dtype too large for a version 1.0 header"))?;
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(bytes::slice_as_bytes(records))
+}
+
+/// Reads the records of a `.npy` file holding a one dimensional structured array of `T`.
+///
+/// The dtype of the file must have the names, formats and offsets of the fields of `T`, in
+/// native byte order, and its item size must be the size of `T`. Otherwise, an `InvalidData`
+/// error describing the first difference is returned.
+pub fn read_npy<T: NpyRecord>(reader: &mut impl Read) -> io::Result<Vec<T>> {
+    let magic = reader.read_binary::<[u8; 8]>()?;
+
+    if magic[..6] != *MAGIC {
+        return Err(invalid_data("missing .npy magic string"));
+    }
+
+    let len = match magic[6] {
+        1 => reader.read_binary::<[u8; 2]>().map(|len| u16::from_le_bytes(len) as usize)?,
+        2 | 3 => reader.read_binary::<[u8; 4]>().map(|len| u32::from_le_bytes(len) as usize)?,
+        major => return Err(invalid_data(format!("unsupported .npy version {major}.{}", magic[7])))
+    };
+
+    let header = reader.read_binary_vec::<u8>(len)?;
+    let header = std::str::from_utf8(&header).map_err(|_| invalid_data(".npy header isn't valid text"))?;
+    let header = Parser::new(header).parse()?;
+
+    let entry = |key: &str| header.entry(key).ok_or_else(|| invalid_data(format!(".npy header misses '{key}'")));
+
+    let count = match entry("shape")? {
+        Value::List(dims) if dims.len() == 1 => dims[0].int()
+            .ok_or_else(|| invalid_data("invalid .npy shape"))?,
+        _ => return Err(invalid_data(".npy array isn't one dimensional"))
+    };
+
+    // A one dimensional array is laid out the same way in either order.
+    if !matches!(entry("fortran_order")?, Value::Bool) {
+        return Err(invalid_data("invalid .npy fortran_order"));
+    }
+
+    check_dtype::<T>(entry("descr")?)?;
+
+    reader.read_binary_vec(count)
+}
+
+/// Checks the dtype of a `.npy` file describes the fields of `T`.
+fn check_dtype<T: NpyRecord>(descr: &Value) -> io::Result<()> {
+    let Value::List(entries) = descr else {
+        return Err(invalid_data("dtype isn't a structured dtype"));
+    };
+
+    let mut fields = T::FIELDS.iter();
+    let mut offset = 0;
+
+    for entry in entries {
+        let (name, format, len) = match entry {
+            Value::List(items) => match items.as_slice() {
+                [Value::Str(name), Value::Str(format)] => (name.as_str(), format.as_str(), None),
+                [Value::Str(name), Value::Str(format), Value::List(shape)] if shape.len() == 1 =>
+                    (name.as_str(), format.as_str(), shape[0].int()),
+                _ => return Err(invalid_data(format!("unsupported dtype field {entry:?}")))
+            },
+            _ => return Err(invalid_data(format!("unsupported dtype field {entry:?}")))
+        };
+
+        let size = descr_size(format)
+            .ok_or_else(|| invalid_data(format!("unsupported format '{format}'")))?;
+
+        if name.is_empty() && format.trim_start_matches('|').starts_with('V') {
+            offset += size;
+            continue;
+        }
+
+        let Some(field) = fields.next() else {
+            return Err(invalid_data(format!("dtype has a field '{name}' beyond the fields of the type")));
+        };
+
+        if name != field.name {
+            return Err(invalid_data(format!("dtype field '{name}' found where '{}' was expected", field.name)));
+        }
+
+        let native = format.replacen('=', if cfg!(target_endian = "little") { "<" } else { ">" }, 1);
+
+        if native != field.descr && !(size == 1 && native[1..] == field.descr[1..]) || len != field.len {
+            return Err(invalid_data(format!(
+                "field '{name}' has format '{format}'{}, expected '{}'{}",
+                len.map(|len| format!(" x {len}")).unwrap_or_default(),
+                field.descr,
+                field.len.map(|len| format!(" x {len}")).unwrap_or_default()
+            )));
+        }
+
+        if offset != field.offset {
+            return Err(invalid_data(format!("field '{name}' is at offset {offset}, expected {}", field.offset)));
+        }
+
+        offset += size * len.unwrap_or(1);
+    }
+
+    if let Some(field) = fields.next() {
+        return Err(invalid_data(format!("dtype misses the field '{}'", field.name)));
+    }
+
+    if offset != size_of::<T>() {
+        return Err(invalid_data(format!("dtype item size {offset} doesn't match the size {} of the type", size_of::<T>())));
+    }
+
+    Ok(())
+}
+
+/// A value of the Python literal making up a `.npy` header.
+#[derive(Debug)]
+enum Value {
+    Str(String),
+    Int(usize),
+    /// `True` or `False`, whose value is never needed.
+    Bool,
+    /// Lists and tuples.
+    List(Vec<Value>),
+    Dict(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn int(&self) -> Option<usize> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    fn entry(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None
+        }
+    }
+}
+
+/// Parses the subset of Python literals found in `.npy` headers.
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    fn parse(mut self) -> io::Result<Value> {
+        let value = self.value()?;
+
+        match self.input.trim() {
+            "" => Ok(value),
+            rest => Err(invalid_data(format!("unexpected '{rest}' after the .npy header")))
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.input = self.input.trim_start();
+
+        match self.input.strip_prefix(token) {
+            Some(rest) => {
+                self.input = rest;
+                true
+            },
+            None => false
+        }
+    }
+
+    fn value(&mut self) -> io::Result<Value> {
+        if self.eat("{") {
+            let mut entries = Vec::new();
+
+            while !self.eat("}") {
+                let Value::Str(key) = self.value()? else {
+                    return Err(invalid_data(".npy header keys must be strings"));
+                };
+
+                if !self.eat(":") {
+                    return Err(invalid_data(format!("expected ':' after '{key}' in the .npy header")));
+                }
+
+                entries.push((key, self.value()?));
+                self.separator("}")?;
+            }
+
+            return Ok(Value::Dict(entries));
+        }
+
+        for (open, close) in [("[", "]"), ("(", ")")] {
+            if self.eat(open) {
+                let mut items = Vec::new();
+
+                while !self.eat(close) {
+                    items.push(self.value()?);
+                    self.separator(close)?;
+                }
+
+                return Ok(Value::List(items));
+            }
+        }
+
+        if self.eat("True") || self.eat("False") {
+            return Ok(Value::Bool);
+        }
+
+        for quote in ['\'', '"'] {
+            if self.eat(&quote.to_string()) {
+                let end = self.input.find(quote)
+                    .ok_or_else(|| invalid_data("unterminated string in the .npy header"))?;
+                let value = self.input[..end].to_string();
+                self.input = &self.input[end + 1..];
+
+                return Ok(Value::Str(value));
+            }
+        }
+
+        let digits = self.input.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.input.len());
+        let value = self.input[..digits].parse()
+            .map_err(|_| invalid_data(format!("unexpected '{}' in the .npy header", self.input.chars().take(16).collect::<String>())))?;
+        self.input = &self.input[digits..];
+
+        Ok(Value::Int(value))
+    }
+
+    /// Consumes the comma after an item, which can only be left out before `close`.
+    fn separator(&mut self, close: &str) -> io::Result<()> {
+        if self.eat(",") || self.input.trim_start().starts_with(close) {
+            return Ok(());
+        }
+
+        Err(invalid_data(format!("expected ',' or '{close}' in the .npy header")))
+    }
+}
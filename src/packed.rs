@@ -0,0 +1,64 @@
+use std::{error::Error, fmt, io::{self, Read, Write}};
+
+/// Records written field by field with no padding, whose trailing fields may be left out
+/// depending on the earlier ones, read with [read_binary_packed] and written with
+/// [write_binary_packed].
+///
+/// This trait should be implemented with the [packed] macro.
+///
+/// [read_binary_packed]: crate::BinaryRead::read_binary_packed
+/// [write_binary_packed]: crate::BinaryWrite::write_binary_packed
+/// [packed]: crate::packed
+pub trait Packed: Sized {
+    /// Writes each field present in turn.
+    ///
+    /// A conditional field that is `Some` when its condition doesn't hold, or `None` when it
+    /// does, is reported as an `InvalidInput` error carrying a [PresenceMismatch].
+    ///
+    /// [PresenceMismatch]: PresenceMismatch
+    fn write_packed<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Reads each field in turn, checking the condition of each conditional field over the ones
+    /// read before it.
+    fn read_packed<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+/// Error carried by the `InvalidInput` [io::Error] returned when writing a [Packed] record whose
+/// conditional field doesn't agree with its condition.
+///
+/// [io::Error]: std::io::Error
+/// [Packed]: Packed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PresenceMismatch {
+    /// Name of the record being written.
+    pub name: &'static str,
+    /// Name of the conditional field.
+    pub field: &'static str,
+    /// Whether the condition of the field holds.
+    pub expected: bool,
+}
+
+impl fmt::Display for PresenceMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "field `{}` of `{}` must be {} since its condition is {}",
+            self.field, self.name, if self.expected { "Some" } else { "None" }, self.expected
+        )
+    }
+}
+
+impl Error for PresenceMismatch {}
+
+impl From<PresenceMismatch> for io::Error {
+    fn from(error: PresenceMismatch) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
+/// Gives the condition of a conditional field the type of a predicate over the record.
+#[doc(hidden)]
+pub fn condition<T>(condition: impl Fn(&T) -> bool) -> impl Fn(&T) -> bool {
+    condition
+}
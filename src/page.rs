@@ -0,0 +1,230 @@
+use std::{fmt, io, marker::PhantomData, mem::{align_of, size_of}, ptr};
+use crate::{bytes, crc::crc32_excluding, Validate};
+
+/// Size of the bookkeeping header every page starts with.
+const BOOKKEEPING_SIZE: usize = 16;
+/// Offsets of the fields of the bookkeeping header.
+const COUNT_OFFSET: usize = 0;
+const FREE_OFFSET: usize = 4;
+const CHECKSUM_OFFSET: usize = 8;
+
+/// Index of a record within a [Page].
+///
+/// [Page]: Page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlotIdx(pub u32);
+
+/// A fixed-size page of `SIZE` bytes holding a header `H` followed by packed records of `T`,
+/// the building block of page based storage.
+///
+/// The page starts with 16 bytes of bookkeeping: the number of records, the offset of the
+/// first free byte and a CRC-32 of the used bytes of the page, skipping the CRC itself, as
+/// native `u32`s, followed by four reserved zero bytes. Then come the header `H` and the
+/// records, laid out like an array of `T`, each one at the next offset multiple of its
+/// alignment. The bytes past the last record are zero.
+///
+/// The checksum is kept up to date on every change, so [as_bytes] can be written or mapped at
+/// any time, and pages read back with [from_bytes] are checked against it, along with the
+/// counts and every record. A page whose header doesn't fit in `SIZE` bytes fails to compile.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{Page, SlotIdx};
+///
+/// #[repr(C)]
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Leaf {
+///     next_page: u32,
+///     level: u32
+/// }
+///
+/// binext::validate!(Leaf { next_page: u32, level: u32 });
+///
+/// let mut page = Page::<Leaf, u64, 4096>::new(Leaf { next_page: 7, level: 0 });
+/// let slot = page.push(&42).unwrap();
+/// assert_eq!(page.get(slot), Some(42));
+///
+/// let loaded = Page::<Leaf, u64, 4096>::from_bytes(page.as_bytes()).unwrap();
+/// assert_eq!(loaded.header(), Leaf { next_page: 7, level: 0 });
+/// assert_eq!(loaded.iter().collect::<Vec<_>>(), [42]);
+/// assert_eq!(loaded.remaining_capacity(), (4096 - 24) / 8 - 1);
+/// ```
+///
+/// [as_bytes]: Page::as_bytes
+/// [from_bytes]: Page::from_bytes
+#[derive(Clone)]
+pub struct Page<H, T, const SIZE: usize> {
+    bytes: [u8; SIZE],
+    _marker: PhantomData<(H, T)>,
+}
+
+impl<H: Copy, T: Copy, const SIZE: usize> Page<H, T, SIZE> {
+    /// Offset of the header `H`.
+    pub const HEADER_OFFSET: usize = BOOKKEEPING_SIZE.next_multiple_of(align_of::<H>());
+
+    /// Offset of the first record.
+    pub const RECORDS_OFFSET: usize = (Self::HEADER_OFFSET + size_of::<H>()).next_multiple_of(align_of::<T>());
+
+    /// Number of records that fit in a page.
+    pub const CAPACITY: usize = {
+        assert!(size_of::<T>() > 0, "pages can't hold zero sized records");
+        assert!(SIZE <= u32::MAX as usize, "pages can't be larger than 4 GiB");
+        assert!(Self::RECORDS_OFFSET <= SIZE, "the header doesn't fit in the page");
+
+        (SIZE - Self::RECORDS_OFFSET) / size_of::<T>()
+    };
+
+    /// Creates an empty page with the given header.
+    pub fn new(header: H) -> Self {
+        // Checks the layout at compile time.
+        let _ = Self::CAPACITY;
+        let mut page = Self { bytes: [0; SIZE], _marker: PhantomData };
+
+        page.set_u32(FREE_OFFSET, Self::RECORDS_OFFSET as u32);
+        page.set_header(header);
+        page
+    }
+
+    /// Loads a page from its bytes, as returned by [as_bytes], checking it.
+    ///
+    /// Bytes of a length other than `SIZE`, not matching their checksum, with counts that don't
+    /// fit in the page or disagree with each other, or holding an invalid header or record are
+    /// reported as an `InvalidData` error.
+    ///
+    /// [as_bytes]: Page::as_bytes
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self>
+    where
+        H: Validate,
+        T: Validate
+    {
+        let bytes: [u8; SIZE] = bytes.try_into()
+            .map_err(|_| invalid(format!("{} bytes don't make a page of {SIZE}", bytes.len())))?;
+
+        let page = Self { bytes, _marker: PhantomData };
+        let (count, free) = (page.u32_at(COUNT_OFFSET) as usize, page.u32_at(FREE_OFFSET) as usize);
+
+        if count > Self::CAPACITY {
+            return Err(invalid(format!("page holds {count} records, more than the {} that fit", Self::CAPACITY)));
+        }
+
+        if free != Self::RECORDS_OFFSET + count * size_of::<T>() {
+            return Err(invalid(format!("free offset {free} doesn't follow the {count} records of the page")));
+        }
+
+        let (stored, computed) = (page.u32_at(CHECKSUM_OFFSET), crc32_excluding(&page.bytes[..free], CHECKSUM_OFFSET));
+
+        if stored != computed {
+            return Err(invalid(format!("page checksum {stored:#010x} doesn't match its bytes, {computed:#010x}")));
+        }
+
+        H::validate_bytes(&page.bytes[Self::HEADER_OFFSET..Self::HEADER_OFFSET + size_of::<H>()])
+            .map_err(|e| invalid(format!("invalid page header: {e}")))?;
+
+        for index in 0..count {
+            T::validate_bytes(page.record_bytes(index))
+                .map_err(|e| invalid(format!("invalid record {index}: {e}")))?;
+        }
+
+        Ok(page)
+    }
+
+    /// Returns the bytes of the page, to be written to a file or copied into a mapping.
+    pub fn as_bytes(&self) -> &[u8; SIZE] {
+        &self.bytes
+    }
+
+    /// Returns the header.
+    pub fn header(&self) -> H {
+        // SAFETY: the header bytes always hold a valid H, written by set_header or validated.
+        unsafe { ptr::read_unaligned(self.bytes[Self::HEADER_OFFSET..].as_ptr() as *const H) }
+    }
+
+    /// Replaces the header.
+    pub fn set_header(&mut self, header: H) {
+        let offset = Self::HEADER_OFFSET;
+        self.bytes[offset..offset + size_of::<H>()].copy_from_slice(bytes::as_bytes(&header));
+        self.stamp();
+    }
+
+    /// Appends a record, returning its slot, or `None` if the page is full.
+    pub fn push(&mut self, item: &T) -> Option<SlotIdx> {
+        let index = self.len();
+
+        if index >= Self::CAPACITY {
+            return None;
+        }
+
+        let offset = Self::RECORDS_OFFSET + index * size_of::<T>();
+        self.bytes[offset..offset + size_of::<T>()].copy_from_slice(bytes::as_bytes(item));
+        self.set_u32(COUNT_OFFSET, index as u32 + 1);
+        self.set_u32(FREE_OFFSET, (offset + size_of::<T>()) as u32);
+        self.stamp();
+
+        Some(SlotIdx(index as u32))
+    }
+
+    /// Returns the record at `slot`, or `None` if there's no record there.
+    pub fn get(&self, slot: SlotIdx) -> Option<T> {
+        let index = slot.0 as usize;
+        (index < self.len()).then(|| self.record(index))
+    }
+
+    /// Returns an iterator over the records, in slot order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + '_ {
+        (0..self.len()).map(|index| self.record(index))
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.u32_at(COUNT_OFFSET) as usize
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of records that can still be pushed.
+    pub fn remaining_capacity(&self) -> usize {
+        Self::CAPACITY - self.len()
+    }
+
+    fn record_bytes(&self, index: usize) -> &[u8] {
+        let offset = Self::RECORDS_OFFSET + index * size_of::<T>();
+        &self.bytes[offset..offset + size_of::<T>()]
+    }
+
+    fn record(&self, index: usize) -> T {
+        // SAFETY: records below the count always hold a valid T, pushed or validated.
+        unsafe { ptr::read_unaligned(self.record_bytes(index).as_ptr() as *const T) }
+    }
+
+    fn u32_at(&self, offset: usize) -> u32 {
+        u32::from_ne_bytes(self.bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn set_u32(&mut self, offset: usize, value: u32) {
+        self.bytes[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+    }
+
+    /// Updates the checksum after a change.
+    fn stamp(&mut self) {
+        let free = self.u32_at(FREE_OFFSET) as usize;
+        let crc = crc32_excluding(&self.bytes[..free], CHECKSUM_OFFSET);
+        self.set_u32(CHECKSUM_OFFSET, crc);
+    }
+}
+
+impl<H: Copy + fmt::Debug, T: Copy + fmt::Debug, const SIZE: usize> fmt::Debug for Page<H, T, SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Page")
+            .field("header", &self.header())
+            .field("records", &self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
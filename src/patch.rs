@@ -0,0 +1,281 @@
+use std::{io::{self, Cursor}, mem::{align_of, size_of}};
+use crate::{bytes, BinaryRead, Columns, Validate};
+
+/// The bytes of a record that changed, at `offset` within it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeltaRange {
+    /// Offset of the first changed byte.
+    pub offset: usize,
+    /// The new value of the bytes.
+    pub bytes: Vec<u8>,
+}
+
+/// The bytes that changed between two records of the same type, made with [make_delta] and
+/// applied with [apply_delta].
+///
+/// Deltas carry the [layout_fingerprint] of the type they were made for, so applying one to a
+/// record of another type, or of an older version of the type, fails instead of scrambling it.
+/// They are serialized with [to_bytes] into a payload meant to be sent as a frame through a
+/// [FramedWriter], whose checksum protects it on the way, and parsed back with [from_bytes].
+///
+/// The serialized form is the fingerprint as a `u64`, the size of the record and the number of
+/// ranges as `u32`s, then the offset and length of each range as `u32`s followed by its bytes,
+/// every integer in little endian byte order.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{apply_delta, columns, make_delta, validate, Delta, FramedReader, FramedWriter};
+/// use std::io::{self, Cursor};
+///
+/// #[repr(C)]
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Sensor {
+///     id: u32,
+///     readings: [f32; 256]
+/// }
+///
+/// columns!(Sensor { id: u32, readings: [f32; 256] });
+/// validate!(Sensor { id: u32, readings: [f32; 256] });
+///
+/// fn main() -> io::Result<()> {
+///     let old = Sensor { id: 1, readings: [0.0; 256] };
+///     let mut new = old.clone();
+///     new.readings[100] = 2.5;
+///
+///     let delta = make_delta(&old, &new);
+///     assert!(delta.changed_bytes() <= 4);
+///
+///     let mut writer = FramedWriter::new(Vec::new());
+///     writer.write_frame_bytes(1, &delta.to_bytes())?;
+///
+///     let mut reader = FramedReader::new(Cursor::new(writer.into_inner()));
+///     let (_, payload) = reader.next_frame()?.unwrap();
+///
+///     assert_eq!(apply_delta(&old, &Delta::from_bytes(&payload)?)?, new);
+///     Ok(())
+/// }
+/// ```
+///
+/// [make_delta]: make_delta
+/// [apply_delta]: apply_delta
+/// [layout_fingerprint]: layout_fingerprint
+/// [to_bytes]: Delta::to_bytes
+/// [FramedWriter]: crate::FramedWriter
+/// [from_bytes]: Delta::from_bytes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Delta {
+    fingerprint: u64,
+    size: usize,
+    ranges: Vec<DeltaRange>,
+}
+
+impl Delta {
+    /// Returns the fingerprint of the type the delta was made for.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Returns the size of the records the delta was made for.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the changed ranges, sorted by offset.
+    pub fn ranges(&self) -> &[DeltaRange] {
+        &self.ranges
+    }
+
+    /// Returns whether no byte changed.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of changed bytes.
+    pub fn changed_bytes(&self) -> usize {
+        self.ranges.iter().map(|range| range.bytes.len()).sum()
+    }
+
+    /// Serializes the delta.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(16 + self.ranges.len() * 8 + self.changed_bytes());
+        out.extend_from_slice(&self.fingerprint.to_le_bytes());
+        // Records of more than 4 GiB can't be described by the format, and neither are made by
+        // make_delta, which needs them in memory twice.
+        out.extend_from_slice(&(self.size as u32).to_le_bytes());
+        out.extend_from_slice(&(self.ranges.len() as u32).to_le_bytes());
+
+        for range in &self.ranges {
+            out.extend_from_slice(&(range.offset as u32).to_le_bytes());
+            out.extend_from_slice(&(range.bytes.len() as u32).to_le_bytes());
+            out.extend_from_slice(&range.bytes);
+        }
+
+        out
+    }
+
+    /// Parses a delta serialized with [to_bytes].
+    ///
+    /// Truncated or trailing bytes, and ranges out of the bounds of the record, overlapping or
+    /// out of order, are reported as `InvalidData` errors.
+    ///
+    /// [to_bytes]: Delta::to_bytes
+    pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+        let fingerprint = u64::from_le_bytes(take(&mut bytes)?);
+        let size = u32::from_le_bytes(take(&mut bytes)?) as usize;
+        let count = u32::from_le_bytes(take(&mut bytes)?);
+
+        let mut ranges = Vec::new();
+        let mut end = 0;
+
+        for index in 0..count {
+            let offset = u32::from_le_bytes(take(&mut bytes)?) as usize;
+            let len = u32::from_le_bytes(take(&mut bytes)?) as usize;
+
+            if len == 0 || offset < end || offset.checked_add(len).is_none_or(|range_end| range_end > size) {
+                return Err(invalid(format!(
+                    "range {index} of {len} bytes at offset {offset} is empty, out of order or \
+                    out of a {size} bytes record"
+                )));
+            }
+
+            if bytes.len() < len {
+                return Err(invalid("delta is truncated".to_string()));
+            }
+
+            let (range, rest) = bytes.split_at(len);
+            bytes = rest;
+            end = offset + len;
+            ranges.push(DeltaRange { offset, bytes: range.to_vec() });
+        }
+
+        if !bytes.is_empty() {
+            return Err(invalid(format!("{} trailing bytes after the delta", bytes.len())));
+        }
+
+        Ok(Self { fingerprint, size, ranges })
+    }
+}
+
+/// Splits an integer off the start of `bytes`.
+fn take<const N: usize>(bytes: &mut &[u8]) -> io::Result<[u8; N]> {
+    match bytes.split_first_chunk::<N>() {
+        Some((head, rest)) => {
+            *bytes = rest;
+            Ok(*head)
+        },
+        None => Err(invalid("delta is truncated".to_string()))
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Returns a fingerprint of the layout of `T`: its size, alignment and the name, offset and size
+/// of each of its fields.
+///
+/// It's stable across builds and platforms as long as the layout is the same, and changes when
+/// fields are added, removed, renamed, resized or moved.
+pub fn layout_fingerprint<T: Columns>() -> u64 {
+    // 64 bits FNV-1a.
+    fn hash(state: u64, bytes: &[u8]) -> u64 {
+        bytes.iter().fold(state, |state, &byte| (state ^ u64::from(byte)).wrapping_mul(0x100000001b3))
+    }
+
+    let mut state = 0xcbf29ce484222325;
+    state = hash(state, &(size_of::<T>() as u64).to_le_bytes());
+    state = hash(state, &(align_of::<T>() as u64).to_le_bytes());
+
+    for field in T::FIELDS {
+        state = hash(state, &(field.name.len() as u64).to_le_bytes());
+        state = hash(state, field.name.as_bytes());
+        state = hash(state, &(field.offset as u64).to_le_bytes());
+        state = hash(state, &(field.size as u64).to_le_bytes());
+    }
+
+    state
+}
+
+/// Returns which bytes of `T` belong to a field, as opposed to padding.
+fn field_bytes<T: Columns>() -> Vec<bool> {
+    let mut mask = vec![false; size_of::<T>()];
+
+    for field in T::FIELDS {
+        mask[field.offset..field.offset + field.size].fill(true);
+    }
+
+    mask
+}
+
+/// Returns the bytes of the fields of `new` that differ from those of `old`, as a [Delta] to
+/// apply to `old` with [apply_delta].
+///
+/// Each run of consecutive changed bytes becomes one range, so changing a field produces at most
+/// as many bytes as the field has. Padding bytes are never compared, as their contents are
+/// arbitrary, and so never appear in the delta.
+///
+/// [Delta]: Delta
+/// [apply_delta]: apply_delta
+pub fn make_delta<T: Columns>(old: &T, new: &T) -> Delta {
+    let (old, new) = (bytes::as_bytes(old), bytes::as_bytes(new));
+    let mask = field_bytes::<T>();
+    let mut ranges: Vec<DeltaRange> = Vec::new();
+
+    for offset in 0..mask.len() {
+        if !mask[offset] || old[offset] == new[offset] {
+            continue;
+        }
+
+        match ranges.last_mut() {
+            Some(range) if range.offset + range.bytes.len() == offset => range.bytes.push(new[offset]),
+            _ => ranges.push(DeltaRange { offset, bytes: vec![new[offset]] })
+        }
+    }
+
+    Delta {
+        fingerprint: layout_fingerprint::<T>(),
+        size: size_of::<T>(),
+        ranges,
+    }
+}
+
+/// Applies `delta` to `base`, returning the new record.
+///
+/// A delta made for a type with another [layout_fingerprint] or size, or with ranges out of the
+/// bounds of the record or covering padding, is reported as an `InvalidData` error, and so is a
+/// result that isn't a valid `T`.
+///
+/// [layout_fingerprint]: layout_fingerprint
+pub fn apply_delta<T: Columns + Validate>(base: &T, delta: &Delta) -> io::Result<T> {
+    if delta.fingerprint != layout_fingerprint::<T>() || delta.size != size_of::<T>() {
+        return Err(invalid(format!(
+            "delta was made for a {} bytes type with fingerprint {:#018x}, not for `{}`",
+            delta.size,
+            delta.fingerprint,
+            std::any::type_name::<T>()
+        )));
+    }
+
+    let mask = field_bytes::<T>();
+    let mut bytes = bytes::as_bytes(base).to_vec();
+
+    for range in &delta.ranges {
+        let covered = range.offset.checked_add(range.bytes.len())
+            .and_then(|end| mask.get(range.offset..end));
+
+        match covered {
+            Some(covered) if covered.iter().all(|&field| field) => {
+                bytes[range.offset..range.offset + range.bytes.len()].copy_from_slice(&range.bytes)
+            },
+            _ => return Err(invalid(format!(
+                "range of {} bytes at offset {} isn't within the fields of `{}`",
+                range.bytes.len(),
+                range.offset,
+                std::any::type_name::<T>()
+            )))
+        }
+    }
+
+    Cursor::new(bytes).read_binary_validated()
+}
@@ -0,0 +1,103 @@
+use std::io::{self, Write};
+use crate::BinaryWrite;
+
+/// A writer flushing the underlying one every `n` records.
+///
+/// Flushing after every record is safe but slow, and never flushing leaves an unbounded amount
+/// of data in buffers, so this sits in between, like the flush interval of a write-ahead log.
+/// Records are written with [write_record]; bytes written through the [Write] implementation
+/// pass through without counting as records. [flush] forces a flush and restarts the count.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::PeriodicFlushWriter;
+/// use std::{fs::File, io::{self, BufWriter}};
+///
+/// fn main() -> io::Result<()> {
+///     let file = BufWriter::new(File::create("wal.bin")?);
+///     let mut writer = PeriodicFlushWriter::new(file, 64);
+///
+///     for sequence in 0..1000u64 {
+///         writer.write_record(&sequence)?;
+///     }
+///
+///     writer.into_inner().into_inner()?.sync_data()
+/// }
+/// ```
+///
+/// [write_record]: PeriodicFlushWriter::write_record
+/// [flush]: PeriodicFlushWriter::flush
+pub struct PeriodicFlushWriter<W> {
+    inner: W,
+    every: usize,
+    pending: usize,
+}
+
+impl<W: Write> PeriodicFlushWriter<W> {
+    /// Creates a new writer over `inner`, flushing it every `every` records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is zero.
+    pub fn new(inner: W, every: usize) -> Self {
+        assert!(every > 0, "records must be flushed every one or more records");
+
+        Self {
+            inner,
+            every,
+            pending: 0,
+        }
+    }
+
+    /// Writes a record, flushing the underlying writer if it's the `n`th since the last flush.
+    pub fn write_record<T>(&mut self, item: &T) -> io::Result<()> {
+        self.inner.write_binary(item)?;
+        self.pending += 1;
+
+        if self.pending == self.every {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns how many records are written between flushes.
+    pub fn every(&self) -> usize {
+        self.every
+    }
+
+    /// Returns how many records were written since the last flush.
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one without flushing it.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for PeriodicFlushWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    /// Flushes the underlying writer and restarts the record count.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        self.pending = 0;
+
+        Ok(())
+    }
+}
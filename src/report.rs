@@ -0,0 +1,327 @@
+use std::{fmt, fs::File, io::{self, BufReader, Read}, mem::size_of, path::Path};
+use crate::{iter::read_up_to, Validate, ValidationError};
+
+/// Options of [validate_record_file_with]: the header expected before the records, and whether
+/// every record is checked.
+///
+/// The header holds the fields that are set, in this order and in little endian byte order: the
+/// magic as a `u32`, the version as a `u16` and the layout fingerprint as a `u64`. With a magic
+/// and a version only, that's the prefix [open_binary_file] checks. Records start right after
+/// it. Without any of them, there's no header.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{columns, layout_fingerprint, validate_record_file_with, RecordFileOptions};
+/// use std::io;
+///
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     value: u32
+/// }
+///
+/// columns!(Record { id: u64, value: u32 });
+/// binext::validate!(Record { id: u64, value: u32 });
+///
+/// fn main() -> io::Result<()> {
+///     let options = RecordFileOptions::new()
+///         .magic(u32::from_le_bytes(*b"RECS"))
+///         .version(3)
+///         .fingerprint(layout_fingerprint::<Record>())
+///         .deep(true);
+///
+///     let report = validate_record_file_with::<Record>("records.bin", options)?;
+///
+///     if !report.is_clean() {
+///         eprintln!("rejecting records.bin: {report}");
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [validate_record_file_with]: validate_record_file_with
+/// [open_binary_file]: crate::open_binary_file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RecordFileOptions {
+    magic: Option<u32>,
+    version: Option<u16>,
+    fingerprint: Option<u64>,
+    deep: bool,
+}
+
+impl RecordFileOptions {
+    /// Creates the default options, without a header nor record checks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the magic the header starts with.
+    pub fn magic(mut self, magic: u32) -> Self {
+        self.magic = Some(magic);
+        self
+    }
+
+    /// Sets the version the header holds after the magic.
+    pub fn version(mut self, version: u16) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Sets the layout fingerprint the header ends with, usually from [layout_fingerprint].
+    ///
+    /// [layout_fingerprint]: crate::layout_fingerprint
+    pub fn fingerprint(mut self, fingerprint: u64) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// Sets whether every record is validated, checksum fields included, which reads the whole
+    /// file.
+    pub fn deep(mut self, deep: bool) -> Self {
+        self.deep = deep;
+        self
+    }
+
+    fn header_size(&self) -> usize {
+        self.magic.map_or(0, |_| size_of::<u32>())
+            + self.version.map_or(0, |_| size_of::<u16>())
+            + self.fingerprint.map_or(0, |_| size_of::<u64>())
+    }
+
+    /// Checks the header, whose missing bytes, if the file is shorter, match nothing.
+    fn check_header(&self, mut header: &[u8]) -> HeaderCheck {
+        fn check<const N: usize>(header: &mut &[u8], expected: [u8; N]) -> bool {
+            let matches = header.get(..N) == Some(&expected[..]);
+            *header = header.get(N..).unwrap_or_default();
+            matches
+        }
+
+        HeaderCheck {
+            magic: self.magic.map(|magic| check(&mut header, magic.to_le_bytes())),
+            version: self.version.map(|version| check(&mut header, version.to_le_bytes())),
+            fingerprint: self.fingerprint.map(|fingerprint| check(&mut header, fingerprint.to_le_bytes())),
+        }
+    }
+}
+
+/// Which fields of the header described by [RecordFileOptions] match, each `None` if it wasn't
+/// checked.
+///
+/// [RecordFileOptions]: RecordFileOptions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HeaderCheck {
+    /// Whether the magic matches.
+    pub magic: Option<bool>,
+    /// Whether the version matches.
+    pub version: Option<bool>,
+    /// Whether the layout fingerprint matches.
+    pub fingerprint: Option<bool>,
+}
+
+impl HeaderCheck {
+    /// Returns whether every checked field matches.
+    pub fn matches(&self) -> bool {
+        [self.magic, self.version, self.fingerprint].into_iter().all(|check| check != Some(false))
+    }
+}
+
+impl fmt::Display for HeaderCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let checks = [("magic", self.magic), ("version", self.version), ("fingerprint", self.fingerprint)];
+        let mut first = true;
+
+        for (name, check) in checks {
+            if let Some(matches) = check {
+                let separator = if first { "" } else { ", " };
+                write!(f, "{separator}{name} {}", if matches { "ok" } else { "mismatch" })?;
+                first = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Structural summary of a file of fixed-size records, returned by [validate_record_file],
+/// [validate_record_file_deep] and [validate_record_file_with].
+///
+/// [validate_record_file]: validate_record_file
+/// [validate_record_file_deep]: validate_record_file_deep
+/// [validate_record_file_with]: validate_record_file_with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    /// Total size of the file in bytes.
+    pub total_size: u64,
+    /// Size of the header before the records, zero if there's none.
+    pub header_size: u64,
+    /// What matched in the header, if one was checked with [validate_record_file_with].
+    ///
+    /// [validate_record_file_with]: validate_record_file_with
+    pub header: Option<HeaderCheck>,
+    /// Size of each record in bytes.
+    pub record_size: usize,
+    /// Number of complete records in the file.
+    pub record_count: u64,
+    /// Bytes after the last complete record, a non zero value usually means the file was
+    /// truncated or is corrupted.
+    pub trailing_bytes: u64,
+    /// Whether the records themselves were checked, and not only the file size.
+    pub deep: bool,
+    /// Number of records that failed validation, always zero if the check wasn't deep.
+    pub invalid_records: u64,
+    /// Index of the first record that failed validation, and why.
+    pub first_invalid: Option<(u64, ValidationError)>,
+}
+
+impl FileReport {
+    pub(crate) fn new(total_size: u64, record_size: usize) -> Self {
+        let (record_count, trailing_bytes) = match record_size as u64 {
+            0 => (0, total_size),
+            size => (total_size / size, total_size % size)
+        };
+
+        Self {
+            total_size,
+            header_size: 0,
+            header: None,
+            record_size,
+            record_count,
+            trailing_bytes,
+            deep: false,
+            invalid_records: 0,
+            first_invalid: None,
+        }
+    }
+
+    /// Returns whether no issues were detected in the file.
+    pub fn is_clean(&self) -> bool {
+        self.trailing_bytes == 0 && self.invalid_records == 0 && self.header.is_none_or(|header| header.matches())
+    }
+
+    pub(crate) fn record_invalid(&mut self, index: u64, error: ValidationError) {
+        self.invalid_records += 1;
+        self.first_invalid.get_or_insert((index, error));
+    }
+}
+
+impl fmt::Display for FileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes: {} records of {} bytes, {} trailing bytes",
+            self.total_size, self.record_count, self.record_size, self.trailing_bytes
+        )?;
+
+        if let Some(header) = &self.header {
+            write!(f, ", {} header bytes ({header})", self.header_size)?;
+        }
+
+        if self.deep {
+            write!(f, ", {} invalid records", self.invalid_records)?;
+
+            if let Some((index, error)) = &self.first_invalid {
+                write!(f, " (first at record {index}: {error})")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the records of `T` read from `reader`, which must hold exactly the complete records
+/// counted in `report`, marking the report as deep.
+pub(crate) fn check_records<T: Validate, R: Read + ?Sized>(reader: &mut R, report: &mut FileReport) -> io::Result<()> {
+    let mut record = vec![0u8; size_of::<T>()];
+
+    for index in 0..report.record_count {
+        reader.read_exact(&mut record)?;
+
+        if let Err(error) = T::validate_bytes(&record) {
+            report.record_invalid(index, error);
+        }
+    }
+
+    report.deep = true;
+    Ok(())
+}
+
+/// Performs a cheap structural check over a file of records of `T`.
+///
+/// Only the file metadata is read, so this is fast even for huge files. The report tells how
+/// many records the file holds and whether there are trailing bytes after the last complete
+/// one. See [validate_record_file_deep] to also check every record.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::io;
+///
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     value: u32
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let report = binext::validate_record_file::<Record>("records.bin")?;
+///
+///     if !report.is_clean() {
+///         eprintln!("rejecting records.bin: {report}");
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [validate_record_file_deep]: validate_record_file_deep
+pub fn validate_record_file<T>(path: impl AsRef<Path>) -> io::Result<FileReport> {
+    let total_size = std::fs::metadata(path)?.len();
+    Ok(FileReport::new(total_size, size_of::<T>()))
+}
+
+/// Performs a structural check over a file of records of `T`, validating every record.
+///
+/// This reads the whole file, running [Validate::validate_bytes] over each complete record, and
+/// reports how many of them are invalid along with the first failure.
+///
+/// [Validate::validate_bytes]: Validate::validate_bytes
+pub fn validate_record_file_deep<T: Validate>(path: impl AsRef<Path>) -> io::Result<FileReport> {
+    let file = File::open(path)?;
+    let mut report = FileReport::new(file.metadata()?.len(), size_of::<T>());
+
+    check_records::<T, _>(&mut BufReader::new(file), &mut report)?;
+    Ok(report)
+}
+
+/// Performs a structural check over a file of records of `T` following a header, checking the
+/// header fields and, if asked to, every record, as set in `options`.
+///
+/// Only the file metadata and the header are read, unless the check is deep. A file too short
+/// for its header reports the fields it doesn't hold as mismatched, and no records. See
+/// [RecordFileOptions] for an example.
+///
+/// [RecordFileOptions]: RecordFileOptions
+pub fn validate_record_file_with<T: Validate>(path: impl AsRef<Path>, options: RecordFileOptions) -> io::Result<FileReport> {
+    let file = File::open(path)?;
+    let total_size = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    let mut header = vec![0; options.header_size()];
+    let read = read_up_to(&mut reader, &mut header)?;
+
+    let mut report = FileReport::new(total_size - read as u64, size_of::<T>());
+    report.total_size = total_size;
+
+    if !header.is_empty() {
+        report.header_size = header.len() as u64;
+        report.header = Some(options.check_header(&header[..read]));
+    }
+
+    if options.deep {
+        check_records::<T, _>(&mut reader, &mut report)?;
+    }
+
+    Ok(report)
+}
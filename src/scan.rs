@@ -0,0 +1,120 @@
+use std::{io::{self, Read}, mem::size_of, ptr};
+use crate::Validate;
+
+/// Size of the chunks read at once when scanning a reader.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Panics if records of `T` can't be scanned for with the given step.
+fn check_scan<T>(step: usize) {
+    assert!(step > 0, "the scan step can't be zero");
+    assert!(size_of::<T>() > 0, "zero sized types can't be scanned for");
+}
+
+/// Returns the `T` held by `window` if its bytes are a valid `T`.
+fn carve<T: Validate>(window: &[u8]) -> Option<T> {
+    T::is_valid_bytes(window)
+        // SAFETY: the window holds size_of::<T>() bytes forming a valid T, possibly unaligned.
+        .then(|| unsafe { ptr::read_unaligned(window.as_ptr() as *const T) })
+}
+
+/// Finds every offset of `haystack` holding a valid `T`, returning them along with the records.
+///
+/// A window of `size_of::<T>()` bytes is slid over the haystack, moving `step` bytes at a time:
+/// `1` finds records at any offset, while `size_of::<T>()` only looks at offsets multiple of it.
+/// Each window is checked with [Validate::validate_bytes], which for types implemented with
+/// [validate] stops at the first invalid field, so types with magic numbers or tight ranges are
+/// both fast to scan for and unlikely to be found by chance. Matches may overlap.
+///
+/// Use [scan_reader] for sources that don't fit in memory.
+///
+/// # Panics
+///
+/// Panics if `step` is zero or `T` is zero sized.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{scan_for, validate};
+///
+/// #[repr(C)]
+/// #[derive(Debug, PartialEq)]
+/// struct Marker {
+///     magic: [u8; 4],
+///     value: u32
+/// }
+///
+/// validate!(Marker {
+///     #[magic = *b"MARK"]
+///     magic: [u8; 4],
+///     value: u32,
+/// });
+///
+/// let mut dump = vec![0xAA; 64];
+/// dump[13..17].copy_from_slice(b"MARK");
+/// dump[17..21].copy_from_slice(&7u32.to_ne_bytes());
+///
+/// let found = scan_for::<Marker>(&dump, 1);
+/// assert_eq!(found, [(13, Marker { magic: *b"MARK", value: 7 })]);
+/// ```
+///
+/// [Validate::validate_bytes]: crate::Validate::validate_bytes
+/// [validate]: crate::validate
+/// [scan_reader]: scan_reader
+pub fn scan_for<T: Validate>(haystack: &[u8], step: usize) -> Vec<(usize, T)> {
+    check_scan::<T>(step);
+
+    haystack.windows(size_of::<T>())
+        .enumerate()
+        .step_by(step)
+        .filter_map(|(offset, window)| Some((offset, carve(window)?)))
+        .collect()
+}
+
+/// Finds every offset of `reader` holding a valid `T` like [scan_for], reading it in chunks so
+/// sources bigger than memory, like disk images or memory dumps, can be scanned.
+///
+/// The last `size_of::<T>() - 1` bytes of each chunk are kept for the next one, so records
+/// crossing chunk boundaries are found too. Offsets are counted from the current position of
+/// the reader.
+///
+/// # Panics
+///
+/// Panics if `step` is zero or `T` is zero sized.
+///
+/// [scan_for]: scan_for
+pub fn scan_reader<T: Validate, R: Read>(mut reader: R, step: usize) -> io::Result<Vec<(u64, T)>> {
+    check_scan::<T>(step);
+
+    let size = size_of::<T>();
+    let capacity = CHUNK_SIZE.max(2 * size);
+    let mut buf = Vec::with_capacity(capacity);
+    let mut matches = Vec::new();
+    // Offset of the start of the buffer, and of the next window to check.
+    let mut base = 0u64;
+    let mut next = 0u64;
+
+    loop {
+        let len = buf.len();
+        (&mut reader).take((capacity - len) as u64).read_to_end(&mut buf)?;
+        let ended = buf.len() == len;
+
+        while next + size as u64 <= base + buf.len() as u64 {
+            let at = (next - base) as usize;
+
+            if let Some(item) = carve(&buf[at..at + size]) {
+                matches.push((next, item));
+            }
+
+            next += step as u64;
+        }
+
+        if ended {
+            return Ok(matches);
+        }
+
+        // Drop the bytes before the next window, which may be past the end of the buffer.
+        let consumed = buf.len().min((next - base) as usize);
+        buf.drain(..consumed);
+        base += consumed as u64;
+    }
+}
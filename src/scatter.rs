@@ -0,0 +1,93 @@
+use std::io::{self, BufRead, Read};
+
+/// A reader over a list of byte segments, as if they were a single contiguous buffer.
+///
+/// Buffers received with scatter-gather IO can split a record across segments; reading them
+/// through this reader lets [read_binary] put the record back together without concatenating
+/// the segments first. Any segment type viewable as bytes works, like `&[u8]`, `Vec<u8>` or
+/// `bytes::Bytes`, and empty segments are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, ChainReader};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let bytes = 0x0102030405060708u64.to_ne_bytes();
+///     let segments = [&bytes[..3], &bytes[3..5], &bytes[5..]];
+///
+///     let mut reader = ChainReader::new(&segments);
+///     assert_eq!(reader.read_binary::<u64>()?, 0x0102030405060708);
+///     assert_eq!(reader.remaining(), 0);
+///     Ok(())
+/// }
+/// ```
+///
+/// [read_binary]: crate::BinaryRead::read_binary
+pub struct ChainReader<'a, S> {
+    segments: &'a [S],
+    /// Bytes of the first segment already read.
+    offset: usize,
+}
+
+impl<'a, S: AsRef<[u8]>> ChainReader<'a, S> {
+    /// Creates a new reader over `segments`, read in order.
+    pub fn new(segments: &'a [S]) -> Self {
+        Self { segments, offset: 0 }
+    }
+
+    /// Returns the number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        let total = self.segments.iter().map(|segment| segment.as_ref().len()).sum::<usize>();
+        total - self.offset
+    }
+
+    /// Returns the unread part of the current segment, moving past exhausted segments.
+    fn current(&mut self) -> &'a [u8] {
+        while let Some((first, rest)) = self.segments.split_first() {
+            let first = first.as_ref();
+
+            if self.offset < first.len() {
+                return &first[self.offset..];
+            }
+
+            self.segments = rest;
+            self.offset = 0;
+        }
+
+        &[]
+    }
+}
+
+impl<S: AsRef<[u8]>> Read for ChainReader<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+
+        // Fill as much of buf as possible, so records straddling segments take a single call.
+        while read < buf.len() {
+            let current = self.current();
+
+            if current.is_empty() {
+                break;
+            }
+
+            let len = current.len().min(buf.len() - read);
+            buf[read..read + len].copy_from_slice(&current[..len]);
+            self.offset += len;
+            read += len;
+        }
+
+        Ok(read)
+    }
+}
+
+impl<S: AsRef<[u8]>> BufRead for ChainReader<'_, S> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.current())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.offset += amt;
+    }
+}
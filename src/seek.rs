@@ -0,0 +1,266 @@
+use std::{error::Error, fmt, io::{self, Read, Seek, SeekFrom}, mem::size_of};
+use crate::{report, BinaryIterRev, BinaryIterWithOffset, FileReport, Validate};
+
+/// Error carried by the `InvalidData` [io::Error] returned when a byte count or offset doesn't
+/// divide evenly into records.
+///
+/// [io::Error]: std::io::Error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Misaligned {
+    /// The byte count or offset that was checked.
+    pub bytes: u64,
+    /// Size of the records it was checked against.
+    pub record_size: usize,
+    /// Bytes left over after the last whole record.
+    pub remainder: u64,
+}
+
+impl fmt::Display for Misaligned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes are not a multiple of the record size {}, {} bytes left over",
+            self.bytes, self.record_size, self.remainder
+        )
+    }
+}
+
+impl Error for Misaligned {}
+
+/// Returns the size of `T` as a record, erroring for zero sized types since they can't be
+/// located within a stream.
+pub(crate) fn record_size<T>() -> io::Result<u64> {
+    match size_of::<T>() {
+        0 => Err(io::Error::new(io::ErrorKind::InvalidInput, "zero sized types can't be used as records")),
+        size => Ok(size as u64)
+    }
+}
+
+/// Returns the size in bytes of `count` records of `T`, erroring if it doesn't fit in a `usize`.
+pub(crate) fn records_to_bytes<T>(count: usize) -> io::Result<usize> {
+    count.checked_mul(size_of::<T>())
+        .ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{count} records of {} bytes overflow a usize", size_of::<T>())
+        ))
+}
+
+/// Returns the byte offset of the record of `T` at `index`, erroring if it doesn't fit in a
+/// `u64`.
+pub(crate) fn record_offset<T>(index: u64) -> io::Result<u64> {
+    index.checked_mul(size_of::<T>() as u64)
+        .ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("offset of record {index} of {} bytes overflows a u64", size_of::<T>())
+        ))
+}
+
+/// Divides `bytes` into records of `T`, erroring with [Misaligned] if they don't divide evenly.
+///
+/// [Misaligned]: Misaligned
+pub(crate) fn whole_records<T>(bytes: u64) -> io::Result<u64> {
+    let size = record_size::<T>()?;
+
+    match bytes % size {
+        0 => Ok(bytes / size),
+        remainder => Err(io::Error::new(io::ErrorKind::InvalidData, Misaligned {
+            bytes,
+            record_size: size_of::<T>(),
+            remainder,
+        }))
+    }
+}
+
+/// Returns the total length of a stream, leaving its cursor untouched.
+pub(crate) fn stream_len<S: Seek + ?Sized>(stream: &mut S) -> io::Result<u64> {
+    let position = stream.stream_position()?;
+    let len = stream.seek(SeekFrom::End(0))?;
+
+    if position != len {
+        stream.seek(SeekFrom::Start(position))?;
+    }
+
+    Ok(len)
+}
+
+/// The BinaryReadSeek trait provides record-level navigation for seekable sources of fixed-size
+/// records.
+///
+/// All the methods use checked arithmetic, and fail with an `InvalidData` error carrying a
+/// [Misaligned] when the stream isn't laid out in whole records.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryReadSeek, BinaryRead, BinaryWrite};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut buffer = Vec::new();
+///     buffer.write_binary(&[10u32, 20, 30, 40])?;
+///
+///     let mut cursor = Cursor::new(buffer);
+///     cursor.seek_to_record::<u32>(1)?;
+///
+///     assert_eq!(cursor.record_position::<u32>()?, 1);
+///     assert_eq!(cursor.records_remaining::<u32>()?, 3);
+///     assert_eq!(cursor.read_binary::<u32>()?, 20);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [Misaligned]: Misaligned
+pub trait BinaryReadSeek: Read + Seek {
+    /// Returns how many records of `T` are left between the current position and the end of
+    /// the stream.
+    fn records_remaining<T>(&mut self) -> io::Result<u64> {
+        let position = self.stream_position()?;
+        let len = stream_len(self)?;
+
+        whole_records::<T>(len.saturating_sub(position))
+    }
+
+    /// Returns the index of the record of `T` the stream is currently positioned at, erroring if
+    /// the position is not at a record boundary.
+    fn record_position<T>(&mut self) -> io::Result<u64> {
+        let position = self.stream_position()?;
+        whole_records::<T>(position)
+    }
+
+    /// Checks that the whole stream holds whole records of `T`, erroring with the bytes left
+    /// over after the last one otherwise.
+    ///
+    /// A length that isn't a multiple of the record size usually means the file is truncated
+    /// or corrupted, so this is a cheap check to run before iterating over it. The position is
+    /// left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryReadSeek, BinaryWrite, Misaligned};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u32, 2])?;
+    ///     buffer.push(0);
+    ///
+    ///     let error = Cursor::new(buffer).check_alignment::<u32>().unwrap_err();
+    ///     let misaligned = error.get_ref().unwrap().downcast_ref::<Misaligned>().unwrap();
+    ///
+    ///     assert_eq!(misaligned.remainder, 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn check_alignment<T>(&mut self) -> io::Result<()> {
+        whole_records::<T>(stream_len(self)?).map(|_| ())
+    }
+
+    /// Moves the stream to the start of the record of `T` at `index`, returning the new byte
+    /// offset.
+    fn seek_to_record<T>(&mut self, index: u64) -> io::Result<u64> {
+        record_size::<T>()?;
+        self.seek(SeekFrom::Start(record_offset::<T>(index)?))
+    }
+
+    /// Returns an iterator reading records of `T` until the source ends, yielding each one along
+    /// with the byte offset it starts at.
+    ///
+    /// This is useful for mapping records back to positions in the source, for example to jump
+    /// to them later.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryReadSeek, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u16, 2])?;
+    ///
+    ///     let records = Cursor::new(buffer)
+    ///         .binary_iter_with_offset::<u16>()?
+    ///         .collect::<io::Result<Vec<_>>>()?;
+    ///
+    ///     assert_eq!(records, [(0, 1), (2, 2)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn binary_iter_with_offset<T>(&mut self) -> io::Result<BinaryIterWithOffset<'_, Self, T>> {
+        let offset = self.stream_position()?;
+        Ok(BinaryIterWithOffset::new(self, offset))
+    }
+
+    /// Returns an iterator reading the records of `T` of the whole stream backwards, from the
+    /// last one to the first.
+    ///
+    /// This is meant for showing the newest records of a log first. Records are counted when
+    /// the iterator is created, so records appended afterwards aren't yielded, and an empty
+    /// stream yields none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryReadSeek, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[1u16, 2, 3])?;
+    ///
+    ///     let records = Cursor::new(buffer)
+    ///         .binary_iter_rev::<u16>()?
+    ///         .collect::<io::Result<Vec<_>>>()?;
+    ///
+    ///     assert_eq!(records, [3, 2, 1]);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn binary_iter_rev<T>(&mut self) -> io::Result<BinaryIterRev<'_, Self, T>> {
+        let records = whole_records::<T>(stream_len(self)?)?;
+        Ok(BinaryIterRev::new(self, records))
+    }
+
+    /// Checks the structural integrity of the whole stream as a file of records of `T`, in a
+    /// single pass.
+    ///
+    /// Like [validate_record_file_deep], this reports the records the stream holds, the trailing
+    /// bytes after the last complete one and the records that fail validation. The whole stream
+    /// is scanned from its start, and the position is restored afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binext::{BinaryReadSeek, BinaryWrite};
+    /// use std::io::{self, Cursor};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut buffer = Vec::new();
+    ///     buffer.write_binary(&[true, false, true])?;
+    ///     buffer.push(2);
+    ///
+    ///     let report = Cursor::new(buffer).validate_file::<bool>()?;
+    ///
+    ///     assert_eq!(report.record_count, 4);
+    ///     assert_eq!(report.invalid_records, 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [validate_record_file_deep]: crate::validate_record_file_deep
+    fn validate_file<T: Validate>(&mut self) -> io::Result<FileReport> {
+        let position = self.stream_position()?;
+        let mut report = FileReport::new(stream_len(self)?, size_of::<T>());
+
+        self.seek(SeekFrom::Start(0))?;
+        let checked = report::check_records::<T, _>(self, &mut report);
+        self.seek(SeekFrom::Start(position))?;
+
+        checked.map(|_| report)
+    }
+}
+
+impl<I: Read + Seek> BinaryReadSeek for I {}
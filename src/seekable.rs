@@ -0,0 +1,314 @@
+use std::{io::{self, Read, Seek, SeekFrom, Write}, marker::PhantomData, mem::size_of};
+use crate::{bytes, seek::{record_size, records_to_bytes, whole_records}, BinaryRead};
+
+const SKIPPABLE_MAGIC: u32 = 0x184D2A5E;
+const SEEKABLE_MAGIC: u32 = 0x8F92EAB1;
+const FOOTER_SIZE: usize = 9;
+const ENTRY_SIZE: usize = 8;
+/// Bit of the seek table descriptor telling entries carry a checksum.
+const CHECKSUM_FLAG: u8 = 0x80;
+/// Reserved bits of the seek table descriptor, which must be zero.
+const RESERVED_BITS: u8 = 0x7C;
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A writer compressing records of `T` into the [zstd seekable format], where every frame holds
+/// a fixed number of records and can be decompressed on its own.
+///
+/// Records are buffered until a frame is full, then compressed with its content checksum and
+/// written. [finish] must be called once done to write the last frame and the seek table, the
+/// file can't be read with [SeekableReader] otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{SeekableReader, SeekableWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = SeekableWriter::new(Vec::new(), 1024)?;
+///
+///     for value in 0..10_000u64 {
+///         writer.write_record(&value)?;
+///     }
+///
+///     let mut reader = SeekableReader::<_, u64>::new(Cursor::new(writer.finish()?))?;
+///
+///     assert_eq!(reader.len(), 10_000);
+///     assert_eq!(reader.get(4321)?, 4321);
+///     Ok(())
+/// }
+/// ```
+///
+/// [zstd seekable format]: https://github.com/facebook/zstd/blob/dev/contrib/seekable_format/zstd_seekable_compression_format.md
+/// [finish]: SeekableWriter::finish
+/// [SeekableReader]: SeekableReader
+pub struct SeekableWriter<W, T> {
+    inner: W,
+    compressor: zstd::bulk::Compressor<'static>,
+    records_per_frame: usize,
+    buffer: Vec<u8>,
+    entries: Vec<(u32, u32)>,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<W: Write, T> SeekableWriter<W, T> {
+    /// Creates a new writer over `inner` putting `records_per_frame` records in each frame,
+    /// with the default compression level.
+    pub fn new(inner: W, records_per_frame: usize) -> io::Result<Self> {
+        Self::with_level(inner, records_per_frame, zstd::DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    /// Creates a new writer over `inner` putting `records_per_frame` records in each frame,
+    /// with the given compression level.
+    pub fn with_level(inner: W, records_per_frame: usize, level: i32) -> io::Result<Self> {
+        record_size::<T>()?;
+        let frame_size = records_to_bytes::<T>(records_per_frame).ok()
+            .filter(|&size| size > 0 && u32::try_from(size).is_ok())
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames must hold at least one record and at most 4 GiB"
+            ))?;
+
+        let mut compressor = zstd::bulk::Compressor::new(level)?;
+        compressor.include_checksum(true)?;
+        compressor.include_contentsize(true)?;
+
+        Ok(Self {
+            inner,
+            compressor,
+            records_per_frame,
+            buffer: Vec::with_capacity(frame_size),
+            entries: Vec::new(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Writes a record, compressing the current frame if it becomes full.
+    pub fn write_record(&mut self, item: &T) -> io::Result<()> {
+        self.buffer.extend_from_slice(bytes::as_bytes(item));
+
+        if self.buffer.len() == self.records_per_frame * size_of::<T>() {
+            self.write_frame()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the last frame and the seek table, returning the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.buffer.is_empty() {
+            self.write_frame()?;
+        }
+
+        let frames = u32::try_from(self.entries.len())
+            .map_err(|_| invalid_data("too many frames for a seek table"))?;
+        let table_size = self.entries.len() * ENTRY_SIZE + FOOTER_SIZE;
+
+        let mut table = Vec::with_capacity(table_size + 8);
+        table.extend_from_slice(&SKIPPABLE_MAGIC.to_le_bytes());
+        table.extend_from_slice(&(table_size as u32).to_le_bytes());
+
+        for (compressed, decompressed) in &self.entries {
+            table.extend_from_slice(&compressed.to_le_bytes());
+            table.extend_from_slice(&decompressed.to_le_bytes());
+        }
+
+        table.extend_from_slice(&frames.to_le_bytes());
+        table.push(0);
+        table.extend_from_slice(&SEEKABLE_MAGIC.to_le_bytes());
+
+        self.inner.write_all(&table)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+    fn write_frame(&mut self) -> io::Result<()> {
+        let frame = self.compressor.compress(&self.buffer)?;
+        let compressed = u32::try_from(frame.len())
+            .map_err(|_| invalid_data("compressed frame larger than 4 GiB"))?;
+
+        self.inner.write_all(&frame)?;
+        self.entries.push((compressed, self.buffer.len() as u32));
+        self.buffer.clear();
+
+        Ok(())
+    }
+}
+
+/// A frame of the seek table.
+struct Frame {
+    /// Offset of the compressed frame within the source.
+    offset: u64,
+    compressed: u32,
+    decompressed: u32,
+    /// Index of the first record of the frame.
+    first_record: u64,
+}
+
+/// A reader giving random access to the records of `T` in a [zstd seekable format] file, such
+/// as the ones written by [SeekableWriter].
+///
+/// Only the frame holding the requested record is decompressed, and the most recently used
+/// frames are kept, four by default, so reading nearby records doesn't decompress them again.
+/// Every frame must hold whole records, and frames whose content doesn't match their checksum
+/// or their size in the seek table are reported as `InvalidData` errors.
+///
+/// [zstd seekable format]: https://github.com/facebook/zstd/blob/dev/contrib/seekable_format/zstd_seekable_compression_format.md
+/// [SeekableWriter]: SeekableWriter
+pub struct SeekableReader<R, T> {
+    inner: R,
+    frames: Vec<Frame>,
+    len: u64,
+    cache: Vec<(usize, Vec<u8>)>,
+    cache_size: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read + Seek, T> SeekableReader<R, T> {
+    /// Creates a new reader over `inner`, reading its seek table.
+    pub fn new(inner: R) -> io::Result<Self> {
+        Self::with_cache_size(inner, 4)
+    }
+
+    /// Creates a new reader over `inner` keeping up to `cache_size` decompressed frames.
+    pub fn with_cache_size(mut inner: R, cache_size: usize) -> io::Result<Self> {
+        record_size::<T>()?;
+
+        let end = inner.seek(SeekFrom::End(0))?;
+        let footer_start = end.checked_sub(FOOTER_SIZE as u64)
+            .ok_or_else(|| invalid_data("source too small for a seek table"))?;
+
+        inner.seek(SeekFrom::Start(footer_start))?;
+        let footer = inner.read_binary::<[u8; FOOTER_SIZE]>()?;
+
+        if footer[5..] != SEEKABLE_MAGIC.to_le_bytes() {
+            return Err(invalid_data("missing seekable format magic number"));
+        }
+
+        let descriptor = footer[4];
+
+        if descriptor & RESERVED_BITS != 0 {
+            return Err(invalid_data("reserved bits set in the seek table descriptor"));
+        }
+
+        let entry_size = match descriptor & CHECKSUM_FLAG {
+            0 => ENTRY_SIZE,
+            _ => ENTRY_SIZE + 4
+        };
+
+        let count = u32::from_le_bytes(footer[..4].try_into().unwrap()) as u64;
+        let table_size = count * entry_size as u64 + FOOTER_SIZE as u64;
+        let table_start = end.checked_sub(table_size + 8)
+            .ok_or_else(|| invalid_data("source too small for its seek table"))?;
+
+        inner.seek(SeekFrom::Start(table_start))?;
+        let header = inner.read_binary::<[u8; 8]>()?;
+
+        if header[..4] != SKIPPABLE_MAGIC.to_le_bytes() || header[4..] != (table_size as u32).to_le_bytes() {
+            return Err(invalid_data("invalid seek table header"));
+        }
+
+        let mut entries = vec![0u8; usize::try_from(count * entry_size as u64)
+            .map_err(|_| invalid_data("seek table too large for this platform"))?];
+        inner.read_exact(&mut entries)?;
+
+        let mut frames = Vec::with_capacity(count as usize);
+        let mut offset = 0;
+        let mut len = 0;
+
+        for entry in entries.chunks_exact(entry_size) {
+            let compressed = u32::from_le_bytes(entry[..4].try_into().unwrap());
+            let decompressed = u32::from_le_bytes(entry[4..8].try_into().unwrap());
+
+            frames.push(Frame { offset, compressed, decompressed, first_record: len });
+            offset += compressed as u64;
+            len += whole_records::<T>(decompressed as u64)?;
+        }
+
+        if offset != table_start {
+            return Err(invalid_data("frame sizes don't match the seek table position"));
+        }
+
+        Ok(Self {
+            inner,
+            frames,
+            len,
+            cache: Vec::with_capacity(cache_size),
+            cache_size: cache_size.max(1),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of records.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of frames.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Reads the record at `index`, decompressing its frame unless it's cached.
+    pub fn get(&mut self, index: u64) -> io::Result<T> {
+        if index >= self.len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("record {index} out of bounds for {} records", self.len)
+            ));
+        }
+
+        let frame = self.frames.partition_point(|frame| frame.first_record <= index) - 1;
+        let start = (index - self.frames[frame].first_record) as usize * size_of::<T>();
+
+        (&self.frame(frame)?[start..]).read_binary()
+    }
+
+    /// Returns an iterator over all the records, decompressing each frame once.
+    pub fn iter(&mut self) -> impl Iterator<Item = io::Result<T>> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the decompressed content of a frame, moving it to the front of the cache.
+    fn frame(&mut self, index: usize) -> io::Result<&[u8]> {
+        match self.cache.iter().position(|(cached, _)| *cached == index) {
+            Some(position) => {
+                let entry = self.cache.remove(position);
+                self.cache.insert(0, entry);
+            },
+            None => {
+                let frame = &self.frames[index];
+                let mut compressed = vec![0u8; frame.compressed as usize];
+
+                self.inner.seek(SeekFrom::Start(frame.offset))?;
+                self.inner.read_exact(&mut compressed)?;
+
+                let content = zstd::bulk::decompress(&compressed, frame.decompressed as usize)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                if content.len() != frame.decompressed as usize {
+                    return Err(invalid_data("frame size doesn't match the seek table"));
+                }
+
+                self.cache.truncate(self.cache_size - 1);
+                self.cache.insert(0, (index, content));
+            }
+        }
+
+        Ok(&self.cache[0].1)
+    }
+}
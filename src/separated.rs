@@ -0,0 +1,136 @@
+use std::io::{self, Read, Write};
+use crate::{iter::read_record_bytes, BinaryRead, BinaryWrite};
+
+/// A writer of records separated by a fixed byte sequence, for formats delimiting records with
+/// markers instead of length prefixes.
+///
+/// The separator goes between records, so the output ends with the last record, unless
+/// [with_trailing] is used to also write it after the last one. Read the records back with a
+/// [SeparatedReader].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{SeparatedReader, SeparatedWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = SeparatedWriter::new(Vec::new(), *b"\r\n");
+///     writer.write_record(&[1u8, 2])?;
+///     writer.write_record(&[3u8, 4])?;
+///
+///     let bytes = writer.into_inner();
+///     assert_eq!(bytes, [1, 2, b'\r', b'\n', 3, 4]);
+///
+///     let mut reader = SeparatedReader::new(Cursor::new(bytes), *b"\r\n");
+///     assert_eq!(reader.read_record::<[u8; 2]>()?, [1, 2]);
+///     assert_eq!(reader.read_record::<[u8; 2]>()?, [3, 4]);
+///     Ok(())
+/// }
+/// ```
+///
+/// [with_trailing]: SeparatedWriter::with_trailing
+/// [SeparatedReader]: SeparatedReader
+pub struct SeparatedWriter<W> {
+    inner: W,
+    separator: Vec<u8>,
+    trailing: bool,
+    records: u64,
+}
+
+impl<W: Write> SeparatedWriter<W> {
+    /// Creates a new writer over `inner`, separating records with `separator`.
+    pub fn new(inner: W, separator: impl Into<Vec<u8>>) -> Self {
+        Self {
+            inner,
+            separator: separator.into(),
+            trailing: false,
+            records: 0,
+        }
+    }
+
+    /// Sets whether the separator is also written after the last record, by writing it after
+    /// every record instead of before every one but the first.
+    pub fn with_trailing(mut self, trailing: bool) -> Self {
+        self.trailing = trailing;
+        self
+    }
+
+    /// Writes a record of `T`, preceded or followed by the separator as needed.
+    pub fn write_record<T>(&mut self, item: &T) -> io::Result<()> {
+        if !self.trailing && self.records > 0 {
+            self.inner.write_all(&self.separator)?;
+        }
+
+        self.inner.write_binary(item)?;
+
+        if self.trailing {
+            self.inner.write_all(&self.separator)?;
+        }
+
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Returns the number of records written.
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A reader of the records written by [SeparatedWriter], with or without a trailing
+/// separator.
+///
+/// Each record is read and followed by the separator, which is checked; a separator with
+/// other bytes is reported as an `InvalidData` error. The source may end right after a record
+/// in place of a separator, which is how the last record of a stream without a trailing
+/// separator ends.
+///
+/// [SeparatedWriter]: SeparatedWriter
+pub struct SeparatedReader<R> {
+    inner: R,
+    separator: Vec<u8>,
+    buf: Vec<u8>,
+}
+
+impl<R: Read> SeparatedReader<R> {
+    /// Creates a new reader over `inner`, expecting records separated by `separator`.
+    pub fn new(inner: R, separator: impl Into<Vec<u8>>) -> Self {
+        let separator = separator.into();
+
+        Self {
+            inner,
+            buf: vec![0; separator.len()],
+            separator,
+        }
+    }
+
+    /// Reads a record of `T` and the separator following it, if any.
+    pub fn read_record<T>(&mut self) -> io::Result<T> {
+        let item = self.inner.read_binary::<T>()?;
+
+        if read_record_bytes(&mut self.inner, &mut self.buf)? && self.buf != self.separator {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("record followed by {:02x?} instead of the separator {:02x?}", self.buf, self.separator)
+            ));
+        }
+
+        Ok(item)
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
@@ -0,0 +1,147 @@
+use std::{cell::UnsafeCell, hint, mem::MaybeUninit, ptr, sync::atomic::{fence, AtomicU32, Ordering}};
+
+/// A value that can be read consistently while it's being written concurrently, without locks.
+///
+/// This is a sequence lock, suited for small structs written often and read from many places,
+/// like a block of telemetry in a shared memory region. Writers increment a sequence counter
+/// before and after updating the value, so it's odd while a write is in progress, and readers
+/// copy the value and retry if the counter was odd or changed in the meantime. Readers never
+/// block writers, but may have to retry while they write; [try_read] bounds the retries for
+/// readers that must not spin forever.
+///
+/// The layout is `#[repr(C)]`, a `u32` counter followed by the value, so it can be placed in
+/// memory shared with other processes using the same protocol. Readers may copy a value while
+/// it's being overwritten, so the copy is kept uninitialized until the counter shows it wasn't
+/// torn, and `T` must be [Copy].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::SeqLocked;
+/// use std::{sync::Arc, thread};
+///
+/// #[derive(Clone, Copy)]
+/// struct Telemetry {
+///     rpm: u32,
+///     temperature: f32
+/// }
+///
+/// let shared = Arc::new(SeqLocked::new(Telemetry { rpm: 0, temperature: 20.0 }));
+///
+/// let writer = {
+///     let shared = Arc::clone(&shared);
+///     thread::spawn(move || shared.write(Telemetry { rpm: 3000, temperature: 85.5 }))
+/// };
+///
+/// let telemetry = shared.read();
+/// assert!(telemetry.rpm == 0 || telemetry.rpm == 3000);
+///
+/// writer.join().unwrap();
+/// assert_eq!(shared.read().rpm, 3000);
+/// ```
+///
+/// [try_read]: SeqLocked::try_read
+#[repr(C)]
+pub struct SeqLocked<T> {
+    pub(crate) sequence: AtomicU32,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: readers only keep copies that were not torn, and writers exclude each other through
+// the sequence counter.
+unsafe impl<T: Copy + Send> Sync for SeqLocked<T> {}
+
+impl<T: Copy> SeqLocked<T> {
+    /// Creates a new sequence lock holding `value`.
+    pub const fn new(value: T) -> Self {
+        Self {
+            sequence: AtomicU32::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a consistent copy of the value, retrying until no write overlaps the read.
+    pub fn read(&self) -> T {
+        loop {
+            if let Some(value) = self.read_once() {
+                return value;
+            }
+
+            hint::spin_loop();
+        }
+    }
+
+    /// Returns a consistent copy of the value, or `None` if every one of `1 + retries` attempts
+    /// overlapped a write.
+    pub fn try_read(&self, retries: usize) -> Option<T> {
+        for _ in 0..retries {
+            if let Some(value) = self.read_once() {
+                return Some(value);
+            }
+
+            hint::spin_loop();
+        }
+
+        self.read_once()
+    }
+
+    /// Replaces the value, waiting for any other write in progress to finish first.
+    pub fn write(&self, value: T) {
+        let mut sequence = self.sequence.load(Ordering::Relaxed);
+
+        // Makes the counter odd, which also excludes other writers.
+        loop {
+            if sequence & 1 == 0 {
+                match self.sequence.compare_exchange_weak(
+                    sequence, sequence.wrapping_add(1), Ordering::Acquire, Ordering::Relaxed
+                ) {
+                    Ok(_) => break,
+                    Err(current) => sequence = current
+                }
+            } else {
+                hint::spin_loop();
+                sequence = self.sequence.load(Ordering::Relaxed);
+            }
+        }
+
+        fence(Ordering::Release);
+
+        // SAFETY: no other writer can get here until the counter is even again, and readers
+        // discard what they read meanwhile.
+        unsafe { ptr::write_volatile(self.value.get(), value) };
+
+        self.sequence.store(sequence.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Returns the current sequence number, which is odd while a write is in progress and grows
+    /// by two with every write.
+    pub fn sequence(&self) -> u32 {
+        self.sequence.load(Ordering::Acquire)
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    fn read_once(&self) -> Option<T> {
+        let before = self.sequence.load(Ordering::Acquire);
+
+        if before & 1 == 1 {
+            return None;
+        }
+
+        // SAFETY: the pointer is valid, and a torn copy is never assumed to be a valid T.
+        let value = unsafe { ptr::read_volatile(self.value.get() as *const MaybeUninit<T>) };
+        fence(Ordering::Acquire);
+
+        // SAFETY: no write overlapped the copy, so it holds the T last written.
+        (self.sequence.load(Ordering::Relaxed) == before).then(|| unsafe { value.assume_init() })
+    }
+}
+
+impl<T: Copy + Default> Default for SeqLocked<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
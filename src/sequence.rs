@@ -0,0 +1,276 @@
+use std::{collections::{BTreeMap, VecDeque}, error::Error, fmt, io, ops::Range};
+use crate::Envelope;
+
+/// Number of gaps remembered to tell late records apart from duplicates.
+const REMEMBERED_GAPS: usize = 64;
+
+/// What a [SequenceChecker] does with records arriving out of sequence.
+///
+/// [SequenceChecker]: SequenceChecker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisorderPolicy {
+    /// Fails with an `InvalidData` error carrying an [OutOfSequence] on the first record out of
+    /// sequence, and stops.
+    ///
+    /// [OutOfSequence]: OutOfSequence
+    Error,
+    /// Reports gaps and delivers the record after them, dropping duplicate and late records.
+    Skip,
+    /// Holds up to the given number of records arriving ahead of sequence, delivering them in
+    /// order once the missing ones arrive. When the window fills up, the missing records are
+    /// declared a gap and the delivery moves on, like with [Skip].
+    ///
+    /// [Skip]: DisorderPolicy::Skip
+    Reorder(usize),
+}
+
+/// An event yielded by a [SequenceChecker].
+///
+/// [SequenceChecker]: SequenceChecker
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceEvent<T> {
+    /// The payload of the next record in sequence.
+    InOrder(T),
+    /// Records were missing before the next one delivered.
+    Gap {
+        /// The sequence number that was expected.
+        expected: u64,
+        /// The sequence number delivered next.
+        got: u64,
+        /// How many records are missing.
+        missing: u64,
+    },
+    /// A record already delivered arrived again, and was dropped.
+    Duplicate(u64),
+    /// A record declared missing arrived late, and was dropped.
+    OutOfOrder {
+        /// Its sequence number.
+        seq: u64,
+    },
+}
+
+/// Error carried by the `InvalidData` [io::Error] returned by a [SequenceChecker] using
+/// [DisorderPolicy::Error].
+///
+/// [io::Error]: std::io::Error
+/// [SequenceChecker]: SequenceChecker
+/// [DisorderPolicy::Error]: DisorderPolicy::Error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutOfSequence {
+    /// The sequence number that was expected.
+    pub expected: u64,
+    /// The sequence number that arrived.
+    pub got: u64,
+}
+
+impl fmt::Display for OutOfSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected record {} but got {}", self.expected, self.got)
+    }
+}
+
+impl Error for OutOfSequence {}
+
+/// An iterator checking the sequence numbers of a stream of [Envelope]s, reporting gaps,
+/// duplicates and reordering.
+///
+/// It wraps an iterator of envelopes, like a [BinaryIter] over them, and yields the payloads
+/// delivered in sequence along with the anomalies found, as [SequenceEvent]s. What happens with
+/// records out of sequence is set with a [DisorderPolicy]. The first envelope sets the starting
+/// sequence unless one is given with [starting_at].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinDateTimeUtc, DisorderPolicy, Envelope, SequenceChecker, SequenceEvent};
+/// use std::io;
+///
+/// let arrived = [0, 2, 1, 5].map(|seq| Ok(Envelope::new(seq, BinDateTimeUtc::UNIX_EPOCH, seq)));
+///
+/// let events = SequenceChecker::new(arrived.into_iter(), DisorderPolicy::Reorder(2))
+///     .collect::<io::Result<Vec<_>>>()
+///     .unwrap();
+///
+/// assert_eq!(events, [
+///     SequenceEvent::InOrder(0),
+///     SequenceEvent::InOrder(1),
+///     SequenceEvent::InOrder(2),
+///     SequenceEvent::Gap { expected: 3, got: 5, missing: 2 },
+///     SequenceEvent::InOrder(5),
+/// ]);
+/// ```
+///
+/// [Envelope]: crate::Envelope
+/// [BinaryIter]: crate::BinaryIter
+/// [SequenceEvent]: SequenceEvent
+/// [DisorderPolicy]: DisorderPolicy
+/// [starting_at]: SequenceChecker::starting_at
+pub struct SequenceChecker<I, T> {
+    inner: I,
+    policy: DisorderPolicy,
+    expected: Option<u64>,
+    ahead: BTreeMap<u64, T>,
+    gaps: VecDeque<Range<u64>>,
+    events: VecDeque<SequenceEvent<T>>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = io::Result<Envelope<T>>>, T> SequenceChecker<I, T> {
+    /// Creates a new checker over `inner`.
+    pub fn new(inner: I, policy: DisorderPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            expected: None,
+            ahead: BTreeMap::new(),
+            gaps: VecDeque::new(),
+            events: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Sets the sequence number of the first record expected.
+    pub fn starting_at(mut self, sequence: u64) -> Self {
+        self.expected = Some(sequence);
+        self
+    }
+
+    /// Returns the sequence number of the next record to deliver, if known yet.
+    pub fn expected(&self) -> Option<u64> {
+        self.expected
+    }
+
+    /// Unwraps this checker, returning the underlying iterator. Records held for reordering
+    /// are lost.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    fn check(&mut self, envelope: Envelope<T>) -> io::Result<()> {
+        let seq = envelope.sequence();
+        let expected = *self.expected.get_or_insert(seq);
+
+        if seq == expected {
+            self.deliver(seq, envelope.into_payload());
+            self.deliver_ahead();
+            return Ok(());
+        }
+
+        if self.policy == DisorderPolicy::Error {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, OutOfSequence { expected, got: seq }));
+        }
+
+        if seq < expected {
+            let event = match self.gaps.iter().position(|gap| gap.contains(&seq)) {
+                Some(index) => {
+                    let gap = self.gaps.remove(index).unwrap();
+                    self.remember_gap(gap.start..seq);
+                    self.remember_gap(seq + 1..gap.end);
+
+                    SequenceEvent::OutOfOrder { seq }
+                },
+                None => SequenceEvent::Duplicate(seq)
+            };
+
+            self.events.push_back(event);
+            return Ok(());
+        }
+
+        match self.policy {
+            DisorderPolicy::Reorder(window) => {
+                if self.ahead.contains_key(&seq) {
+                    self.events.push_back(SequenceEvent::Duplicate(seq));
+                    return Ok(());
+                }
+
+                self.ahead.insert(seq, envelope.into_payload());
+
+                if self.ahead.len() > window {
+                    self.skip_to_ahead();
+                }
+            },
+            _ => {
+                self.declare_gap(expected, seq);
+                self.deliver(seq, envelope.into_payload());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn deliver(&mut self, seq: u64, payload: T) {
+        self.events.push_back(SequenceEvent::InOrder(payload));
+        self.expected = Some(seq.wrapping_add(1));
+    }
+
+    /// Delivers the records held ahead that are next in sequence.
+    fn deliver_ahead(&mut self) {
+        while let Some(expected) = self.expected {
+            match self.ahead.remove(&expected) {
+                Some(payload) => self.deliver(expected, payload),
+                None => break
+            }
+        }
+    }
+
+    /// Gives up on the missing records before the first one held ahead, and delivers from it.
+    fn skip_to_ahead(&mut self) {
+        if let (Some(expected), Some((&first, _))) = (self.expected, self.ahead.first_key_value()) {
+            self.declare_gap(expected, first);
+            self.expected = Some(first);
+            self.deliver_ahead();
+        }
+    }
+
+    fn declare_gap(&mut self, expected: u64, got: u64) {
+        self.events.push_back(SequenceEvent::Gap { expected, got, missing: got - expected });
+        self.remember_gap(expected..got);
+    }
+
+    fn remember_gap(&mut self, gap: Range<u64>) {
+        if gap.is_empty() {
+            return;
+        }
+
+        if self.gaps.len() == REMEMBERED_GAPS {
+            self.gaps.pop_front();
+        }
+
+        self.gaps.push_back(gap);
+    }
+}
+
+impl<I: Iterator<Item = io::Result<Envelope<T>>>, T> Iterator for SequenceChecker<I, T> {
+    type Item = io::Result<SequenceEvent<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(Ok(event));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            match self.inner.next() {
+                Some(Ok(envelope)) => if let Err(e) = self.check(envelope) {
+                    self.done = true;
+                    return Some(Err(e));
+                },
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                },
+                None => {
+                    self.done = true;
+
+                    while !self.ahead.is_empty() {
+                        self.skip_to_ahead();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::{io::{self, Seek, SeekFrom, Write}, marker::PhantomData, mem::size_of};
+use crate::BinaryWrite;
+
+/// A reserved record slot, returned by [SlotWriter::reserve] and consumed when filled.
+///
+/// [SlotWriter::reserve]: SlotWriter::reserve
+#[derive(Debug, PartialEq, Eq)]
+pub struct SlotHandle {
+    offset: u64,
+}
+
+impl SlotHandle {
+    /// Returns the byte offset the slot starts at.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+/// A writer that can reserve room for records of `T` and fill them later.
+///
+/// This is the forward-reference pattern used by indexes and headers whose content depends on
+/// data written after them: [reserve] writes a zeroed record and returns a handle to it, and
+/// [fill] seeks back to write the real record, returning to the previous position afterwards.
+/// Any other data can be written through the [Write] implementation in between.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, BinaryWrite, SlotWriter};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = SlotWriter::<u32, _>::new(Cursor::new(Vec::new()));
+///
+///     let len = writer.reserve()?;
+///     writer.write_binary(&[1u8, 2, 3])?;
+///     writer.fill(len, &3)?;
+///
+///     let mut cursor = Cursor::new(writer.into_inner().into_inner());
+///     assert_eq!(cursor.read_binary::<u32>()?, 3);
+///     Ok(())
+/// }
+/// ```
+///
+/// [reserve]: SlotWriter::reserve
+/// [fill]: SlotWriter::fill
+/// [Write]: std::io::Write
+pub struct SlotWriter<T, W> {
+    inner: W,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T, W: Write + Seek> SlotWriter<T, W> {
+    /// Creates a new writer over `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes a zeroed record at the current position, returning a handle to fill it later.
+    pub fn reserve(&mut self) -> io::Result<SlotHandle> {
+        let offset = self.inner.stream_position()?;
+        io::copy(&mut io::Read::take(io::repeat(0), size_of::<T>() as u64), &mut self.inner)?;
+
+        Ok(SlotHandle { offset })
+    }
+
+    /// Writes `item` into a reserved slot, leaving the position where it was.
+    pub fn fill(&mut self, handle: SlotHandle, item: &T) -> io::Result<()> {
+        let position = self.inner.stream_position()?;
+
+        self.inner.seek(SeekFrom::Start(handle.offset))?;
+        self.inner.write_binary(item)?;
+        self.inner.seek(SeekFrom::Start(position))?;
+
+        Ok(())
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<T, W: Write> Write for SlotWriter<T, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,205 @@
+use std::{fs::File, io::{self, Write}};
+use crate::{bytes, reserve_zeroed, seek::record_size};
+
+/// Size of the chunks copied at once, rounded down to whole records.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Copies the records a file holds right now into `dst`, returning how many were copied.
+///
+/// The length of `src` is captured first and rounded down to whole records of `T`, so records
+/// being appended concurrently, in full or in part, are left out and the copy ends at a record
+/// boundary that existed when the snapshot started. The bytes are read at explicit positions, so
+/// on unix the cursor of `src`, which may be shared with the appender, is left untouched. On
+/// windows positioned reads move the cursor, so files appended to through the same handle
+/// should be opened in append mode.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::snapshot_records;
+/// use std::{fs::File, io};
+///
+/// fn main() -> io::Result<()> {
+///     let live = File::open("events.bin")?;
+///     let copied = snapshot_records::<u64>(&live, File::create("events.snapshot.bin")?)?;
+///
+///     println!("{copied} records copied");
+///     Ok(())
+/// }
+/// ```
+pub fn snapshot_records<T>(src: &File, dst: impl Write) -> io::Result<u64> {
+    snapshot_records_verified::<T>(src, dst, |_| true)
+}
+
+/// Copies the records a file holds right now into `dst` like [snapshot_records], checking each
+/// one with `verify` as it's copied.
+///
+/// `verify` gets the bytes of each record, usually to check a checksum stored within it. The
+/// copy stops with an `InvalidData` error at the first record it rejects, which is not written.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::snapshot_records_verified;
+/// use std::{fs::File, io};
+///
+/// #[repr(C)]
+/// struct Entry {
+///     value: u64,
+///     checksum: u32,
+///     reserved: u32
+/// }
+///
+/// fn checksum(bytes: &[u8]) -> u32 {
+///     bytes.iter().fold(0u32, |sum, &byte| sum.rotate_left(5) ^ byte as u32)
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let live = File::open("entries.bin")?;
+///     let dst = File::create("entries.snapshot.bin")?;
+///
+///     snapshot_records_verified::<Entry>(&live, dst, |record| {
+///         record[8..12] == checksum(&record[..8]).to_ne_bytes()
+///     })?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [snapshot_records]: snapshot_records
+pub fn snapshot_records_verified<T>(src: &File, mut dst: impl Write, mut verify: impl FnMut(&[u8]) -> bool) -> io::Result<u64> {
+    let size = record_size::<T>()? as usize;
+    let len = src.metadata()?.len();
+    let records = len / size as u64;
+    let end = records * size as u64;
+
+    let mut chunk = vec![0u8; (CHUNK_SIZE / size).max(1) * size];
+    let mut offset = 0;
+    let mut index = 0;
+
+    while offset < end {
+        let len = chunk.len().min((end - offset) as usize);
+        read_exact_at(src, &mut chunk[..len], offset)?;
+
+        for record in chunk[..len].chunks_exact(size) {
+            if !verify(record) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("record {index} of the snapshot failed verification")
+                ));
+            }
+
+            dst.write_all(record)?;
+            index += 1;
+        }
+
+        offset += len as u64;
+    }
+
+    dst.flush()?;
+    Ok(records)
+}
+
+/// Reads the records of `T` found at each of `offsets` in `src`, returning them in the order of
+/// `offsets`.
+///
+/// The offsets are visited in increasing order, and records overlapping or right next to each
+/// other are fetched with a single read, so scattered lookups from an index become mostly
+/// sequential IO. The bytes are read at explicit positions like with [snapshot_records], so the
+/// cursor of `src` is left untouched on unix, and since no state is shared between reads they
+/// can be split among threads.
+///
+/// An offset whose record doesn't fit in the file is reported as an `InvalidInput` error naming
+/// its index in `offsets`, before anything is read.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::read_binary_at_offsets;
+/// use std::{fs::File, io};
+///
+/// fn main() -> io::Result<()> {
+///     let file = File::open("events.bin")?;
+///     let events = read_binary_at_offsets::<u64>(&file, &[800, 16, 24, 8])?;
+///
+///     println!("{events:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// [snapshot_records]: snapshot_records
+pub fn read_binary_at_offsets<T>(src: &File, offsets: &[u64]) -> io::Result<Vec<T>> {
+    let size = record_size::<T>()?;
+    let len = src.metadata()?.len();
+
+    for (index, &offset) in offsets.iter().enumerate() {
+        if offset.checked_add(size).is_none_or(|end| end > len) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("offset {offset} at index {index} is out of the {len} bytes file for a {size} bytes record")
+            ));
+        }
+    }
+
+    let mut order = (0..offsets.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&index| offsets[index]);
+
+    let mut records = Vec::new();
+    let spare = bytes::slice_as_bytes_mut(reserve_zeroed(&mut records, offsets.len())?);
+    let size = size as usize;
+    let mut buf = Vec::new();
+    let mut remaining = order.as_slice();
+
+    while let Some(&first) = remaining.first() {
+        // Extend the read over every following record starting before it ends.
+        let start = offsets[first];
+        let mut end = start + size as u64;
+        let members = remaining.iter()
+            .take_while(|&&index| {
+                let overlaps = offsets[index] <= end;
+                if overlaps {
+                    end = end.max(offsets[index] + size as u64);
+                }
+                overlaps
+            })
+            .count();
+
+        buf.resize((end - start) as usize, 0);
+        read_exact_at(src, &mut buf, start)?;
+
+        for &index in &remaining[..members] {
+            let at = (offsets[index] - start) as usize;
+            spare[index * size..][..size].copy_from_slice(&buf[at..at + size]);
+        }
+
+        remaining = &remaining[members..];
+    }
+
+    // SAFETY: the record of every offset has been read into its place.
+    unsafe { records.set_len(offsets.len()) };
+    Ok(records)
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+
+    while !buf.is_empty() {
+        match file.seek_read(buf, offset) {
+            Ok(0) => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "file shrank while reading it")),
+            Ok(read) => {
+                buf = &mut buf[read..];
+                offset += read as u64;
+            },
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e)
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::{io::{self, BufRead, Read}, marker::PhantomData, mem::size_of, ptr};
+
+/// Default minimum buffer size, so several records are read per call to the source.
+const DEFAULT_BUF_SIZE: usize = 64 * 1024;
+
+/// A buffered reader whose buffer holds a whole number of records of `T`, so every record is
+/// served from a single contiguous region of it.
+///
+/// `BufReader` buffers a fixed 8 KiB, which smaller records straddle, taking a refill in the
+/// middle and a copy split in two, and which larger ones bypass, taking a call to the source
+/// each. This reader sizes its buffer to a multiple of `size_of::<T>()`, by default the fewest
+/// records taking at least 64 KiB, and when fewer bytes than a record are left in it, moves
+/// them to the front before refilling. So [record_bytes] can always hand out the next record as
+/// one slice of the buffer without copying it, however the source splits its reads.
+///
+/// It also implements [Read] and [BufRead], so the usual [read_binary] calls work on it.
+///
+/// # Panics
+///
+/// The constructors panic if `T` is zero sized.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryBufReader, BinaryWrite};
+/// use std::io::{self, Cursor};
+///
+/// #[repr(C)]
+/// struct Frame {
+///     pixels: [u8; 12_000]
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut bytes = Vec::new();
+///     bytes.write_binary(&Frame { pixels: [1; 12_000] })?;
+///     bytes.write_binary(&Frame { pixels: [2; 12_000] })?;
+///
+///     let mut reader = BinaryBufReader::<Frame, _>::new(Cursor::new(bytes));
+///     assert_eq!(reader.capacity(), 6 * 12_000);
+///
+///     assert_eq!(reader.read_record()?.unwrap().pixels[0], 1);
+///     assert_eq!(reader.record_bytes()?.unwrap()[0], 2);
+///     assert!(reader.read_record()?.is_none());
+///     Ok(())
+/// }
+/// ```
+///
+/// [record_bytes]: BinaryBufReader::record_bytes
+/// [Read]: std::io::Read
+/// [BufRead]: std::io::BufRead
+/// [read_binary]: crate::BinaryRead::read_binary
+pub struct BinaryBufReader<T, R> {
+    inner: R,
+    buf: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, R: Read> BinaryBufReader<T, R> {
+    /// Creates a new reader over `inner`, buffering the fewest records taking at least 64 KiB.
+    pub fn new(inner: R) -> Self {
+        let size = size_of::<T>().max(1);
+        Self::with_record_capacity(inner, DEFAULT_BUF_SIZE.div_ceil(size))
+    }
+
+    /// Creates a new reader over `inner`, buffering `records` records, at least one.
+    pub fn with_record_capacity(inner: R, records: usize) -> Self {
+        assert!(size_of::<T>() > 0, "zero sized records can't be buffered");
+
+        let capacity = records.max(1).checked_mul(size_of::<T>())
+            .expect("buffer size overflows a usize");
+
+        Self {
+            inner,
+            buf: vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the bytes of the next record, straight from the buffer, and moves past it.
+    ///
+    /// Returns `None` if the source ended right before the record, and an `UnexpectedEof`
+    /// error if it ended in the middle of it.
+    pub fn record_bytes(&mut self) -> io::Result<Option<&[u8]>> {
+        let size = size_of::<T>();
+
+        if self.filled - self.pos < size {
+            // Keep the partial record at the front, so it's completed contiguously.
+            self.buf.copy_within(self.pos..self.filled, 0);
+            self.filled -= self.pos;
+            self.pos = 0;
+
+            while self.filled < size {
+                match self.inner.read(&mut self.buf[self.filled..]) {
+                    Ok(0) if self.filled == 0 => return Ok(None),
+                    Ok(0) => return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("source ended {} bytes into a {size} bytes record", self.filled)
+                    )),
+                    Ok(read) => self.filled += read,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+                    Err(e) => return Err(e)
+                }
+            }
+        }
+
+        let start = self.pos;
+        self.pos += size;
+        Ok(Some(&self.buf[start..self.pos]))
+    }
+
+    /// Reads the next record, returning `None` if the source ended right before it.
+    ///
+    /// Ending in the middle of the record is reported as an `UnexpectedEof` error.
+    pub fn read_record(&mut self) -> io::Result<Option<T>> {
+        let record = self.record_bytes()?
+            // SAFETY: the slice holds the size_of::<T>() bytes of a record, possibly unaligned.
+            .map(|bytes| unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) });
+
+        Ok(record)
+    }
+
+    /// Returns the size of the buffer in bytes, a multiple of the size of `T`.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the buffered bytes not read yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwraps this reader, returning the underlying one. Buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<T, R: Read> Read for BinaryBufReader<T, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Large reads skip the buffer when it's empty, like BufReader does.
+        if self.pos == self.filled && buf.len() >= self.buf.len() {
+            return self.inner.read(buf);
+        }
+
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+
+        Ok(len)
+    }
+}
+
+impl<T, R: Read> BufRead for BinaryBufReader<T, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.filled {
+            self.filled = self.inner.read(&mut self.buf)?;
+            self.pos = 0;
+        }
+
+        Ok(self.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+    }
+}
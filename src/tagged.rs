@@ -0,0 +1,143 @@
+use std::{io::{self, Read}, mem::{size_of, MaybeUninit}};
+
+/// A reader allowing to peek a leading tag before deciding which record to read, without
+/// needing `Seek`.
+///
+/// Sockets and pipes can't seek back, so dispatching on a tag at the start of each record would
+/// otherwise need the tag to be read on its own and the rest of the record to be stitched back
+/// together by hand. [peek_binary] reads the tag into an internal buffer and returns a copy of
+/// it, and since the buffered bytes are served first by the [Read] implementation, the full
+/// record can then be read normally with [read_binary].
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, BinaryWrite, TaggedStreamReader};
+/// use std::io;
+///
+/// #[repr(C)]
+/// struct Tag {
+///     kind: u32
+/// }
+///
+/// #[repr(C)]
+/// struct Ping {
+///     tag: Tag,
+///     id: u32
+/// }
+///
+/// #[repr(C)]
+/// struct Data {
+///     tag: Tag,
+///     values: [u64; 2]
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let mut buffer = Vec::new();
+///     buffer.write_binary(&Ping { tag: Tag { kind: 1 }, id: 7 })?;
+///
+///     let mut reader = TaggedStreamReader::new(buffer.as_slice());
+///
+///     match reader.peek_binary::<Tag>()?.kind {
+///         1 => assert_eq!(reader.read_binary::<Ping>()?.id, 7),
+///         _ => { reader.read_binary::<Data>()?; }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [peek_binary]: TaggedStreamReader::peek_binary
+/// [Read]: std::io::Read
+/// [read_binary]: crate::BinaryRead::read_binary
+pub struct TaggedStreamReader<R> {
+    inner: R,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> TaggedStreamReader<R> {
+    /// Creates a new reader over `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Reads the leading `T` of the next record without consuming it.
+    ///
+    /// Peeking again, even with a different type, sees the same bytes, reading more from the
+    /// source only if the new type is bigger than what is already buffered.
+    pub fn peek_binary<T>(&mut self) -> io::Result<T> {
+        self.fill_to(size_of::<T>())?;
+
+        let mut item = MaybeUninit::<T>::uninit();
+
+        // SAFETY: at least size_of::<T>() bytes are buffered, and they are copied to the item
+        // without assuming any alignment.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.buffer[self.position..].as_ptr(),
+                item.as_mut_ptr() as *mut u8,
+                size_of::<T>()
+            );
+
+            Ok(item.assume_init())
+        }
+    }
+
+    /// Returns the bytes buffered by peeking and not read yet.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.position..]
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    ///
+    /// Any buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn fill_to(&mut self, len: usize) -> io::Result<()> {
+        let buffered = self.buffer.len() - self.position;
+
+        if buffered >= len {
+            return Ok(());
+        }
+
+        self.buffer.drain(..self.position);
+        self.position = 0;
+        self.buffer.resize(len, 0);
+
+        if let Err(e) = self.inner.read_exact(&mut self.buffer[buffered..]) {
+            // Whatever was read is unknown, so only keep what was there before.
+            self.buffer.truncate(buffered);
+            return Err(e);
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for TaggedStreamReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.buffer.len() {
+            return self.inner.read(buf);
+        }
+
+        let buffered = &self.buffer[self.position..];
+        let len = buffered.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&buffered[..len]);
+        self.position += len;
+
+        if self.position == self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+        }
+
+        Ok(len)
+    }
+}
@@ -0,0 +1,113 @@
+use std::{error::Error, fmt, io::{self, Read}};
+
+/// Error carried by the `UnexpectedEof` [io::Error] returned when a read goes past the limit of
+/// a [TakeExact].
+///
+/// It can be recovered with `error.get_ref().and_then(|e| e.downcast_ref::<LimitReached>())`,
+/// telling a record cut short by the limit apart from one cut short by the end of the source.
+///
+/// [io::Error]: std::io::Error
+/// [TakeExact]: TakeExact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LimitReached {
+    /// The limit that was reached, in bytes.
+    pub limit: u64,
+}
+
+impl fmt::Display for LimitReached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "read limit of {} bytes reached", self.limit)
+    }
+}
+
+impl Error for LimitReached {}
+
+/// A [Read] adapter reading at most `limit` bytes from a source, like [Read::take], but failing
+/// once the limit is reached instead of reporting the end of the source.
+///
+/// Reading past the limit returns an `UnexpectedEof` error carrying a [LimitReached], while the
+/// source ending before the limit is reported as usual. Since every read at the limit fails,
+/// use [remaining] to know whether the limit was consumed exactly.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, LimitReached, TakeExact};
+/// use std::io::{self, Cursor};
+///
+/// fn main() -> io::Result<()> {
+///     let mut reader = TakeExact::new(Cursor::new([0u8; 32]), 8);
+///     let error = reader.read_binary::<[u8; 16]>().unwrap_err();
+///
+///     assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+///     assert!(error.get_ref().is_some_and(|e| e.is::<LimitReached>()));
+///     Ok(())
+/// }
+/// ```
+///
+/// [Read]: std::io::Read
+/// [Read::take]: std::io::Read::take
+/// [LimitReached]: LimitReached
+/// [remaining]: TakeExact::remaining
+pub struct TakeExact<R> {
+    inner: R,
+    limit: u64,
+    remaining: u64,
+}
+
+impl<R> TakeExact<R> {
+    /// Creates a reader over `inner` allowing `limit` bytes to be read.
+    pub fn new(inner: R, limit: u64) -> Self {
+        Self { inner, limit, remaining: limit }
+    }
+
+    /// Returns the number of bytes that can still be read before reaching the limit.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the limit this reader was created with, or last set to.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets a new limit, allowing `limit` more bytes to be read.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+        self.remaining = limit;
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this reader, returning the underlying one.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for TakeExact<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, LimitReached { limit: self.limit }));
+        }
+
+        let max = buf.len().min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let read = self.inner.read(&mut buf[..max])?;
+        self.remaining -= read as u64;
+
+        Ok(read)
+    }
+}
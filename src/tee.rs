@@ -0,0 +1,118 @@
+use std::{error::Error, fmt, io::{self, Write}};
+
+/// Error carried by the [io::Error] returned when writing through a [TeeWriter] fails, telling
+/// which of its writers failed.
+///
+/// A writer without an error received every byte, so a write failing in only one of them left
+/// the other one complete. The returned [io::Error] has the kind of the first writer's error if
+/// it failed, of the second's otherwise. It can be recovered with
+/// `error.get_ref().and_then(|e| e.downcast_ref::<TeeFailed>())`.
+///
+/// [io::Error]: std::io::Error
+/// [TeeWriter]: TeeWriter
+#[derive(Debug)]
+pub struct TeeFailed {
+    /// The error of the first writer, `None` if it succeeded.
+    pub first: Option<io::Error>,
+    /// The error of the second writer, `None` if it succeeded.
+    pub second: Option<io::Error>,
+}
+
+impl fmt::Display for TeeFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.first, &self.second) {
+            (Some(first), Some(second)) => write!(f, "both writers failed: {first}; {second}"),
+            (Some(first), None) => write!(f, "first writer failed, the second succeeded: {first}"),
+            (None, Some(second)) => write!(f, "second writer failed, the first succeeded: {second}"),
+            (None, None) => f.write_str("no writer failed")
+        }
+    }
+}
+
+impl Error for TeeFailed {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.first.as_ref().or(self.second.as_ref()).map(|e| e as _)
+    }
+}
+
+/// A writer duplicating everything written to it into two writers, like the `tee` command.
+///
+/// Records written through it with [write_binary] are turned into bytes once and written in
+/// full to both writers, the second one being written to even if the first one fails. Failures
+/// are reported as an [io::Error] carrying a [TeeFailed], telling which writer failed and
+/// whether the other one succeeded.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryWrite, TeeWriter};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut tee = TeeWriter::new(Vec::new(), Vec::new());
+///     tee.write_binary(&42u32)?;
+///
+///     let (file, socket) = tee.into_inner();
+///     assert_eq!(file, 42u32.to_ne_bytes());
+///     assert_eq!(file, socket);
+///     Ok(())
+/// }
+/// ```
+///
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [io::Error]: std::io::Error
+/// [TeeFailed]: TeeFailed
+pub struct TeeWriter<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> TeeWriter<A, B> {
+    /// Creates a new writer duplicating its output into `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns references to the underlying writers.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Returns mutable references to the underlying writers.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Unwraps this writer, returning the underlying ones.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+/// Combines the results of both writers, reporting failures as a [TeeFailed].
+fn combine(first: io::Result<()>, second: io::Result<()>) -> io::Result<()> {
+    let (first, second) = (first.err(), second.err());
+
+    let kind = match (&first, &second) {
+        (None, None) => return Ok(()),
+        (Some(error), _) | (None, Some(error)) => error.kind()
+    };
+
+    Err(io::Error::new(kind, TeeFailed { first, second }))
+}
+
+impl<A: Write, B: Write> Write for TeeWriter<A, B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Both writers must take the same bytes, so partial writes can't be reported.
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        combine(self.first.write_all(buf), self.second.write_all(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        combine(self.first.flush(), self.second.flush())
+    }
+}
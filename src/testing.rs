@@ -0,0 +1,286 @@
+use std::{any::type_name, collections::VecDeque, fmt, hint::black_box, io::{self, Read, Write}, mem::size_of, time::{Duration, Instant}};
+use crate::{bytes, BinaryRead, BinaryWrite};
+
+/// How fast records were serialized by [measure_throughput].
+///
+/// [measure_throughput]: measure_throughput
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThroughputReport {
+    /// Number of records written.
+    pub records: usize,
+    /// Number of bytes written.
+    pub bytes: u64,
+    /// Time spent writing them.
+    pub elapsed: Duration,
+}
+
+impl ThroughputReport {
+    /// Returns the bytes written per second.
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the records written per second.
+    pub fn records_per_sec(&self) -> f64 {
+        self.records as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Measures how fast records of `T` are serialized on the current hardware, writing a default
+/// `T` into an in-memory buffer `iterations` times.
+///
+/// This is a quick estimate for capacity planning, the benchmarks of the crate are a better fit
+/// for precise comparisons. The buffer is reused between writes, so the measure doesn't include
+/// any allocation or I/O.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::measure_throughput;
+///
+/// #[derive(Default)]
+/// struct Sample {
+///     timestamp: u64,
+///     value: f64
+/// }
+///
+/// let report = measure_throughput::<Sample>(10_000);
+///
+/// assert_eq!(report.bytes, 160_000);
+/// println!("{:.0} records/s", report.records_per_sec());
+/// ```
+pub fn measure_throughput<T: Default>(iterations: usize) -> ThroughputReport {
+    let item = T::default();
+    let mut buffer = Vec::with_capacity(size_of::<T>());
+
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        buffer.clear();
+        black_box(&mut buffer)
+            .write_binary(black_box(&item))
+            .expect("writing to a Vec can't fail");
+    }
+
+    ThroughputReport {
+        records: iterations,
+        bytes: (iterations as u64).saturating_mul(size_of::<T>() as u64),
+        // Keeps the rates finite even if the clock didn't tick.
+        elapsed: start.elapsed().max(Duration::from_nanos(1)),
+    }
+}
+
+/// A [Write] test double recording everything written, to assert on it record by record.
+///
+/// Assertions consume the written bytes in order, the way a reader would, so a test states the
+/// records it expects one after the other instead of building the bytes by hand. Records are
+/// read back like with [read_binary] and compared with `PartialEq`, so padding is ignored.
+/// Failed assertions panic with the offset and type of the mismatching record.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryWrite, MockBinarySink};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Header {
+///     magic: u32,
+///     len: u16
+/// }
+///
+/// let mut sink = MockBinarySink::new();
+/// sink.write_binary(&Header { magic: 0xCAFE, len: 2 }).unwrap();
+/// sink.write_binary(&[7u8, 9]).unwrap();
+///
+/// sink.assert_wrote(&Header { magic: 0xCAFE, len: 2 });
+/// sink.assert_wrote_bytes(&[7, 9]);
+/// sink.assert_done();
+/// ```
+///
+/// [Write]: std::io::Write
+/// [read_binary]: crate::BinaryRead::read_binary
+#[derive(Debug, Default)]
+pub struct MockBinarySink {
+    bytes: Vec<u8>,
+    checked: usize,
+}
+
+impl MockBinarySink {
+    /// Creates an empty sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns every byte written.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Asserts that the next bytes written hold `expected`, and moves past them.
+    #[track_caller]
+    pub fn assert_wrote<T: PartialEq + fmt::Debug>(&mut self, expected: &T) {
+        let offset = self.checked;
+        let mut record = self.take(size_of::<T>(), type_name::<T>());
+        let actual = record.read_binary::<T>().expect("the record bytes were taken");
+
+        assert!(
+            actual == *expected,
+            "the {} at byte {offset} is {actual:?}, expected {expected:?}",
+            type_name::<T>()
+        );
+    }
+
+    /// Asserts that the next bytes written are `expected`, and moves past them.
+    #[track_caller]
+    pub fn assert_wrote_bytes(&mut self, expected: &[u8]) {
+        let offset = self.checked;
+        let actual = self.take(expected.len(), "bytes");
+
+        assert!(actual == expected, "the bytes at {offset} are {actual:02x?}, expected {expected:02x?}");
+    }
+
+    /// Asserts that every byte written has been checked.
+    #[track_caller]
+    pub fn assert_done(&self) {
+        assert!(
+            self.checked == self.bytes.len(),
+            "{} bytes were written past byte {}: {:02x?}",
+            self.bytes.len() - self.checked,
+            self.checked,
+            &self.bytes[self.checked..]
+        );
+    }
+
+    #[track_caller]
+    fn take(&mut self, len: usize, what: &str) -> &[u8] {
+        let start = self.checked;
+        let left = self.bytes.len() - start;
+
+        assert!(len <= left, "expected {len} bytes of {what} at byte {start}, but only {left} were written");
+
+        self.checked += len;
+        &self.bytes[start..self.checked]
+    }
+}
+
+impl Write for MockBinarySink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A step of the script of a [MockBinarySource].
+///
+/// [MockBinarySource]: MockBinarySource
+#[derive(Debug)]
+enum Step {
+    Bytes(Vec<u8>),
+    Error(io::ErrorKind),
+}
+
+/// A [Read] test double playing a script of records, raw bytes and errors, to test how code
+/// reading records handles failures and partial data.
+///
+/// The script is built step by step. A read never returns bytes of two steps, so every step
+/// boundary is a short read, like the ones sockets and pipes make, and splitting a record
+/// between steps tests its reassembly. An error step fails a single read and is then
+/// consumed, so reading can go on with the next step. Once the script is over the source
+/// reports its end. [with_max_read] makes reads shorter still.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, MockBinarySource};
+/// use std::io::ErrorKind;
+///
+/// let mut source = MockBinarySource::new()
+///     .then_record(&7u32)
+///     .then_bytes(&[1, 0])
+///     .then_error(ErrorKind::Interrupted)
+///     .then_bytes(&[0, 0])
+///     .then_error(ErrorKind::ConnectionReset);
+///
+/// assert_eq!(source.read_binary::<u32>().unwrap(), 7);
+/// // The record is reassembled across the short read and the interruption.
+/// assert_eq!(source.read_binary::<u32>().unwrap(), u32::from_le(1));
+/// assert_eq!(source.read_binary::<u32>().unwrap_err().kind(), ErrorKind::ConnectionReset);
+/// ```
+///
+/// [Read]: std::io::Read
+/// [with_max_read]: MockBinarySource::with_max_read
+#[derive(Debug, Default)]
+pub struct MockBinarySource {
+    steps: VecDeque<Step>,
+    max_read: Option<usize>,
+}
+
+impl MockBinarySource {
+    /// Creates a source with an empty script, which reports its end right away.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the bytes of `item` to the script, as written by [write_binary].
+    ///
+    /// [write_binary]: crate::BinaryWrite::write_binary
+    pub fn then_record<T>(self, item: &T) -> Self {
+        self.then_bytes(bytes::as_bytes(item))
+    }
+
+    /// Adds raw bytes to the script.
+    pub fn then_bytes(mut self, bytes: &[u8]) -> Self {
+        self.steps.push_back(Step::Bytes(bytes.to_vec()));
+        self
+    }
+
+    /// Adds a read failing with an error of the given kind to the script.
+    pub fn then_error(mut self, kind: io::ErrorKind) -> Self {
+        self.steps.push_back(Step::Error(kind));
+        self
+    }
+
+    /// Limits every read to at most `max` bytes, at least one.
+    pub fn with_max_read(mut self, max: usize) -> Self {
+        self.max_read = Some(max.max(1));
+        self
+    }
+
+    /// Returns whether the whole script has been read.
+    pub fn is_done(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl Read for MockBinarySource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.steps.front_mut() {
+                None => return Ok(0),
+                Some(Step::Error(kind)) => {
+                    let kind = *kind;
+                    self.steps.pop_front();
+                    return Err(io::Error::new(kind, "error injected by MockBinarySource"));
+                },
+                Some(Step::Bytes(bytes)) if bytes.is_empty() => {
+                    self.steps.pop_front();
+                },
+                Some(Step::Bytes(bytes)) => {
+                    let len = bytes.len().min(buf.len()).min(self.max_read.unwrap_or(usize::MAX));
+                    buf[..len].copy_from_slice(&bytes[..len]);
+                    bytes.drain(..len);
+
+                    if bytes.is_empty() {
+                        self.steps.pop_front();
+                    }
+
+                    return Ok(len);
+                }
+            }
+        }
+    }
+}
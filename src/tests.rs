@@ -1,7 +1,7 @@
 use crate::{BinaryWrite, BinaryRead};
 use std::{fs::{OpenOptions}, io::{self}};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(unused)]
 struct Test {
     f: u32,
@@ -37,12 +37,22 @@ fn write_file() -> io::Result<()> {
 
 #[test]
 fn read_file() -> io::Result<()> {
+    let s = Test::random();
+
+    OpenOptions::new()
+    .create(true)
+    .write(true)
+    .truncate(true)
+    .open("./test_read_file.bin")?
+    .write_binary(&s)?;
+
     let mut file = OpenOptions::new()
     .read(true)
-    .open("./test_file.bin")?;
+    .open("./test_read_file.bin")?;
 
     let a = file.read_binary::<Test>()?;
     println!("{a:?}");
+    assert_eq!(s, a);
 
     Ok(())
 }
@@ -64,3 +74,122 @@ fn read_write_buffer() -> io::Result<()> {
     assert_eq!(original, test);
     Ok(())
 }
+
+#[test]
+fn split_after_header() -> io::Result<()> {
+    let (header, record) = (Test::random(), Test::random());
+    let mut buf = Vec::new();
+
+    buf.write_binary(&header)?;
+    buf.write_binary(&record)?;
+
+    let mut cursor = io::Cursor::new(buf);
+    let (read_header, rest) = cursor.read_binary_split::<Test>()?;
+
+    assert_eq!(read_header, header);
+    assert_eq!(rest.read_binary::<Test>()?, record);
+    Ok(())
+}
+
+#[test]
+fn variable_width_field() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&u32::MAX)?;
+    buf.write_binary(&(u64::from(u32::MAX) + 1))?;
+    buf.write_binary(&7u32)?;
+
+    let mut cursor = io::Cursor::new(buf);
+
+    assert_eq!(cursor.read_u32_or_u64(false)?, u64::from(u32::MAX));
+    assert_eq!(cursor.read_u32_or_u64(true)?, 1 << 32);
+    assert_eq!(cursor.read_u32_or_u64(true).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
+mod deadline;
+mod validate;
+mod macros;
+mod verified;
+mod slices;
+mod seek;
+mod iter;
+mod report;
+mod fam;
+mod dedup;
+mod cbool;
+mod phantom;
+mod tagged;
+mod endian;
+mod tuple;
+mod enums;
+mod borrowed;
+mod slot;
+mod retry;
+#[cfg(feature = "bitflags")]
+mod flags;
+#[cfg(feature = "half")]
+mod float16;
+mod chain;
+mod datetime;
+mod large;
+mod seqlock;
+mod array;
+#[cfg(feature = "digest")]
+mod hashing;
+mod buffering;
+#[cfg(feature = "zstd-seekable")]
+mod seekable;
+mod durable;
+mod delta;
+mod envelope;
+mod sequence;
+mod packed;
+mod overflow;
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(any(unix, windows))]
+mod snapshot;
+mod framing;
+mod periodic;
+mod file;
+mod recover;
+mod crc;
+mod self_sized;
+mod take;
+#[cfg(feature = "ndarray")]
+mod ndim;
+mod columns;
+mod npy;
+mod map;
+#[cfg(feature = "glam")]
+mod vecmath;
+#[cfg(feature = "nalgebra")]
+mod linalg;
+mod patch;
+mod tee;
+mod scan;
+mod checksum;
+mod delimited;
+mod fragment;
+mod scatter;
+mod page;
+mod bitmap;
+mod separated;
+mod stride;
+mod throttle;
+mod convert;
+#[cfg(unix)]
+mod fd;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod aligned;
+mod wire;
+#[cfg(feature = "text")]
+mod text;
+#[cfg(feature = "memmap2")]
+mod mapped;
+mod lazy;
+mod indexed;
+#[cfg(feature = "testing")]
+mod compare;
+mod compile_fail;
@@ -0,0 +1,79 @@
+use crate::{aligned_buffer, AlignedBytes, BinaryWrite};
+use std::{io::{self, Cursor, Read}, mem::{align_of, size_of}};
+use super::Test;
+
+#[repr(C, align(64))]
+struct CacheLine {
+    data: [u8; 64],
+}
+
+#[test]
+fn length_and_alignment() {
+    let buffer = aligned_buffer::<CacheLine>();
+    assert_eq!(buffer.len(), 64);
+    assert_eq!(buffer.as_ptr() as usize % align_of::<CacheLine>(), 0);
+    assert!(buffer.iter().all(|b| *b == 0));
+
+    let buffer = aligned_buffer::<Test>();
+    assert_eq!(buffer.len(), size_of::<Test>());
+    assert_eq!(buffer.as_ptr() as usize % align_of::<Test>(), 0);
+
+    assert!(aligned_buffer::<()>().is_empty());
+}
+
+#[test]
+fn read_into_buffer() -> io::Result<()> {
+    let record = Test::random();
+    let mut bytes = Vec::new();
+    bytes.write_binary(&record)?;
+
+    let mut buffer = aligned_buffer::<Test>();
+    Cursor::new(bytes).read_exact(&mut buffer)?;
+
+    // SAFETY: the bytes were written from a Test.
+    assert_eq!(*unsafe { buffer.assume_init() }, record);
+
+    let mut buffer = aligned_buffer::<bool>();
+    buffer[0] = 2;
+    assert!(buffer.into_validated().is_err());
+    Ok(())
+}
+
+#[test]
+fn aligned_bytes_from_unaligned_vec() -> io::Result<()> {
+    let records = [Test::random(), Test::random(), Test::random()];
+    let mut bytes = vec![0];
+    records.iter().try_for_each(|record| bytes.write_binary(record))?;
+
+    // The records start at an odd address of the Vec.
+    let buffer = AlignedBytes::<Test>::from_bytes(&bytes[1..])?;
+
+    assert_eq!(buffer.records(), 3);
+    assert_eq!(buffer.len(), 3 * size_of::<Test>());
+    assert_eq!(buffer.as_ptr() as usize % align_of::<Test>(), 0);
+    // SAFETY: the bytes were written from records of Test.
+    assert_eq!(unsafe { buffer.as_records_unchecked() }, records);
+    assert_eq!(unsafe { buffer.clone().as_records_unchecked() }, records);
+
+    let error = AlignedBytes::<Test>::from_bytes(&bytes).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+fn aligned_bytes_fill_and_validate() -> io::Result<()> {
+    let buffer = AlignedBytes::<CacheLine>::with_records(2);
+    assert_eq!(buffer.as_ptr() as usize % align_of::<CacheLine>(), 0);
+    assert!(buffer.iter().all(|b| *b == 0));
+
+    let mut buffer = AlignedBytes::<bool>::read_from(&mut Cursor::new([1, 0, 1]), 3)?;
+    assert_eq!(buffer.as_records().unwrap(), [true, false, true]);
+
+    buffer[2] = 7;
+    assert_eq!(buffer.as_records().unwrap_err().offset, 2);
+
+    assert!(AlignedBytes::<u64>::with_records(0).as_records().unwrap().is_empty());
+    assert_eq!(AlignedBytes::<()>::with_records(4).as_records().unwrap().len(), 4);
+    assert!(AlignedBytes::<u32>::read_from(&mut Cursor::new([0; 7]), 2).is_err());
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::{from_array, read_binary_from_slice_into, to_array, write_binary_to_slice, write_into_vec, BinaryWrite, BufferTooSmall};
+use std::num::NonZeroU32;
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct MyStruct {
+    id: NonZeroU32,
+    value: i32,
+    flag: bool,
+    level: u8,
+}
+
+crate::validate!(MyStruct { id: NonZeroU32, value: i32, flag: bool, level: u8 });
+
+#[test]
+fn array_round_trip() {
+    let item = MyStruct { id: NonZeroU32::new(9).unwrap(), value: -4, flag: true, level: 3 };
+
+    let mut array = to_array::<_, 12>(&item);
+    assert_eq!(array[..4], 9u32.to_ne_bytes());
+    assert_eq!(from_array::<MyStruct, 12>(array).unwrap(), item);
+
+    array[8] = 2;
+    assert_eq!(from_array::<MyStruct, 12>(array).unwrap_err().field, Some("flag"));
+
+    assert_eq!(from_array::<u32, 4>(7u32.to_ne_bytes()).unwrap(), 7);
+}
+
+#[test]
+fn into_vec_matches_write() -> std::io::Result<()> {
+    let records = (0..64).map(|_| super::Test::random()).collect::<Vec<_>>();
+
+    let mut written = vec![0xAB];
+    let mut copied = vec![0xAB];
+
+    for record in &records {
+        written.write_binary(record)?;
+        write_into_vec(&mut copied, record);
+    }
+
+    assert_eq!(copied, written);
+    assert_eq!(copied.len(), 1 + 64 * std::mem::size_of::<super::Test>());
+    Ok(())
+}
+
+#[derive(Debug, Default, PartialEq)]
+#[repr(C)]
+struct Telemetry {
+    sequence: u32,
+    uptime_ms: u32,
+    temperature: f32,
+    battery_mv: u16,
+    flags: u16,
+}
+
+crate::validate!(Telemetry { sequence: u32, uptime_ms: u32, temperature: f32, battery_mv: u16, flags: u16 });
+
+#[test]
+fn fixed_buffer_round_trip() {
+    let samples = (0..16)
+        .map(|i| Telemetry { sequence: i, uptime_ms: i * 1000, temperature: 21.5 + i as f32, battery_mv: 3700 - i as u16, flags: 1 })
+        .collect::<Vec<_>>();
+
+    let mut buffer = [0u8; 256];
+    let mut used = 0;
+
+    for sample in &samples {
+        used += write_binary_to_slice(&mut buffer[used..], sample).unwrap();
+    }
+    assert_eq!(used, 256);
+
+    let error = write_binary_to_slice(&mut buffer[used..], &samples[0]).unwrap_err();
+    assert_eq!(error, BufferTooSmall { needed: 16, available: 0 });
+
+    let mut read = 0;
+    for sample in &samples {
+        let mut received = Telemetry::default();
+        read += read_binary_from_slice_into(&buffer[read..], &mut received).unwrap();
+        assert_eq!(&received, sample);
+    }
+
+    let mut untouched = Telemetry::default();
+    assert!(read_binary_from_slice_into(&buffer[250..], &mut untouched).is_err());
+    assert_eq!(untouched, Telemetry::default());
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn heapless_vec() {
+    let mut buffer = heapless::Vec::<u8, 20>::new();
+    let sample = Telemetry { sequence: 1, uptime_ms: 5, temperature: -3.0, battery_mv: 3300, flags: 0 };
+
+    crate::write_into_heapless_vec(&mut buffer, &sample).unwrap();
+    let error = crate::write_into_heapless_vec(&mut buffer, &sample).unwrap_err();
+    assert_eq!(error, BufferTooSmall { needed: 16, available: 4 });
+
+    let mut received = Telemetry::default();
+    assert_eq!(read_binary_from_slice_into(&buffer, &mut received).unwrap(), 16);
+    assert_eq!(received, sample);
+}
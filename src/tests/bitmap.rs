@@ -0,0 +1,63 @@
+use crate::{BinaryRead, BinaryWrite};
+use std::{io::{self, Cursor}, mem::offset_of};
+
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+struct Sparse {
+    id: u64,
+    altitude: f64,
+    speed: u32,
+    heading: u16,
+    flags: u16,
+}
+
+const FIELDS: [(usize, usize); 4] = [
+    (offset_of!(Sparse, altitude), 8),
+    (offset_of!(Sparse, speed), 4),
+    (offset_of!(Sparse, heading), 2),
+    (offset_of!(Sparse, flags), 2),
+];
+
+#[test]
+fn two_of_four_present() -> io::Result<()> {
+    let mut bytes = Vec::new();
+    bytes.write_binary(&0b0101u32)?;
+    bytes.write_binary(&1250.5f64)?;
+    bytes.write_binary(&270u16)?;
+    bytes.write_binary(&0xEEu8)?;
+
+    let mut cursor = Cursor::new(bytes);
+    let record = cursor.read_binary_bitmap::<Sparse>(&FIELDS)?;
+
+    assert_eq!(record, Sparse { altitude: 1250.5, heading: 270, ..Default::default() });
+    assert_eq!(cursor.read_binary::<u8>()?, 0xEE);
+    Ok(())
+}
+
+/// Forty bytes, since arrays only implement Default up to 32 elements.
+#[repr(C)]
+#[derive(Default)]
+struct Wide {
+    head: [u8; 32],
+    tail: [u8; 8],
+}
+
+#[test]
+fn wide_and_invalid_bitmaps() -> io::Result<()> {
+    // 40 one byte fields take a u64 bitmap.
+    let fields = (0..40).map(|i| (i, 1)).collect::<Vec<_>>();
+    let mut bytes = Vec::new();
+    bytes.write_binary(&(1u64 << 39))?;
+    bytes.write_binary(&7u8)?;
+
+    let record = Cursor::new(bytes).read_binary_bitmap::<Wide>(&fields)?;
+    assert_eq!(record.tail[7], 7);
+    assert!(record.head.iter().chain(&record.tail[..7]).all(|&byte| byte == 0));
+
+    let error = Cursor::new(0b10000u32.to_ne_bytes()).read_binary_bitmap::<Sparse>(&FIELDS).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let error = Cursor::new([0; 4]).read_binary_bitmap::<Sparse>(&[(20, 8)]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    Ok(())
+}
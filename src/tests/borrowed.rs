@@ -0,0 +1,59 @@
+use crate::{BinaryReadSlice, BinaryWrite};
+use super::Test;
+use std::{borrow::Cow, io::{self, Cursor}, mem::size_of, slice};
+
+/// Returns `len` zeroed bytes starting at an 8 bytes boundary.
+fn aligned_storage(len: usize) -> Vec<u64> {
+    vec![0; len.div_ceil(8)]
+}
+
+fn as_bytes_mut(storage: &mut [u64]) -> &mut [u8] {
+    // SAFETY: the storage holds this many bytes, and any byte is a valid u64 byte.
+    unsafe { slice::from_raw_parts_mut(storage.as_mut_ptr() as *mut u8, storage.len() * 8) }
+}
+
+#[test]
+fn aligned_is_borrowed() -> io::Result<()> {
+    let records = [Test::random(), Test::random()];
+    let mut written = Vec::new();
+    written.write_binary(&records)?;
+
+    let mut storage = aligned_storage(written.len());
+    let bytes = as_bytes_mut(&mut storage);
+    bytes[..written.len()].copy_from_slice(&written);
+
+    let mut reader = &bytes[..written.len()];
+
+    for record in &records {
+        match reader.read_binary_auto::<Test>()? {
+            Cow::Borrowed(read) => assert_eq!(read, record),
+            Cow::Owned(_) => panic!("aligned record was copied")
+        }
+    }
+
+    assert!(reader.is_empty());
+    assert_eq!(reader.read_binary_auto::<Test>().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
+#[test]
+fn misaligned_is_owned() -> io::Result<()> {
+    let record = Test::random();
+    let mut written = vec![0xAA];
+    written.write_binary(&record)?;
+
+    let mut storage = aligned_storage(written.len());
+    let bytes = as_bytes_mut(&mut storage);
+    bytes[..written.len()].copy_from_slice(&written);
+
+    let mut cursor = Cursor::new(&bytes[..written.len()]);
+    cursor.set_position(1);
+
+    match cursor.read_binary_auto::<Test>()? {
+        Cow::Owned(read) => assert_eq!(read, record),
+        Cow::Borrowed(_) => panic!("misaligned record was borrowed")
+    }
+
+    assert_eq!(cursor.position(), 1 + size_of::<Test>() as u64);
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use crate::{BinaryWrite, BufferingBinaryWriter};
+use super::Test;
+use std::io::{self, Write};
+
+/// Accepts at most 4 bytes per call, and fails with `WouldBlock` once it holds `capacity`
+/// bytes until they are drained.
+struct Bounded {
+    data: Vec<u8>,
+    drained: usize,
+    capacity: usize,
+}
+
+impl Bounded {
+    fn drain(&mut self) {
+        self.drained = self.data.len();
+    }
+}
+
+impl Write for Bounded {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let free = self.capacity - (self.data.len() - self.drained);
+
+        if free == 0 {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let len = buf.len().min(4).min(free);
+        self.data.extend_from_slice(&buf[..len]);
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn buffer_until_drained() -> io::Result<()> {
+    let items = [Test::random(), Test::random()];
+    let mut writer = BufferingBinaryWriter::new(Bounded { data: Vec::new(), drained: 0, capacity: 16 });
+
+    for item in &items {
+        writer.write_binary(item)?;
+    }
+
+    let total = 2 * std::mem::size_of::<Test>();
+    assert_eq!(writer.get_ref().data.len(), 16);
+    assert_eq!(writer.pending(), total - 16);
+    assert!(writer.flush().is_err_and(|e| e.kind() == io::ErrorKind::WouldBlock));
+
+    while !writer.try_flush_pending()? {
+        writer.get_mut().drain();
+    }
+
+    assert_eq!(writer.pending(), 0);
+    writer.flush()?;
+
+    let mut expected = Vec::new();
+    for item in &items {
+        expected.write_binary(item)?;
+    }
+
+    assert_eq!(writer.into_inner().data, expected);
+    Ok(())
+}
+
+#[test]
+fn write_through_when_ready() -> io::Result<()> {
+    let mut writer = BufferingBinaryWriter::new(Bounded { data: Vec::new(), drained: 0, capacity: usize::MAX });
+    writer.write_binary(&[7u32; 8])?;
+
+    assert_eq!(writer.pending(), 0);
+    assert!(writer.try_flush_pending()?);
+    assert_eq!(writer.get_ref().data.len(), 32);
+
+    Ok(())
+}
+
+/// Accepts 2 bytes, then fails with `BrokenPipe` once.
+struct Failing {
+    data: Vec<u8>,
+    failed: bool,
+}
+
+impl Write for Failing {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.data.len() >= 2 && !self.failed {
+            self.failed = true;
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+
+        let len = buf.len().min(2);
+        self.data.extend_from_slice(&buf[..len]);
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn queued_write_reports_error_later() -> io::Result<()> {
+    let mut writer = BufferingBinaryWriter::new(Failing { data: Vec::new(), failed: false });
+
+    assert_eq!(writer.write(&[1, 2, 3, 4])?, 4);
+    assert_eq!(writer.pending(), 2);
+    assert!(writer.flush().is_err_and(|e| e.kind() == io::ErrorKind::BrokenPipe));
+
+    writer.flush()?;
+    assert_eq!(writer.into_inner().data, [1, 2, 3, 4]);
+
+    Ok(())
+}
+
+#[test]
+fn pending_limit() -> io::Result<()> {
+    let mut writer = BufferingBinaryWriter::new(Bounded { data: Vec::new(), drained: 0, capacity: 4 })
+        .with_max_pending(8);
+
+    // Larger than the limit, but nothing is pending.
+    writer.write_all(&[0; 12])?;
+    assert_eq!(writer.pending(), 8);
+
+    assert!(writer.write(&[1]).is_err_and(|e| e.kind() == io::ErrorKind::WouldBlock));
+    assert_eq!(writer.pending(), 8);
+
+    writer.get_mut().drain();
+    writer.write_all(&[1; 4])?;
+    assert_eq!(writer.pending(), 8);
+
+    while !writer.try_flush_pending()? {
+        writer.get_mut().drain();
+    }
+
+    let mut expected = vec![0; 12];
+    expected.extend_from_slice(&[1; 4]);
+    assert_eq!(writer.into_inner().data, expected);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{BinaryRead, BinaryWrite, CBool, CBool16, CBool32, CBool8};
+use std::{io::{self, Cursor}, mem::size_of};
+
+#[repr(C)]
+struct Flags {
+    enabled: CBool32,
+    visible: CBool16,
+    dirty: CBool8,
+}
+
+#[test]
+fn nonzero_is_true() -> io::Result<()> {
+    assert_eq!(size_of::<Flags>(), 8);
+
+    // enabled = 0x00000002, visible = 0, dirty = 0xFF.
+    let mut fixture = [0u8; 8];
+    fixture[..4].copy_from_slice(&2u32.to_ne_bytes());
+    fixture[6] = 0xFF;
+
+    let flags = Cursor::new(fixture).read_binary::<Flags>()?;
+
+    assert!(flags.enabled.get());
+    assert_eq!(flags.enabled.raw(), 2);
+    assert_eq!(flags.enabled.get_strict(), None);
+    assert!(!flags.visible.get());
+    assert!(flags.dirty.get());
+
+    Ok(())
+}
+
+#[test]
+fn writes_canonical_values() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&CBool32::new(true))?;
+    buf.write_binary(&CBool32::new(false))?;
+
+    assert_eq!(buf[..4], 1u32.to_ne_bytes());
+    assert_eq!(buf[4..], 0u32.to_ne_bytes());
+    Ok(())
+}
+
+#[test]
+fn strict_rejects_other_values() -> io::Result<()> {
+    type Strict = CBool<u32, true>;
+
+    let mut cursor = Cursor::new(2u32.to_ne_bytes());
+    let error = cursor.read_binary_validated::<Strict>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let mut cursor = Cursor::new(2u32.to_ne_bytes());
+    assert!(cursor.read_binary_validated::<CBool32>()?.get());
+
+    let mut cursor = Cursor::new(1u32.to_ne_bytes());
+    assert!(cursor.read_binary_validated::<Strict>()?.get());
+
+    Ok(())
+}
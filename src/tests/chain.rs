@@ -0,0 +1,183 @@
+use crate::{BinaryWrite, ChainBroken, ChainedLogReader, ChainedLogWriter, FileHasher, LinkBroken, LinkedFileReader};
+use std::{fs, hash::{DefaultHasher, Hasher}, io::{self, Cursor}, mem::{offset_of, size_of}};
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Entry {
+    sequence: u64,
+    prev: [u8; 32],
+    amount: i64,
+}
+
+fn hash(bytes: &[u8]) -> [u8; 32] {
+    let mut out = [0; 32];
+
+    for (seed, chunk) in out.chunks_exact_mut(8).enumerate() {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_usize(seed);
+        hasher.write(bytes);
+        chunk.copy_from_slice(&hasher.finish().to_ne_bytes());
+    }
+
+    out
+}
+
+/// Computes [hash] incrementally.
+struct TestHasher([DefaultHasher; 4]);
+
+impl TestHasher {
+    fn new() -> Self {
+        Self(std::array::from_fn(|seed| {
+            let mut hasher = DefaultHasher::new();
+            hasher.write_usize(seed);
+            hasher
+        }))
+    }
+}
+
+impl FileHasher for TestHasher {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.iter_mut().for_each(|hasher| hasher.write(bytes));
+    }
+
+    fn finish_reset(&mut self) -> [u8; 32] {
+        let hashers = std::mem::replace(self, Self::new()).0;
+        let mut out = [0; 32];
+
+        for (chunk, hasher) in out.chunks_exact_mut(8).zip(hashers) {
+            chunk.copy_from_slice(&hasher.finish().to_ne_bytes());
+        }
+
+        out
+    }
+}
+
+fn write_log(count: u64) -> io::Result<Vec<u8>> {
+    let mut writer = ChainedLogWriter::new(Vec::new(), offset_of!(Entry, prev), hash);
+
+    for sequence in 0..count {
+        writer.write_record(&Entry { sequence, prev: [0xAA; 32], amount: sequence as i64 * 10 })?;
+    }
+
+    Ok(writer.into_inner())
+}
+
+fn read_log(buf: Vec<u8>) -> impl Iterator<Item = io::Result<Entry>> {
+    ChainedLogReader::new(Cursor::new(buf), offset_of!(Entry, prev), hash)
+}
+
+#[test]
+fn verify_chain() -> io::Result<()> {
+    let buf = write_log(4)?;
+    assert_eq!(buf[8..40], [0; 32]);
+
+    let entries = read_log(buf).collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[3].amount, 30);
+    assert_eq!(entries[2].prev, hash(crate::bytes::as_bytes(&entries[1])));
+
+    Ok(())
+}
+
+#[test]
+fn tampering_breaks_next_record() -> io::Result<()> {
+    let mut buf = write_log(4)?;
+    let size = size_of::<Entry>();
+
+    // Changes the amount of the second record.
+    buf[size + offset_of!(Entry, amount)] ^= 1;
+
+    let mut reader = read_log(buf);
+    assert_eq!(reader.next().unwrap()?.sequence, 0);
+    assert_eq!(reader.next().unwrap()?.sequence, 1);
+
+    let error = reader.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.get_ref().and_then(|e| e.downcast_ref::<ChainBroken>()), Some(&ChainBroken { index: 2 }));
+    assert!(reader.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn hash_out_of_bounds() {
+    ChainedLogWriter::<_, Entry, _>::new(Vec::new(), offset_of!(Entry, amount), hash);
+}
+
+#[repr(C)]
+struct Shard {
+    prev: [u8; 32],
+    index: u32,
+    records: u32,
+}
+
+/// Writes three linked shards of four records each, returning their paths.
+fn write_shards() -> io::Result<[&'static str; 3]> {
+    let paths = ["./test_shard_0.bin", "./test_shard_1.bin", "./test_shard_2.bin"];
+    let mut prev = [0; 32];
+
+    for (index, path) in paths.iter().enumerate() {
+        let mut bytes = Vec::new();
+        bytes.write_binary(&Shard { prev, index: index as u32, records: 4 })?;
+        (0..4).try_for_each(|record| bytes.write_binary(&(index as u64 * 4 + record)))?;
+
+        prev = hash(&bytes);
+        fs::write(path, bytes)?;
+    }
+
+    Ok(paths)
+}
+
+fn read_shards(paths: &[&str]) -> impl Iterator<Item = io::Result<u64>> {
+    LinkedFileReader::<Shard, _, _>::new(paths.iter().copied(), offset_of!(Shard, prev), TestHasher::new())
+}
+
+#[test]
+fn verify_linked_files() -> io::Result<()> {
+    let [first, second, third] = write_shards()?;
+    let mut reader = LinkedFileReader::<Shard, u64, _>::new([first, second, third], offset_of!(Shard, prev), TestHasher::new());
+
+    assert!(reader.header().is_none());
+    assert_eq!(reader.next().transpose()?, Some(0));
+    assert_eq!(reader.header().map(|header| header.index), Some(0));
+
+    assert_eq!(reader.collect::<io::Result<Vec<_>>>()?, (1..12).collect::<Vec<_>>());
+
+    // Reordering the last two shards breaks the link of the first one out of place.
+    let records = read_shards(&[first, third, second]).collect::<Vec<_>>();
+    assert_eq!(records.len(), 5);
+    assert!(records[..4].iter().all(Result::is_ok));
+
+    let error = records[4].as_ref().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.get_ref().and_then(|e| e.downcast_ref::<LinkBroken>()), Some(&LinkBroken { file: 1 }));
+
+    // So does leaving one out.
+    let error = read_shards(&[first, third]).find_map(Result::err).unwrap();
+    assert_eq!(error.get_ref().and_then(|e| e.downcast_ref::<LinkBroken>()), Some(&LinkBroken { file: 1 }));
+    Ok(())
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn linked_files_with_digest() -> io::Result<()> {
+    use sha2::{Digest, Sha256};
+
+    let paths = ["./test_digest_shard_0.bin", "./test_digest_shard_1.bin"];
+    let mut prev = [0; 32];
+
+    for (index, path) in paths.iter().enumerate() {
+        let mut bytes = Vec::new();
+        bytes.write_binary(&Shard { prev, index: index as u32, records: 2 })?;
+        bytes.write_binary(&[index as u64; 2])?;
+
+        prev = Sha256::digest(&bytes).into();
+        fs::write(path, bytes)?;
+    }
+
+    let mut reader = LinkedFileReader::<Shard, u64, _>::new(paths, offset_of!(Shard, prev), Sha256::new());
+    assert_eq!(reader.by_ref().collect::<io::Result<Vec<_>>>()?, [0, 0, 1, 1]);
+    assert_eq!(reader.last_hash(), &prev);
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use crate::{validate, BinaryRead, BinaryWrite, Checksum, ChecksumMismatch, Validate};
+use std::{io::{self, Cursor}, mem::offset_of};
+
+/// The IPv4 header of a captured UDP datagram, from 192.168.0.1 to 192.168.0.199.
+const IPV4_HEADER: [u8; 20] = [
+    0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11,
+    0xB8, 0x61, 0xC0, 0xA8, 0x00, 0x01, 0xC0, 0xA8, 0x00, 0xC7,
+];
+
+/// A Modbus RTU request reading 10 holding registers of slave 1, with its CRC.
+const MODBUS_REQUEST: [u8; 8] = [0x01, 0x03, 0x00, 0x00, 0x00, 0x0A, 0xC5, 0xCD];
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct Ipv4Header {
+    version_ihl: u8,
+    tos: u8,
+    total_len: [u8; 2],
+    id: [u8; 2],
+    flags_fragment: [u8; 2],
+    ttl: u8,
+    protocol: u8,
+    checksum: [u8; 2],
+    source: [u8; 4],
+    destination: [u8; 4],
+}
+
+validate!(Ipv4Header {
+    version_ihl: u8,
+    tos: u8,
+    total_len: [u8; 2],
+    id: [u8; 2],
+    flags_fragment: [u8; 2],
+    ttl: u8,
+    protocol: u8,
+    #[checksum = Checksum::Internet.of(..)]
+    checksum: [u8; 2],
+    source: [u8; 4],
+    destination: [u8; 4],
+});
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct ReadHoldingRegisters {
+    slave: u8,
+    function: u8,
+    address: [u8; 2],
+    count: [u8; 2],
+    crc: [u8; 2],
+}
+
+validate!(ReadHoldingRegisters {
+    slave: u8,
+    #[magic = 3]
+    function: u8,
+    address: [u8; 2],
+    count: [u8; 2],
+    #[checksum = Checksum::Crc16Modbus.of(..6).little_endian()]
+    crc: [u8; 2],
+});
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct Chunk {
+    len: u32,
+    kind: [u8; 4],
+    data: [u8; 8],
+    crc: u32,
+}
+
+validate!(Chunk {
+    len: u32,
+    kind: [u8; 4],
+    data: [u8; 8],
+    #[checksum = Checksum::Crc32.of(4..16)]
+    crc: u32,
+});
+
+#[test]
+fn check_values() {
+    assert_eq!(Checksum::Crc16Arc.compute(b"123456789"), 0xBB3D);
+    assert_eq!(Checksum::Crc16Modbus.compute(b"123456789"), 0x4B37);
+    assert_eq!(Checksum::Crc16Ccitt.compute(b"123456789"), 0x29B1);
+    assert_eq!(Checksum::Crc32.compute(b"123456789"), 0xCBF4_3926);
+
+    // An odd length is padded with a zero byte.
+    assert_eq!(Checksum::Internet.compute(&[0x01]), 0xFEFF);
+    assert_eq!(Checksum::Internet.compute(&[0xFF, 0xFF, 0x00, 0x01]), 0xFFFE);
+}
+
+#[test]
+fn captured_ipv4_header() -> io::Result<()> {
+    let header = Cursor::new(IPV4_HEADER).read_binary_validated::<Ipv4Header>()?;
+    assert_eq!(header.checksum, [0xB8, 0x61]);
+
+    let mut buffer = Vec::new();
+    buffer.write_binary_with_checksums(&Ipv4Header { checksum: [0; 2], ..header })?;
+    assert_eq!(buffer, IPV4_HEADER);
+
+    // Decrementing the TTL, like a router, breaks the checksum.
+    buffer[8] -= 1;
+    let error = Ipv4Header::validate_bytes(&buffer).unwrap_err();
+    assert_eq!((error.field, error.offset), (Some("checksum"), offset_of!(Ipv4Header, checksum)));
+
+    let mismatch = Checksum::Internet.of(..).verify(&buffer, 10).unwrap_err();
+    assert_eq!(mismatch, ChecksumMismatch { stored: 0xB861, computed: 0xB961 });
+    Ok(())
+}
+
+#[test]
+fn captured_modbus_request() -> io::Result<()> {
+    let request = Cursor::new(MODBUS_REQUEST).read_binary_validated::<ReadHoldingRegisters>()?;
+    assert_eq!(request.count, [0x00, 0x0A]);
+
+    let mut buffer = Vec::new();
+    buffer.write_binary_with_checksums(&ReadHoldingRegisters { crc: [0; 2], ..request })?;
+    assert_eq!(buffer, MODBUS_REQUEST);
+
+    let mut corrupted = MODBUS_REQUEST;
+    corrupted[5] = 0x0B;
+    assert!(!ReadHoldingRegisters::is_valid_bytes(&corrupted));
+    Ok(())
+}
+
+#[test]
+fn partial_range() -> io::Result<()> {
+    let chunk = Chunk { len: 8, kind: *b"IDAT", data: *b"pixels!!", crc: 0 };
+
+    let mut buffer = Vec::new();
+    buffer.write_binary_with_checksums(&chunk)?;
+    assert_eq!(chunk.crc, 0);
+    assert_eq!(buffer[16..], Checksum::Crc32.compute(&buffer[4..16]).to_be_bytes());
+
+    // The length isn't covered.
+    buffer[0] = 9;
+    assert!(Chunk::is_valid_bytes(&buffer));
+
+    buffer[4] = b'J';
+    assert!(!Chunk::is_valid_bytes(&buffer));
+    Ok(())
+}
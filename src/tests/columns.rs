@@ -0,0 +1,93 @@
+use crate::{columns, join_columns, read_columns, split_columns, write_columns, ColumnSet, Columns};
+use std::{io::{self, Cursor}, marker::PhantomData, mem::size_of};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tick {
+    flag: u8,
+    timestamp: u64,
+    price: f32,
+    volume: u16,
+}
+
+columns!(Tick { flag: u8, timestamp: u64, price: f32, volume: u16 });
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Pair<T>(u16, T, PhantomData<T>);
+
+columns!([T] Pair<T>(u16, T, PhantomData<T>));
+
+fn ticks(count: u64) -> Vec<Tick> {
+    (0..count)
+        .map(|i| Tick { flag: i as u8 & 1, timestamp: 1_000 + i, price: i as f32 * 0.25, volume: i as u16 * 3 })
+        .collect()
+}
+
+#[test]
+fn field_metadata() {
+    let fields = Tick::FIELDS.iter().map(|f| (f.name, f.offset, f.size)).collect::<Vec<_>>();
+    assert_eq!(fields, [("flag", 0, 1), ("timestamp", 8, 8), ("price", 16, 4), ("volume", 20, 2)]);
+
+    let fields = Pair::<u32>::FIELDS.iter().map(|f| (f.name, f.offset, f.size)).collect::<Vec<_>>();
+    assert_eq!(fields, [(".0", 0, 2), (".1", 4, 4), (".2", 8, 0)]);
+}
+
+#[test]
+fn split_join_round_trip() -> io::Result<()> {
+    let records = ticks(100);
+    let columns = split_columns(&records);
+
+    assert_eq!(columns.len(), 100);
+
+    for (field, bytes) in columns.columns() {
+        assert_eq!(bytes.len(), field.size * records.len());
+    }
+
+    assert_eq!(columns.column_as::<u64>("timestamp").unwrap(), (1_000..1_100).collect::<Vec<_>>());
+    assert_eq!(columns.column_as::<u16>("volume").unwrap()[5], 15);
+    assert!(columns.column_as::<u32>("timestamp").is_none());
+    assert!(columns.column("missing").is_none());
+
+    assert_eq!(join_columns::<Tick>(&columns)?, records);
+
+    let rebuilt = ColumnSet::from_columns::<Tick>(columns.clone().into_columns())?;
+    assert_eq!(rebuilt, columns);
+
+    let pairs = [Pair(1, 'a', PhantomData), Pair(2, 'b', PhantomData)];
+    assert_eq!(join_columns::<Pair<char>>(&split_columns(&pairs))?, pairs);
+
+    Ok(())
+}
+
+#[test]
+fn join_mismatched_columns() {
+    let columns = split_columns(&ticks(3));
+    assert_eq!(join_columns::<Pair<u64>>(&columns).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    let mut raw = columns.into_columns();
+    raw[2].pop();
+    assert_eq!(ColumnSet::from_columns::<Tick>(raw).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn streaming_columns() -> io::Result<()> {
+    let records = ticks(33);
+    let mut sinks = vec![Vec::new(); 4];
+
+    write_columns(&records, &mut sinks)?;
+
+    for (field, sink) in Tick::FIELDS.iter().zip(&sinks) {
+        assert_eq!(sink.len(), field.size * records.len());
+    }
+
+    assert_eq!(sinks[1].len(), size_of::<u64>() * 33);
+
+    let mut sources = sinks.into_iter().map(Cursor::new).collect::<Vec<_>>();
+    assert_eq!(read_columns::<Tick, _>(&mut sources, 33)?, records);
+
+    let error = write_columns(&records, &mut [Vec::new()]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::{assert_eq_binary, binary_eq_report, binary_fields, BinaryRead};
+use std::mem::{offset_of, size_of};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    channel: u8,
+    // Three bytes of padding.
+    value: f32,
+    tags: [u16; 4],
+}
+
+binary_fields!(Sample { channel: u8, value: f32, tags: [u16; 4] });
+
+#[repr(C)]
+struct Pair(u32, i64);
+
+binary_fields!(Pair(u32, i64));
+
+#[test]
+fn report_first_difference() {
+    let left = Sample { channel: 1, value: 0.5, tags: [1, 2, 3, 4] };
+    let right = Sample { tags: [1, 2, 9, 4], ..left };
+
+    let report = binary_eq_report(&left, &right).unwrap();
+    let offset = offset_of!(Sample, tags) + 2 * size_of::<u16>();
+
+    assert_eq!(report.offset, offset);
+    assert_eq!((report.field.name(), report.field.ty()), ("tags", "[u16; 4]"));
+    assert_eq!(report.field.offset(), offset_of!(Sample, tags));
+    assert_eq!(report.left, "[1, 2, 3, 4]");
+    assert_eq!(report.right, "[1, 2, 9, 4]");
+    assert_eq!(report.excerpt_offset, offset - 8);
+    assert_eq!(report.left_excerpt.len(), size_of::<Sample>() - report.excerpt_offset);
+
+    let message = report.to_string();
+    assert!(message.starts_with(&format!("first difference at byte {offset}, in field `tags: [u16; 4]` (bytes 8..16)")));
+
+    let report = binary_eq_report(&Pair(1, -1), &Pair(1, -2)).unwrap();
+    assert_eq!((report.field.name(), report.left.as_str(), report.right.as_str()), (".1", "-1", "-2"));
+}
+
+#[test]
+fn padding_is_ignored() {
+    let mut bytes = [0u8; size_of::<Sample>()];
+    let sample = (&bytes[..]).read_binary::<Sample>().unwrap();
+
+    bytes[1..4].copy_from_slice(&[0xFF; 3]);
+    let padded = (&bytes[..]).read_binary::<Sample>().unwrap();
+
+    assert!(binary_eq_report(&sample, &padded).is_none());
+    assert_eq_binary!(sample, padded);
+}
+
+#[test]
+fn floats_compare_bits() {
+    let nan = Sample { channel: 0, value: f32::NAN, tags: [0; 4] };
+    assert_eq_binary!(nan, nan);
+
+    let negative_zero = Sample { value: -0.0, ..nan };
+    let zero = Sample { value: 0.0, ..nan };
+    assert_eq!(binary_eq_report(&negative_zero, &zero).unwrap().field.name(), "value");
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed: sample 3\nfirst difference at byte 0, in field `channel: u8`")]
+fn assert_panics_with_report() {
+    let left = Sample { channel: 1, value: 0.0, tags: [0; 4] };
+    assert_eq_binary!(left, Sample { channel: 2, ..left }, "sample {}", 3);
+}
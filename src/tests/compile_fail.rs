@@ -0,0 +1,11 @@
+/// Checks that misuses of the macros and traits fail to compile with the intended diagnostic,
+/// comparing each error with its `.stderr` snapshot.
+///
+/// Having a passing case makes trybuild build the cases instead of only checking them, so the
+/// size assertions only evaluated for each instantiation of a generic function are caught too.
+#[test]
+fn compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/pass/*.rs");
+    cases.compile_fail("tests/ui/*.rs");
+}
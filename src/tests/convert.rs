@@ -0,0 +1,52 @@
+use crate::{convert_records, BinaryRead, BinaryWrite};
+use std::{fs::File, io::{self, BufReader, BufWriter, Cursor, Write}};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Old {
+    id: u32,
+    celsius: i16,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct New {
+    id: u64,
+    kelvin: f32,
+    flags: u8,
+}
+
+fn migrate(old: Old) -> New {
+    New { id: u64::from(old.id), kelvin: f32::from(old.celsius) + 273.15, flags: 1 }
+}
+
+#[test]
+fn convert_file() -> io::Result<()> {
+    let records = (0..1000).map(|id| Old { id, celsius: id as i16 - 500 }).collect::<Vec<_>>();
+    let mut file = BufWriter::new(File::create("./test_convert_old.bin")?);
+    records.iter().try_for_each(|record| file.write_binary(record))?;
+    file.flush()?;
+
+    let mut reader = BufReader::new(File::open("./test_convert_old.bin")?);
+    let mut writer = BufWriter::new(File::create("./test_convert_new.bin")?);
+    assert_eq!(convert_records(&mut reader, &mut writer, migrate)?, 1000);
+    writer.flush()?;
+
+    let converted = File::open("./test_convert_new.bin")?.read_binary_vec::<New>(1000)?;
+    assert_eq!(converted, records.into_iter().map(migrate).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn convert_truncated() {
+    let mut bytes = Vec::new();
+    bytes.write_binary(&Old { id: 1, celsius: 20 }).unwrap();
+    bytes.write_binary(&Old { id: 2, celsius: 21 }).unwrap();
+    bytes.pop();
+
+    let mut output = Vec::new();
+    let error = convert_records(&mut Cursor::new(bytes), &mut output, migrate).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(Cursor::new(output).read_binary::<New>().unwrap(), migrate(Old { id: 1, celsius: 20 }));
+}
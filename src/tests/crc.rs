@@ -0,0 +1,69 @@
+use crate::{crc32, BinaryRead, BinaryWrite};
+use std::{io::{self, Cursor}, mem::offset_of};
+
+#[test]
+fn crc32_vectors() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"a"), 0xE8B7_BE43);
+    assert_eq!(crc32(b"abc"), 0x3524_41C2);
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+    assert_eq!(crc32(&[0; 32]), 0x190A_55AD);
+    assert_eq!(crc32(&[0xFF; 32]), 0xFF6C_AB0B);
+}
+
+#[test]
+fn crc32_of_byte_ranges() {
+    let buffer = b"header123456789trailer";
+
+    assert_eq!(crc32(&buffer[6..15]), crc32(b"123456789"));
+    assert_ne!(crc32(&buffer[6..14]), crc32(b"123456789"));
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct Sealed {
+    sequence: u64,
+    crc: u32,
+    kind: u16,
+    flags: u16,
+    body: [u8; 24],
+}
+
+#[test]
+fn self_checksummed_record() -> io::Result<()> {
+    let crc_offset = offset_of!(Sealed, crc);
+    let record = Sealed { sequence: 9, crc: 0, kind: 2, flags: 1, body: [7; 24] };
+
+    let mut buffer = Vec::new();
+    buffer.write_binary_self_crc(&record, crc_offset)?;
+    assert_eq!(record.crc, 0);
+
+    let read = Cursor::new(&buffer).read_binary_self_crc::<Sealed>(crc_offset)?;
+    assert_eq!(read.crc, crc32(&[&buffer[..crc_offset], &buffer[crc_offset + 4..]].concat()));
+    assert_eq!(Sealed { crc: 0, ..read }, record);
+
+    for corrupted in [0, crc_offset, crc_offset + 4, buffer.len() - 1] {
+        let mut bytes = buffer.clone();
+        bytes[corrupted] ^= 0x10;
+
+        let error = Cursor::new(&bytes).read_binary_self_crc::<Sealed>(crc_offset).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn self_checksum_out_of_record() {
+    let mut buffer = Vec::new();
+    let error = buffer.write_binary_self_crc(&0u32, 1).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(buffer.is_empty());
+
+    // The offset is rejected before consuming anything from the source.
+    let mut cursor = Cursor::new([0u8; 8]);
+    let error = cursor.read_binary_self_crc::<u32>(usize::MAX).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(cursor.position(), 0);
+}
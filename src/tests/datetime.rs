@@ -0,0 +1,103 @@
+use crate::{BinDateTimeUtc, BinaryRead, BinaryWrite, Validate};
+use std::{io::{self, Cursor}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+#[test]
+fn system_time_round_trip() -> io::Result<()> {
+    for time in [
+        UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+        UNIX_EPOCH - Duration::new(86_400, 250_000_000),
+        UNIX_EPOCH,
+    ] {
+        let timestamp = BinDateTimeUtc::try_from(time).unwrap();
+
+        let mut buf = Vec::new();
+        buf.write_binary(&timestamp)?;
+        assert_eq!(buf.len(), 16);
+
+        let read = Cursor::new(buf).read_binary_validated::<BinDateTimeUtc>()?;
+        assert_eq!(SystemTime::try_from(read).unwrap(), time);
+    }
+
+    let before = BinDateTimeUtc::try_from(UNIX_EPOCH - Duration::new(86_400, 250_000_000)).unwrap();
+    assert_eq!((before.secs(), before.nanos()), (-86_401, 750_000_000));
+    assert_eq!(before.to_string(), "1969-12-30T23:59:59.750000000Z");
+    assert!(before < BinDateTimeUtc::UNIX_EPOCH);
+
+    Ok(())
+}
+
+#[test]
+fn reject_invalid_nanos() {
+    let mut bytes = [0u8; 16];
+    bytes[8..12].copy_from_slice(&1_000_000_000u32.to_ne_bytes());
+
+    let error = BinDateTimeUtc::validate_bytes(&bytes).unwrap_err();
+    assert_eq!(error.field, Some("nanos"));
+    assert_eq!(error.offset, 8);
+
+    bytes[8..12].copy_from_slice(&999_999_999u32.to_ne_bytes());
+    assert!(BinDateTimeUtc::is_valid_bytes(&bytes));
+}
+
+#[test]
+fn display() {
+    let timestamp = BinDateTimeUtc::new(951_782_400, 1).unwrap();
+    assert_eq!(timestamp.to_string(), "2000-02-29T00:00:00.000000001Z");
+}
+
+#[cfg(all(feature = "chrono", feature = "time"))]
+#[test]
+fn same_wire_format_across_libraries() -> io::Result<()> {
+    let chrono_time = chrono::DateTime::from_timestamp(1_700_000_000, 42).unwrap();
+    let time_time = time::OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_000_000_042).unwrap()
+        .to_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+
+    let mut from_chrono = Vec::new();
+    from_chrono.write_binary(&BinDateTimeUtc::try_from(chrono_time).unwrap())?;
+
+    let mut from_time = Vec::new();
+    from_time.write_binary(&BinDateTimeUtc::from(time_time))?;
+
+    assert_eq!(from_chrono, from_time);
+
+    let read = Cursor::new(from_time).read_binary_validated::<BinDateTimeUtc>()?;
+    assert_eq!(chrono::DateTime::try_from(read).unwrap(), chrono_time);
+    assert_eq!(time::OffsetDateTime::try_from(read).unwrap(), time_time);
+
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_leap_second() {
+    let leap = chrono::DateTime::from_timestamp(1_483_228_799, 1_500_000_000).unwrap();
+    assert!(BinDateTimeUtc::try_from(leap).is_err());
+}
+
+#[test]
+fn duration_round_trip() -> io::Result<()> {
+    let durations = [Duration::new(3, 141_592_653), Duration::from_nanos(1), Duration::MAX, Duration::ZERO];
+
+    let mut buf = Vec::new();
+    for duration in durations {
+        buf.write_duration(duration)?;
+    }
+    buf.write_system_time(UNIX_EPOCH + durations[0])?;
+    assert_eq!(buf.len(), 5 * 12);
+
+    let mut cursor = Cursor::new(buf);
+    for duration in durations {
+        assert_eq!(cursor.read_duration()?, duration);
+    }
+    assert_eq!(cursor.read_system_time()?, UNIX_EPOCH + durations[0]);
+
+    let error = Vec::new().write_system_time(UNIX_EPOCH - Duration::from_secs(1)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    let mut bytes = [0u8; 12];
+    bytes[8..].copy_from_slice(&1_000_000_000u32.to_le_bytes());
+    let error = Cursor::new(bytes).read_duration().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::{BinaryRead, BinaryWrite, DeadlineExceeded, DeadlineReader};
+use super::Test;
+use std::{io::{self, Write}, net::{TcpListener, TcpStream}, thread, time::{Duration, Instant}};
+
+/// Spawns a peer that writes `bytes` one at a time, waiting `delay` before each one.
+fn trickling_peer(bytes: Vec<u8>, delay: Duration) -> io::Result<TcpStream> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        for byte in bytes {
+            thread::sleep(delay);
+            if stream.write_all(&[byte]).is_err() {
+                break;
+            }
+        }
+    });
+
+    TcpStream::connect(addr)
+}
+
+#[test]
+fn slow_peer_times_out() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&Test::random())?;
+
+    let stream = trickling_peer(buf, Duration::from_millis(100))?;
+    let mut reader = DeadlineReader::with_timeouts(stream, Duration::from_millis(350));
+
+    let start = Instant::now();
+    let error = reader.read_binary::<Test>().unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    let exceeded = error.get_ref()
+        .and_then(|e| e.downcast_ref::<DeadlineExceeded>())
+        .unwrap();
+
+    assert!(exceeded.consumed > 0 && exceeded.consumed < std::mem::size_of::<Test>());
+    assert_eq!(exceeded.consumed, reader.consumed());
+
+    Ok(())
+}
+
+#[test]
+fn peer_within_budget() -> io::Result<()> {
+    let original = Test::random();
+    let mut buf = Vec::new();
+    buf.write_binary(&original)?;
+
+    let stream = trickling_peer(buf, Duration::from_millis(1))?;
+    let mut reader = DeadlineReader::with_timeouts(stream, Duration::from_secs(5));
+
+    assert_eq!(reader.read_binary::<Test>()?, original);
+    assert_eq!(reader.consumed(), std::mem::size_of::<Test>());
+
+    reader.reset();
+    assert_eq!(reader.consumed(), 0);
+
+    Ok(())
+}
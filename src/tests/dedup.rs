@@ -0,0 +1,42 @@
+use crate::{DedupReader, DedupWriter};
+use super::Test;
+use std::{io::{self, Cursor}, mem::size_of};
+
+#[test]
+fn identical_records_collapse() -> io::Result<()> {
+    let record = Test::random();
+    let mut writer = DedupWriter::new(Vec::new());
+
+    for _ in 0..100 {
+        writer.write_record(record.clone())?;
+    }
+
+    let buf = writer.finish()?;
+
+    assert_eq!(buf.len(), size_of::<u32>() + size_of::<Test>());
+    assert_eq!(buf[..4], 100u32.to_ne_bytes());
+
+    let read = DedupReader::<_, Test>::new(Cursor::new(buf)).collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(read.len(), 100);
+    assert!(read.iter().all(|item| *item == record));
+    Ok(())
+}
+
+#[test]
+fn runs_round_trip() -> io::Result<()> {
+    let values = [1u64, 1, 2, 3, 3, 3, 1];
+    let mut writer = DedupWriter::new(Vec::new());
+
+    for value in values {
+        writer.write_record(value)?;
+    }
+
+    let buf = writer.finish()?;
+    assert_eq!(buf.len(), 4 * (size_of::<u32>() + size_of::<u64>()));
+
+    let read = DedupReader::<_, u64>::new(Cursor::new(buf)).collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, values);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::{varint, BinaryRead, BinaryWrite};
+use std::io::{self, Cursor};
+use super::Test;
+
+#[test]
+fn protobuf_delimited_message() -> io::Result<()> {
+    // `message Test1 { int32 a = 1; }` with `a = 150`, written with writeDelimitedTo.
+    let bytes = [0x03, 0x08, 0x96, 0x01];
+    let message = Cursor::new(bytes).read_binary_delimited::<[u8; 3]>()?;
+    assert_eq!(message, [0x08, 0x96, 0x01]);
+
+    let mut buffer = Vec::new();
+    buffer.write_binary_delimited(&message)?;
+    assert_eq!(buffer, bytes);
+    Ok(())
+}
+
+#[test]
+fn round_trip_and_mismatch() -> io::Result<()> {
+    let records = [Test::random(), Test::random()];
+    let mut buffer = Vec::new();
+    buffer.write_binary_delimited(&records[0])?;
+    buffer.write_binary_delimited(&7u32)?;
+    buffer.write_binary_delimited(&records[1])?;
+
+    let mut cursor = Cursor::new(buffer);
+    assert_eq!(cursor.read_binary_delimited::<Test>()?, records[0]);
+
+    // The mismatched record is skipped, leaving the stream at the next one.
+    let error = cursor.read_binary_delimited::<Test>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(cursor.read_binary_delimited::<Test>()?, records[1]);
+    Ok(())
+}
+
+#[test]
+fn varint_encoding() -> io::Result<()> {
+    for (value, encoded) in [
+        (0, &[0x00][..]),
+        (1, &[0x01]),
+        (127, &[0x7F]),
+        (128, &[0x80, 0x01]),
+        (300, &[0xAC, 0x02]),
+        (u64::MAX, &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]),
+    ] {
+        let mut buffer = Vec::new();
+        varint::write_varint(&mut buffer, value)?;
+        assert_eq!(buffer, encoded);
+        assert_eq!(varint::read_varint(&mut Cursor::new(encoded))?, value);
+    }
+
+    let too_long = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+    assert_eq!(varint::read_varint(&mut Cursor::new(too_long)).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(varint::read_varint(&mut Cursor::new([0x80])).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::{DeltaReader, DeltaWriter};
+use std::{io::{self, Cursor}, mem::{offset_of, size_of}};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tick {
+    price: u32,
+    volume: u32,
+    timestamp: u64,
+}
+
+#[test]
+fn delta_timestamps_roundtrip() -> io::Result<()> {
+    let offset = offset_of!(Tick, timestamp);
+    let ticks = (0..100u64)
+        .map(|i| Tick { price: 100 + i as u32, volume: 7, timestamp: 1_700_000_000_000 + i * 250 })
+        .collect::<Vec<_>>();
+
+    let mut writer = DeltaWriter::new(Vec::new(), offset);
+
+    for tick in &ticks {
+        writer.write_record(tick)?;
+    }
+
+    let buf = writer.into_inner();
+    assert_eq!(buf.len(), ticks.len() * size_of::<Tick>());
+
+    let second = &buf[size_of::<Tick>()..];
+    assert_eq!(u64::from_ne_bytes(second[offset..offset + 8].try_into().unwrap()), 250);
+
+    let read = DeltaReader::<_, Tick>::new(Cursor::new(buf), offset).collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, ticks);
+
+    Ok(())
+}
+
+#[test]
+fn delta_signed_decreasing() -> io::Result<()> {
+    let values = [5i64, -3, i64::MIN, i64::MAX, 0];
+    let mut writer = DeltaWriter::new(Vec::new(), 0);
+
+    for value in &values {
+        writer.write_record(value)?;
+    }
+
+    let read = DeltaReader::<_, i64>::new(Cursor::new(writer.into_inner()), 0).collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, values);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn delta_offset_out_of_bounds() {
+    DeltaWriter::<_, Tick>::new(Vec::new(), 12);
+}
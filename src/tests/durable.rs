@@ -0,0 +1,273 @@
+use crate::{BinaryLog, BinaryRead, BinaryWrite, DurabilityPolicy, Durable, GroupCommitLog, SyncMode};
+use std::{fs::OpenOptions, io::{self, BufWriter, Cursor, Read, Seek, SeekFrom, Write}, sync::{Arc, Mutex}, thread, time::Duration};
+
+/// An in-memory file recording how many bytes were written at each sync.
+#[derive(Default)]
+struct MockFile {
+    data: Cursor<Vec<u8>>,
+    syncs: Vec<(usize, SyncMode)>,
+}
+
+impl Read for MockFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.data.read(buf)
+    }
+}
+
+impl Write for MockFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for MockFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.data.seek(pos)
+    }
+}
+
+impl Durable for MockFile {
+    fn sync(&mut self, mode: SyncMode) -> io::Result<()> {
+        self.syncs.push((self.data.get_ref().len(), mode));
+        Ok(())
+    }
+}
+
+fn sync_points(policy: DurabilityPolicy, appends: u32) -> io::Result<Vec<usize>> {
+    let mut log = BinaryLog::<u32, _>::from_file(MockFile::default())?.with_durability(policy);
+
+    for i in 0..appends {
+        log.append(&i)?;
+    }
+
+    Ok(log.into_inner().syncs.into_iter().map(|(len, _)| len / 4).collect())
+}
+
+#[test]
+fn policy_call_patterns() -> io::Result<()> {
+    assert!(sync_points(DurabilityPolicy::None, 10)?.is_empty());
+    assert_eq!(sync_points(DurabilityPolicy::Always, 3)?, [1, 2, 3]);
+    assert_eq!(sync_points(DurabilityPolicy::EveryN(4), 10)?, [4, 8]);
+    assert!(sync_points(DurabilityPolicy::Every(Duration::from_secs(3600)), 10)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn policy_every_duration() -> io::Result<()> {
+    let mut log = BinaryLog::<u32, _>::from_file(MockFile::default())?
+        .with_durability(DurabilityPolicy::Every(Duration::from_millis(20)))
+        .with_sync_mode(SyncMode::All);
+
+    log.append(&1)?;
+    thread::sleep(Duration::from_millis(30));
+    log.append(&2)?;
+    log.append(&3)?;
+
+    assert_eq!(log.unsynced(), 1);
+    assert_eq!(log.into_inner().syncs, [(8, SyncMode::All)]);
+
+    Ok(())
+}
+
+#[test]
+fn write_sync_flushes_buffer() -> io::Result<()> {
+    let mut writer = BufWriter::new(MockFile::default());
+    writer.write_binary_sync(&[1u16, 2, 3], SyncMode::Data)?;
+
+    assert_eq!(writer.get_ref().syncs, [(6, SyncMode::Data)]);
+    Ok(())
+}
+
+#[test]
+fn log_file_roundtrip() -> io::Result<()> {
+    OpenOptions::new().create(true).write(true).truncate(true).open("./test_log.bin")?;
+
+    let mut log = BinaryLog::<super::Test>::open("./test_log.bin")?
+        .with_durability(DurabilityPolicy::EveryN(2));
+    let records = (0..5).map(|_| super::Test::random()).collect::<Vec<_>>();
+
+    for record in &records {
+        log.append(record)?;
+    }
+
+    log.sync()?;
+    drop(log);
+
+    let mut log = BinaryLog::<super::Test>::open("./test_log.bin")?;
+    assert_eq!(log.len(), 5);
+    assert_eq!(log.get(3)?, records[3]);
+    assert!(log.get(5).is_err());
+
+    log.append(&records[0])?;
+    let mut file = log.into_inner();
+    file.seek(SeekFrom::Start(5 * std::mem::size_of::<super::Test>() as u64))?;
+    assert_eq!(file.read_binary::<super::Test>()?, records[0]);
+
+    Ok(())
+}
+
+#[test]
+fn log_rejects_torn_records() -> io::Result<()> {
+    let file = MockFile { data: Cursor::new(vec![0; 6]), syncs: Vec::new() };
+    let error = BinaryLog::<u32, _>::from_file(file).err().unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    Ok(())
+}
+
+/// An in-memory file with slow syncs, recording the bytes covered by each where other threads
+/// can see them.
+struct SharedSyncFile {
+    data: Cursor<Vec<u8>>,
+    syncs: Arc<Mutex<Vec<usize>>>,
+    fail: bool,
+}
+
+impl SharedSyncFile {
+    fn new(fail: bool) -> (Self, Arc<Mutex<Vec<usize>>>) {
+        let syncs = Arc::new(Mutex::new(Vec::new()));
+        (Self { data: Cursor::default(), syncs: Arc::clone(&syncs), fail }, syncs)
+    }
+}
+
+impl Read for SharedSyncFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.data.read(buf)
+    }
+}
+
+impl Write for SharedSyncFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SharedSyncFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.data.seek(pos)
+    }
+}
+
+impl Durable for SharedSyncFile {
+    fn sync(&mut self, _: SyncMode) -> io::Result<()> {
+        thread::sleep(Duration::from_millis(2));
+
+        if self.fail {
+            return Err(io::Error::other("device unplugged"));
+        }
+
+        self.syncs.lock().unwrap().push(self.data.get_ref().len());
+        Ok(())
+    }
+}
+
+#[test]
+fn group_commit_durable_appends() -> io::Result<()> {
+    let (file, syncs) = SharedSyncFile::new(false);
+    let log = GroupCommitLog::new(BinaryLog::<u32, _>::from_file(file)?, Duration::from_millis(1), 1000);
+
+    thread::scope(|scope| {
+        for worker in 0..8 {
+            let (log, syncs) = (&log, &syncs);
+
+            scope.spawn(move || {
+                for i in 0..25 {
+                    let index = log.append_durable(&(worker * 100 + i)).unwrap();
+                    let synced = syncs.lock().unwrap().last().map_or(0, |len| len / 4);
+                    assert!(synced as u64 > index, "record {index} returned before being synced");
+                }
+            });
+        }
+    });
+
+    assert_eq!(log.synced(), 200);
+    let log = log.close()?;
+    assert_eq!(log.len(), 200);
+
+    // Appenders waiting on the same sync share it.
+    let syncs = syncs.lock().unwrap();
+    assert!(syncs.len() < 200, "{} syncs for 200 appends", syncs.len());
+    assert!(syncs.windows(2).all(|pair| pair[0] < pair[1]));
+    Ok(())
+}
+
+#[test]
+fn group_commit_full_batches() -> io::Result<()> {
+    let (file, syncs) = SharedSyncFile::new(false);
+    let log = GroupCommitLog::new(BinaryLog::<u32, _>::from_file(file)?, Duration::from_secs(3600), 4);
+
+    for i in 0..4 {
+        log.append(&i)?;
+    }
+
+    // The batch is full, so it's synced without waiting for the delay.
+    log.wait_synced(3)?;
+    log.append(&4)?;
+    assert_eq!(log.synced(), 4);
+
+    // Records that haven't been appended can't be waited on.
+    assert_eq!(log.wait_synced(5).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    // Closing syncs what's pending.
+    let log = log.close()?;
+    assert_eq!(log.len(), 5);
+    assert_eq!(*syncs.lock().unwrap(), [16, 20]);
+    Ok(())
+}
+
+#[test]
+fn group_commit_failed_sync() -> io::Result<()> {
+    let (file, syncs) = SharedSyncFile::new(true);
+    let log = GroupCommitLog::new(BinaryLog::<u32, _>::from_file(file)?, Duration::from_millis(1), 16);
+
+    let error = log.append_durable(&1).unwrap_err();
+    assert!(error.to_string().contains("device unplugged"));
+
+    // Later appends can't be made durable either.
+    log.append(&2)?;
+    assert!(log.wait_synced(1).is_err());
+    assert!(log.close().is_err());
+    assert!(syncs.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn group_commit_file() -> io::Result<()> {
+    OpenOptions::new().create(true).write(true).truncate(true).open("./test_group_log.bin")?;
+
+    let log = GroupCommitLog::new(BinaryLog::<u64>::open("./test_group_log.bin")?, Duration::from_millis(1), 8);
+
+    // Files are synced through a cloned handle, without holding the log.
+    thread::scope(|scope| {
+        for worker in 0..4u64 {
+            let log = &log;
+            scope.spawn(move || {
+                for i in 0..25 {
+                    log.append_durable(&(worker * 100 + i)).unwrap();
+                }
+            });
+        }
+    });
+
+    assert_eq!(log.synced(), 100);
+
+    let log = log.close()?;
+    assert_eq!((log.len(), log.unsynced()), (100, 0));
+    drop(log);
+
+    let mut log = BinaryLog::<u64>::open("./test_group_log.bin")?;
+    let mut records = (0..100).map(|index| log.get(index)).collect::<io::Result<Vec<_>>>()?;
+    records.sort_unstable();
+
+    assert_eq!(records, (0..4).flat_map(|worker| (0..25).map(move |i| worker * 100 + i)).collect::<Vec<_>>());
+    Ok(())
+}
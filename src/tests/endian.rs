@@ -0,0 +1,73 @@
+use crate::{BinaryRead, BinaryWrite, SwapBytes};
+use std::io::{self, Cursor};
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Record {
+    id: u16,
+    letter: char,
+    values: [i32; 2],
+    flag: bool,
+}
+
+crate::swap_bytes!(Record {
+    id: u16,
+    letter: char,
+    values: [i32; 2],
+    flag: bool,
+});
+
+#[test]
+fn fixed_byte_order_round_trip() -> io::Result<()> {
+    let record = Record {
+        id: 0x0102,
+        letter: 'é',
+        values: [-1, 0x01020304],
+        flag: true,
+    };
+
+    let mut le = Vec::new();
+    le.write_binary_le(&record)?;
+    let mut be = Vec::new();
+    be.write_binary_be(&record)?;
+
+    assert_eq!(le[..2], [2, 1]);
+    assert_eq!(be[..2], [1, 2]);
+    assert_eq!(be[8..12], [0xFF; 4]);
+    assert_eq!(be[12..16], [1, 2, 3, 4]);
+
+    assert_eq!(Cursor::new(le).read_binary_le::<Record>()?, record);
+    assert_eq!(Cursor::new(be).read_binary_be::<Record>()?, record);
+    Ok(())
+}
+
+#[test]
+fn swapped_char_must_be_valid() {
+    // 'a' is 0x00000061, which byte swapped is 0x61000000, out of the unicode range.
+    let native = ('a' as u32).to_ne_bytes();
+
+    let mut bytes = native;
+    let error = char::swap_bytes_checked(&mut bytes).unwrap_err();
+    assert_eq!(error.offset, 0);
+
+    // Read in the order foreign to the host, so the bytes get swapped.
+    let mut cursor = Cursor::new(native);
+    let result = if cfg!(target_endian = "little") {
+        cursor.read_binary_be::<char>()
+    } else {
+        cursor.read_binary_le::<char>()
+    };
+
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn swapped_char_field_reports_field() {
+    let mut bytes = vec![0u8; std::mem::size_of::<Record>()];
+    bytes[4..8].copy_from_slice(&('a' as u32).to_ne_bytes());
+
+    let error = Record::swap_bytes_checked(&mut bytes).unwrap_err();
+
+    assert_eq!(error.field, Some("letter"));
+    assert_eq!(error.offset, 4);
+}
@@ -0,0 +1,104 @@
+use crate::{BinaryEnum, BinaryRead, BinaryWrite, UnknownVariant};
+use std::{io::{self, Cursor}, mem::size_of};
+
+#[derive(Debug, Clone, PartialEq)]
+#[repr(C)]
+struct DataBody {
+    id: u32,
+    payload: [u8; 12],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Compact {
+    Ping(u64),
+    Data(DataBody),
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Uniform {
+    Ping(u64),
+    Data(DataBody),
+    Close,
+}
+
+crate::binary_enum!(Compact: u8 {
+    Ping(u64) = 0,
+    Data(DataBody) = 1,
+    Close = 2,
+});
+
+crate::binary_enum!(#[layout = uniform] Uniform: u16 {
+    Ping(u64) = 10,
+    Data(DataBody) = 20,
+    Close = 30,
+});
+
+#[test]
+fn compact_round_trip() -> io::Result<()> {
+    let messages = [
+        Compact::Ping(42),
+        Compact::Data(DataBody { id: 7, payload: [3; 12] }),
+        Compact::Close,
+    ];
+
+    let mut buf = Vec::new();
+    for message in &messages {
+        buf.write_binary_enum(message)?;
+    }
+
+    assert_eq!(Compact::FRAME_SIZE, None);
+    assert_eq!(buf.len(), 3 + size_of::<u64>() + size_of::<DataBody>());
+
+    let mut cursor = Cursor::new(buf);
+    for message in messages {
+        assert_eq!(cursor.read_binary_enum::<Compact>()?, message);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn uniform_round_trip() -> io::Result<()> {
+    let messages = [
+        Uniform::Close,
+        Uniform::Ping(42),
+        Uniform::Data(DataBody { id: 7, payload: [3; 12] }),
+    ];
+
+    let mut buf = Vec::new();
+    for message in &messages {
+        buf.write_binary_enum(message)?;
+    }
+
+    let frame = size_of::<u16>() + size_of::<DataBody>();
+    assert_eq!(Uniform::FRAME_SIZE, Some(frame));
+    assert_eq!(buf.len(), 3 * frame);
+    assert!(buf[2..frame].iter().all(|&b| b == 0));
+
+    let mut cursor = Cursor::new(buf);
+    for message in messages {
+        assert_eq!(cursor.read_binary_enum::<Uniform>()?, message);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unknown_discriminant() {
+    let error = Cursor::new([3u8, 0, 0]).read_binary_enum::<Compact>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let unknown = error.get_ref().and_then(|e| e.downcast_ref::<UnknownVariant>()).unwrap();
+    assert_eq!(*unknown, UnknownVariant { name: "Compact", tag: 3 });
+}
+
+#[test]
+fn uniform_truncated_padding() {
+    let mut buf = Vec::new();
+    buf.write_binary_enum(&Uniform::Close).unwrap();
+    buf.pop();
+
+    let error = Cursor::new(buf).read_binary_enum::<Uniform>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,100 @@
+use crate::{BinDateTimeUtc, BinaryLog, BinaryRead, BinaryWrite, Envelope, EnvelopedWriter, Validate, Zeroable};
+use std::{fs::OpenOptions, io::{self, Cursor, Seek, SeekFrom}, mem::size_of};
+
+fn clock() -> impl FnMut() -> BinDateTimeUtc {
+    let mut secs = 1_700_000_000;
+
+    move || {
+        secs += 1;
+        BinDateTimeUtc::new(secs, 0).unwrap()
+    }
+}
+
+#[test]
+fn envelope_layout() {
+    assert_eq!(size_of::<Envelope<u64>>(), 32);
+    assert_eq!(Envelope::<u32>::zeroed(), Envelope::new(0, BinDateTimeUtc::UNIX_EPOCH, 0));
+}
+
+#[test]
+fn enveloped_writer_deterministic() -> io::Result<()> {
+    let mut writer = EnvelopedWriter::new(Vec::new()).with_sequence(5).with_clock(clock());
+
+    assert_eq!(writer.write_payload(super::Test::random())?, 5);
+    assert_eq!(writer.write_payload(super::Test::random())?, 6);
+    assert_eq!(writer.next_sequence(), 7);
+
+    let envelopes = Cursor::new(writer.into_inner()).read_binary_vec::<Envelope<super::Test>>(2)?;
+
+    assert_eq!(envelopes[0].sequence(), 5);
+    assert_eq!(envelopes[1].written_at().secs(), 1_700_000_002);
+    Ok(())
+}
+
+#[test]
+fn enveloped_writer_resumes() -> io::Result<()> {
+    let mut existing = Cursor::new(Vec::new());
+    let mut writer = EnvelopedWriter::new(&mut existing).resume_from(&mut Cursor::new(Vec::<u8>::new()))?;
+
+    for payload in 0..3u32 {
+        writer.write_payload(payload)?;
+    }
+
+    existing.seek(SeekFrom::Start(0))?;
+    let writer = EnvelopedWriter::<_, u32>::new(Vec::new()).resume_from(&mut existing)?;
+
+    assert_eq!(writer.next_sequence(), 3);
+    assert_eq!(existing.stream_position()?, 0);
+    Ok(())
+}
+
+#[test]
+fn envelope_validates_payload() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&Envelope::new(0, BinDateTimeUtc::UNIX_EPOCH, true))?;
+
+    assert!(Envelope::<bool>::is_valid_bytes(&buf));
+
+    buf[24] = 2;
+    assert_eq!(Envelope::<bool>::validate_bytes(&buf).unwrap_err().field, Some("payload"));
+
+    buf[24] = 1;
+    buf[16] = 0xff;
+    buf[17] = 0xff;
+    buf[18] = 0xff;
+    buf[19] = 0xff;
+    assert_eq!(Envelope::<bool>::validate_bytes(&buf).unwrap_err().field, Some("nanos"));
+
+    Ok(())
+}
+
+#[test]
+fn envelope_big_endian_roundtrip() -> io::Result<()> {
+    let envelope = Envelope::new(9, BinDateTimeUtc::new(3, 4).unwrap(), 0x0102u16);
+    let mut buf = Vec::new();
+    buf.write_binary_be(&envelope)?;
+
+    assert_eq!(buf[7], 9);
+    assert_eq!(Cursor::new(buf).read_binary_be::<Envelope<u16>>()?, envelope);
+    Ok(())
+}
+
+#[test]
+fn log_next_sequence() -> io::Result<()> {
+    OpenOptions::new().create(true).write(true).truncate(true).open("./test_envelope_log.bin")?;
+
+    let mut log = BinaryLog::<Envelope<u64>>::open("./test_envelope_log.bin")?;
+    assert_eq!(log.next_sequence()?, 0);
+
+    for order in 0..4 {
+        let sequence = log.next_sequence()?;
+        log.append(&Envelope::new(sequence, BinDateTimeUtc::now(), order))?;
+    }
+
+    drop(log);
+
+    let mut log = BinaryLog::<Envelope<u64>>::open("./test_envelope_log.bin")?;
+    assert_eq!(log.next_sequence()?, 4);
+    assert_eq!(*log.get(3)?.payload(), 3);
+    Ok(())
+}
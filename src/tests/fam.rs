@@ -0,0 +1,52 @@
+use crate::{flexible_array, BinaryRead, BinaryWrite, FlexibleArray};
+use std::io::{self, Cursor};
+
+#[derive(Debug, PartialEq, Eq)]
+#[repr(C)]
+struct Header {
+    kind: u16,
+    len: u16,
+    data: [u32; 0],
+}
+
+flexible_array!(Header, data: u32);
+
+#[test]
+fn fam_round_trip() -> io::Result<()> {
+    let header = Header {
+        kind: 1,
+        len: 3,
+        data: [],
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary_fam(&header, &[10, 20, 30])?;
+    assert_eq!(buf.len(), Header::FAM_OFFSET + 3 * 4);
+
+    let (read, data) = Cursor::new(buf).read_binary_fam::<Header>(3)?;
+
+    assert_eq!(read, header);
+    assert_eq!(data, [10, 20, 30]);
+    Ok(())
+}
+
+#[test]
+fn fam_missing_elements() {
+    let error = Cursor::new([1, 0, 3, 0, 10, 0, 0, 0])
+        .read_binary_fam::<Header>(3)
+        .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn vec_of_records() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&[1i16, -2, 3])?;
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary_vec::<i16>(3)?, [1, -2, 3]);
+    assert!(cursor.read_binary_vec::<i16>(usize::MAX).is_err());
+
+    Ok(())
+}
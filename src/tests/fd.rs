@@ -0,0 +1,44 @@
+use crate::{BinaryRead, BinaryWrite, FdReader, FdWriter};
+use std::{io::{self, Read, Write}, os::fd::{AsRawFd, OwnedFd}};
+use super::Test;
+
+#[test]
+fn borrowed_round_trip() -> io::Result<()> {
+    let (pipe_reader, mut pipe_writer) = io::pipe()?;
+    let records = [Test::random(), Test::random(), Test::random()];
+
+    {
+        // SAFETY: the pipe ends outlive the reader and writer.
+        let mut writer = unsafe { FdWriter::borrow(pipe_writer.as_raw_fd()) };
+        let mut reader = unsafe { FdReader::borrow(pipe_reader.as_raw_fd()) };
+        assert_eq!(writer.as_raw_fd(), pipe_writer.as_raw_fd());
+
+        records.iter().try_for_each(|record| writer.write_binary(record))?;
+        assert_eq!(reader.read_binary_vec::<Test>(3)?, records);
+    }
+
+    // Dropping the borrowed reader and writer left both ends open.
+    pipe_writer.write_binary(&records[0])?;
+    drop(pipe_writer);
+
+    // SAFETY: pipe_reader outlives the reader.
+    let mut reader = unsafe { FdReader::borrow(pipe_reader.as_raw_fd()) };
+    assert_eq!(reader.read_binary::<Test>()?, records[0]);
+    Ok(())
+}
+
+#[test]
+fn owned_closes_on_drop() -> io::Result<()> {
+    let (pipe_reader, pipe_writer) = io::pipe()?;
+    let mut reader = FdReader::from_owned(OwnedFd::from(pipe_reader));
+    let mut writer = FdWriter::from_owned(OwnedFd::from(pipe_writer));
+
+    writer.write_binary(&7u32)?;
+    writer.flush()?;
+    drop(writer);
+
+    // The write end was closed, so the pipe ends after the record.
+    assert_eq!(reader.read_binary::<u32>()?, 7);
+    assert_eq!(reader.read(&mut [0; 4])?, 0);
+    Ok(())
+}
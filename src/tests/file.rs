@@ -0,0 +1,173 @@
+use crate::{open_binary_file, write_records_atomic, BinaryFile, BinaryFileWriter, BinaryRead, BinaryWrite, FileFooter};
+use std::{fs, io::{self, Cursor}, mem::size_of, path::Path, thread};
+use super::Test;
+
+fn write_records(records: &[Test]) -> io::Result<BinaryFileWriter<Vec<u8>, Test>> {
+    let mut writer = BinaryFileWriter::new(Vec::new());
+
+    for record in records {
+        writer.write_record(record)?;
+    }
+
+    Ok(writer)
+}
+
+#[test]
+fn cleanly_closed() -> io::Result<()> {
+    let records = (0..5).map(|_| Test::random()).collect::<Vec<_>>();
+    fs::write("./test_footer.bin", write_records(&records)?.finish()?)?;
+
+    let mut file = BinaryFile::<Test>::open_verified("./test_footer.bin")?;
+    let footer = *file.footer().unwrap();
+
+    assert!(footer.is_clean());
+    assert_eq!(footer.record_count, 5);
+    assert_eq!(footer.payload_bytes, 5 * size_of::<Test>() as u64);
+    assert_eq!(file.len(), 5);
+    assert_eq!(file.get(4)?, records[4]);
+    assert!(file.get(5).is_err());
+
+    assert_eq!(fs::metadata("./test_footer.bin")?.len(), footer.payload_bytes + FileFooter::SIZE as u64);
+    Ok(())
+}
+
+#[test]
+fn footer_fixture_is_little_endian() -> io::Result<()> {
+    let mut writer = BinaryFileWriter::new(Vec::new());
+    writer.write_record(b"123456789")?;
+
+    let buf = writer.finish()?;
+
+    assert_eq!(buf[9..], [
+        1, 0, 0, 0, 0, 0, 0, 0,
+        9, 0, 0, 0, 0, 0, 0, 0,
+        0x26, 0x39, 0xF4, 0xCB,
+        1, 0, 0, 0,
+        b'B', b'X', b'F', b'O', b'O', b'T', b'0', b'1',
+    ]);
+    Ok(())
+}
+
+#[test]
+fn crashed_without_footer() -> io::Result<()> {
+    let records = (0..3).map(|_| Test::random()).collect::<Vec<_>>();
+    let mut buf = write_records(&records)?.get_ref().clone();
+    buf.extend_from_slice(&[0xff; 5]);
+
+    let mut file = BinaryFile::<Test, _>::from_reader_verified(Cursor::new(buf))?;
+
+    assert!(file.footer().is_none());
+    assert_eq!(file.len(), 3);
+    assert_eq!(file.trailing_bytes(), 5);
+    assert_eq!(file.get(2)?, records[2]);
+
+    let empty = BinaryFile::<Test, _>::from_reader(Cursor::new(Vec::new()))?;
+    assert!(empty.is_empty() && empty.footer().is_none());
+    Ok(())
+}
+
+#[test]
+fn corrupted_payload() -> io::Result<()> {
+    let records = (0..3).map(|_| Test::random()).collect::<Vec<_>>();
+    let mut buf = write_records(&records)?.finish()?;
+    buf[size_of::<Test>()] ^= 0x40;
+
+    assert!(BinaryFile::<Test, _>::from_reader(Cursor::new(buf.clone()))?.footer().is_some());
+
+    let error = BinaryFile::<Test, _>::from_reader_verified(Cursor::new(buf)).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+fn footer_not_matching_size() -> io::Result<()> {
+    let records = (0..3).map(|_| Test::random()).collect::<Vec<_>>();
+    let buf = write_records(&records)?.finish()?;
+
+    let mut truncated = buf[size_of::<Test>()..].to_vec();
+    let error = BinaryFile::<Test, _>::from_reader(Cursor::new(truncated.clone())).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    truncated.clear();
+    truncated.write_binary(&records[0])?;
+    truncated.extend_from_slice(&buf[buf.len() - FileFooter::SIZE..]);
+    assert!(BinaryFile::<Test, _>::from_reader(Cursor::new(truncated)).is_err());
+
+    assert!(BinaryFile::<Test>::open("./test_footer_missing.bin").is_err());
+    Ok(())
+}
+
+#[test]
+fn open_with_header() -> io::Result<()> {
+    let (header, record) = (Test::random(), Test::random());
+    let mut bytes = Vec::new();
+    bytes.write_binary_le(&0xB1A5_F11Eu32)?;
+    bytes.write_binary_le(&3u16)?;
+    bytes.write_binary(&header)?;
+    bytes.write_binary(&record)?;
+    fs::write("./test_open_header.bin", &bytes)?;
+
+    let path = Path::new("./test_open_header.bin");
+    let (mut file, read) = open_binary_file::<Test>(path, 0xB1A5_F11E, 3)?;
+    assert_eq!(read, header);
+    assert_eq!(file.read_binary::<Test>()?, record);
+
+    let error = open_binary_file::<Test>(path, 0xDEAD_BEEF, 3).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("0xdeadbeef"));
+
+    let error = open_binary_file::<Test>(path, 0xB1A5_F11E, 4).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    fs::write("./test_open_header.bin", &bytes[..8])?;
+    let error = open_binary_file::<Test>(path, 0xB1A5_F11E, 3).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
+fn temp_files(name: &str) -> io::Result<usize> {
+    let prefix = format!("{name}.");
+    let entries = fs::read_dir(".")?.collect::<io::Result<Vec<_>>>()?;
+
+    Ok(entries.iter().filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix)).count())
+}
+
+#[test]
+fn atomic_replace() -> io::Result<()> {
+    let path = Path::new("./test_atomic.bin");
+    let (old, new) = ([Test::random(), Test::random()], [Test::random()]);
+
+    write_records_atomic(path, &old)?;
+    write_records_atomic(path, &new)?;
+    assert_eq!(fs::File::open(path)?.read_binary_vec::<Test>(1)?, new);
+    assert_eq!(fs::metadata(path)?.len(), size_of::<Test>() as u64);
+    assert_eq!(temp_files("test_atomic.bin")?, 0);
+
+    // Concurrent writers each get their own temporary file, and one of them wins.
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| write_records_atomic(path, &old).unwrap());
+        }
+    });
+
+    assert_eq!(fs::File::open(path)?.read_binary_vec::<Test>(2)?, old);
+    assert_eq!(temp_files("test_atomic.bin")?, 0);
+    Ok(())
+}
+
+#[test]
+fn atomic_replace_failed() -> io::Result<()> {
+    let path = Path::new("./test_atomic_dir.bin");
+    let _ = fs::remove_dir_all(path);
+
+    // A directory that isn't empty can't be replaced, so the write fails at the rename.
+    fs::create_dir(path)?;
+    fs::write(path.join("record.bin"), [1, 2, 3])?;
+    let result = write_records_atomic(path, &[Test::random()]);
+
+    assert!(result.is_err());
+    assert_eq!(fs::read(path.join("record.bin"))?, [1, 2, 3]);
+    assert_eq!(temp_files("test_atomic_dir.bin")?, 0);
+
+    fs::remove_dir_all(path)
+}
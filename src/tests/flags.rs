@@ -0,0 +1,91 @@
+use crate::{BinaryRead, BinaryWrite, UnknownBits, Validate};
+use std::io::{self, Cursor};
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Features: u32 {
+        const COMPRESSED = 1 << 0;
+        const ENCRYPTED = 1 << 1;
+        const SIGNED = 1 << 4;
+    }
+}
+
+/// COMPRESSED, SIGNED and two bits outside the defined set.
+const FIXTURE: u32 = 0b1000_0001 | 1 << 4 | 1 << 31;
+
+#[test]
+fn retain_unknown_bits() -> io::Result<()> {
+    let flags = Cursor::new(FIXTURE.to_ne_bytes()).read_binary_flags::<Features>(UnknownBits::default())?;
+    assert_eq!(flags.bits(), FIXTURE);
+
+    let mut buf = Vec::new();
+    buf.write_binary_flags(&flags)?;
+    assert_eq!(buf, FIXTURE.to_ne_bytes());
+
+    Ok(())
+}
+
+#[test]
+fn truncate_unknown_bits() -> io::Result<()> {
+    let flags = Cursor::new(FIXTURE.to_ne_bytes()).read_binary_flags::<Features>(UnknownBits::Truncate)?;
+    assert_eq!(flags, Features::COMPRESSED | Features::SIGNED);
+
+    Ok(())
+}
+
+#[test]
+fn reject_unknown_bits() {
+    let error = Cursor::new(FIXTURE.to_ne_bytes())
+        .read_binary_flags::<Features>(UnknownBits::Reject)
+        .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let known = (Features::ENCRYPTED | Features::SIGNED).bits();
+    let flags = Cursor::new(known.to_ne_bytes()).read_binary_flags::<Features>(UnknownBits::Reject).unwrap();
+    assert_eq!(flags, Features::ENCRYPTED | Features::SIGNED);
+}
+
+crate::validate_flags!(Features);
+
+#[repr(C)]
+struct Entry {
+    id: u32,
+    features: Features,
+}
+
+crate::validate!(Entry {
+    id: u32,
+    #[unknown_bits = UnknownBits::Reject]
+    features: Features,
+});
+
+#[repr(C)]
+struct LenientEntry {
+    id: u32,
+    features: Features,
+}
+
+crate::validate!(LenientEntry {
+    id: u32,
+    #[unknown_bits = UnknownBits::Truncate]
+    features: Features,
+});
+
+#[test]
+fn validate_unknown_bits() {
+    let mut bytes = [0u8; 8];
+    bytes[4..].copy_from_slice(&FIXTURE.to_ne_bytes());
+
+    let error = Entry::validate_bytes(&bytes).unwrap_err();
+    assert_eq!((error.field, error.offset), (Some("features"), 4));
+    assert!(LenientEntry::is_valid_bytes(&bytes));
+    assert!(Features::is_valid_bytes(&bytes[4..]));
+
+    bytes[4..].copy_from_slice(&(Features::COMPRESSED | Features::SIGNED).bits().to_ne_bytes());
+    assert!(Entry::is_valid_bytes(&bytes));
+
+    let entry = Cursor::new(bytes).read_binary_validated::<Entry>().unwrap();
+    assert_eq!(entry.features, Features::COMPRESSED | Features::SIGNED);
+    assert_eq!(entry.id, 0);
+}
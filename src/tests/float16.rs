@@ -0,0 +1,67 @@
+use crate::{as_f32_vec, BinaryRead, BinaryWrite, Validate, Zeroable};
+use half::{bf16, f16};
+use std::io::{self, Cursor};
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Features {
+    scale: f16,
+    bias: bf16,
+    values: [f16; 1024],
+}
+
+crate::zeroable!(Features { scale: f16, bias: bf16, values: [f16; 1024] });
+crate::validate!(Features {
+    #[nan = false]
+    scale: f16,
+    bias: bf16,
+    values: [f16; 1024],
+});
+crate::swap_bytes!(Features { scale: f16, bias: bf16, values: [f16; 1024] });
+
+#[test]
+fn known_bit_patterns() -> io::Result<()> {
+    // 1.0, -2.0, 65504 (the largest f16) and +inf.
+    let bytes = [0x3C, 0x00, 0xC0, 0x00, 0x7B, 0xFF, 0x7C, 0x00];
+    let values = Cursor::new(bytes).read_binary_be::<[f16; 4]>()?;
+    assert_eq!(as_f32_vec(&values), [1.0, -2.0, 65504.0, f32::INFINITY]);
+
+    // 1.0 and -0.5.
+    let bytes = [0x80, 0x3F, 0x00, 0xBF];
+    let values = Cursor::new(bytes).read_binary_le::<[bf16; 2]>()?;
+    assert_eq!(as_f32_vec(&values), [1.0, -0.5]);
+
+    let mut buf = Vec::new();
+    buf.write_binary_be(&f16::from_f32(0.5))?;
+    assert_eq!(buf, [0x38, 0x00]);
+
+    Ok(())
+}
+
+#[test]
+fn large_array_field() -> io::Result<()> {
+    let mut features = Box::new(Features::zeroed());
+    features.scale = f16::ONE;
+    features.bias = bf16::NEG_ONE;
+    features.values[1023] = f16::MAX;
+
+    let mut buf = Vec::new();
+    buf.write_binary_be(&*features)?;
+    assert_eq!(buf[..4], [0x3C, 0x00, 0xBF, 0x80]);
+    assert_eq!(buf[buf.len() - 2..], [0x7B, 0xFF]);
+
+    assert_eq!(Cursor::new(buf).read_binary_be::<Features>()?, *features);
+
+    Ok(())
+}
+
+#[test]
+fn nan_policy() {
+    let mut features = Features::zeroed();
+    features.values[0] = f16::NAN;
+    assert!(Features::is_valid_bytes(crate::bytes::as_bytes(&features)));
+
+    features.scale = f16::NAN;
+    let error = Features::validate_bytes(crate::bytes::as_bytes(&features)).unwrap_err();
+    assert_eq!(error.field, Some("scale"));
+}
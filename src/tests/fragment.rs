@@ -0,0 +1,109 @@
+use crate::{FragmentHeader, Reassembler, fragment_binary};
+use rand::seq::SliceRandom;
+use std::{io, time::Duration};
+
+type Frame = [u32; 300];
+
+fn frame(seed: u32) -> Frame {
+    std::array::from_fn(|i| seed.wrapping_mul(31) ^ i as u32)
+}
+
+#[test]
+fn shuffled_and_duplicated() -> io::Result<()> {
+    let mut fragments = Vec::new();
+    for id in 0..4 {
+        fragments.extend(fragment_binary(&frame(id), id, 100)?);
+    }
+
+    // 1200 bytes in fragments of 100.
+    assert_eq!(fragments.len(), 4 * 12);
+    assert!(fragments.iter().all(|f| f.len() == FragmentHeader::SIZE + 100));
+
+    let duplicates = fragments.clone();
+    fragments.extend(duplicates);
+    fragments.shuffle(&mut rand::thread_rng());
+
+    let mut reassembler = Reassembler::<Frame>::new(Duration::from_secs(60));
+    let mut received = Vec::new();
+
+    for fragment in &fragments {
+        if let Some(frame) = reassembler.accept(fragment)? {
+            received.push(frame);
+        }
+    }
+
+    // Late duplicates of complete messages start new ones which never complete.
+    received.sort_by_key(|f| f[0]);
+    let mut expected = (0..4).map(frame).collect::<Vec<_>>();
+    expected.sort_by_key(|f| f[0]);
+    assert_eq!(received, expected);
+
+    Ok(())
+}
+
+#[test]
+fn dropped_fragments() -> io::Result<()> {
+    let mut fragments = fragment_binary(&frame(1), 1, 512)?;
+    fragments.extend(fragment_binary(&frame(2), 2, 512)?);
+    assert_eq!(fragments.len(), 6);
+
+    // Lose the middle fragment of the first message.
+    fragments.remove(1);
+    fragments.shuffle(&mut rand::thread_rng());
+
+    let mut reassembler = Reassembler::<Frame>::new(Duration::ZERO);
+    let received = fragments.iter()
+        .map(|f| reassembler.accept(f))
+        .collect::<io::Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    assert_eq!(received, [frame(2)]);
+    assert_eq!(reassembler.pending(), 1);
+
+    std::thread::sleep(Duration::from_millis(1));
+    assert_eq!(reassembler.evict_expired(), 1);
+    assert_eq!(reassembler.pending(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn rejects_inconsistent_fragments() -> io::Result<()> {
+    let mut reassembler = Reassembler::<Frame>::new(Duration::from_secs(60));
+    let fragments = fragment_binary(&frame(3), 3, 700)?;
+
+    let invalid = |result: io::Result<Option<Frame>>| {
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    };
+
+    // Shorter than the header, and a payload not matching its declared length.
+    invalid(reassembler.accept(&fragments[0][..8]));
+    invalid(reassembler.accept(&fragments[0][..FragmentHeader::SIZE + 10]));
+
+    // A message of a different record type, with more fragments than this one.
+    let other = fragment_binary(&[0u8; 2000], 3, 700)?;
+    assert!(reassembler.accept(&fragments[0])?.is_none());
+    invalid(reassembler.accept(&other[2]));
+
+    // A message of a smaller record is complete without holding a whole Frame.
+    invalid(reassembler.accept(&fragment_binary(&[0u8; 100], 4, 700)?[0]));
+
+    assert_eq!(reassembler.accept(&fragments[1])?, Some(frame(3)));
+    assert_eq!(reassembler.pending(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn fragment_limits() {
+    let kind = |result: io::Result<Vec<Vec<u8>>>| result.unwrap_err().kind();
+
+    assert_eq!(kind(fragment_binary(&frame(0), 0, 0)), io::ErrorKind::InvalidInput);
+    assert_eq!(kind(fragment_binary(&[0u8; 70_000], 0, 1)), io::ErrorKind::InvalidInput);
+
+    let fragments = fragment_binary(&(), 0, 1).unwrap();
+    assert_eq!(fragments.len(), 1);
+    assert_eq!(fragments[0].len(), FragmentHeader::SIZE);
+}
@@ -0,0 +1,105 @@
+use crate::{BinaryRead, BinaryWrite, FramedReader, FramedWriter, RecordHeader};
+use std::io::{self, Cursor};
+
+#[test]
+fn header_fixture_is_little_endian() -> io::Result<()> {
+    let header = RecordHeader {
+        magic: RecordHeader::MAGIC,
+        tag: 0x0102,
+        flags: RecordHeader::FLAG_CHECKSUM,
+        len: 0x0304_0506,
+        crc: 0xA1B2_C3D4,
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary_le(&header)?;
+
+    assert_eq!(buf, [
+        b'B', b'X', b'F', b'1',
+        0x02, 0x01,
+        0x01, 0x00,
+        0x06, 0x05, 0x04, 0x03,
+        0xD4, 0xC3, 0xB2, 0xA1,
+    ]);
+    assert_eq!(Cursor::new(buf).read_binary_le::<RecordHeader>()?, header);
+    Ok(())
+}
+
+#[test]
+fn frame_fixture() -> io::Result<()> {
+    let mut writer = FramedWriter::new(Vec::new());
+    writer.write_frame_bytes(9, b"123456789")?;
+
+    let buf = writer.into_inner();
+
+    // 0xCBF43926 is the CRC-32 check value of "123456789".
+    assert_eq!(buf[..16], [b'B', b'X', b'F', b'1', 9, 0, 1, 0, 9, 0, 0, 0, 0x26, 0x39, 0xF4, 0xCB]);
+    assert_eq!(&buf[16..], b"123456789");
+    Ok(())
+}
+
+#[test]
+fn frames_roundtrip() -> io::Result<()> {
+    let records = [super::Test::random(), super::Test::random()];
+    let mut writer = FramedWriter::new(Vec::new());
+
+    writer.write_frame(1, &records[0])?;
+    writer.write_frame_bytes(2, &[1, 2, 3])?;
+    writer.write_frame(3, &records[1])?;
+
+    let mut unchecked = FramedWriter::new(Vec::new()).with_checksums(false);
+    unchecked.write_frame(4, &7u32)?;
+    assert_eq!(unchecked.get_ref()[6..8], [0, 0]);
+
+    let mut reader = FramedReader::new(Cursor::new(writer.into_inner()));
+    assert_eq!(reader.read_frame::<super::Test>()?, (1, records[0].clone()));
+    assert!(reader.read_frame::<u8>().is_err());
+    assert_eq!(reader.read_frame::<super::Test>()?, (3, records[1].clone()));
+    assert!(reader.next_frame()?.is_none());
+
+    let mut reader = FramedReader::new(unchecked.get_ref().as_slice());
+    assert_eq!(reader.read_frame::<u32>()?, (4, 7));
+    Ok(())
+}
+
+#[test]
+fn corrupted_frames_rejected() -> io::Result<()> {
+    let mut writer = FramedWriter::new(Vec::new());
+    writer.write_frame(1, &0xDEADBEEFu32)?;
+
+    let mut buf = writer.into_inner();
+    buf[17] ^= 1;
+
+    let error = FramedReader::new(buf.as_slice()).read_frame::<u32>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    buf[17] ^= 1;
+    buf[0] = b'X';
+
+    let error = FramedReader::new(buf.as_slice()).next_frame().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    Ok(())
+}
+
+#[test]
+fn oversized_frames_rejected() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary_le(&RecordHeader::new(3, u32::MAX))?;
+    buf.extend_from_slice(&[0; 64]);
+
+    let error = FramedReader::new(buf.as_slice()).next_frame().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let error = FramedReader::new(buf.as_slice()).with_max_frame_len(u32::MAX).next_frame().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    let error = FramedReader::new(buf.as_slice()).with_max_frame_len(32).next_frame().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let mut writer = FramedWriter::new(Vec::new());
+    writer.write_frame_bytes(1, &[9; 32])?;
+
+    let (_, payload) = FramedReader::new(writer.get_ref().as_slice()).with_max_frame_len(32).next_frame()?.unwrap();
+    assert_eq!(payload, [9; 32]);
+    Ok(())
+}
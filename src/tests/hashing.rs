@@ -0,0 +1,51 @@
+use crate::{BinaryRead, BinaryWrite, DedupWriter, HashingReader, HashingWriter};
+use super::Test;
+use sha2::{Digest, Sha256};
+use std::{fs::File, io::{self, BufReader, BufWriter}};
+
+#[test]
+fn digest_matches_file() -> io::Result<()> {
+    let items = (0..64).map(|_| Test::random()).collect::<Vec<_>>();
+
+    let mut writer = HashingWriter::<_, Sha256>::new(BufWriter::new(File::create("./test_hashing.bin")?));
+    for item in &items {
+        writer.write_binary(item)?;
+    }
+
+    let (file, written) = writer.finalize();
+    file.into_inner()?;
+
+    assert_eq!(written, Sha256::digest(std::fs::read("./test_hashing.bin")?));
+
+    let mut reader = HashingReader::<_, Sha256>::new(BufReader::new(File::open("./test_hashing.bin")?));
+    for item in &items {
+        assert_eq!(reader.read_binary::<Test>()?, *item);
+    }
+
+    assert_eq!(reader.finalize_reset(), written);
+    assert_eq!(reader.finalize().1, Sha256::digest([]));
+
+    Ok(())
+}
+
+#[test]
+fn nesting_order() -> io::Result<()> {
+    // Hashing the deduplicated output.
+    let mut writer = DedupWriter::new(HashingWriter::<_, Sha256>::new(Vec::new()));
+    for value in [1u8, 1, 1, 2] {
+        writer.write_record(value)?;
+    }
+
+    let (buffer, digest) = writer.finish()?.finalize();
+    assert_eq!(digest, Sha256::digest(&buffer));
+
+    // Hashing the records before deduplication.
+    let mut writer = HashingWriter::<_, Sha256>::new(Vec::new());
+    for value in [1u8, 1, 1, 2] {
+        writer.write_binary(&value)?;
+    }
+
+    assert_eq!(writer.finalize().1, Sha256::digest([1, 1, 1, 2]));
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::{IndexFooter, IndexedReader, IndexedWriter};
+use std::io::{self, Cursor, ErrorKind};
+use super::Test;
+
+#[test]
+fn index_locates_records() -> io::Result<()> {
+    let records = (0..50).map(|_| Test::random()).collect::<Vec<_>>();
+
+    let mut writer = IndexedWriter::new(Vec::new());
+    writer.write_header(&[0xAB; 5])?;
+    records.iter().try_for_each(|record| writer.write_record(record))?;
+    assert_eq!(writer.record_count(), 50);
+
+    let bytes = writer.finish()?;
+    let table_offset = 5 + 50 * size_of::<Test>();
+    assert_eq!(bytes.len(), table_offset + 50 * 8 + IndexFooter::SIZE);
+    assert_eq!(bytes[bytes.len() - 8..], IndexFooter::MAGIC);
+
+    let mut reader = IndexedReader::<Test, _>::new(Cursor::new(bytes))?;
+    assert_eq!(reader.len(), 50);
+
+    // Read out of order, so every lookup seeks.
+    for index in (0..50).rev() {
+        assert_eq!(reader.offset(index), Some(5 + index * size_of::<Test>() as u64));
+        assert_eq!(reader.read_binary_at(index)?, records[index as usize]);
+    }
+
+    assert_eq!(reader.offset(50), None);
+    assert_eq!(reader.read_binary_at(50).unwrap_err().kind(), ErrorKind::InvalidInput);
+    Ok(())
+}
+
+#[test]
+fn empty_index() -> io::Result<()> {
+    let bytes = IndexedWriter::<_, u64>::new(Vec::new()).finish()?;
+    assert_eq!(bytes.len(), IndexFooter::SIZE);
+    assert!(IndexedReader::<u64, _>::new(Cursor::new(bytes))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn corrupt_index() -> io::Result<()> {
+    let mut writer = IndexedWriter::new(Vec::new());
+    [1u64, 2, 3].iter().try_for_each(|value| writer.write_record(value))?;
+    let bytes = writer.finish()?;
+
+    let invalid = |bytes: Vec<u8>| IndexedReader::<u64, _>::new(Cursor::new(bytes)).err().unwrap().kind();
+
+    let mut bad_offset = bytes.clone();
+    bad_offset[24 + 8..24 + 16].copy_from_slice(&20u64.to_le_bytes());
+    assert_eq!(invalid(bad_offset), ErrorKind::InvalidData);
+
+    let mut bad_count = bytes.clone();
+    let count = bytes.len() - 16;
+    bad_count[count..count + 8].copy_from_slice(&4u64.to_le_bytes());
+    assert_eq!(invalid(bad_count), ErrorKind::InvalidData);
+
+    assert_eq!(invalid(bytes[..bytes.len() - 1].to_vec()), ErrorKind::InvalidData);
+    assert_eq!(invalid(vec![0; 4]), ErrorKind::InvalidData);
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::{BinaryRead, BinaryReadSeek, BinaryWrite, CollectBinary};
+use super::Test;
+use std::{io::{self, Cursor}, mem::size_of};
+
+#[test]
+fn iter_with_offsets() -> io::Result<()> {
+    let records = [Test::random(), Test::random(), Test::random()];
+    let mut buf = Vec::new();
+    buf.write_binary(&records)?;
+
+    let read = Cursor::new(buf)
+        .binary_iter_with_offset::<Test>()?
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let size = size_of::<Test>() as u64;
+    let offsets = read.iter().map(|(offset, _)| *offset).collect::<Vec<_>>();
+
+    assert_eq!(offsets, [0, size, 2 * size]);
+    assert!(read.iter().map(|(_, item)| item).eq(records.iter()));
+
+    Ok(())
+}
+
+#[test]
+fn iter_trailing_partial_record() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&Test::random())?;
+    buf.push(0);
+
+    let mut cursor = Cursor::new(buf);
+    let mut iter = cursor.binary_iter::<Test>();
+
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(iter.next().unwrap().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert!(iter.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn collect_binary_matches_writes() -> io::Result<()> {
+    let records = [Test::random(), Test::random(), Test::random()];
+
+    let mut expected = Vec::new();
+    for record in &records {
+        expected.write_binary(record)?;
+    }
+
+    let bytes = records.iter().collect_binary();
+    assert_eq!(bytes.len(), 3 * size_of::<Test>());
+    assert_eq!(bytes, expected);
+
+    assert_eq!(records.into_iter().collect_binary_owned(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn iter_rev() -> io::Result<()> {
+    let records = [Test::random(), Test::random(), Test::random()];
+    let mut buf = Vec::new();
+    buf.write_binary(&records)?;
+
+    let read = Cursor::new(buf)
+        .binary_iter_rev::<Test>()?
+        .collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(read, [records[2].clone(), records[1].clone(), records[0].clone()]);
+
+    assert_eq!(Cursor::new(Vec::new()).binary_iter_rev::<Test>()?.count(), 0);
+    assert_eq!(Cursor::new([0; 3]).binary_iter_rev::<u16>().err().unwrap().kind(), io::ErrorKind::InvalidData);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{BinaryRead, BinaryWrite};
+use std::{io::{self, Cursor}, mem::size_of, thread};
+
+/// 8 MiB, far larger than the stack of the threads below.
+#[derive(PartialEq)]
+#[repr(C)]
+struct Huge {
+    id: u64,
+    data: [u64; 1024 * 1024 - 1],
+}
+
+const STACK: usize = 256 * 1024;
+
+fn on_small_stack(f: impl FnOnce() -> io::Result<()> + Send + 'static) -> io::Result<()> {
+    thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(f)?
+        .join()
+        .unwrap()
+}
+
+fn pattern() -> Vec<u8> {
+    (0..size_of::<Huge>()).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn write_huge_without_copies() -> io::Result<()> {
+    on_small_stack(|| {
+        let source = pattern();
+        let huge = Cursor::new(&source).read_binary_boxed::<Huge>()?;
+
+        let mut buf = Vec::with_capacity(size_of::<Huge>());
+        buf.write_binary(&*huge)?;
+        assert!(buf == source);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn write_verified_huge_without_copies() -> io::Result<()> {
+    on_small_stack(|| {
+        let huge = Cursor::new(pattern()).read_binary_boxed::<Huge>()?;
+
+        let mut cursor = Cursor::new(Vec::new());
+        cursor.write_binary_verified(&*huge)?;
+        assert_eq!(cursor.position(), size_of::<Huge>() as u64);
+
+        Ok(())
+    })
+}
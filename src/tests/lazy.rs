@@ -0,0 +1,66 @@
+use crate::{to_array, validate, BinaryRead, BufferTooSmall, LazyStruct};
+use std::{borrow::Cow, io::Cursor, mem::{offset_of, size_of}};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Reading {
+    sensor: u32,
+    online: bool,
+    value: f64,
+}
+
+validate!(Reading { sensor: u32, online: bool, value: f64 });
+
+#[test]
+fn get_fields_and_materialize() {
+    let reading = Reading { sensor: 12, online: true, value: 1.5 };
+    let mut bytes = vec![0xAA];
+    bytes.extend_from_slice(&to_array::<_, { size_of::<Reading>() }>(&reading));
+    bytes.push(0xBB);
+
+    // Fields are loaded unaligned, past the leading byte.
+    let lazy = LazyStruct::<Reading>::new(&bytes[1..]).unwrap();
+
+    assert_eq!(lazy.bytes().len(), size_of::<Reading>());
+    assert_eq!(lazy.get::<u32>(offset_of!(Reading, sensor)).unwrap(), 12);
+    assert!(lazy.get::<bool>(offset_of!(Reading, online)).unwrap());
+    assert_eq!(lazy.get::<f64>(offset_of!(Reading, value)).unwrap(), 1.5);
+    assert_eq!(lazy.materialize(), reading);
+    assert_eq!(lazy.into_owned().materialize_validated().unwrap(), reading);
+}
+
+#[test]
+fn only_accessed_fields_are_validated() {
+    let mut bytes = to_array::<_, { size_of::<Reading>() }>(&Reading { sensor: 1, online: false, value: 0.0 });
+    bytes[offset_of!(Reading, online)] = 2;
+
+    let lazy = LazyStruct::<Reading>::new(Cow::Owned(bytes.to_vec())).unwrap();
+
+    assert_eq!(lazy.get::<u32>(offset_of!(Reading, sensor)).unwrap(), 1);
+    assert_eq!(lazy.get::<bool>(offset_of!(Reading, online)).unwrap_err().offset, offset_of!(Reading, online));
+    assert_eq!(lazy.materialize_validated().unwrap_err().field, Some("online"));
+}
+
+#[test]
+fn out_of_bounds() {
+    let bytes = [0; 8];
+
+    assert_eq!(
+        LazyStruct::<Reading>::new(&bytes[..]).unwrap_err(),
+        BufferTooSmall { needed: size_of::<Reading>(), available: 8 }
+    );
+
+    let lazy = LazyStruct::<u64>::new(&bytes[..]).unwrap();
+    assert!(lazy.get::<u32>(6).is_err());
+    assert!(lazy.get::<u32>(usize::MAX).is_err());
+}
+
+#[test]
+fn read_lazy() {
+    let record = super::Test::random();
+    let bytes = to_array::<_, { size_of::<super::Test>() }>(&record);
+
+    let lazy = Cursor::new(bytes).read_binary_lazy::<super::Test>().unwrap();
+    assert_eq!(lazy.get::<usize>(offset_of!(super::Test, d)).unwrap(), record.d);
+    assert_eq!(lazy.materialize(), record);
+}
@@ -0,0 +1,54 @@
+use crate::{npy_record, BinaryRead, BinaryWrite, NpyRecord, Zeroable};
+use nalgebra::{Matrix4, Point3, Quaternion, Vector3};
+use std::{io::{self, Cursor}, mem::{align_of, size_of}};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pose {
+    origin: Point3<f64>,
+    orientation: Quaternion<f32>,
+    model: Matrix4<f32>,
+}
+
+crate::zeroable!(Pose { origin: Point3<f64>, orientation: Quaternion<f32>, model: Matrix4<f32> });
+crate::validate!(Pose { origin: Point3<f64>, orientation: Quaternion<f32>, model: Matrix4<f32> });
+crate::swap_bytes!(Pose { origin: Point3<f64>, orientation: Quaternion<f32>, model: Matrix4<f32> });
+npy_record!(Pose { origin: Point3<f64>, orientation: Quaternion<f32>, model: Matrix4<f32> });
+
+#[test]
+fn array_layout() {
+    assert_eq!((size_of::<Vector3<f32>>(), align_of::<Vector3<f32>>()), (12, 4));
+    assert_eq!((size_of::<Matrix4<f32>>(), align_of::<Matrix4<f32>>()), (64, 4));
+    assert_eq!((size_of::<Point3<f64>>(), align_of::<Point3<f64>>()), (24, 8));
+    assert_eq!((size_of::<Quaternion<f32>>(), align_of::<Quaternion<f32>>()), (16, 4));
+    assert_eq!(size_of::<Pose>(), 104);
+}
+
+#[test]
+fn column_major_round_trip() -> io::Result<()> {
+    let pose = Pose {
+        origin: Point3::new(1.0, 2.0, 3.0),
+        orientation: Quaternion::new(1.0, 0.0, 0.0, 0.0),
+        model: Matrix4::new_translation(&Vector3::new(5.0, 6.0, 7.0)),
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary_be(&pose)?;
+    assert_eq!(buf[..8], 1.0f64.to_be_bytes());
+    // Quaternions store `w` last.
+    assert_eq!(buf[36..40], 1.0f32.to_be_bytes());
+    // The translation is the last column of the matrix.
+    assert_eq!(buf[88..92], 5.0f32.to_be_bytes());
+
+    assert_eq!(Cursor::new(buf).read_binary_be::<Pose>()?, pose);
+    assert_eq!(Pose::zeroed().model, Matrix4::zeros());
+    Ok(())
+}
+
+#[test]
+fn reflection() {
+    let fields = Pose::FIELDS;
+    assert_eq!((fields[0].len, fields[0].descr), (Some(3), <f64 as crate::NpyType>::DESCR));
+    assert_eq!((fields[1].len, fields[1].offset), (Some(4), 24));
+    assert_eq!((fields[2].len, fields[2].offset), (Some(16), 40));
+}
@@ -0,0 +1,58 @@
+use crate::assert_offset;
+
+#[allow(unused)]
+#[repr(C)]
+struct Counts {
+    kind: u8,
+    count: u32,
+}
+
+#[allow(unused)]
+#[repr(C)]
+struct Header {
+    magic: [u8; 4],
+    version: u16,
+    flags: u16,
+    len: u64,
+    counts: Counts,
+}
+
+assert_offset!(Header, magic, 0);
+assert_offset!(Header, flags, 6);
+assert_offset!(Header, len, 8);
+assert_offset!(Header, counts.count, 20);
+
+crate::binary_layout!(Counts, size = 8, align = 4);
+crate::binary_layout!(Header, size = 24, align = 8);
+
+#[test]
+fn declared_layout() {
+    use crate::BinaryLayout;
+
+    assert_eq!(Header::SIZE, std::mem::size_of::<Header>());
+    assert_eq!(Counts::ALIGN, std::mem::align_of::<Counts>());
+}
+
+#[allow(unused)]
+#[repr(C)]
+struct Buffer {
+    len: u32,
+    id: Option<std::num::NonZeroU64>,
+    data: [f32; 16],
+    counts: [Counts; 2],
+}
+
+crate::zeroable!(Counts { kind: u8, count: u32 });
+crate::zeroable!(Buffer { len: u32, id: Option<std::num::NonZeroU64>, data: [f32; 16], counts: [Counts; 2] });
+
+#[test]
+fn zeroed_struct() {
+    use crate::Zeroable;
+
+    let buffer = Buffer::zeroed();
+
+    assert_eq!(buffer.len, 0);
+    assert_eq!(buffer.id, None);
+    assert_eq!(buffer.data, [0.0; 16]);
+    assert_eq!(buffer.counts[1].count, 0);
+}
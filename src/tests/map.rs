@@ -0,0 +1,50 @@
+use crate::{BinaryRead, BinaryWrite};
+use std::{collections::{BTreeMap, HashMap}, io::{self, Cursor}};
+
+#[test]
+fn map_round_trip() -> io::Result<()> {
+    let map = (0..100u32).map(|key| (key * 7 % 101, u64::from(key) << 33)).collect::<BTreeMap<_, _>>();
+
+    let mut buf = Vec::new();
+    buf.write_binary_map(&map)?;
+    assert_eq!(buf.len(), 8 + 100 * (4 + 8));
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary_map::<u32, u64>()?, map);
+    assert_eq!(cursor.position(), 8 + 100 * 12);
+
+    let mut empty = Vec::new();
+    empty.write_binary_map(&BTreeMap::<u32, u64>::new())?;
+    assert!(Cursor::new(empty).read_binary_map::<u32, u64>()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn map_deterministic() -> io::Result<()> {
+    let first = HashMap::from([(3u32, 30u64), (1, 10), (2, 20)]);
+    let second = HashMap::from([(2u32, 20u64), (3, 30), (1, 10)]);
+
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+    a.write_binary_map(&first.into_iter().collect())?;
+    b.write_binary_map(&second.into_iter().collect())?;
+
+    assert_eq!(a, b);
+    Ok(())
+}
+
+#[test]
+fn map_unordered_keys() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&2u64)?;
+    buf.write_binary(&5u32)?;
+    buf.write_binary(&50u64)?;
+    buf.write_binary(&5u32)?;
+    buf.write_binary(&51u64)?;
+
+    let error = Cursor::new(buf).read_binary_map::<u32, u64>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
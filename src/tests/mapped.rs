@@ -0,0 +1,33 @@
+use crate::{mmap_iter, BinaryWrite, Misaligned};
+use std::{fs::{self, File}, io::ErrorKind};
+
+#[test]
+fn iterate_mapped_file() {
+    let path = "./test_mapped.bin";
+    let records = (0..1000).map(|_| super::Test::random()).collect::<Vec<_>>();
+    let mut file = File::create(path).unwrap();
+    records.iter().for_each(|record| file.write_binary(record).unwrap());
+
+    let file = File::open(path).unwrap();
+    let mapped = unsafe { mmap_iter::<super::Test>(&file).unwrap() };
+
+    assert_eq!(mapped.len(), 1000);
+    assert_eq!(mapped.iter().len(), 1000);
+    assert!(mapped.iter().eq(records.iter()));
+    assert_eq!(mapped.get(999), records.last());
+    assert_eq!(mapped.get(1000), None);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn partial_record_is_rejected() {
+    let path = "./test_mapped_partial.bin";
+    fs::write(path, [0u8; 10]).unwrap();
+
+    let error = unsafe { mmap_iter::<u64>(&File::open(path).unwrap()) }.err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(error.into_inner().unwrap().downcast::<Misaligned>().unwrap().remainder, 2);
+
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,108 @@
+use crate::{write_records_atomic, BinaryRead, BinaryWrite};
+use metrics::{Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use std::{any::type_name, collections::HashMap, io::{self, Cursor}, path::Path, sync::{Arc, Mutex}};
+use super::Test;
+
+/// A recorder keeping the value of every counter and the samples of every histogram, keyed by
+/// the metric name and its labels.
+#[derive(Default)]
+struct Recording {
+    counters: Mutex<HashMap<String, Arc<Value>>>,
+    histograms: Mutex<HashMap<String, Arc<Value>>>,
+}
+
+#[derive(Default)]
+struct Value(Mutex<Vec<f64>>);
+
+impl CounterFn for Value {
+    fn increment(&self, value: u64) {
+        let mut values = self.0.lock().unwrap();
+        match values.first_mut() {
+            Some(total) => *total += value as f64,
+            None => values.push(value as f64)
+        }
+    }
+
+    fn absolute(&self, value: u64) {
+        *self.0.lock().unwrap() = vec![value as f64];
+    }
+}
+
+impl HistogramFn for Value {
+    fn record(&self, value: f64) {
+        self.0.lock().unwrap().push(value);
+    }
+}
+
+fn key_string(key: &Key) -> String {
+    let labels = key.labels().map(|label| format!("{}={}", label.key(), label.value())).collect::<Vec<_>>();
+    format!("{}{{{}}}", key.name(), labels.join(","))
+}
+
+impl Recording {
+    fn counter(&self, name: &str, ty: &str) -> u64 {
+        self.counters.lock().unwrap()
+            .get(&format!("{name}{{type={ty}}}"))
+            .map_or(0, |value| value.0.lock().unwrap()[0] as u64)
+    }
+
+    fn samples(&self, ty: &str, operation: &str) -> usize {
+        self.histograms.lock().unwrap()
+            .get(&format!("binext_bulk_duration_seconds{{type={ty},operation={operation}}}"))
+            .map_or(0, |value| value.0.lock().unwrap().len())
+    }
+}
+
+impl Recorder for Recording {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let value = self.counters.lock().unwrap().entry(key_string(key)).or_default().clone();
+        Counter::from_arc(value)
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        let value = self.histograms.lock().unwrap().entry(key_string(key)).or_default().clone();
+        Histogram::from_arc(value)
+    }
+}
+
+#[test]
+fn counts_typed_io() -> io::Result<()> {
+    let recording = Recording::default();
+    let (test, u32s) = (type_name::<Test>(), type_name::<u32>());
+    let records = [Test::random(), Test::random(), Test::random()];
+
+    metrics::with_local_recorder(&recording, || -> io::Result<()> {
+        let mut buffer = Vec::new();
+        records.iter().try_for_each(|record| buffer.write_binary(record))?;
+
+        let mut cursor = Cursor::new(buffer);
+        cursor.read_binary::<Test>()?;
+        cursor.read_binary_vec::<Test>(2)?;
+        assert!(cursor.read_binary::<Test>().is_err());
+
+        write_records_atomic(Path::new("./test_metrics.bin"), &[1u32, 2, 3, 4])?;
+        Ok(())
+    })?;
+
+    let size = std::mem::size_of::<Test>() as u64;
+    assert_eq!(recording.counter("binext_records_written_total", test), 3);
+    assert_eq!(recording.counter("binext_bytes_written_total", test), 3 * size);
+    assert_eq!(recording.counter("binext_records_read_total", test), 3);
+    assert_eq!(recording.counter("binext_bytes_read_total", test), 3 * size);
+    assert_eq!(recording.counter("binext_read_errors_total", test), 1);
+    assert_eq!(recording.samples(test, "read_binary_extend"), 1);
+
+    assert_eq!(recording.counter("binext_records_written_total", u32s), 4);
+    assert_eq!(recording.counter("binext_bytes_written_total", u32s), 16);
+    assert_eq!(recording.counter("binext_write_errors_total", u32s), 0);
+    assert_eq!(recording.samples(u32s, "write_records_atomic"), 1);
+    Ok(())
+}
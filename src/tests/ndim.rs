@@ -0,0 +1,89 @@
+use crate::{BinaryRead, BinaryWrite, MAX_NDIM};
+use ndarray::{array, Array, Array2, Array3, ArrayD, Dimension, Ix2, IxDyn, ShapeBuilder};
+use std::io::{self, Cursor};
+
+fn round_trip<D: Dimension>(array: &Array<f32, D>) -> io::Result<Array<f32, D>> {
+    let mut buf = Vec::new();
+    buf.write_binary_array(array)?;
+    Cursor::new(buf).read_binary_array(array.len())
+}
+
+#[test]
+fn owned_round_trip() -> io::Result<()> {
+    let matrix = Array2::from_shape_fn((3, 4), |(i, j)| (i * 4 + j) as f32);
+    assert_eq!(round_trip(&matrix)?, matrix);
+
+    let cube = Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as f32);
+    assert_eq!(round_trip(&cube)?, cube);
+
+    let dynamic = ArrayD::from_shape_fn(IxDyn(&[2, 1, 3, 2]), |index| index.slice().iter().sum::<usize>() as f32);
+    assert_eq!(round_trip(&dynamic)?, dynamic);
+
+    Ok(())
+}
+
+#[test]
+fn header_layout() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary_array(&array![[1u16, 2, 3], [4, 5, 6]])?;
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary::<[u64; 4]>()?, [2, 2, 3, 2]);
+    assert_eq!(cursor.read_binary::<[u16; 6]>()?, [1, 2, 3, 4, 5, 6]);
+
+    Ok(())
+}
+
+#[test]
+fn view_round_trip() -> io::Result<()> {
+    let matrix = Array2::from_shape_fn((6, 6), |(i, j)| (i * 6 + j) as f32);
+    let view = matrix.slice(ndarray::s![1..5;2, ..;3]);
+
+    let mut buf = Vec::new();
+    buf.write_binary_array(&view)?;
+    buf.write_binary_array(&matrix.row(2))?;
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary_array::<f32, Ix2>(4)?, view);
+    assert_eq!(cursor.read_binary_array::<f32, ndarray::Ix1>(6)?, matrix.row(2));
+
+    Ok(())
+}
+
+#[test]
+fn fortran_round_trip() -> io::Result<()> {
+    let fortran = Array2::from_shape_vec((2, 3).f(), vec![1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0]).unwrap();
+    assert!(fortran.as_slice().is_none());
+
+    let read = round_trip(&fortran)?;
+
+    assert_eq!(read, array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    assert_eq!(read.as_slice(), Some(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0][..]));
+
+    Ok(())
+}
+
+fn invalid<T>(result: io::Result<T>) -> bool {
+    result.is_err_and(|e| e.kind() == io::ErrorKind::InvalidData)
+}
+
+#[test]
+fn shape_validation() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary_array(&Array2::<u32>::zeros((10, 10)))?;
+
+    assert!(invalid(Cursor::new(&buf).read_binary_array::<u32, ndarray::Ix3>(1000)));
+    assert!(invalid(Cursor::new(&buf).read_binary_array::<u64, Ix2>(1000)));
+    assert!(invalid(Cursor::new(&buf).read_binary_array::<u32, Ix2>(99)));
+    assert_eq!(Cursor::new(&buf).read_binary_array::<u32, IxDyn>(100)?.shape(), [10, 10]);
+
+    let mut huge = Vec::new();
+    huge.write_binary(&[2u64, u64::MAX, u64::MAX, 4])?;
+    assert!(invalid(Cursor::new(&huge).read_binary_array::<u32, Ix2>(usize::MAX - 1)));
+
+    let mut deep = Vec::new();
+    deep.write_binary(&(MAX_NDIM as u64 + 1))?;
+    assert!(invalid(Cursor::new(&deep).read_binary_array::<u32, IxDyn>(100)));
+
+    Ok(())
+}
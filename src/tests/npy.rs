@@ -0,0 +1,122 @@
+use crate::{npy_record, read_npy, write_npy, NpyRecord};
+use std::io::{self, Cursor};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tick {
+    flag: u8,
+    timestamp: u64,
+    price: f32,
+    volume: u16,
+    levels: [i16; 3],
+}
+
+npy_record!(Tick { flag: u8, timestamp: u64, price: f32, volume: u16, levels: [i16; 3] });
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Renamed {
+    flag: u8,
+    time: u64,
+    price: f32,
+    volume: u16,
+    levels: [i16; 3],
+}
+
+npy_record!(Renamed { flag: u8, time: u64, price: f32, volume: u16, levels: [i16; 3] });
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Retyped {
+    flag: u8,
+    timestamp: u64,
+    price: f32,
+    volume: u16,
+    levels: [u16; 3],
+}
+
+npy_record!(Retyped { flag: u8, timestamp: u64, price: f32, volume: u16, levels: [u16; 3] });
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Fewer {
+    flag: u8,
+    timestamp: u64,
+}
+
+npy_record!(Fewer { flag: u8, timestamp: u64 });
+
+/// The layout numpy saves for `np.dtype({'names': ['flag', 'timestamp', 'price', 'volume',
+/// 'levels'], 'formats': ['u1', '<u8', '<f4', '<u2', ('<i2', 3)], 'offsets': [0, 8, 16, 20, 22],
+/// 'itemsize': 32})`, with the header `np.save` writes for it.
+#[cfg(target_endian = "little")]
+const GOLDEN: &[u8] = include_bytes!("fixtures/ticks.npy");
+
+fn ticks() -> [Tick; 3] {
+    [
+        Tick { flag: 1, timestamp: 1_700_000_000, price: 101.5, volume: 300, levels: [-1, 0, 1] },
+        Tick { flag: 0, timestamp: 1_700_000_001, price: 99.25, volume: 0, levels: [5, 6, 7] },
+        Tick { flag: 1, timestamp: 1_700_000_002, price: -0.5, volume: 65535, levels: [-32768, 32767, 2] },
+    ]
+}
+
+fn dtype_error<T: NpyRecord>(bytes: &[u8]) -> String {
+    let error = read_npy::<T>(&mut Cursor::new(bytes)).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    error.to_string()
+}
+
+#[test]
+#[cfg(target_endian = "little")]
+fn golden_fixture() -> io::Result<()> {
+    assert_eq!(read_npy::<Tick>(&mut Cursor::new(GOLDEN))?, ticks());
+
+    let mut written = Vec::new();
+    write_npy(&mut written, &ticks())?;
+
+    // Padding bytes are copied from memory, so only the header is compared byte by byte.
+    let data_start = GOLDEN.len() - 3 * 32;
+    assert_eq!(written.len(), GOLDEN.len());
+    assert_eq!(written[..data_start], GOLDEN[..data_start]);
+    assert_eq!(read_npy::<Tick>(&mut Cursor::new(written))?, ticks());
+
+    Ok(())
+}
+
+#[test]
+fn header_alignment() -> io::Result<()> {
+    for count in [0, 1, 9, 10, 12345] {
+        let mut buf = Vec::new();
+        write_npy(&mut buf, &vec![ticks()[0]; count])?;
+
+        let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        assert_eq!(buf[9 + header_len], b'\n');
+        assert_eq!(buf.len(), 10 + header_len + count * 32);
+
+        assert_eq!(read_npy::<Tick>(&mut Cursor::new(buf))?.len(), count);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn dtype_mismatch() -> io::Result<()> {
+    let mut buf = Vec::new();
+    write_npy(&mut buf, &ticks())?;
+
+    assert!(dtype_error::<Renamed>(&buf).contains("'timestamp' found where 'time' was expected"));
+    assert!(dtype_error::<Retyped>(&buf).contains("field 'levels' has format '<i2' x 3, expected '<u2' x 3"));
+    assert!(dtype_error::<Fewer>(&buf).contains("beyond the fields of the type"));
+
+    let header = "{'descr': '<u8', 'fortran_order': False, 'shape': (1,), }";
+    let mut plain = b"\x93NUMPY\x01\x00".to_vec();
+    plain.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    plain.extend_from_slice(header.as_bytes());
+    assert!(dtype_error::<Tick>(&plain).contains("isn't a structured dtype"));
+
+    buf[0] = b'N';
+    assert!(dtype_error::<Tick>(&buf).contains("magic"));
+
+    Ok(())
+}
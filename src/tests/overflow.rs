@@ -0,0 +1,110 @@
+use crate::{BinaryLog, BinaryRead, BinaryReadSeek, CollectBinary};
+use std::io::{self, Cursor};
+
+#[repr(C)]
+struct Message {
+    len: u16,
+    data: [u32; 0],
+}
+
+crate::flexible_array!(Message, data: u32);
+
+fn assert_invalid_input<T>(result: io::Result<T>) {
+    match result {
+        Err(e) => assert_eq!(e.kind(), io::ErrorKind::InvalidInput),
+        Ok(_) => panic!("expected an InvalidInput error")
+    }
+}
+
+#[test]
+fn huge_counts_error() {
+    let mut cursor = Cursor::new([0u8; 64]);
+
+    for count in [usize::MAX, usize::MAX / 2 + 1, usize::MAX / 4 + 1] {
+        assert_invalid_input(cursor.read_binary_vec::<u32>(count));
+        assert_invalid_input(cursor.read_binary_boxed_slice::<u32>(count));
+        assert_invalid_input(cursor.read_binary_extend::<u32>(&mut Vec::new(), count));
+        assert_invalid_input(cursor.read_binary_extend_partial::<u32>(&mut Vec::new(), count));
+        assert_invalid_input(cursor.read_binary_fam::<Message>(count));
+    }
+
+    assert_invalid_input(cursor.read_binary_vec::<super::Test>(usize::MAX / 16 + 1));
+}
+
+#[test]
+fn huge_zero_sized_counts() -> io::Result<()> {
+    let mut cursor = Cursor::new([0u8; 0]);
+    let mut records = cursor.read_binary_vec::<()>(usize::MAX)?;
+
+    assert_eq!(records.len(), usize::MAX);
+    assert_invalid_input(cursor.read_binary_extend(&mut records, 1));
+    Ok(())
+}
+
+#[test]
+fn huge_indices_error() -> io::Result<()> {
+    let mut cursor = Cursor::new(vec![0u8; 64]);
+
+    assert_invalid_input(cursor.seek_to_record::<u32>(u64::MAX));
+    assert_invalid_input(cursor.seek_to_record::<u64>(u64::MAX / 4));
+    assert_invalid_input(cursor.seek_to_record::<()>(u64::MAX));
+    assert_eq!(cursor.seek_to_record::<u8>(u64::MAX)?, u64::MAX);
+
+    let mut log = BinaryLog::<u64, _>::from_file(MockFile(cursor))?;
+    assert_invalid_input(log.get(u64::MAX));
+
+    Ok(())
+}
+
+#[test]
+fn huge_size_hint_collects() {
+    let bytes = std::iter::repeat_n(&7u32, usize::MAX).take(2).collect_binary();
+    assert_eq!(bytes, [7u32.to_ne_bytes(), 7u32.to_ne_bytes()].concat());
+}
+
+#[cfg(feature = "zstd-seekable")]
+#[test]
+fn huge_seekable_arguments() -> io::Result<()> {
+    use crate::{SeekableReader, SeekableWriter};
+
+    assert_invalid_input(SeekableWriter::<_, u32>::new(Vec::new(), usize::MAX).map(|_| ()));
+    assert_invalid_input(SeekableWriter::<_, u32>::new(Vec::new(), 1 << 31).map(|_| ()));
+
+    let mut writer = SeekableWriter::new(Vec::new(), 4)?;
+    writer.write_record(&1u32)?;
+
+    let mut reader = SeekableReader::<_, u32>::new(Cursor::new(writer.finish()?))?;
+    assert_invalid_input(reader.get(u64::MAX));
+
+    Ok(())
+}
+
+struct MockFile(Cursor<Vec<u8>>);
+
+impl io::Read for MockFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl io::Write for MockFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl io::Seek for MockFile {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+impl crate::Durable for MockFile {
+    fn sync(&mut self, _: crate::SyncMode) -> io::Result<()> {
+        Ok(())
+    }
+}
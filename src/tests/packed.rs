@@ -0,0 +1,116 @@
+use crate::{BinaryRead, BinaryWrite, PresenceMismatch};
+use std::io::{self, Cursor};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Section {
+    kind: u16,
+    value: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    flags: u8,
+    id: u32,
+    extension: Option<u16>,
+    section: Option<Section>,
+}
+
+crate::packed!(Record {
+    flags: u8,
+    id: u32,
+    #[present_if = |record| record.flags & 0x01 != 0]
+    extension: Option<u16>,
+    #[present_if = |record| record.flags & 0x02 != 0 && record.extension != Some(0)]
+    section: Option<Section>,
+});
+
+#[test]
+fn packed_flags_clear() -> io::Result<()> {
+    let record = Record { flags: 0, id: 42, extension: None, section: None };
+    let fixture = [&[0][..], &42u32.to_ne_bytes()].concat();
+
+    let mut buf = Vec::new();
+    buf.write_binary_packed(&record)?;
+
+    assert_eq!(buf, fixture);
+    assert_eq!(Cursor::new(buf).read_binary_packed::<Record>()?, record);
+    Ok(())
+}
+
+#[test]
+fn packed_flags_set() -> io::Result<()> {
+    let section = Section { kind: 1, value: 9 };
+    let record = Record { flags: 0x03, id: 42, extension: Some(7), section: Some(section.clone()) };
+
+    let mut fixture = [&[0x03][..], &42u32.to_ne_bytes(), &7u16.to_ne_bytes()].concat();
+    fixture.write_binary(&section)?;
+
+    let mut buf = Vec::new();
+    buf.write_binary_packed(&record)?;
+
+    assert_eq!(buf, fixture);
+    assert_eq!(Cursor::new(buf).read_binary_packed::<Record>()?, record);
+    Ok(())
+}
+
+#[test]
+fn packed_condition_over_earlier_section() -> io::Result<()> {
+    let record = Record { flags: 0x03, id: 1, extension: Some(0), section: None };
+
+    let mut buf = Vec::new();
+    buf.write_binary_packed(&record)?;
+    buf.push(0xff);
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary_packed::<Record>()?, record);
+    assert_eq!(cursor.read_binary::<u8>()?, 0xff);
+    Ok(())
+}
+
+#[test]
+fn packed_presence_mismatch() {
+    let record = Record { flags: 0x01, id: 1, extension: None, section: None };
+    let error = Vec::new().write_binary_packed(&record).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(
+        error.get_ref().and_then(|e| e.downcast_ref::<PresenceMismatch>()),
+        Some(&PresenceMismatch { name: "Record", field: "extension", expected: true })
+    );
+
+    let record = Record { flags: 0, id: 1, extension: None, section: Some(Section { kind: 0, value: 0 }) };
+    assert!(Vec::new().write_binary_packed(&record).is_err());
+}
+
+#[derive(Debug, PartialEq)]
+struct Plain {
+    kind: u8,
+    len: u16,
+}
+
+crate::packed!(Plain { kind: u8, len: u16 });
+
+#[derive(Debug, PartialEq)]
+struct Tagged {
+    tag: u8,
+    payload: Option<u32>,
+}
+
+crate::packed!(Tagged {
+    tag: u8,
+    #[present_if = |tagged| tagged.tag != 0]
+    payload: Option<u32>
+});
+
+#[test]
+fn packed_without_trailing_comma() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary_packed(&Plain { kind: 1, len: 2 })?;
+    buf.write_binary_packed(&Tagged { tag: 1, payload: Some(3) })?;
+    assert_eq!(buf.len(), 3 + 5);
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary_packed::<Plain>()?, Plain { kind: 1, len: 2 });
+    assert_eq!(cursor.read_binary_packed::<Tagged>()?, Tagged { tag: 1, payload: Some(3) });
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::{BinaryRead, BinaryWrite, Page, SlotIdx};
+use std::{fs::File, io};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Leaf {
+    page_id: u32,
+    next_page: u32,
+}
+
+crate::validate!(Leaf { page_id: u32, next_page: u32 });
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entry {
+    key: u64,
+    present: bool,
+}
+
+crate::validate!(Entry { key: u64, present: bool });
+
+type LeafPage = Page<Leaf, Entry, 256>;
+
+fn full_page() -> LeafPage {
+    let mut page = LeafPage::new(Leaf { page_id: 3, next_page: 4 });
+
+    for key in 0.. {
+        if page.push(&Entry { key, present: key % 3 != 0 }).is_none() {
+            break;
+        }
+    }
+
+    page
+}
+
+#[test]
+fn fill_and_read() {
+    // 16 bytes of bookkeeping and 8 of header, then 16 bytes entries.
+    assert_eq!(LeafPage::RECORDS_OFFSET, 24);
+    assert_eq!(LeafPage::CAPACITY, (256 - 24) / 16);
+
+    let page = full_page();
+    assert_eq!(page.len(), LeafPage::CAPACITY);
+    assert_eq!(page.remaining_capacity(), 0);
+
+    assert_eq!(page.get(SlotIdx(5)), Some(Entry { key: 5, present: true }));
+    assert_eq!(page.get(SlotIdx(LeafPage::CAPACITY as u32)), None);
+    assert!(page.iter().map(|entry| entry.key).eq(0..LeafPage::CAPACITY as u64));
+}
+
+#[test]
+fn file_round_trip() -> io::Result<()> {
+    let mut file = File::create("./test_page.bin")?;
+    let mut pages = vec![full_page(), LeafPage::new(Leaf { page_id: 4, next_page: 0 })];
+    pages[1].push(&Entry { key: 99, present: true });
+
+    for page in &pages {
+        file.write_binary(page.as_bytes())?;
+    }
+
+    let mut file = File::open("./test_page.bin")?;
+
+    for page in &pages {
+        let bytes = file.read_binary::<[u8; 256]>()?;
+        let loaded = LeafPage::from_bytes(&bytes)?;
+
+        assert_eq!(loaded.header(), page.header());
+        assert!(loaded.iter().eq(page.iter()));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rejects_corrupt_pages() {
+    let page = full_page();
+    let check = |edit: &dyn Fn(&mut [u8; 256]), reason: &str| {
+        let mut bytes = *page.as_bytes();
+        edit(&mut bytes);
+
+        let error = LeafPage::from_bytes(&bytes).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains(reason), "{error}");
+    };
+
+    check(&|bytes| bytes[40] ^= 1, "checksum");
+    check(&|bytes| bytes[0..4].copy_from_slice(&1000u32.to_ne_bytes()), "more than the 14 that fit");
+    check(&|bytes| bytes[4..8].copy_from_slice(&100u32.to_ne_bytes()), "free offset");
+
+    // An invalid bool with a valid checksum.
+    let mut bytes = *page.as_bytes();
+    bytes[24 + 8] = 2;
+    let crc = crate::crc::crc32_excluding(&bytes[..24 + 14 * 16], 8);
+    bytes[8..12].copy_from_slice(&crc.to_ne_bytes());
+
+    let error = LeafPage::from_bytes(&bytes).unwrap_err();
+    assert!(error.to_string().contains("invalid record 0"), "{error}");
+
+    assert!(LeafPage::from_bytes(&bytes[..255]).is_err());
+}
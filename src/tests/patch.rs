@@ -0,0 +1,146 @@
+use crate::{apply_delta, columns, layout_fingerprint, make_delta, validate, Delta, FramedReader, FramedWriter};
+use std::{io::{self, Cursor}, mem::offset_of};
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct Entity {
+    flag: u8,
+    id: u64,
+    health: u32,
+    alive: bool,
+    name: [u8; 16],
+}
+
+columns!(Entity { flag: u8, id: u64, health: u32, alive: bool, name: [u8; 16] });
+validate!(Entity { flag: u8, id: u64, health: u32, alive: bool, name: [u8; 16] });
+
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq)]
+struct EntityV2 {
+    flag: u8,
+    id: u64,
+    health: u32,
+    alive: bool,
+    name: [u8; 16],
+    armor: u32,
+}
+
+columns!(EntityV2 { flag: u8, id: u64, health: u32, alive: bool, name: [u8; 16], armor: u32 });
+validate!(EntityV2 { flag: u8, id: u64, health: u32, alive: bool, name: [u8; 16], armor: u32 });
+
+fn entity() -> Entity {
+    Entity { flag: 1, id: 42, health: 100, alive: true, name: *b"goblin\0\0\0\0\0\0\0\0\0\0" }
+}
+
+#[test]
+fn identical_values() {
+    let delta = make_delta(&entity(), &entity());
+    assert!(delta.is_empty());
+    assert_eq!(delta.to_bytes().len(), 16);
+}
+
+#[test]
+fn padding_is_ignored() {
+    let old = entity();
+    let mut new = entity();
+
+    // The padding after `flag` holds whatever was there; scribble over it.
+    unsafe { (&mut new as *mut Entity as *mut u8).add(3).write(0xAB) };
+    assert!(make_delta(&old, &new).is_empty());
+}
+
+#[test]
+fn single_field_change() -> io::Result<()> {
+    let old = entity();
+    let mut new = entity();
+    new.health = 100 + 0x0100;
+
+    let delta = make_delta(&old, &new);
+    let field = offset_of!(Entity, health)..offset_of!(Entity, health) + 4;
+
+    assert_eq!(delta.ranges().len(), 1);
+    assert_eq!(delta.changed_bytes(), 1);
+    assert!(field.contains(&delta.ranges()[0].offset));
+
+    new.name[..4].copy_from_slice(b"ogre");
+    let delta = make_delta(&old, &new);
+    assert_eq!(delta.ranges().len(), 2);
+    assert_eq!(delta.ranges()[1].offset, offset_of!(Entity, name));
+    assert_eq!(delta.ranges()[1].bytes, b"ogre");
+
+    assert_eq!(apply_delta(&old, &delta)?, new);
+    Ok(())
+}
+
+#[test]
+fn through_framing() -> io::Result<()> {
+    let old = entity();
+    let mut new = entity();
+    new.id = 7;
+    new.alive = false;
+
+    let mut writer = FramedWriter::new(Vec::new());
+    writer.write_frame_bytes(3, &make_delta(&old, &new).to_bytes())?;
+
+    let mut reader = FramedReader::new(Cursor::new(writer.into_inner()));
+    let (header, payload) = reader.next_frame()?.unwrap();
+    assert_eq!(header.tag, 3);
+
+    assert_eq!(apply_delta(&old, &Delta::from_bytes(&payload)?)?, new);
+    Ok(())
+}
+
+#[test]
+fn wrong_layout() {
+    let old = entity();
+    let mut new = entity();
+    new.id = 7;
+
+    assert_ne!(layout_fingerprint::<Entity>(), layout_fingerprint::<EntityV2>());
+
+    let delta = make_delta(&old, &new);
+    let base = EntityV2 { flag: 1, id: 42, health: 100, alive: true, name: [0; 16], armor: 0 };
+    let error = apply_delta(&base, &delta).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn corrupted_deltas() {
+    let old = entity();
+    let mut new = entity();
+    new.health = 5;
+    new.name[0] = b'G';
+    let bytes = make_delta(&old, &new).to_bytes();
+
+    let parse = |bytes: &[u8]| Delta::from_bytes(bytes).err().unwrap().kind();
+
+    // Truncated, and with trailing bytes.
+    assert_eq!(parse(&bytes[..bytes.len() - 1]), io::ErrorKind::InvalidData);
+    assert_eq!(parse(&[&bytes[..], &[0]].concat()), io::ErrorKind::InvalidData);
+
+    // The first range moved past the end of the record.
+    let mut moved = bytes.clone();
+    moved[16..20].copy_from_slice(&1000u32.to_le_bytes());
+    assert_eq!(parse(&moved), io::ErrorKind::InvalidData);
+
+    // The second range moved before the first one.
+    let mut unordered = bytes.clone();
+    let second = 16 + 8 + make_delta(&old, &new).ranges()[0].bytes.len();
+    unordered[second..second + 4].copy_from_slice(&0u32.to_le_bytes());
+    assert_eq!(parse(&unordered), io::ErrorKind::InvalidData);
+
+    // A range covering padding parses, but doesn't apply.
+    let mut padding = bytes.clone();
+    padding[16..20].copy_from_slice(&1u32.to_le_bytes());
+    let delta = Delta::from_bytes(&padding).unwrap();
+    assert_eq!(apply_delta(&old, &delta).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    // A range producing an invalid bool.
+    let mut invalid = bytes[..16].to_vec();
+    invalid[12..16].copy_from_slice(&1u32.to_le_bytes());
+    invalid.extend_from_slice(&(offset_of!(Entity, alive) as u32).to_le_bytes());
+    invalid.extend_from_slice(&1u32.to_le_bytes());
+    invalid.push(2);
+    let delta = Delta::from_bytes(&invalid).unwrap();
+    assert_eq!(apply_delta(&old, &delta).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,58 @@
+use crate::PeriodicFlushWriter;
+use std::io::{self, Write};
+
+/// Records how many bytes had been written at each flush.
+#[derive(Default)]
+struct FlushLog {
+    written: usize,
+    flushes: Vec<usize>,
+}
+
+impl Write for FlushLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes.push(self.written);
+        Ok(())
+    }
+}
+
+#[test]
+fn flushes_every_n_records() -> io::Result<()> {
+    let mut writer = PeriodicFlushWriter::new(FlushLog::default(), 3);
+
+    for record in 0..7u32 {
+        writer.write_record(&record)?;
+    }
+
+    assert_eq!(writer.every(), 3);
+    assert_eq!(writer.pending(), 1);
+    assert_eq!(writer.get_ref().flushes, [3 * 4, 6 * 4]);
+
+    writer.flush()?;
+    writer.write_record(&0u32)?;
+    writer.write_record(&0u32)?;
+
+    assert_eq!(writer.pending(), 2);
+    assert_eq!(writer.into_inner().flushes, [12, 24, 28]);
+    Ok(())
+}
+
+#[test]
+fn raw_writes_are_not_records() -> io::Result<()> {
+    let mut writer = PeriodicFlushWriter::new(FlushLog::default(), 1);
+    writer.write_all(&[0; 16])?;
+
+    assert_eq!(writer.pending(), 0);
+    assert!(writer.get_ref().flushes.is_empty());
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn zero_interval_panics() {
+    PeriodicFlushWriter::new(FlushLog::default(), 0);
+}
@@ -0,0 +1,61 @@
+use crate::{BinaryRead, BinaryWrite, Validate, Zeroable};
+use std::{io::{self, Cursor}, marker::PhantomData, mem::size_of};
+
+/// Neither Zeroable nor Validate, so any bound on it would fail to compile.
+#[derive(Debug, PartialEq)]
+struct Celsius;
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Reading<Unit, const N: usize> {
+    sensor: u32,
+    samples: [u16; N],
+    unit: PhantomData<Unit>,
+    marker: (),
+}
+
+crate::zeroable!([Unit, const N: usize] Reading<Unit, N> {
+    sensor: u32,
+    samples: [u16; N],
+    unit: PhantomData<Unit>,
+    marker: (),
+});
+
+crate::validate!([Unit, const N: usize] Reading<Unit, N> {
+    sensor: u32,
+    #[magic = [0; N]]
+    samples: [u16; N],
+    unit: PhantomData<Unit>,
+    marker: (),
+});
+
+#[test]
+fn phantom_fields_take_no_bytes() -> io::Result<()> {
+    let reading = Reading::<Celsius, 3> {
+        sensor: 7,
+        samples: [0; 3],
+        unit: PhantomData,
+        marker: (),
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary(&reading)?;
+
+    assert_eq!(buf.len(), size_of::<u32>() + 3 * size_of::<u16>() + 2);
+    assert_eq!(Cursor::new(&buf).read_binary_validated::<Reading<Celsius, 3>>()?, reading);
+    assert!(Reading::<Celsius, 3>::is_valid_bytes(&buf));
+
+    buf[4] = 1;
+    let error = Reading::<Celsius, 3>::validate_bytes(&buf).unwrap_err();
+    assert_eq!(error.field, Some("samples"));
+
+    Ok(())
+}
+
+#[test]
+fn phantom_zeroed() {
+    let reading = Reading::<Celsius, 8>::zeroed();
+
+    assert_eq!(reading.sensor, 0);
+    assert_eq!(reading.samples, [0; 8]);
+}
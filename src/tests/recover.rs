@@ -0,0 +1,135 @@
+use crate::{BinaryLog, LogOptions, RecoveryReport};
+use std::{fs::{self, OpenOptions}, io::{self, Write}};
+
+fn write_log(path: &str, options: LogOptions, values: &[u64]) -> io::Result<()> {
+    OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    let mut log = BinaryLog::<u64>::open_with(path, options)?;
+
+    for value in values {
+        log.append(value)?;
+    }
+
+    Ok(())
+}
+
+fn append_raw(path: &str, bytes: &[u8]) -> io::Result<()> {
+    OpenOptions::new().append(true).open(path)?.write_all(bytes)
+}
+
+#[test]
+fn recover_mid_record() -> io::Result<()> {
+    let path = "./test_recover_record.bin";
+    write_log(path, LogOptions::new(), &[1, 2, 3])?;
+    append_raw(path, &4u64.to_ne_bytes()[..5])?;
+
+    let error = BinaryLog::<u64>::open(path).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let mut log = BinaryLog::<u64>::open_with(path, LogOptions::new().recover(true))?;
+
+    assert_eq!(log.recovery(), Some(&RecoveryReport { records: 3, discarded_records: 0, discarded_bytes: 5 }));
+    assert_eq!(fs::metadata(path)?.len(), 24);
+
+    log.append(&4)?;
+    assert_eq!(log.get(3)?, 4);
+    assert_eq!(log.recover()?, RecoveryReport { records: 4, discarded_records: 0, discarded_bytes: 0 });
+
+    Ok(())
+}
+
+#[test]
+fn recover_mid_checksum() -> io::Result<()> {
+    let path = "./test_recover_checksum.bin";
+    let options = LogOptions::new().checksums(true);
+    write_log(path, options, &[1, 2, 3])?;
+    assert_eq!(fs::metadata(path)?.len(), 36);
+
+    // The record made it to the file, but only half of its checksum did.
+    append_raw(path, &4u64.to_ne_bytes())?;
+    append_raw(path, &[0xAB, 0xCD])?;
+
+    let mut log = BinaryLog::<u64>::open_with(path, options.recover(true))?;
+
+    assert_eq!(log.recovery(), Some(&RecoveryReport { records: 3, discarded_records: 0, discarded_bytes: 10 }));
+    assert_eq!(log.get(2)?, 3);
+    assert_eq!(fs::metadata(path)?.len(), 36);
+
+    Ok(())
+}
+
+#[test]
+fn recover_garbage_tail() -> io::Result<()> {
+    let path = "./test_recover_garbage.bin";
+    let options = LogOptions::new().checksums(true);
+    write_log(path, options, &[1, 2])?;
+    append_raw(path, &[0x5A; 12 * 3])?;
+
+    // Whole but corrupt records are only caught by their checksums.
+    let mut log = BinaryLog::<u64>::open_with(path, options)?;
+    assert_eq!(log.len(), 5);
+    assert_eq!(log.get(4).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    append_raw(path, &[0x5A; 7])?;
+
+    let report = log.recover()?;
+
+    assert_eq!(report, RecoveryReport { records: 2, discarded_records: 3, discarded_bytes: 43 });
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.get(1)?, 2);
+    assert_eq!(fs::metadata(path)?.len(), 24);
+
+    Ok(())
+}
+
+#[test]
+fn recover_all_garbage() -> io::Result<()> {
+    let path = "./test_recover_empty.bin";
+    let options = LogOptions::new().checksums(true).recover(true);
+
+    write_log(path, LogOptions::new().checksums(true), &[])?;
+    let log = BinaryLog::<u64>::open_with(path, options)?;
+    assert_eq!(log.recovery(), Some(&RecoveryReport::default()));
+
+    fs::write(path, [0xFF; 30])?;
+    let mut log = BinaryLog::<u64>::open_with(path, options)?;
+
+    assert_eq!(log.recovery(), Some(&RecoveryReport { records: 0, discarded_records: 2, discarded_bytes: 30 }));
+    assert!(log.is_empty());
+    assert_eq!(fs::metadata(path)?.len(), 0);
+
+    log.append(&7)?;
+    assert_eq!(log.get(0)?, 7);
+
+    Ok(())
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Padded {
+    tag: u8,
+    value: u32
+}
+
+#[test]
+fn checksum_covers_padding() -> io::Result<()> {
+    let path = "./test_recover_padding.bin";
+    let options = LogOptions::new().checksums(true);
+    OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+
+    let record = Padded { tag: 7, value: 0xDEAD_BEEF };
+    let mut log = BinaryLog::<Padded>::open_with(path, options)?;
+    log.append(&record)?;
+    assert_eq!(log.get(0)?, record);
+
+    // Flipping a padding byte on disk doesn't change the record, but it breaks the checksum
+    // for `get` as much as it does for `recover`.
+    let mut bytes = fs::read(path)?;
+    bytes[1] ^= 0xFF;
+    fs::write(path, bytes)?;
+
+    let mut log = BinaryLog::<Padded>::open_with(path, options)?;
+    assert_eq!(log.get(0).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(log.recover()?, RecoveryReport { records: 0, discarded_records: 1, discarded_bytes: 12 });
+
+    Ok(())
+}
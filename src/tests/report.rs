@@ -0,0 +1,122 @@
+use crate::{BinaryReadSeek, BinaryWrite, RecordFileOptions, validate_record_file, validate_record_file_deep, validate_record_file_with};
+use std::{fs, io::{self, Seek, SeekFrom}, mem::size_of};
+
+#[test]
+fn report_trailing_bytes() -> io::Result<()> {
+    let path = "./test_report_trailing.bin";
+    let mut buf = Vec::new();
+    buf.write_binary(&[1u32, 2, 3])?;
+    buf.push(0);
+    fs::write(path, buf)?;
+
+    let report = validate_record_file::<u32>(path)?;
+
+    assert_eq!(report.total_size, 13);
+    assert_eq!(report.record_count, 3);
+    assert_eq!(report.trailing_bytes, 1);
+    assert!(!report.is_clean());
+    assert_eq!(report.to_string(), "13 bytes: 3 records of 4 bytes, 1 trailing bytes");
+
+    Ok(())
+}
+
+#[test]
+fn deep_report_finds_invalid_records() -> io::Result<()> {
+    let path = "./test_report_deep.bin";
+    let mut buf = Vec::new();
+    buf.write_binary(&['a', 'b', 'c'])?;
+    buf[size_of::<char>()..2 * size_of::<char>()].copy_from_slice(&0xD800u32.to_ne_bytes());
+    fs::write(path, buf)?;
+
+    let report = validate_record_file_deep::<char>(path)?;
+
+    assert_eq!(report.record_count, 3);
+    assert_eq!(report.invalid_records, 1);
+    assert_eq!(report.first_invalid.unwrap().0, 1);
+    assert!(!report.is_clean());
+
+    Ok(())
+}
+
+#[test]
+fn validate_clean_file() -> io::Result<()> {
+    let path = "./test_report_validate_clean.bin";
+    let mut buf = Vec::new();
+    buf.write_binary(&[1u64, 2, 3, 4])?;
+    fs::write(path, buf)?;
+
+    let mut file = fs::File::open(path)?;
+    file.seek(SeekFrom::Start(8))?;
+
+    let report = file.validate_file::<u64>()?;
+    assert!(report.is_clean());
+    assert!(report.deep);
+    assert_eq!(report.record_count, 4);
+    assert_eq!(file.stream_position()?, 8);
+
+    Ok(())
+}
+
+#[test]
+fn validate_trailing_partial_record() -> io::Result<()> {
+    let path = "./test_report_validate_partial.bin";
+    let mut buf = Vec::new();
+    buf.write_binary(&[true, false])?;
+    buf.write_binary(&[1u8, 1, 1])?;
+    fs::write(path, &buf)?;
+
+    let report = fs::File::open(path)?.validate_file::<[bool; 2]>()?;
+    assert_eq!(report.record_count, 2);
+    assert_eq!(report.trailing_bytes, 1);
+    assert_eq!(report.invalid_records, 0);
+    assert!(!report.is_clean());
+
+    Ok(())
+}
+
+#[test]
+fn header_checks() -> io::Result<()> {
+    let path = "./test_report_header.bin";
+    let magic = u32::from_le_bytes(*b"RECS");
+    let options = RecordFileOptions::new().magic(magic).version(2).fingerprint(0x1234).deep(true);
+
+    let mut buf = Vec::new();
+    buf.write_binary_le(&magic)?;
+    buf.write_binary_le(&2u16)?;
+    buf.write_binary_le(&0x1234u64)?;
+    buf.write_binary(&['a', 'b'])?;
+    fs::write(path, &buf)?;
+
+    let report = validate_record_file_with::<char>(path, options)?;
+    assert_eq!((report.header_size, report.record_count, report.trailing_bytes), (14, 2, 0));
+    assert!(report.header.unwrap().matches());
+    assert!(report.deep);
+    assert!(report.is_clean());
+
+    // Version 3 and a different fingerprint.
+    buf[4] = 3;
+    buf[6] = 0;
+    buf[14..18].copy_from_slice(&0xD800u32.to_ne_bytes());
+    fs::write(path, &buf)?;
+
+    let report = validate_record_file_with::<char>(path, options)?;
+    let header = report.header.unwrap();
+    assert_eq!((header.magic, header.version, header.fingerprint), (Some(true), Some(false), Some(false)));
+    assert_eq!(report.invalid_records, 1);
+    assert!(!report.is_clean());
+    assert_eq!(
+        report.to_string(),
+        "22 bytes: 2 records of 4 bytes, 0 trailing bytes, 14 header bytes (magic ok, version mismatch, \
+         fingerprint mismatch), 1 invalid records (first at record 0: invalid value at offset 0: not a valid unicode scalar value)"
+    );
+
+    // Too short for the fingerprint.
+    fs::write(path, &buf[..8])?;
+
+    let report = validate_record_file_with::<char>(path, RecordFileOptions::new().magic(magic).fingerprint(0))?;
+    assert_eq!(report.header.unwrap().fingerprint, Some(false));
+    assert_eq!((report.record_count, report.trailing_bytes), (0, 0));
+    assert!(!report.deep);
+
+    Ok(())
+}
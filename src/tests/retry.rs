@@ -0,0 +1,78 @@
+use crate::{BinaryRead, BinaryWrite};
+use super::Test;
+use std::io::{self, Read};
+
+/// Fails with `WouldBlock` a number of times before every chunk of data it yields.
+struct Flaky {
+    data: Vec<u8>,
+    position: usize,
+    chunk: usize,
+    failures: usize,
+    remaining: usize,
+}
+
+impl Flaky {
+    fn new(data: Vec<u8>, chunk: usize, failures: usize) -> Self {
+        Self {
+            data,
+            position: 0,
+            chunk,
+            failures,
+            remaining: failures,
+        }
+    }
+}
+
+impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        self.remaining = self.failures;
+
+        let end = (self.position + self.chunk.min(buf.len())).min(self.data.len());
+        let read = end - self.position;
+        buf[..read].copy_from_slice(&self.data[self.position..end]);
+        self.position = end;
+
+        Ok(read)
+    }
+}
+
+fn would_block(e: &io::Error) -> bool {
+    e.kind() == io::ErrorKind::WouldBlock
+}
+
+#[test]
+fn retry_transient_errors() -> io::Result<()> {
+    let item = Test::random();
+
+    let mut buf = Vec::new();
+    buf.write_binary(&item)?;
+
+    let mut reader = Flaky::new(buf, usize::MAX, 2);
+    assert_eq!(reader.read_binary_retry::<Test>(2, would_block)?, item);
+
+    Ok(())
+}
+
+#[test]
+fn retry_keeps_partial_reads() -> io::Result<()> {
+    let mut reader = Flaky::new((0..16).collect(), 5, 1);
+    assert_eq!(reader.read_binary_retry::<[u8; 16]>(4, would_block)?, std::array::from_fn(|i| i as u8));
+
+    Ok(())
+}
+
+#[test]
+fn retries_exhausted() {
+    let mut reader = Flaky::new(vec![0; 8], usize::MAX, 3);
+    let error = reader.read_binary_retry::<u64>(2, would_block).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+
+    let mut reader = Flaky::new(vec![0; 8], usize::MAX, 1);
+    let error = reader.read_binary_retry::<u64>(5, |_| false).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+}
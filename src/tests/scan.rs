@@ -0,0 +1,90 @@
+use crate::{scan_for, scan_reader, validate};
+use std::{io::{self, Cursor, Read}, mem::size_of};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Beacon {
+    magic: [u8; 4],
+    version: u16,
+    healthy: bool,
+    channel: u8,
+    position: u64,
+}
+
+validate!(Beacon {
+    #[magic = *b"BCN!"]
+    magic: [u8; 4],
+    #[range = 1..=3]
+    version: u16,
+    healthy: bool,
+    channel: u8,
+    position: u64,
+});
+
+fn beacon(position: u64) -> Beacon {
+    Beacon { magic: *b"BCN!", version: 2, healthy: position.is_multiple_of(2), channel: 5, position }
+}
+
+fn bytes(beacon: &Beacon) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(beacon as *const Beacon as *const u8, size_of::<Beacon>()) }
+}
+
+/// Random noise with beacons hidden at the given offsets.
+fn dump(len: usize, hidden: &[usize]) -> (Vec<u8>, Vec<(usize, Beacon)>) {
+    let mut dump = (0..len).map(|_| rand::random::<u8>()).collect::<Vec<_>>();
+    let beacons = hidden.iter().map(|&offset| (offset, beacon(offset as u64))).collect::<Vec<_>>();
+
+    for (offset, beacon) in &beacons {
+        dump[*offset..offset + size_of::<Beacon>()].copy_from_slice(bytes(beacon));
+    }
+
+    (dump, beacons)
+}
+
+#[test]
+fn records_hidden_in_noise() {
+    let (dump, beacons) = dump(4096, &[3, 61, 1000, 2047, 4095 - size_of::<Beacon>()]);
+    assert_eq!(scan_for::<Beacon>(&dump, 1), beacons);
+
+    // Only 1000 is a multiple of the step.
+    assert_eq!(scan_for::<Beacon>(&dump, 8), [beacons[2]]);
+    assert!(scan_for::<Beacon>(&dump[..10], 1).is_empty());
+}
+
+#[test]
+fn invalid_fields_are_rejected() {
+    let mut bad_version = beacon(0);
+    bad_version.version = 4;
+
+    let mut dump = bytes(&bad_version).to_vec();
+    dump.extend_from_slice(bytes(&beacon(16)));
+    // An invalid bool.
+    dump[size_of::<Beacon>() + 6] = 2;
+
+    assert!(scan_for::<Beacon>(&dump, 1).is_empty());
+}
+
+/// Reads at most 7 bytes at a time, so records span several reads.
+struct Trickle<R>(R);
+
+impl<R: Read> Read for Trickle<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(7);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn streaming_across_chunks() -> io::Result<()> {
+    // Beacons straddling the 64 KiB chunk boundaries.
+    let hidden = [0, 65_530, 131_067, 200_001, 300_000 - size_of::<Beacon>()];
+    let (dump, beacons) = dump(300_000, &hidden);
+    let expected = beacons.iter().map(|&(offset, beacon)| (offset as u64, beacon)).collect::<Vec<_>>();
+
+    assert_eq!(scan_reader::<Beacon, _>(Cursor::new(&dump), 1)?, expected);
+    assert_eq!(scan_reader::<Beacon, _>(Trickle(Cursor::new(&dump)), 1)?, expected);
+
+    // A step larger than a chunk skips bytes without looking at them.
+    assert_eq!(scan_reader::<Beacon, _>(Cursor::new(&dump), 65_530)?, expected[..2]);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::{BinaryRead, BinaryWrite, ChainReader};
+use super::Test;
+use std::io::{self, BufRead, Read};
+
+#[test]
+fn record_across_segments() -> io::Result<()> {
+    let records = [Test::random(), Test::random()];
+
+    let mut buf = Vec::new();
+    for record in &records {
+        buf.write_binary(record)?;
+    }
+
+    // The first record straddles three small segments, with an empty one in between.
+    let segments = vec![buf[..5].to_vec(), Vec::new(), buf[5..9].to_vec(), buf[9..].to_vec()];
+    let mut reader = ChainReader::new(&segments);
+    assert_eq!(reader.remaining(), buf.len());
+
+    assert_eq!(reader.read_binary::<Test>()?, records[0]);
+    assert_eq!(reader.read_binary::<Test>()?, records[1]);
+    assert_eq!(reader.remaining(), 0);
+
+    let error = reader.read_binary::<u8>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
+#[test]
+fn buffered_segments() -> io::Result<()> {
+    let segments: [&[u8]; 3] = [b"ab", b"", b"cde"];
+    let mut reader = ChainReader::new(&segments);
+
+    assert_eq!(reader.fill_buf()?, b"ab");
+    reader.consume(1);
+    assert_eq!(reader.fill_buf()?, b"b");
+    reader.consume(1);
+    assert_eq!(reader.fill_buf()?, b"cde");
+
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest)?;
+    assert_eq!(rest, "cde");
+    assert!(reader.fill_buf()?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::{BinaryRead, BinaryReadSeek, BinaryWrite, Misaligned};
+use super::Test;
+use std::{io::{self, Cursor, Seek, SeekFrom}, mem::size_of};
+
+fn records(count: usize) -> io::Result<(Vec<Test>, Cursor<Vec<u8>>)> {
+    let records = (0..count).map(|_| Test::random()).collect::<Vec<_>>();
+    let mut buf = Vec::new();
+
+    for record in &records {
+        buf.write_binary(record)?;
+    }
+
+    Ok((records, Cursor::new(buf)))
+}
+
+#[test]
+fn navigate_records() -> io::Result<()> {
+    let (records, mut cursor) = records(4)?;
+
+    assert_eq!(cursor.records_remaining::<Test>()?, 4);
+
+    cursor.seek_to_record::<Test>(2)?;
+    assert_eq!(cursor.record_position::<Test>()?, 2);
+    assert_eq!(cursor.records_remaining::<Test>()?, 2);
+    assert_eq!(cursor.read_binary::<Test>()?, records[2]);
+    assert_eq!(cursor.record_position::<Test>()?, 3);
+
+    Ok(())
+}
+
+#[test]
+fn misaligned_position_reports_remainder() -> io::Result<()> {
+    let (_, mut cursor) = records(2)?;
+    cursor.seek(SeekFrom::Start(size_of::<Test>() as u64 + 3))?;
+
+    let error = cursor.record_position::<Test>().unwrap_err();
+    let misaligned = error.get_ref()
+        .and_then(|e| e.downcast_ref::<Misaligned>())
+        .unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(misaligned.remainder, 3);
+    assert!(cursor.records_remaining::<Test>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn seek_overflow_errors() -> io::Result<()> {
+    let (_, mut cursor) = records(1)?;
+    let error = cursor.seek_to_record::<Test>(u64::MAX).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(cursor.position(), 0);
+    Ok(())
+}
+
+#[test]
+fn check_alignment_trailing_byte() -> io::Result<()> {
+    let (_, mut cursor) = records(3)?;
+    cursor.check_alignment::<Test>()?;
+
+    cursor.get_mut().push(0);
+    cursor.seek(SeekFrom::Start(1))?;
+
+    let error = cursor.check_alignment::<Test>().unwrap_err();
+    let misaligned = error.get_ref()
+        .and_then(|e| e.downcast_ref::<Misaligned>())
+        .unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(misaligned.bytes, 3 * size_of::<Test>() as u64 + 1);
+    assert_eq!(misaligned.remainder, 1);
+    assert_eq!(cursor.stream_position()?, 1);
+
+    Ok(())
+}
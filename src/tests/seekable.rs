@@ -0,0 +1,119 @@
+use crate::{SeekableReader, SeekableWriter};
+use std::{io::{self, Cursor}, mem::size_of};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+struct Sample {
+    timestamp: u64,
+    value: f64,
+    sensor: u32,
+    flags: u32,
+}
+
+impl Sample {
+    fn new(index: u64) -> Self {
+        Self {
+            timestamp: 1_700_000_000 + index,
+            value: (index % 100) as f64 / 4.0,
+            sensor: (index % 7) as u32,
+            flags: 0,
+        }
+    }
+}
+
+const RECORDS_PER_FRAME: usize = 100;
+
+fn write_samples(count: u64) -> io::Result<Vec<u8>> {
+    let mut writer = SeekableWriter::new(Vec::new(), RECORDS_PER_FRAME)?;
+
+    for index in 0..count {
+        writer.write_record(&Sample::new(index))?;
+    }
+
+    writer.finish()
+}
+
+#[test]
+fn random_access() -> io::Result<()> {
+    let buf = write_samples(1050)?;
+    assert!(buf.len() < 1050 * size_of::<Sample>() / 4);
+
+    let mut reader = SeekableReader::<_, Sample>::new(Cursor::new(buf))?;
+    assert_eq!(reader.len(), 1050);
+    assert_eq!(reader.frame_count(), 11);
+
+    for index in [0, 99, 100, 1049, 500, 501, 3] {
+        assert_eq!(reader.get(index)?, Sample::new(index));
+    }
+
+    assert_eq!(reader.get(1050).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    let all = reader.iter().collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(all, (0..1050).map(Sample::new).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[test]
+fn frames_hold_whole_records() -> io::Result<()> {
+    let buf = write_samples(RECORDS_PER_FRAME as u64 * 2)?;
+
+    // Every entry of the seek table is a multiple of the record size.
+    let table_end = buf.len() - 9;
+    let entries = &buf[table_end - 2 * 8..table_end];
+
+    for entry in entries.chunks_exact(8) {
+        let decompressed = u32::from_le_bytes(entry[4..].try_into().unwrap()) as usize;
+        assert_eq!(decompressed, RECORDS_PER_FRAME * size_of::<Sample>());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn empty_file() -> io::Result<()> {
+    let reader = SeekableReader::<_, Sample>::new(Cursor::new(write_samples(0)?))?;
+
+    assert!(reader.is_empty());
+    assert_eq!(reader.frame_count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn corrupted_frame() -> io::Result<()> {
+    let mut buf = write_samples(300)?;
+
+    // Locates the second frame from the sizes in the seek table.
+    let table_start = buf.len() - 9 - 3 * 8 - 8;
+    let first = u32::from_le_bytes(buf[table_start + 8..table_start + 12].try_into().unwrap()) as usize;
+    let second = u32::from_le_bytes(buf[table_start + 16..table_start + 20].try_into().unwrap()) as usize;
+    buf[first + second / 2] ^= 0x55;
+
+    let mut reader = SeekableReader::<_, Sample>::new(Cursor::new(buf))?;
+    assert_eq!(reader.get(5)?, Sample::new(5));
+    assert_eq!(reader.get(150).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(reader.get(250)?, Sample::new(250));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_seek_table() -> io::Result<()> {
+    let mut buf = write_samples(10)?;
+    let len = buf.len();
+    buf[len - 1] = 0;
+
+    let error = SeekableReader::<_, Sample>::new(Cursor::new(buf)).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    // A frame that doesn't hold whole records.
+    let mut buf = write_samples(10)?;
+    let entry = len - 9 - 8;
+    buf[entry + 4..entry + 8].copy_from_slice(&(10 * size_of::<Sample>() as u32 - 1).to_le_bytes());
+
+    let error = SeekableReader::<_, Sample>::new(Cursor::new(buf)).err().unwrap();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::{BinaryRead, BinaryWrite, MAX_SELF_SIZED_SURPLUS};
+use std::{io::{self, Cursor}, mem::size_of};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HeaderV1 {
+    size: u32,
+    flags: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HeaderV2 {
+    size: u32,
+    flags: u32,
+    timeout: u32,
+    retries: u16,
+    priority: u16,
+}
+
+#[test]
+fn write_stamps_size() -> io::Result<()> {
+    let mut buf = Vec::new();
+    let mut header = HeaderV1 { size: 0, flags: 5 };
+
+    buf.write_self_sized(&mut header, &[], |h| &mut h.size)?;
+    assert_eq!(header.size, 8);
+    assert_eq!(buf[..4], 8u32.to_ne_bytes());
+
+    buf.clear();
+    buf.write_self_sized(&mut header, &[1, 2, 3], |h| &mut h.size)?;
+    assert_eq!(header.size, 11);
+    assert_eq!(buf.len(), 11);
+    assert_eq!(buf[8..], [1, 2, 3]);
+
+    Ok(())
+}
+
+#[test]
+fn declared_smaller_than_t() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_self_sized(&mut HeaderV1 { size: 0, flags: 9 }, &[], |h| &mut h.size)?;
+    buf.write_binary(&0xAAu8)?;
+
+    let mut cursor = Cursor::new(buf);
+    let (header, surplus) = cursor.read_self_sized(|h: &HeaderV2| h.size)?;
+
+    assert_eq!(header, HeaderV2 { size: 8, flags: 9, timeout: 0, retries: 0, priority: 0 });
+    assert!(surplus.is_empty());
+    assert_eq!(cursor.read_binary::<u8>()?, 0xAA);
+
+    Ok(())
+}
+
+#[test]
+fn declared_larger_than_t() -> io::Result<()> {
+    let mut v2 = HeaderV2 { size: 0, flags: 1, timeout: 30, retries: 2, priority: 7 };
+    let mut buf = Vec::new();
+    buf.write_self_sized(&mut v2, &[0xEE; 4], |h| &mut h.size)?;
+    buf.write_binary(&0xAAu8)?;
+
+    let mut cursor = Cursor::new(buf);
+    let (header, surplus) = cursor.read_self_sized(|h: &HeaderV1| h.size)?;
+
+    assert_eq!(header, HeaderV1 { size: 20, flags: 1 });
+    assert_eq!(surplus.len(), 12);
+    assert_eq!(surplus[..4], 30u32.to_ne_bytes());
+    assert_eq!(surplus[8..], [0xEE; 4]);
+    assert_eq!(cursor.read_binary::<u8>()?, 0xAA);
+
+    let mut cursor = Cursor::new(cursor.into_inner());
+    let (header, surplus) = cursor.read_self_sized(|h: &HeaderV2| h.size)?;
+
+    assert_eq!(header, v2);
+    assert_eq!(surplus, [0xEE; 4]);
+
+    Ok(())
+}
+
+#[test]
+fn declared_size_errors() {
+    let error = Cursor::new(2u32.to_ne_bytes()).read_self_sized(|h: &HeaderV1| h.size).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let error = Cursor::new(12u32.to_ne_bytes()).read_self_sized(|h: &HeaderV1| h.size).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    let error = Cursor::new([0u8; 8]).read_self_sized(|size: &u16| u32::from(*size)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn declared_size_too_large() {
+    let mut buf = u32::MAX.to_ne_bytes().to_vec();
+    buf.extend_from_slice(&[0; 64]);
+
+    // Rejected right after the size field, without reading or allocating the surplus.
+    let mut cursor = Cursor::new(buf);
+    let error = cursor.read_self_sized(|h: &HeaderV1| h.size).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(cursor.position(), 4);
+
+    let declared = (size_of::<HeaderV1>() + MAX_SELF_SIZED_SURPLUS) as u32;
+    let error = Cursor::new(declared.to_ne_bytes()).read_self_sized(|h: &HeaderV1| h.size).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    let error = Cursor::new((declared + 1).to_ne_bytes()).read_self_sized(|h: &HeaderV1| h.size).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn forward_compat_longer_frame() -> io::Result<()> {
+    let v2 = HeaderV2 { size: 16, flags: 3, timeout: 9, retries: 1, priority: 2 };
+
+    let mut buf = Vec::new();
+    buf.write_binary(&HeaderV1 { size: 8, flags: 4 })?;
+    buf.write_binary(&0xDEAD_BEEFu32)?;
+    buf.write_binary(&v2)?;
+
+    let mut cursor = Cursor::new(buf);
+
+    // A frame 4 bytes longer than the struct.
+    assert_eq!(cursor.read_binary_forward_compat::<HeaderV1>(12)?, HeaderV1 { size: 8, flags: 4 });
+    assert_eq!(cursor.read_binary_forward_compat_strict::<HeaderV1>(16)?, HeaderV1 { size: 16, flags: 3 });
+    assert_eq!(cursor.position(), 28);
+
+    Ok(())
+}
+
+#[test]
+fn forward_compat_shorter_frame() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&HeaderV1 { size: 8, flags: 4 })?;
+
+    let header = Cursor::new(&buf).read_binary_forward_compat::<HeaderV2>(8)?;
+    assert_eq!(header, HeaderV2 { size: 8, flags: 4, timeout: 0, retries: 0, priority: 0 });
+
+    let error = Cursor::new(&buf).read_binary_forward_compat_strict::<HeaderV2>(8).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let error = Cursor::new(&buf).read_binary_forward_compat::<HeaderV1>(12).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::{BinaryWrite, SeparatedReader, SeparatedWriter};
+use super::Test;
+use std::{io::{self, Cursor}, mem::size_of};
+
+const SEP: [u8; 3] = *b"\0|\0";
+
+fn write_three(trailing: bool) -> io::Result<([Test; 3], Vec<u8>)> {
+    let records = [Test::random(), Test::random(), Test::random()];
+    let mut writer = SeparatedWriter::new(Vec::new(), SEP).with_trailing(trailing);
+
+    for record in &records {
+        writer.write_record(record)?;
+    }
+
+    assert_eq!(writer.records(), 3);
+    Ok((records, writer.into_inner()))
+}
+
+#[test]
+fn record_sep_record() -> io::Result<()> {
+    for trailing in [false, true] {
+        let (records, bytes) = write_three(trailing)?;
+
+        let mut expected = Vec::new();
+        for (i, record) in records.iter().enumerate() {
+            expected.write_binary(record)?;
+
+            if trailing || i < 2 {
+                expected.extend_from_slice(&SEP);
+            }
+        }
+        assert_eq!(bytes, expected);
+
+        let mut reader = SeparatedReader::new(Cursor::new(bytes), SEP);
+        for record in &records {
+            assert_eq!(&reader.read_record::<Test>()?, record);
+        }
+
+        let error = reader.read_record::<Test>().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rejects_wrong_separator() -> io::Result<()> {
+    let (records, mut bytes) = write_three(false)?;
+    bytes[size_of::<Test>() + 1] = b'/';
+
+    let mut reader = SeparatedReader::new(Cursor::new(bytes), SEP);
+    let error = reader.read_record::<Test>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    // The next record still follows.
+    assert_eq!(reader.read_record::<Test>()?, records[1]);
+    Ok(())
+}
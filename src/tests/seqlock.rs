@@ -0,0 +1,84 @@
+use crate::SeqLocked;
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, thread};
+
+/// Every field holds the same value, so a torn copy is detectable.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+struct Pattern {
+    counter: u64,
+    words: [u64; 15],
+    last: u32,
+}
+
+impl Pattern {
+    fn new(counter: u64) -> Self {
+        Self {
+            counter,
+            words: [counter; 15],
+            last: counter as u32,
+        }
+    }
+
+    fn is_consistent(&self) -> bool {
+        self.words.iter().all(|&word| word == self.counter) && self.last == self.counter as u32
+    }
+}
+
+#[test]
+fn concurrent_reads_are_consistent() {
+    let shared = Arc::new(SeqLocked::new(Pattern::new(0)));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers = (0..4).map(|_| {
+        let shared = Arc::clone(&shared);
+        let done = Arc::clone(&done);
+
+        thread::spawn(move || {
+            while !done.load(Ordering::Relaxed) {
+                let value = shared.read();
+                assert!(value.is_consistent(), "torn read: {value:?}");
+
+                if let Some(value) = shared.try_read(0) {
+                    assert!(value.is_consistent(), "torn read: {value:?}");
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    let writers = (0..2).map(|writer| {
+        let shared = Arc::clone(&shared);
+
+        thread::spawn(move || {
+            for counter in 1..=50_000 {
+                if counter % 2 == writer {
+                    shared.write(Pattern::new(counter));
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    done.store(true, Ordering::Relaxed);
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert!(shared.read().is_consistent());
+    assert_eq!(shared.sequence(), 100_000);
+}
+
+#[test]
+fn try_read_during_write() {
+    let shared = SeqLocked::new(Pattern::new(7));
+
+    // Simulates a writer stopped in the middle of a write.
+    shared.sequence.store(1, Ordering::SeqCst);
+    assert!(shared.try_read(10).is_none());
+
+    shared.sequence.store(2, Ordering::SeqCst);
+    assert_eq!(shared.try_read(0).unwrap().counter, 7);
+}
@@ -0,0 +1,89 @@
+use crate::{BinDateTimeUtc, BinaryIter, DisorderPolicy, Envelope, EnvelopedWriter, OutOfSequence, SequenceChecker, SequenceEvent};
+use std::io::{self, Cursor};
+use SequenceEvent::*;
+
+fn check(arrived: &[u64], policy: DisorderPolicy) -> io::Result<Vec<SequenceEvent<u64>>> {
+    let envelopes = arrived.iter().map(|&seq| Ok(Envelope::new(seq, BinDateTimeUtc::UNIX_EPOCH, seq)));
+    SequenceChecker::new(envelopes, policy).starting_at(0).collect()
+}
+
+#[test]
+fn in_order_stream() -> io::Result<()> {
+    let mut writer = EnvelopedWriter::new(Vec::new()).with_sequence(10);
+
+    for payload in 0..4u32 {
+        writer.write_payload(payload)?;
+    }
+
+    let mut reader = Cursor::new(writer.into_inner());
+    let events = SequenceChecker::new(BinaryIter::<_, Envelope<u32>>::new(&mut reader), DisorderPolicy::Error)
+        .starting_at(10)
+        .collect::<io::Result<Vec<_>>>()?;
+
+    assert_eq!(events, [InOrder(0), InOrder(1), InOrder(2), InOrder(3)]);
+    Ok(())
+}
+
+#[test]
+fn error_policy_stops() {
+    let envelopes = [0, 1, 3, 4].map(|seq| Ok(Envelope::new(seq, BinDateTimeUtc::UNIX_EPOCH, ())));
+    let mut checker = SequenceChecker::new(envelopes.into_iter(), DisorderPolicy::Error);
+
+    assert!(matches!(checker.next(), Some(Ok(InOrder(())))));
+    assert!(matches!(checker.next(), Some(Ok(InOrder(())))));
+
+    let error = checker.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        error.get_ref().and_then(|e| e.downcast_ref::<OutOfSequence>()),
+        Some(&OutOfSequence { expected: 2, got: 3 })
+    );
+    assert!(checker.next().is_none());
+}
+
+#[test]
+fn skip_lossy_and_shuffled() -> io::Result<()> {
+    let events = check(&[0, 1, 4, 2, 5, 5, 3, 3, 1], DisorderPolicy::Skip)?;
+
+    assert_eq!(events, [
+        InOrder(0),
+        InOrder(1),
+        Gap { expected: 2, got: 4, missing: 2 },
+        InOrder(4),
+        OutOfOrder { seq: 2 },
+        InOrder(5),
+        Duplicate(5),
+        OutOfOrder { seq: 3 },
+        Duplicate(3),
+        Duplicate(1),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn reorder_within_window() -> io::Result<()> {
+    let events = check(&[3, 1, 0, 2, 5, 4, 4], DisorderPolicy::Reorder(4))?;
+
+    assert_eq!(events, (0..6).map(InOrder).chain([Duplicate(4)]).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn reorder_window_overflow_declares_gap() -> io::Result<()> {
+    let events = check(&[0, 3, 4, 5, 1, 7, 2], DisorderPolicy::Reorder(2))?;
+
+    assert_eq!(events, [
+        InOrder(0),
+        Gap { expected: 1, got: 3, missing: 2 },
+        InOrder(3),
+        InOrder(4),
+        InOrder(5),
+        OutOfOrder { seq: 1 },
+        OutOfOrder { seq: 2 },
+        Gap { expected: 6, got: 7, missing: 1 },
+        InOrder(7),
+    ]);
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use crate::{BinaryRead, BinaryWrite};
+use super::Test;
+use std::io::{self, Cursor};
+
+#[test]
+fn slice_into_refills() -> io::Result<()> {
+    let first = [Test::random(), Test::random()];
+    let second = [Test::random(), Test::random()];
+
+    let mut buf = Vec::new();
+    buf.write_binary(&first)?;
+    buf.write_binary(&second)?;
+
+    let mut cursor = Cursor::new(buf);
+    let mut records = vec![Test::random(), Test::random()];
+    let ptr = records.as_ptr();
+
+    cursor.read_binary_slice_into(&mut records)?;
+    assert_eq!(records, first);
+
+    cursor.read_binary_slice_into(&mut records)?;
+    assert_eq!(records, second);
+    assert_eq!(records.as_ptr(), ptr);
+
+    let error = cursor.read_binary_slice_into(&mut records).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+    Ok(())
+}
+
+#[test]
+fn slice_partial_counts_complete_records() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&[1u64, 2, 3])?;
+    buf.truncate(buf.len() - 3);
+
+    let mut records = [0u64; 5];
+    assert_eq!(Cursor::new(buf).read_binary_slice_partial(&mut records)?, 2);
+    assert_eq!(records[..2], [1, 2]);
+    assert_eq!(records[3..], [0, 0]);
+
+    Ok(())
+}
+
+#[test]
+fn boxed_slice() -> io::Result<()> {
+    let records = [Test::random(), Test::random(), Test::random()];
+    let mut buf = Vec::new();
+    buf.write_binary(&records)?;
+
+    let read = Cursor::new(buf).read_binary_boxed_slice::<Test>(3)?;
+
+    assert_eq!(read.len(), 3);
+    assert_eq!(std::mem::size_of_val(&*read), 3 * std::mem::size_of::<Test>());
+    assert_eq!(*read, records);
+    Ok(())
+}
+
+#[test]
+fn extend_reuses_capacity() -> io::Result<()> {
+    let items = (0..8).map(|_| Test::random()).collect::<Vec<_>>();
+
+    let mut buf = Vec::new();
+    for item in &items {
+        buf.write_binary(item)?;
+    }
+    let mut cursor = Cursor::new(buf);
+
+    let mut dst = Vec::<Test>::with_capacity(8);
+    let ptr = dst.as_ptr();
+
+    for chunk in items.chunks(4) {
+        dst.clear();
+        cursor.read_binary_extend(&mut dst, 4)?;
+        assert_eq!(dst, chunk);
+    }
+
+    assert_eq!(dst.as_ptr(), ptr);
+
+    Ok(())
+}
+
+#[test]
+fn extend_failure_keeps_contents() -> io::Result<()> {
+    let items = [Test::random(), Test::random(), Test::random()];
+
+    let mut buf = Vec::new();
+    for item in &items {
+        buf.write_binary(item)?;
+    }
+    buf.truncate(buf.len() - 1);
+
+    let mut dst = vec![items[2].clone()];
+    let error = Cursor::new(buf).read_binary_extend(&mut dst, 3).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(dst, [items[2].clone()]);
+
+    Ok(())
+}
+
+#[test]
+fn extend_partial_until_eof() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary(&[1u32, 2, 3, 4, 5])?;
+    buf.push(9);
+
+    let mut cursor = Cursor::new(buf);
+    let mut dst = vec![0u32];
+
+    assert_eq!(cursor.read_binary_extend_partial(&mut dst, 2)?, 2);
+    assert_eq!(cursor.read_binary_extend_partial(&mut dst, 10)?, 3);
+    assert_eq!(cursor.read_binary_extend_partial(&mut dst, 10)?, 0);
+    assert_eq!(dst, [0, 1, 2, 3, 4, 5]);
+
+    Ok(())
+}
+
+#[test]
+fn sorted_by_key() -> io::Result<()> {
+    let mut records = (0..16).map(|_| Test::random()).collect::<Vec<_>>();
+    records[3].f = records[7].f;
+
+    let mut buf = Vec::new();
+
+    for record in &records {
+        buf.write_binary(record)?;
+    }
+
+    let sorted = Cursor::new(buf).read_binary_sorted_by(records.len(), |record: &Test| record.f)?;
+
+    assert!(sorted.windows(2).all(|pair| pair[0].f <= pair[1].f));
+
+    records.sort_by_key(|record| record.f);
+    assert_eq!(sorted, records);
+    Ok(())
+}
+
+#[test]
+fn grouped_by_key() -> io::Result<()> {
+    let mut records = (0..16).map(|_| Test::random()).collect::<Vec<_>>();
+
+    let mut buf = Vec::new();
+
+    for (i, record) in records.iter_mut().enumerate() {
+        record.g = (i % 2) as u8;
+        buf.write_binary(record)?;
+    }
+
+    let groups = Cursor::new(buf).read_binary_grouped_by(records.len(), |record: &Test| record.g)?;
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&0], records.iter().step_by(2).cloned().collect::<Vec<_>>());
+    assert_eq!(groups[&1], records.iter().skip(1).step_by(2).cloned().collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn padded_elements_stride() -> io::Result<()> {
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Padded {
+        value: u16,
+        kind: u8,
+    }
+
+    #[repr(C, packed)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Packed {
+        value: u16,
+        kind: u8,
+    }
+
+    assert_eq!(std::mem::size_of::<Padded>(), 4);
+    assert_eq!(std::mem::size_of::<[Padded; 3]>(), 3 * std::mem::size_of::<Padded>());
+    assert_eq!(std::mem::size_of::<[Packed; 3]>(), 9);
+
+    let padded = [Padded { value: 1, kind: 2 }, Padded { value: 3, kind: 4 }, Padded { value: 5, kind: 6 }];
+    let packed = padded.map(|item| Packed { value: item.value, kind: item.kind });
+
+    let mut buf = Vec::new();
+    buf.write_binary(&padded)?;
+    buf.write_binary(&packed)?;
+    assert_eq!(buf.len(), 12 + 9);
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary_vec::<Padded>(3)?, padded);
+    assert_eq!(cursor.read_binary_vec::<Packed>(3)?, packed);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::{BinaryRead, BinaryWrite, SlotWriter};
+use std::io::{self, Cursor, Seek};
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Entry {
+    offset: u64,
+    len: u64,
+}
+
+#[test]
+fn fill_reserved_slots() -> io::Result<()> {
+    let mut writer = SlotWriter::<Entry, _>::new(Cursor::new(Vec::new()));
+
+    let first = writer.reserve()?;
+    let second = writer.reserve()?;
+    assert_eq!(second.offset(), 16);
+
+    let body = writer.get_mut().stream_position()?;
+    writer.write_binary(&[1u32, 2, 3])?;
+    writer.write_binary(&[4u16; 5])?;
+
+    writer.fill(second, &Entry { offset: body + 12, len: 10 })?;
+    writer.fill(first, &Entry { offset: body, len: 12 })?;
+    writer.write_binary(&0xFFu8)?;
+
+    let buf = writer.into_inner().into_inner();
+    assert_eq!(buf.len(), 32 + 12 + 10 + 1);
+
+    let mut cursor = Cursor::new(buf);
+    assert_eq!(cursor.read_binary::<Entry>()?, Entry { offset: 32, len: 12 });
+    assert_eq!(cursor.read_binary::<Entry>()?, Entry { offset: 44, len: 10 });
+    assert_eq!(cursor.read_binary::<[u32; 3]>()?, [1, 2, 3]);
+    assert_eq!(cursor.read_binary::<[u16; 5]>()?, [4; 5]);
+    assert_eq!(cursor.read_binary::<u8>()?, 0xFF);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::{read_binary_at_offsets, snapshot_records, snapshot_records_verified, BinaryRead, BinaryWrite};
+use std::{fs::{self, OpenOptions}, io::{self, Cursor, Write}, mem::size_of, thread};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entry {
+    index: u64,
+    checksum: u64,
+}
+
+impl Entry {
+    fn new(index: u64) -> Self {
+        Self { index, checksum: !index }
+    }
+}
+
+#[test]
+fn snapshot_concurrent_appender() -> io::Result<()> {
+    OpenOptions::new().create(true).write(true).truncate(true).open("./test_snapshot.bin")?;
+
+    let appender = thread::spawn(|| -> io::Result<()> {
+        let mut file = OpenOptions::new().append(true).open("./test_snapshot.bin")?;
+
+        for index in 0..20_000 {
+            file.write_binary(&Entry::new(index))?;
+        }
+
+        Ok(())
+    });
+
+    let live = OpenOptions::new().read(true).open("./test_snapshot.bin")?;
+    let mut snapshots = Vec::new();
+
+    while !appender.is_finished() {
+        let mut copy = Vec::new();
+        let records = snapshot_records_verified::<Entry>(&live, &mut copy, |record| {
+            record[..8].iter().zip(&record[8..]).all(|(a, b)| *a == !b)
+        })?;
+
+        assert_eq!(copy.len(), records as usize * size_of::<Entry>());
+        snapshots.push(copy);
+    }
+
+    appender.join().unwrap()?;
+    let full = fs::read("./test_snapshot.bin")?;
+
+    for snapshot in snapshots {
+        assert_eq!(snapshot, full[..snapshot.len()]);
+    }
+
+    let mut copy = Vec::new();
+    assert_eq!(snapshot_records::<Entry>(&live, &mut copy)?, 20_000);
+    assert_eq!(Cursor::new(copy).read_binary_vec::<Entry>(20_000)?[19_999], Entry::new(19_999));
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_ignores_partial_record() -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).read(true).write(true).truncate(true).open("./test_snapshot_partial.bin")?;
+    file.write_binary(&[Entry::new(0), Entry::new(1)])?;
+    file.write_all(&[0xff; 5])?;
+
+    let mut copy = Vec::new();
+    assert_eq!(snapshot_records::<Entry>(&file, &mut copy)?, 2);
+    assert_eq!(copy.len(), 2 * size_of::<Entry>());
+
+    let error = snapshot_records_verified::<Entry>(&file, Vec::new(), |record| record[0] == 0).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("record 1"));
+
+    Ok(())
+}
+
+#[test]
+fn scattered_offsets() -> io::Result<()> {
+    let entries = (0..1000).map(Entry::new).collect::<Vec<_>>();
+    let mut file = OpenOptions::new().create(true).write(true).truncate(true).open("./test_offsets.bin")?;
+    entries.iter().try_for_each(|entry| file.write_binary(entry))?;
+    drop(file);
+
+    let size = size_of::<Entry>() as u64;
+    // Shuffled, with duplicates, neighbours and a misaligned offset overlapping two records.
+    let offsets = [900 * size, 3 * size, 4 * size, 999 * size, 3 * size, 0, 500 * size, 2 * size + 8];
+
+    let file = fs::File::open("./test_offsets.bin")?;
+    let read = read_binary_at_offsets::<Entry>(&file, &offsets)?;
+
+    assert_eq!(read[..7], [900, 3, 4, 999, 3, 0, 500].map(|index| entries[index]));
+    assert_eq!(read[7], Entry { index: !2, checksum: 3 });
+    assert!(read_binary_at_offsets::<Entry>(&file, &[])?.is_empty());
+
+    let error = read_binary_at_offsets::<Entry>(&file, &[0, 999 * size + 1, 5]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(error.to_string().contains("index 1"));
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::{BinaryBufReader, BinaryRead, BinaryWrite};
+use std::io::{self, BufRead, Cursor, Read};
+
+/// A source returning at most 1000 bytes per read, splitting records unevenly.
+struct Trickle<R>(R);
+
+impl<R: Read> Read for Trickle<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(1000);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Awkward {
+    id: u32,
+    payload: [u8; 696],
+}
+
+fn records(count: u32) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    for id in 0..count {
+        bytes.write_binary(&Awkward { id, payload: [id as u8; 696] })?;
+    }
+
+    Ok(bytes)
+}
+
+#[test]
+fn buffer_holds_whole_records() {
+    let reader = BinaryBufReader::<Awkward, _>::new(io::empty());
+    assert_eq!(reader.capacity(), 94 * 700);
+
+    let reader = BinaryBufReader::<Awkward, _>::with_record_capacity(io::empty(), 3);
+    assert_eq!(reader.capacity(), 3 * 700);
+}
+
+#[test]
+fn records_across_split_reads() -> io::Result<()> {
+    let mut reader = BinaryBufReader::<Awkward, _>::with_record_capacity(Trickle(Cursor::new(records(20)?)), 4);
+
+    for id in 0..20u32 {
+        let bytes = reader.record_bytes()?.unwrap();
+        assert_eq!(bytes.len(), 700);
+        assert_eq!(bytes[..4], id.to_ne_bytes());
+        assert!(bytes[4..].iter().all(|&byte| byte == id as u8));
+    }
+
+    assert!(reader.read_record()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn mixed_reads() -> io::Result<()> {
+    let mut bytes = records(3)?;
+    bytes.extend_from_slice(&[9; 10]);
+    let mut reader = BinaryBufReader::<Awkward, _>::new(Trickle(Cursor::new(bytes)));
+
+    assert_eq!(reader.read_binary::<u32>()?, 0);
+    reader.consume(696);
+    assert_eq!(reader.read_record()?.unwrap().id, 1);
+    assert_eq!(reader.read_binary::<Awkward>()?.id, 2);
+
+    // Ten bytes left aren't a whole record.
+    let error = reader.read_record().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::{BinaryRead, BinaryWrite, TaggedStreamReader};
+use std::{io, thread};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+struct Tag {
+    kind: u16,
+    len: u16,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[repr(C)]
+struct Small {
+    tag: Tag,
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[repr(C)]
+struct Large {
+    tag: Tag,
+    values: [u64; 4],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Message {
+    Small(Small),
+    Large(Large),
+}
+
+#[test]
+fn dispatch_over_pipe() -> io::Result<()> {
+    let (reader, mut writer) = io::pipe()?;
+
+    let small = Small { tag: Tag { kind: 1, len: 8 }, value: 42 };
+    let large = Large { tag: Tag { kind: 2, len: 40 }, values: [1, 2, 3, 4] };
+
+    let writer_thread = thread::spawn(move || -> io::Result<()> {
+        writer.write_binary(&Large { tag: large.tag, values: large.values })?;
+        writer.write_binary(&Small { tag: small.tag, value: small.value })?;
+        writer.write_binary(&Large { tag: large.tag, values: large.values })
+    });
+
+    let mut reader = TaggedStreamReader::new(reader);
+    let mut messages = Vec::new();
+
+    for _ in 0..3 {
+        let tag = reader.peek_binary::<Tag>()?;
+        assert_eq!(reader.peek_binary::<Tag>()?, tag);
+
+        messages.push(match tag.kind {
+            1 => Message::Small(reader.read_binary()?),
+            _ => Message::Large(reader.read_binary()?),
+        });
+    }
+
+    writer_thread.join().unwrap()?;
+
+    assert_eq!(messages, [
+        Message::Large(Large { tag: large.tag, values: large.values }),
+        Message::Small(small),
+        Message::Large(large),
+    ]);
+
+    assert!(reader.buffered().is_empty());
+    Ok(())
+}
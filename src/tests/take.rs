@@ -0,0 +1,52 @@
+use crate::{BinaryRead, BinaryWrite, LimitReached, TakeExact};
+use super::Test;
+use std::io::{self, Cursor, Read};
+
+fn limit_reached(error: &io::Error) -> Option<&LimitReached> {
+    error.get_ref().and_then(|e| e.downcast_ref::<LimitReached>())
+}
+
+#[test]
+fn past_limit_is_limit_reached() -> io::Result<()> {
+    let mut reader = TakeExact::new(Cursor::new([7u8; 32]), 8);
+    let error = reader.read_binary::<[u8; 16]>().unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(limit_reached(&error), Some(&LimitReached { limit: 8 }));
+    assert_eq!(reader.remaining(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn source_end_is_plain_eof() {
+    let mut reader = TakeExact::new(Cursor::new([7u8; 4]), 8);
+    let error = reader.read_binary::<u64>().unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert!(limit_reached(&error).is_none());
+    assert_eq!(reader.remaining(), 4);
+}
+
+#[test]
+fn records_within_limit() -> io::Result<()> {
+    let records = [Test::random(), Test::random()];
+    let mut buf = Vec::new();
+
+    for record in &records {
+        buf.write_binary(record)?;
+    }
+
+    let size = std::mem::size_of::<Test>() as u64;
+    let mut reader = TakeExact::new(Cursor::new(buf), size);
+
+    assert_eq!(reader.read_binary::<Test>()?, records[0]);
+    assert_eq!(reader.remaining(), 0);
+    assert!(limit_reached(&reader.read_binary::<Test>().unwrap_err()).is_some());
+
+    reader.set_limit(size);
+    assert_eq!(reader.read_binary::<Test>()?, records[1]);
+    assert_eq!(reader.read(&mut [])?, 0);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::{BinaryRead, BinaryWrite, TeeFailed, TeeWriter};
+use std::io::{self, Cursor, Write};
+use super::Test;
+
+struct Broken;
+
+impl Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "connection reset"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn tee_failed(error: &io::Error) -> &TeeFailed {
+    error.get_ref().and_then(|e| e.downcast_ref::<TeeFailed>()).unwrap()
+}
+
+#[test]
+fn record_in_both_writers() -> io::Result<()> {
+    let records = [Test::random(), Test::random()];
+    let mut tee = TeeWriter::new(Vec::new(), Vec::new());
+    records.iter().try_for_each(|record| tee.write_binary(record))?;
+    tee.flush()?;
+
+    let (first, second) = tee.into_inner();
+    assert_eq!(first, second);
+    assert_eq!(Cursor::new(first).read_binary_vec::<Test>(2)?, records);
+    Ok(())
+}
+
+#[test]
+fn reports_failed_writer() {
+    let mut tee = TeeWriter::new(Vec::new(), Broken);
+    let error = tee.write_binary(&7u64).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    let failed = tee_failed(&error);
+    assert!(failed.first.is_none());
+    assert_eq!(failed.second.as_ref().unwrap().kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(tee.get_ref().0, &7u64.to_ne_bytes());
+
+    let mut tee = TeeWriter::new(Broken, Broken);
+    let error = tee.write_binary(&7u64).unwrap_err();
+    let failed = tee_failed(&error);
+    assert!(failed.first.is_some() && failed.second.is_some());
+}
@@ -0,0 +1,83 @@
+use crate::{measure_throughput, BinaryRead, BinaryWrite, MockBinarySink, MockBinarySource};
+use std::{io::{ErrorKind, Read}, mem::size_of};
+
+#[derive(Default)]
+#[allow(unused)]
+struct Small {
+    id: u32,
+    value: u16,
+}
+
+#[test]
+fn throughput_is_nonzero() {
+    let report = measure_throughput::<Small>(1000);
+
+    assert_eq!(report.records, 1000);
+    assert_eq!(report.bytes, 8000);
+    assert!(report.bytes_per_sec() > 0.0);
+    assert!(report.records_per_sec() > 0.0);
+    assert!(report.bytes_per_sec().is_finite());
+}
+
+#[test]
+fn sink_transcript() {
+    let records = [super::Test::random(), super::Test::random()];
+    let mut sink = MockBinarySink::new();
+    records.iter().for_each(|record| sink.write_binary(record).unwrap());
+    sink.write_binary(&3u16).unwrap();
+
+    assert_eq!(sink.bytes().len(), 2 * size_of::<super::Test>() + 2);
+    sink.assert_wrote(&records[0]);
+    sink.assert_wrote(&records[1]);
+    sink.assert_wrote_bytes(&3u16.to_ne_bytes());
+    sink.assert_done();
+}
+
+#[test]
+#[should_panic(expected = "at byte 0 is 1, expected 2")]
+fn sink_mismatch() {
+    let mut sink = MockBinarySink::new();
+    sink.write_binary(&1u32).unwrap();
+    sink.assert_wrote(&2u32);
+}
+
+#[test]
+#[should_panic(expected = "expected 8 bytes of u64 at byte 4, but only 0 were written")]
+fn sink_missing_record() {
+    let mut sink = MockBinarySink::new();
+    sink.write_binary(&1u32).unwrap();
+    sink.assert_wrote(&1u32);
+    sink.assert_wrote(&1u64);
+}
+
+#[test]
+fn source_script() {
+    let record = super::Test::random();
+    let bytes = crate::to_array::<_, { size_of::<super::Test>() }>(&record);
+
+    let mut source = MockBinarySource::new()
+        .then_bytes(&bytes[..3])
+        .then_error(ErrorKind::Interrupted)
+        .then_bytes(&bytes[3..])
+        .then_record(&record)
+        .then_error(ErrorKind::TimedOut)
+        .then_bytes(&[1, 2]);
+
+    assert_eq!(source.read_binary::<super::Test>().unwrap(), record);
+    assert_eq!(source.read_binary::<super::Test>().unwrap(), record);
+    assert_eq!(source.read_binary::<u8>().unwrap_err().kind(), ErrorKind::TimedOut);
+
+    // The script ends in the middle of the record.
+    assert_eq!(source.read_binary::<u32>().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert!(source.is_done());
+}
+
+#[test]
+fn source_short_reads() {
+    let mut source = MockBinarySource::new().then_record(&[1u64, 2, 3]).with_max_read(5);
+    let mut buf = [0; 24];
+
+    assert_eq!(source.read(&mut buf).unwrap(), 5);
+    assert_eq!(source.read_binary::<[u8; 19]>().unwrap().len(), 19);
+    assert_eq!(source.read(&mut buf).unwrap(), 0);
+}
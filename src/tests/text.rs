@@ -0,0 +1,53 @@
+use crate::{text_pairs, BinaryRead, InvalidPair, BINARY_MARKER};
+use std::io::{Cursor, ErrorKind};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Settings {
+    id: u32,
+    ratio: f32,
+    enabled: bool,
+}
+
+text_pairs!(Settings { id: u32, ratio: f32, enabled: bool });
+
+#[test]
+fn binary_and_text_match() {
+    let settings = Settings { id: 42, ratio: 0.5, enabled: true };
+
+    let mut binary = vec![BINARY_MARKER];
+    binary.extend_from_slice(&crate::to_array::<_, { size_of::<Settings>() }>(&settings));
+    let text = "# defaults\n\nid = 42\nratio=0.5\n  enabled = true  \n";
+
+    assert_eq!(Cursor::new(binary).read_binary_or_text::<Settings>().unwrap(), settings);
+    assert_eq!(Cursor::new(text).read_binary_or_text::<Settings>().unwrap(), settings);
+}
+
+#[test]
+fn missing_keys_are_defaulted() {
+    let settings = Cursor::new("enabled=true").read_binary_or_text::<Settings>().unwrap();
+    assert_eq!(settings, Settings { enabled: true, ..Default::default() });
+
+    let empty = Cursor::new([]).read_binary_or_text::<Settings>().unwrap();
+    assert_eq!(empty, Settings::default());
+}
+
+#[test]
+fn invalid_lines() {
+    let invalid = |text: &str| {
+        let error = Cursor::new(text).read_binary_or_text::<Settings>().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        error.into_inner().unwrap().downcast::<InvalidPair>().unwrap()
+    };
+
+    let error = invalid("id=1\nid=-1");
+    assert_eq!((error.line, error.key.as_str()), (2, "id"));
+
+    let error = invalid("\nspeed=3");
+    assert_eq!((error.line, error.key.as_str()), (2, "speed"));
+
+    let error = invalid("enabled");
+    assert_eq!((error.line, error.key.as_str()), (1, ""));
+
+    let error = Cursor::new([b'i', 0xC0]).read_binary_or_text::<Settings>().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
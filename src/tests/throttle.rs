@@ -0,0 +1,107 @@
+use crate::{BinaryWrite, Clock, ThrottledWriter};
+use std::{cell::{Cell, RefCell}, io::{self, Write}, time::{Duration, Instant}};
+
+/// A clock that only moves when slept on or advanced, recording the sleeps.
+struct MockClock {
+    now: Cell<Instant>,
+    sleeps: RefCell<Vec<Duration>>,
+}
+
+impl MockClock {
+    fn new() -> Self {
+        Self { now: Cell::new(Instant::now()), sleeps: RefCell::new(Vec::new()) }
+    }
+
+    fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    fn take_sleeps(&self) -> Vec<Duration> {
+        self.sleeps.take()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.sleeps.borrow_mut().push(duration);
+        self.advance(duration);
+    }
+}
+
+fn assert_close(actual: &[Duration], expected: &[u64]) {
+    assert_eq!(actual.len(), expected.len(), "{actual:?}");
+
+    for (actual, millis) in actual.iter().zip(expected) {
+        let error = actual.as_secs_f64() - *millis as f64 / 1000.0;
+        assert!(error.abs() < 1e-6, "slept {actual:?} instead of {millis}ms");
+    }
+}
+
+#[test]
+fn paces_to_rate() -> io::Result<()> {
+    let clock = MockClock::new();
+    let mut writer = ThrottledWriter::with_clock(Vec::new(), 1000, 100, &clock);
+
+    // The bucket starts full.
+    writer.write_all(&[0; 100])?;
+    assert_close(&clock.take_sleeps(), &[]);
+
+    // Then fills at a token per millisecond.
+    writer.write_all(&[0; 100])?;
+    writer.write_all(&[0; 50])?;
+    assert_close(&clock.take_sleeps(), &[100, 50]);
+
+    // Idle time refills the bucket, up to the burst.
+    clock.advance(Duration::from_secs(10));
+    writer.write_all(&[0; 100])?;
+    writer.write_all(&[0; 1])?;
+    assert_close(&clock.take_sleeps(), &[1]);
+
+    assert_eq!(writer.bytes_written(), 351);
+    assert_eq!(writer.delayed_bytes(), 151);
+    assert!((writer.delay().as_secs_f64() - 0.151).abs() < 1e-6);
+    Ok(())
+}
+
+#[test]
+fn records_larger_than_burst_are_written_whole() -> io::Result<()> {
+    #[repr(C)]
+    struct Block {
+        data: [u8; 250]
+    }
+
+    let clock = MockClock::new();
+    let mut writer = ThrottledWriter::with_clock(Vec::new(), 1000, 100, &clock);
+
+    writer.write_all(&[0; 100])?;
+    // Waits for a full bucket, then leaves it 150 tokens in debt.
+    writer.write_binary(&Block { data: [7; 250] })?;
+    writer.write_all(&[0; 10])?;
+    assert_close(&clock.take_sleeps(), &[100, 160]);
+
+    let bytes = writer.into_inner();
+    assert_eq!(bytes.len(), 360);
+    assert!(bytes[100..350].iter().all(|b| *b == 7));
+    Ok(())
+}
+
+#[test]
+fn rate_changes() -> io::Result<()> {
+    let clock = MockClock::new();
+    let mut writer = ThrottledWriter::with_clock(Vec::new(), 1000, 100, &clock);
+
+    writer.write_all(&[0; 100])?;
+    clock.advance(Duration::from_millis(20));
+
+    // The 20 tokens gathered are kept, the rest comes at the new rate.
+    writer.set_rate(100, 50);
+    assert_eq!((writer.bytes_per_second(), writer.burst()), (100, 50));
+
+    writer.write_all(&[0; 50])?;
+    assert_close(&clock.take_sleeps(), &[300]);
+    Ok(())
+}
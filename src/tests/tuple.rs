@@ -0,0 +1,105 @@
+use crate::{BinaryRead, BinaryWrite, SwapBytes, Validate, Zeroable};
+use std::{io::{self, Cursor}, mem::size_of, num::NonZeroU16};
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Rgb(u8, u8, u8);
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Span {
+    start: u16,
+    len: NonZeroU16,
+}
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Pixel {
+    color: Rgb,
+    alpha: bool,
+}
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Line(u32, Span);
+
+#[derive(Debug, PartialEq)]
+struct Marker;
+
+crate::zeroable!(Rgb(u8, u8, u8));
+crate::validate!(Rgb(u8, #[range = 0..=127] u8, u8));
+crate::swap_bytes!(Rgb(u8, u8, u8));
+
+crate::validate!(Span { start: u16, len: NonZeroU16 });
+crate::swap_bytes!(Span { start: u16, len: NonZeroU16 });
+
+crate::zeroable!(Pixel { color: Rgb, alpha: bool });
+crate::validate!(Pixel { color: Rgb, alpha: bool });
+
+crate::validate!(Line(#[magic = 0xFEED] u32, Span));
+crate::swap_bytes!(Line(u32, Span));
+
+crate::zeroable!(Marker);
+crate::validate!(Marker);
+crate::swap_bytes!(Marker);
+
+#[test]
+fn tuple_in_named() -> io::Result<()> {
+    let pixel = Pixel { color: Rgb(255, 100, 0), alpha: true };
+
+    let mut buf = Vec::new();
+    buf.write_binary(&pixel)?;
+    assert_eq!(Cursor::new(&buf).read_binary_validated::<Pixel>()?, pixel);
+
+    buf[1] = 200;
+    let error = Pixel::validate_bytes(&buf).unwrap_err();
+    assert_eq!(error.field, Some(".1"));
+    assert_eq!(error.offset, 1);
+
+    let pixel = Pixel::zeroed();
+    assert_eq!(pixel.color, Rgb(0, 0, 0));
+    assert!(!pixel.alpha);
+
+    Ok(())
+}
+
+#[test]
+fn named_in_tuple() -> io::Result<()> {
+    let line = Line(0xFEED, Span { start: 3, len: NonZeroU16::new(258).unwrap() });
+
+    let mut buf = Vec::new();
+    buf.write_binary_be(&line)?;
+    assert_eq!(buf[..4], [0, 0, 0xFE, 0xED]);
+    assert_eq!(buf[6..8], [1, 2]);
+
+    let mut foreign = buf.clone();
+    Line::swap_bytes(&mut foreign);
+    assert!(Line::is_valid_bytes(&foreign));
+    assert_eq!(Cursor::new(buf).read_binary_be::<Line>()?, line);
+
+    let mut buf = Vec::new();
+    buf.write_binary(&line)?;
+    buf[6..8].fill(0);
+
+    let error = Line::validate_bytes(&buf).unwrap_err();
+    assert_eq!(error.field, Some("len"));
+    assert_eq!(error.offset, 6);
+
+    buf[..4].fill(0);
+    assert_eq!(Line::validate_bytes(&buf).unwrap_err().field, Some(".0"));
+
+    Ok(())
+}
+
+#[test]
+fn unit_struct() -> io::Result<()> {
+    assert_eq!(size_of::<Marker>(), 0);
+    assert!(Marker::is_valid_bytes(&[]));
+    assert_eq!(Marker::zeroed(), Marker);
+
+    let mut buf = Vec::new();
+    buf.write_binary_be(&Marker)?;
+    assert!(buf.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use crate::{BinaryRead, BinaryWrite, Validate, ValidationError, validate_field};
+use std::{io::{self, Cursor}, mem::offset_of, num::NonZeroU32};
+
+#[derive(Debug, PartialEq, Eq)]
+#[repr(C)]
+struct WithNonZero {
+    len: u32,
+    id: NonZeroU32,
+}
+
+impl Validate for WithNonZero {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        validate_field::<NonZeroU32>(bytes, offset_of!(WithNonZero, id), "id")
+    }
+}
+
+#[test]
+fn zero_non_zero_errors() {
+    let error = Cursor::new([0u8; 4]).read_binary_validated::<NonZeroU32>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let error = Cursor::new([0u8; 8]).read_binary_validated::<WithNonZero>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    let inner = error.get_ref()
+        .and_then(|e| e.downcast_ref::<ValidationError>())
+        .unwrap();
+
+    assert_eq!(inner.field, Some("id"));
+    assert_eq!(inner.offset, 4);
+}
+
+#[test]
+fn valid_non_zero_reads() -> io::Result<()> {
+    let original = WithNonZero {
+        len: 0,
+        id: NonZeroU32::new(7).unwrap(),
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary(&original)?;
+
+    assert_eq!(Cursor::new(buf).read_binary_validated::<WithNonZero>()?, original);
+    assert_eq!(Cursor::new([0u8; 4]).read_binary_validated::<Option<NonZeroU32>>()?, None);
+
+    Ok(())
+}
+
+#[test]
+fn invalid_primitives() {
+    assert!(bool::validate_bytes(&[2]).is_err());
+    assert!(char::validate_bytes(&0xD800u32.to_ne_bytes()).is_err());
+
+    let error = <[bool; 3]>::validate_bytes(&[1, 0, 5]).unwrap_err();
+    assert_eq!(error.offset, 2);
+}
+
+#[allow(unused)]
+#[repr(C)]
+struct Message {
+    magic: [u8; 4],
+    kind: u16,
+    id: NonZeroU32,
+    urgent: bool,
+    letter: char,
+}
+
+crate::validate!(Message {
+    #[magic = *b"MSG1"]
+    magic: [u8; 4],
+    #[range = 1..=4]
+    kind: u16,
+    id: NonZeroU32,
+    urgent: bool,
+    letter: char,
+});
+
+fn message_bytes() -> Vec<u8> {
+    let mut bytes = vec![0u8; std::mem::size_of::<Message>()];
+    bytes[..4].copy_from_slice(b"MSG1");
+    bytes[offset_of!(Message, kind)] = 2;
+    bytes[offset_of!(Message, id)] = 9;
+    bytes[offset_of!(Message, letter)] = b'x';
+    bytes
+}
+
+#[test]
+fn struct_bytes_precheck() {
+    let bytes = message_bytes();
+
+    assert!(Message::is_valid_bytes(&bytes));
+    assert!(!Message::is_valid_bytes(&bytes[1..]));
+    assert!(Message::validate_bytes(&bytes[1..]).is_err());
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert_eq!(Message::validate_bytes(&bad_magic).unwrap_err().field, Some("magic"));
+
+    let mut bad_kind = bytes.clone();
+    bad_kind[offset_of!(Message, kind)] = 7;
+    let error = Message::validate_bytes(&bad_kind).unwrap_err();
+    assert_eq!((error.field, error.offset), (Some("kind"), offset_of!(Message, kind)));
+
+    let mut bad_bool = bytes.clone();
+    bad_bool[offset_of!(Message, urgent)] = 2;
+    assert_eq!(Message::validate_bytes(&bad_bool).unwrap_err().field, Some("urgent"));
+
+    let mut bad_char = bytes;
+    bad_char[offset_of!(Message, letter) + 3] = 0xFF;
+    let error = Message::validate_bytes(&bad_char).unwrap_err();
+    assert_eq!((error.field, error.offset), (Some("letter"), offset_of!(Message, letter)));
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn errors_are_defmt_format() {
+    fn assert_format<T: defmt::Format>() {}
+
+    assert_format::<crate::ValidationError>();
+    assert_format::<crate::UnknownVariant>();
+    assert_format::<crate::PresenceMismatch>();
+    assert_format::<crate::ChainBroken>();
+    assert_format::<crate::Misaligned>();
+    assert_format::<crate::OutOfSequence>();
+    assert_format::<crate::LimitReached>();
+    assert_format::<crate::DeadlineExceeded>();
+    assert_format::<crate::BufferTooSmall>();
+    assert_format::<crate::ChecksumMismatch>();
+}
@@ -0,0 +1,111 @@
+use crate::{npy_record, BinaryRead, BinaryWrite, NpyRecord, UnalignedQuat, UnalignedVec4, Zeroable};
+use glam::{Mat3, Mat3A, Mat4, Quat, Vec3, Vec3A, Vec4};
+use std::{io::{self, Cursor}, mem::{align_of, offset_of, size_of}};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Transform {
+    id: u32,
+    position: Vec3,
+    rotation: Quat,
+    scale: Vec3,
+}
+
+crate::zeroable!(Transform { id: u32, position: Vec3, rotation: Quat, scale: Vec3 });
+crate::validate!(Transform { id: u32, position: Vec3, rotation: Quat, scale: Vec3 });
+crate::swap_bytes!(Transform { id: u32, position: Vec3, rotation: Quat, scale: Vec3 });
+npy_record!(Transform { id: u32, position: Vec3, rotation: Quat, scale: Vec3 });
+
+// The same fields as `struct { uint32_t id; float position[3]; float rotation[4]; float scale[3]; }`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PackedTransform {
+    id: u32,
+    position: Vec3,
+    rotation: UnalignedQuat,
+    scale: Vec3,
+}
+
+crate::swap_bytes!(PackedTransform { id: u32, position: Vec3, rotation: UnalignedQuat, scale: Vec3 });
+
+#[test]
+fn simd_alignment() {
+    assert_eq!((size_of::<Vec3>(), align_of::<Vec3>()), (12, 4));
+    assert_eq!((size_of::<Vec3A>(), align_of::<Vec3A>()), (16, 16));
+    assert_eq!((size_of::<Vec4>(), align_of::<Vec4>()), (16, 16));
+    assert_eq!((size_of::<Quat>(), align_of::<Quat>()), (16, 16));
+    assert_eq!((size_of::<Mat3>(), align_of::<Mat3>()), (36, 4));
+    assert_eq!((size_of::<Mat3A>(), align_of::<Mat3A>()), (48, 16));
+    assert_eq!((size_of::<Mat4>(), align_of::<Mat4>()), (64, 16));
+
+    // The quaternion is pushed to the next multiple of 16, leaving a hole after the position.
+    assert_eq!(offset_of!(Transform, rotation), 16);
+    assert_eq!(size_of::<Transform>(), 48);
+
+    assert_eq!((size_of::<UnalignedQuat>(), align_of::<UnalignedQuat>()), (16, 4));
+    assert_eq!(offset_of!(PackedTransform, rotation), 16);
+    assert_eq!(offset_of!(PackedTransform, scale), 32);
+    assert_eq!(size_of::<PackedTransform>(), 44);
+}
+
+#[test]
+fn nested_fields_swap_by_lane() -> io::Result<()> {
+    let transform = Transform {
+        id: 7,
+        position: Vec3::new(1.0, 2.0, 3.0),
+        rotation: Quat::from_xyzw(0.0, 0.0, 0.0, 1.0),
+        scale: Vec3::ONE,
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary_be(&transform)?;
+    assert_eq!(buf[..4], [0, 0, 0, 7]);
+    assert_eq!(buf[4..8], 1.0f32.to_be_bytes());
+    assert_eq!(buf[28..32], 1.0f32.to_be_bytes());
+
+    assert_eq!(Cursor::new(buf).read_binary_be::<Transform>()?, transform);
+    Ok(())
+}
+
+#[test]
+fn unaligned_mirrors() -> io::Result<()> {
+    let rotation = Quat::from_rotation_z(1.0);
+    let transform = PackedTransform {
+        id: 1,
+        position: Vec3::X,
+        rotation: rotation.into(),
+        scale: Vec3::splat(2.0),
+    };
+
+    let mut buf = Vec::new();
+    buf.write_binary_le(&transform)?;
+    assert_eq!(buf.len(), 44);
+    assert_eq!(buf[16..20], rotation.x.to_le_bytes());
+
+    let read = Cursor::new(buf).read_binary_le::<PackedTransform>()?;
+    assert_eq!(Quat::from(read.rotation), rotation);
+    assert_eq!(Vec4::from(UnalignedVec4::from(Vec4::W)), Vec4::W);
+    Ok(())
+}
+
+#[test]
+fn padded_lanes_are_left_alone() -> io::Result<()> {
+    let mut buf = Vec::new();
+    buf.write_binary_be(&Vec3A::new(1.0, 2.0, 3.0))?;
+    assert_eq!(buf[8..12], 3.0f32.to_be_bytes());
+
+    buf.truncate(12);
+    buf.extend_from_slice(&[0xAB; 4]);
+    let vector = Cursor::new(buf).read_binary_be::<Vec3A>()?;
+    assert_eq!(vector, Vec3A::new(1.0, 2.0, 3.0));
+    assert_eq!(Mat3A::zeroed(), Mat3A::ZERO);
+    Ok(())
+}
+
+#[test]
+fn reflection() {
+    let fields = Transform::FIELDS;
+    assert_eq!(fields[1].len, Some(3));
+    assert_eq!((fields[2].len, fields[2].offset), (Some(4), 16));
+    assert_eq!(fields[3].descr, <f32 as crate::NpyType>::DESCR);
+}
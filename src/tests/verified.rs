@@ -0,0 +1,60 @@
+use crate::BinaryWrite;
+use super::Test;
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+
+/// A cursor whose writes flip the bits of the byte at `corrupt_at`.
+struct Corrupting {
+    inner: Cursor<Vec<u8>>,
+    corrupt_at: u64,
+}
+
+impl Write for Corrupting {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let start = self.inner.position();
+        let mut buf = buf.to_vec();
+
+        if let Some(byte) = self.corrupt_at.checked_sub(start).and_then(|i| buf.get_mut(i as usize)) {
+            *byte = !*byte;
+        }
+
+        self.inner.write(&buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for Corrupting {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl Seek for Corrupting {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn verified_write() -> io::Result<()> {
+    let mut cursor = Cursor::new(Vec::new());
+
+    cursor.write_binary_verified(&Test::random())?;
+    cursor.write_binary_verified(&Test::random())?;
+
+    assert_eq!(cursor.position() as usize, 2 * std::mem::size_of::<Test>());
+    Ok(())
+}
+
+#[test]
+fn corrupted_write_fails() {
+    let mut sink = Corrupting {
+        inner: Cursor::new(Vec::new()),
+        corrupt_at: 1,
+    };
+
+    let error = sink.write_binary_verified(&Test::random()).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
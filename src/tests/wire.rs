@@ -0,0 +1,108 @@
+use crate::{assert_offset, wire_enum, wire_twin, BinaryRead, BinaryWrite, ValidationError, WireOption, WireTwin};
+use std::{io::{self, Cursor}, mem::{offset_of, size_of}, num::NonZeroU32};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Level {
+    Low,
+    High,
+}
+
+wire_enum!(Level: u16 { Low = 10, High = 20 });
+
+#[derive(Debug, PartialEq)]
+struct Limits {
+    level: Level,
+    max: Option<NonZeroU32>,
+}
+
+wire_twin!(Limits => LimitsWire {
+    level: Level,
+    max: Option<NonZeroU32>,
+});
+
+#[derive(Debug, PartialEq)]
+struct Account {
+    active: bool,
+    name: String,
+    limits: [Limits; 2],
+    id: u64,
+}
+
+mod short_name {
+    use crate::ValidationError;
+
+    pub fn to_wire(value: &String) -> [u8; 8] {
+        let mut wire = [0; 8];
+        wire[..value.len()].copy_from_slice(value.as_bytes());
+        wire
+    }
+
+    pub fn from_wire(wire: [u8; 8]) -> Result<String, ValidationError> {
+        let len = wire.iter().position(|b| *b == 0).unwrap_or(8);
+        String::from_utf8(wire[..len].to_vec()).map_err(|_| ValidationError::new(0, "name is not UTF-8"))
+    }
+}
+
+wire_twin!(Account => AccountWire {
+    active: bool,
+    #[with = short_name]
+    name: String as [u8; 8],
+    limits: [Limits; 2],
+    id: u64,
+});
+
+// The twin is laid out in declaration order, like any repr(C) struct.
+assert_offset!(AccountWire, name, 1);
+assert_offset!(AccountWire, limits, 12);
+assert_offset!(AccountWire, id, 40);
+
+fn account() -> Account {
+    Account {
+        active: true,
+        name: "ferris".to_string(),
+        limits: [
+            Limits { level: Level::Low, max: NonZeroU32::new(5) },
+            Limits { level: Level::High, max: None },
+        ],
+        id: 42,
+    }
+}
+
+#[test]
+fn wire_round_trip() -> io::Result<()> {
+    let mut buffer = Vec::new();
+    buffer.write_binary_wire(&account())?;
+    assert_eq!(buffer.len(), size_of::<AccountWire>());
+
+    let wire = account().to_wire();
+    assert_eq!(&wire.name, b"ferris\0\0");
+    assert_eq!(wire.limits[1].level, 20);
+    assert_eq!(wire.limits[1].max, WireOption { present: false, value: 0 });
+
+    assert_eq!(Cursor::new(buffer).read_binary_wire::<Account>()?, account());
+    Ok(())
+}
+
+#[test]
+fn invalid_wire_points_at_field() {
+    let mut wire = account().to_wire();
+    wire.limits[1].level = 30;
+
+    let level = offset_of!(AccountWire, limits) + size_of::<LimitsWire>() + offset_of!(LimitsWire, level);
+    let error = Account::from_wire(wire).unwrap_err();
+    assert_eq!(error, ValidationError { offset: level, field: Some("level"), reason: "unknown discriminant for Level" });
+
+    let mut wire = account().to_wire();
+    wire.limits[0].max.value = 0;
+    let error = Account::from_wire(wire).unwrap_err();
+    assert_eq!(error.field, Some("value"));
+
+    // Bytes that aren't a valid twin are rejected before converting it.
+    let mut buffer = Vec::new();
+    buffer.write_binary_wire(&account()).unwrap();
+    buffer[0] = 2;
+
+    let error = Cursor::new(buffer).read_binary_wire::<Account>().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.get_ref().and_then(|e| e.downcast_ref::<ValidationError>()).unwrap().field, Some("active"));
+}
@@ -0,0 +1,93 @@
+use std::{error::Error, fmt, io};
+use crate::{iter::read_up_to, BinaryRead};
+
+/// First byte of the binary form read by [read_binary_or_text], followed by the raw record.
+///
+/// It's never the first byte of UTF-8 text, so the two forms can't be confused.
+///
+/// [read_binary_or_text]: crate::BinaryRead::read_binary_or_text
+pub const BINARY_MARKER: u8 = 0xFF;
+
+/// Types that can be populated from `key=value` pairs, the text form read by
+/// [read_binary_or_text].
+///
+/// This trait should be implemented with the [text_pairs] macro.
+///
+/// [read_binary_or_text]: crate::BinaryRead::read_binary_or_text
+/// [text_pairs]: crate::text_pairs
+pub trait FromTextPairs {
+    /// Sets the field named `key` from its textual `value`, returning why it couldn't if so,
+    /// unknown keys included.
+    fn set_pair(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// Error carried by the `InvalidData` [io::Error] returned by [read_binary_or_text] when a line
+/// of the text form can't be applied.
+///
+/// [io::Error]: std::io::Error
+/// [read_binary_or_text]: crate::BinaryRead::read_binary_or_text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPair {
+    /// Number of the offending line, starting at 1.
+    pub line: usize,
+    /// Key of the offending line, empty if it has no `=`.
+    pub key: String,
+    /// What was wrong with the line.
+    pub reason: String,
+}
+
+impl fmt::Display for InvalidPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.key.is_empty() {
+            true => write!(f, "invalid line {}: {}", self.line, self.reason),
+            false => write!(f, "invalid pair `{}` on line {}: {}", self.key, self.line, self.reason)
+        }
+    }
+}
+
+impl Error for InvalidPair {}
+
+/// Reads the binary or text form of a `T`, as described in [read_binary_or_text].
+///
+/// [read_binary_or_text]: crate::BinaryRead::read_binary_or_text
+pub(crate) fn read_binary_or_text<T, R>(reader: &mut R) -> io::Result<T>
+where
+    T: Default + FromTextPairs,
+    R: BinaryRead + ?Sized
+{
+    let mut first = [0];
+    let len = read_up_to(reader, &mut first)?;
+
+    if first[..len] == [BINARY_MARKER] {
+        return reader.read_binary();
+    }
+
+    let mut text = first[..len].to_vec();
+    reader.read_to_end(&mut text)?;
+
+    let text = String::from_utf8(text)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("text form is not UTF-8: {e}")))?;
+
+    let mut item = T::default();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |key: &str, reason: String| io::Error::new(
+            io::ErrorKind::InvalidData,
+            InvalidPair { line: index + 1, key: key.to_string(), reason }
+        );
+
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| invalid("", "expected a `key=value` pair".to_string()))?;
+        let key = key.trim();
+
+        item.set_pair(key, value.trim()).map_err(|reason| invalid(key, reason))?;
+    }
+
+    Ok(item)
+}
@@ -0,0 +1,229 @@
+use std::{io::{self, Write}, thread, time::{Duration, Instant}};
+
+/// Source of time for a [ThrottledWriter], which it reads and sleeps on.
+///
+/// [SystemClock] is the real one, other implementations let tests drive the pacing without
+/// actually waiting.
+///
+/// [ThrottledWriter]: ThrottledWriter
+/// [SystemClock]: SystemClock
+pub trait Clock {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Blocks the current thread for `duration`.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system clock, sleeping with [thread::sleep].
+///
+/// [thread::sleep]: std::thread::sleep
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+/// A [Write] adapter capping throughput to a number of bytes per second, for streaming records
+/// over a shared link without starving other traffic.
+///
+/// Pacing follows a token bucket: the bucket fills at `bytes_per_second` up to `burst` bytes,
+/// and every byte written takes one token from it. A write finding too few tokens sleeps until
+/// there are enough, and is then handed whole to the underlying writer. Writes larger than the
+/// burst only wait for a full bucket and leave it in debt, which the following writes pay off,
+/// so the average rate holds without ever breaking a write in pieces. A record written with
+/// [write_binary] is thus delayed before it starts, never stalled in the middle.
+///
+/// The rate can be changed at any time with [set_rate], and [delayed_bytes] and [delay] tell
+/// how much of the output was held back and for how long.
+///
+/// # Panics
+///
+/// The constructors and [set_rate] panic if `bytes_per_second` is zero.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use binext::{BinaryWrite, ThrottledWriter};
+/// use std::{io, net::TcpStream};
+///
+/// fn main() -> io::Result<()> {
+///     let stream = TcpStream::connect("10.0.0.2:9000")?;
+///     // 1 MiB/s on average, at most 64 KiB at once.
+///     let mut writer = ThrottledWriter::new(stream, 1 << 20, 64 << 10);
+///
+///     for _ in 0..1024 {
+///         writer.write_binary(&[0u64; 1024])?;
+///     }
+///
+///     println!("{} bytes held back for {:?}", writer.delayed_bytes(), writer.delay());
+///     Ok(())
+/// }
+/// ```
+///
+/// [Write]: std::io::Write
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [set_rate]: ThrottledWriter::set_rate
+/// [delayed_bytes]: ThrottledWriter::delayed_bytes
+/// [delay]: ThrottledWriter::delay
+pub struct ThrottledWriter<W, C = SystemClock> {
+    inner: W,
+    clock: C,
+    bytes_per_second: u64,
+    burst: u64,
+    /// Tokens in the bucket, negative while in debt after a write larger than the burst.
+    tokens: f64,
+    last_refill: Instant,
+    written: u64,
+    delayed_bytes: u64,
+    delay: Duration,
+}
+
+impl<W: Write> ThrottledWriter<W> {
+    /// Creates a new writer over `inner`, writing `bytes_per_second` on average and at most
+    /// `burst` bytes at once, the bucket starting full.
+    pub fn new(inner: W, bytes_per_second: u64, burst: u64) -> Self {
+        Self::with_clock(inner, bytes_per_second, burst, SystemClock)
+    }
+}
+
+impl<W: Write, C: Clock> ThrottledWriter<W, C> {
+    /// Creates a new writer over `inner` like [new], reading and sleeping on `clock`.
+    ///
+    /// [new]: ThrottledWriter::new
+    pub fn with_clock(inner: W, bytes_per_second: u64, burst: u64, clock: C) -> Self {
+        assert!(bytes_per_second > 0, "the rate must be positive");
+
+        let burst = burst.max(1);
+
+        Self {
+            inner,
+            last_refill: clock.now(),
+            clock,
+            bytes_per_second,
+            burst,
+            tokens: burst as f64,
+            written: 0,
+            delayed_bytes: 0,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Changes the rate and burst size, taking effect from the next write.
+    ///
+    /// The tokens gathered at the previous rate are kept, up to the new burst size.
+    pub fn set_rate(&mut self, bytes_per_second: u64, burst: u64) {
+        assert!(bytes_per_second > 0, "the rate must be positive");
+
+        self.refill();
+        self.bytes_per_second = bytes_per_second;
+        self.burst = burst.max(1);
+        self.tokens = self.tokens.min(self.burst as f64);
+    }
+
+    /// Returns the rate, in bytes per second.
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// Returns the burst size, in bytes.
+    pub fn burst(&self) -> u64 {
+        self.burst
+    }
+
+    /// Returns the number of bytes written.
+    pub fn bytes_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the number of bytes whose write had to wait for tokens.
+    pub fn delayed_bytes(&self) -> u64 {
+        self.delayed_bytes
+    }
+
+    /// Returns the total time spent waiting for tokens.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Bytes written straight to it are not paced.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this writer, returning the underlying one.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Adds the tokens gathered since the last refill.
+    fn refill(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(self.last_refill);
+
+        self.last_refill = now;
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.bytes_per_second as f64)
+            .min(self.burst as f64);
+    }
+}
+
+impl<W: Write, C: Clock> Write for ThrottledWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return self.inner.write(buf);
+        }
+
+        self.refill();
+
+        // Writes larger than the bucket can only wait for it to be full.
+        let needed = (buf.len() as u64).min(self.burst) as f64;
+
+        let delayed = self.tokens < needed;
+
+        if delayed {
+            let wait = Duration::from_secs_f64((needed - self.tokens) / self.bytes_per_second as f64);
+
+            self.clock.sleep(wait);
+            self.delay += wait;
+            self.refill();
+        }
+
+        let written = self.inner.write(buf)?;
+        self.tokens -= written as f64;
+        self.written += written as u64;
+
+        if delayed {
+            self.delayed_bytes += written as u64;
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
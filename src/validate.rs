@@ -0,0 +1,268 @@
+use std::{error::Error, fmt, io, mem::size_of, num::*};
+
+/// Error returned when a byte pattern is not a valid instance of a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ValidationError {
+    /// Offset, from the start of the validated bytes, of the offending value.
+    pub offset: usize,
+    /// Name of the offending field, if known.
+    pub field: Option<&'static str>,
+    /// What was wrong with the value.
+    pub reason: &'static str,
+}
+
+impl ValidationError {
+    /// Creates an error for the value starting at `offset`.
+    pub fn new(offset: usize, reason: &'static str) -> Self {
+        Self {
+            offset,
+            field: None,
+            reason,
+        }
+    }
+
+    /// Rebases this error into the field `name` found at `offset` of a containing type.
+    ///
+    /// The innermost field name is kept, so errors coming from nested structs point at the
+    /// field that actually failed.
+    pub fn in_field(self, name: &'static str, offset: usize) -> Self {
+        Self {
+            offset: offset + self.offset,
+            field: self.field.or(Some(name)),
+            reason: self.reason,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field {
+            Some(field) => write!(f, "invalid field `{field}` at offset {}: {}", self.offset, self.reason),
+            None => write!(f, "invalid value at offset {}: {}", self.offset, self.reason)
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+impl From<ValidationError> for io::Error {
+    fn from(e: ValidationError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Types which can check whether a byte pattern is a valid instance of themselves.
+///
+/// Most plain data types accept any byte pattern, but some have validity invariants: a `bool`
+/// must be `0` or `1`, a `char` must be a valid unicode scalar value and the `NonZero*` integers
+/// must never be zero. Reading bytes breaking those invariants with [read_binary] is undefined
+/// behaviour, so structs containing such fields should implement this trait and be read with
+/// [read_binary_validated], which checks the bytes before any value is produced.
+///
+/// Note that `Option<NonZero*>` accepts every byte pattern, since zero is its `None` niche, so it
+/// is usually the type to pick when a zero value may legitimately appear in the data.
+///
+/// Pointers, function pointers in particular, deliberately don't implement this trait, so no
+/// struct holding them can be made `Validate` with the [validate] macro. An address only means
+/// something within the process that wrote it: read in another process, a function pointer
+/// would be an arbitrary jump target chosen by the data, which is never a valid value to accept.
+///
+/// # Examples
+///
+/// ```rust
+/// use binext::{BinaryRead, Validate, ValidationError, validate_field};
+/// use std::{io::{self, Cursor}, mem::offset_of, num::NonZeroU32};
+///
+/// #[repr(C)]
+/// #[derive(Debug)]
+/// struct Packet {
+///     id: NonZeroU32,
+///     len: u32
+/// }
+///
+/// impl Validate for Packet {
+///     fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+///         validate_field::<NonZeroU32>(bytes, offset_of!(Packet, id), "id")
+///     }
+/// }
+///
+/// fn main() {
+///     // An all-zero id would be undefined behaviour, so it is rejected instead.
+///     let mut cursor = Cursor::new([0u8; 8]);
+///     let error = cursor.read_binary_validated::<Packet>().unwrap_err();
+///
+///     assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+/// }
+/// ```
+///
+/// A struct with a function pointer field is rejected:
+///
+/// ```rust,compile_fail,E0277
+/// use binext::validate;
+///
+/// #[repr(C)]
+/// struct Plugin {
+///     id: u32,
+///     init: extern "C" fn()
+/// }
+///
+/// validate!(Plugin {
+///     id: u32,
+///     init: extern "C" fn(),
+/// });
+/// ```
+///
+/// Neither can types owning or borrowing memory, like `String`, `Vec` or `&str`, whose bytes
+/// are only a pointer to it. The compile error names them as not binary-safe, pointing at the
+/// type of the field, and suggests a fixed size `[u8; N]` in their place:
+///
+/// ```rust,compile_fail,E0277
+/// use binext::validate;
+///
+/// #[repr(C)]
+/// struct User {
+///     id: u32,
+///     name: String
+/// }
+///
+/// validate!(User {
+///     id: u32,
+///     name: String,
+/// });
+/// ```
+///
+/// [read_binary]: crate::BinaryRead::read_binary
+/// [read_binary_validated]: crate::BinaryRead::read_binary_validated
+/// [validate]: crate::validate
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not binary-safe, it can't be validated from raw bytes",
+    label = "not binary-safe",
+    note = "`String`, `Vec`, `&str` and other types owning or borrowing memory only hold a pointer to it, \
+            which means nothing once written out; use a fixed size `[u8; N]` instead"
+)]
+pub trait Validate {
+    /// Checks that `bytes`, which are exactly `size_of::<Self>()` long, form a valid `Self`.
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError>;
+
+    /// Returns whether `bytes` have the size of `Self` and form a valid instance of it.
+    ///
+    /// This is a cheap precheck over candidate bytes, which never constructs the value.
+    fn is_valid_bytes(bytes: &[u8]) -> bool
+    where
+        Self: Sized
+    {
+        bytes.len() == size_of::<Self>() && Self::validate_bytes(bytes).is_ok()
+    }
+
+    /// Computes the checksum fields of the `Self` held in `bytes` and stores them, before it's
+    /// written with [write_binary_with_checksums].
+    ///
+    /// Implementations generated by [validate] stamp the fields marked with `#[checksum = ...]`,
+    /// in declaration order. The default implementation does nothing.
+    ///
+    /// [write_binary_with_checksums]: crate::BinaryWrite::write_binary_with_checksums
+    /// [validate]: crate::validate
+    fn stamp_checksums(bytes: &mut [u8]) {
+        let _ = bytes;
+    }
+}
+
+/// Validates the field of type `F` named `name` found at `offset` of `bytes`.
+///
+/// This is a helper for implementing [Validate] on structs, the returned error points at the
+/// field within the whole struct.
+///
+/// [Validate]: Validate
+pub fn validate_field<F: Validate>(bytes: &[u8], offset: usize, name: &'static str) -> Result<(), ValidationError> {
+    let field = bytes.get(offset..offset + size_of::<F>())
+        .ok_or_else(|| ValidationError::new(offset, "field out of bounds").in_field(name, 0))?;
+
+    F::validate_bytes(field).map_err(|e| e.in_field(name, offset))
+}
+
+macro_rules! always_valid {
+    ($($ty: ty),*) => {
+        $(
+            impl Validate for $ty {
+                fn validate_bytes(_: &[u8]) -> Result<(), ValidationError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+always_valid!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, (), std::marker::PhantomPinned);
+
+// Zero sized, so there are no bytes to check, and no bounds are needed on T.
+impl<T: ?Sized> Validate for std::marker::PhantomData<T> {
+    fn validate_bytes(_: &[u8]) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+macro_rules! non_zero {
+    ($($ty: ty),*) => {
+        $(
+            impl Validate for $ty {
+                fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+                    if bytes.iter().all(|b| *b == 0) {
+                        Err(ValidationError::new(0, concat!("zero is not a valid ", stringify!($ty))))
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+
+            // Zero is the `None` niche, so every byte pattern is valid.
+            impl Validate for Option<$ty> {
+                fn validate_bytes(_: &[u8]) -> Result<(), ValidationError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+non_zero!(
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize
+);
+
+impl Validate for bool {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        match bytes[0] {
+            0 | 1 => Ok(()),
+            _ => Err(ValidationError::new(0, "bool must be 0 or 1"))
+        }
+    }
+}
+
+impl Validate for char {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        let value = u32::from_ne_bytes(bytes.try_into().unwrap());
+
+        match char::from_u32(value) {
+            Some(_) => Ok(()),
+            None => Err(ValidationError::new(0, "not a valid unicode scalar value"))
+        }
+    }
+}
+
+impl<T: Validate, const N: usize> Validate for [T; N] {
+    fn validate_bytes(bytes: &[u8]) -> Result<(), ValidationError> {
+        if size_of::<T>() == 0 {
+            return Ok(());
+        }
+
+        bytes.chunks_exact(size_of::<T>())
+            .enumerate()
+            .try_for_each(|(index, element)| {
+                T::validate_bytes(element).map_err(|e| ValidationError {
+                    offset: index * size_of::<T>() + e.offset,
+                    ..e
+                })
+            })
+    }
+}
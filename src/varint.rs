@@ -0,0 +1,48 @@
+use std::io::{self, Read, Write};
+
+/// Maximum length of a varint holding a `u64`.
+const MAX_LEN: usize = 10;
+
+/// Reads a varint: a little endian base 128 integer, seven bits per byte, where the high bit
+/// of each byte tells whether another one follows, as used by protobuf.
+pub(crate) fn read_varint<R: Read + ?Sized>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0u64;
+
+    for index in 0..MAX_LEN {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+
+        let bits = u64::from(byte[0] & 0x7F);
+        if index == MAX_LEN - 1 && bits > 1 {
+            break;
+        }
+
+        value |= bits << (7 * index);
+
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(io::Error::new(io::ErrorKind::InvalidData, "varint doesn't fit in a u64"))
+}
+
+/// Writes `value` as a varint, see [read_varint].
+pub(crate) fn write_varint<W: Write + ?Sized>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    let mut buf = [0; MAX_LEN];
+    let mut len = 0;
+
+    loop {
+        buf[len] = value as u8 & 0x7F;
+        value >>= 7;
+        len += 1;
+
+        if value == 0 {
+            break;
+        }
+
+        buf[len - 1] |= 0x80;
+    }
+
+    writer.write_all(&buf[..len])
+}
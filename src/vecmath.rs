@@ -0,0 +1,131 @@
+use std::mem::size_of;
+use ::glam::{
+    DMat2, DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Mat2, Mat3, Mat3A, Mat4,
+    Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec3A, Vec4
+};
+use crate::{NpyType, SwapBytes, Validate, ValidationError, Zeroable};
+
+macro_rules! glam_types {
+    ($($elem: ty: $($ty: ty),*;)*) => {
+        $($(
+            impl SwapBytes for $ty {
+                fn swap_bytes(bytes: &mut [u8]) {
+                    <[$elem; size_of::<$ty>() / size_of::<$elem>()]>::swap_bytes(bytes);
+                }
+            }
+
+            impl Validate for $ty {
+                fn validate_bytes(_: &[u8]) -> Result<(), ValidationError> {
+                    Ok(())
+                }
+            }
+
+            // SAFETY: every lane is a plain integer or float, for which all zeroes is zero.
+            unsafe impl Zeroable for $ty {}
+
+            impl NpyType for $ty {
+                const DESCR: &'static str = <$elem as NpyType>::DESCR;
+                const LEN: Option<usize> = Some(size_of::<$ty>() / size_of::<$elem>());
+            }
+        )*)*
+    };
+}
+
+glam_types! {
+    f32: Vec2, Vec3, Vec4, Quat, Mat2, Mat3, Mat4;
+    f64: DVec2, DVec3, DVec4, DQuat, DMat2, DMat3, DMat4;
+    i32: IVec2, IVec3, IVec4;
+    u32: UVec2, UVec3, UVec4;
+}
+
+// Vec3A and Mat3A pad every three lanes up to 16 bytes. Depending on how glam was built the
+// padding is a fourth SIMD lane or just padding, so only the first three lanes of each group are
+// swapped, and they have no npy dtype: use Vec3 and Mat3 for fields that leave the program.
+macro_rules! padded_glam_types {
+    ($($ty: ty),*) => {
+        $(
+            impl SwapBytes for $ty {
+                fn swap_bytes(bytes: &mut [u8]) {
+                    bytes.chunks_exact_mut(size_of::<Vec3A>())
+                        .for_each(|lanes| <[f32; 3]>::swap_bytes(&mut lanes[..size_of::<Vec3>()]));
+                }
+            }
+
+            impl Validate for $ty {
+                fn validate_bytes(_: &[u8]) -> Result<(), ValidationError> {
+                    Ok(())
+                }
+            }
+
+            // SAFETY: every lane is a float, for which all zeroes is positive zero.
+            unsafe impl Zeroable for $ty {}
+        )*
+    };
+}
+
+padded_glam_types!(Vec3A, Mat3A);
+
+macro_rules! unaligned {
+    ($($(#[$attr: meta])* $name: ident($ty: ty, [f32; $len: literal], $to: ident, $from: ident);)*) => {
+        $(
+            $(#[$attr])*
+            #[repr(C)]
+            #[derive(Debug, Clone, Copy, Default, PartialEq)]
+            pub struct $name(pub [f32; $len]);
+
+            impl From<$ty> for $name {
+                fn from(value: $ty) -> Self {
+                    Self(value.$to())
+                }
+            }
+
+            impl From<$name> for $ty {
+                fn from(value: $name) -> Self {
+                    <$ty>::$from(&value.0)
+                }
+            }
+
+            crate::zeroable!($name([f32; $len]));
+            crate::validate!($name([f32; $len]));
+            crate::swap_bytes!($name([f32; $len]));
+
+            impl NpyType for $name {
+                const DESCR: &'static str = <f32 as NpyType>::DESCR;
+                const LEN: Option<usize> = Some($len);
+            }
+        )*
+    };
+}
+
+unaligned! {
+    /// A [Vec4] with the alignment of an `f32`, for `float[4]` fields of C structs that aren't
+    /// 16-byte aligned.
+    ///
+    /// [Vec4] is aligned to 16 bytes so it can be loaded in a single SIMD register, which makes
+    /// structs holding it have a different layout than the same C struct with a `float[4]`.
+    /// This mirror has the layout of the C array and converts to and from [Vec4] with [From].
+    ///
+    /// [Vec4]: glam::Vec4
+    UnalignedVec4(Vec4, [f32; 4], to_array, from_slice);
+
+    /// A [Quat] with the alignment of an `f32`, stored as `x`, `y`, `z` and `w`.
+    ///
+    /// See [UnalignedVec4] for why it's needed.
+    ///
+    /// [Quat]: glam::Quat
+    UnalignedQuat(Quat, [f32; 4], to_array, from_slice);
+
+    /// A [Mat2] with the alignment of an `f32`, stored in column major order.
+    ///
+    /// See [UnalignedVec4] for why it's needed.
+    ///
+    /// [Mat2]: glam::Mat2
+    UnalignedMat2(Mat2, [f32; 4], to_cols_array, from_cols_array);
+
+    /// A [Mat4] with the alignment of an `f32`, stored in column major order.
+    ///
+    /// See [UnalignedVec4] for why it's needed.
+    ///
+    /// [Mat4]: glam::Mat4
+    UnalignedMat4(Mat4, [f32; 16], to_cols_array, from_cols_array);
+}
@@ -0,0 +1,139 @@
+use std::{mem::{offset_of, size_of}, num::*};
+use crate::{Validate, ValidationError, Zeroable};
+
+/// Types with a plain `#[repr(C)]` wire representation they convert to and from, for types
+/// that can't be written with [write_binary] themselves.
+///
+/// Structs that can't be `#[repr(C)]`, because of the layout the compiler picks for them or
+/// the types of their fields, implement it with the [wire_twin] macro, which generates a
+/// `#[repr(C)]` twin struct holding the wire representation of each field. They're then
+/// written with [write_binary_wire], which converts them to their twin first, and read with
+/// [read_binary_wire], which validates the twin and converts it back.
+///
+/// Integers, floats, `bool` and `char` are their own wire representation, and arrays convert
+/// each element. The `NonZero*` integers are written as their plain integer, so an `Option` of
+/// them is still one, and `Option<T>` is written as a [WireOption]. Fieldless enums are written
+/// as their discriminant when implemented with the [wire_enum] macro.
+///
+/// [write_binary]: crate::BinaryWrite::write_binary
+/// [wire_twin]: crate::wire_twin
+/// [write_binary_wire]: crate::BinaryWrite::write_binary_wire
+/// [read_binary_wire]: crate::BinaryRead::read_binary_wire
+/// [WireOption]: WireOption
+/// [wire_enum]: crate::wire_enum
+pub trait WireTwin: Sized {
+    /// The wire representation.
+    type Wire: Copy + Validate;
+
+    /// Converts a value into its wire representation.
+    fn to_wire(&self) -> Self::Wire;
+
+    /// Converts a wire representation back into a value, failing if it doesn't map to any.
+    fn from_wire(wire: Self::Wire) -> Result<Self, ValidationError>;
+}
+
+macro_rules! identity {
+    ($($ty: ty),*) => {
+        $(
+            impl WireTwin for $ty {
+                type Wire = $ty;
+
+                fn to_wire(&self) -> $ty {
+                    *self
+                }
+
+                fn from_wire(wire: $ty) -> Result<Self, ValidationError> {
+                    Ok(wire)
+                }
+            }
+        )*
+    };
+}
+
+identity!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char);
+
+macro_rules! non_zero {
+    ($($ty: ty: $int: ty),*) => {
+        $(
+            impl WireTwin for $ty {
+                type Wire = $int;
+
+                fn to_wire(&self) -> $int {
+                    self.get()
+                }
+
+                fn from_wire(wire: $int) -> Result<Self, ValidationError> {
+                    <$ty>::new(wire).ok_or(ValidationError::new(0, concat!("zero is not a valid ", stringify!($ty))))
+                }
+            }
+        )*
+    };
+}
+
+non_zero!(
+    NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroU128: u128, NonZeroUsize: usize,
+    NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64, NonZeroI128: i128, NonZeroIsize: isize
+);
+
+impl<T: WireTwin, const N: usize> WireTwin for [T; N] {
+    type Wire = [T::Wire; N];
+
+    fn to_wire(&self) -> Self::Wire {
+        self.each_ref().map(T::to_wire)
+    }
+
+    fn from_wire(wire: Self::Wire) -> Result<Self, ValidationError> {
+        let elements = wire.into_iter()
+            .enumerate()
+            .map(|(index, element)| T::from_wire(element).map_err(|e| ValidationError {
+                offset: index * size_of::<T::Wire>() + e.offset,
+                ..e
+            }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match elements.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("the wire array holds N elements")
+        }
+    }
+}
+
+/// The wire representation of an `Option`: whether there's a value, followed by it, all
+/// zeroes if there's none.
+///
+/// Its `present` flag is a `bool`, so bytes other than `0` and `1` fail to validate.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireOption<W> {
+    /// Whether there's a value.
+    pub present: bool,
+    /// The value, all zeroes if there's none.
+    pub value: W,
+}
+
+crate::validate!([W: Validate] WireOption<W> { present: bool, value: W });
+crate::zeroable!([W: Zeroable] WireOption<W> { present: bool, value: W });
+
+impl<T: WireTwin> WireTwin for Option<T>
+where
+    T::Wire: Zeroable
+{
+    type Wire = WireOption<T::Wire>;
+
+    fn to_wire(&self) -> Self::Wire {
+        match self {
+            Some(value) => WireOption { present: true, value: value.to_wire() },
+            None => WireOption { present: false, value: T::Wire::zeroed() }
+        }
+    }
+
+    fn from_wire(wire: Self::Wire) -> Result<Self, ValidationError> {
+        if !wire.present {
+            return Ok(None);
+        }
+
+        T::from_wire(wire.value)
+            .map(Some)
+            .map_err(|e| e.in_field("value", offset_of!(WireOption<T::Wire>, value)))
+    }
+}
@@ -0,0 +1,66 @@
+use std::{mem::MaybeUninit, num::*};
+
+/// Types for which the all-zero byte pattern is a valid value.
+///
+/// Integers, floats, `bool`, `char` and arrays of them are zeroable, as is `Option` of the
+/// `NonZero*` integers, whose zero is `None`. The `NonZero*` integers themselves and references
+/// are not. Neither are function pointers, even optional ones, since no pointer read from a file
+/// or another process can be trusted, see [Validate]. Structs are made zeroable with the
+/// [zeroable] macro, which checks every field.
+///
+/// ```rust,compile_fail,E0277
+/// use binext::zeroable;
+///
+/// #[repr(C)]
+/// struct Plugin {
+///     id: u32,
+///     init: Option<extern "C" fn()>
+/// }
+///
+/// zeroable!(Plugin {
+///     id: u32,
+///     init: Option<extern "C" fn()>,
+/// });
+/// ```
+///
+/// # Safety
+///
+/// Implementors must accept the all-zero byte pattern as a valid value.
+///
+/// [Validate]: crate::Validate
+/// [zeroable]: crate::zeroable
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not binary-safe, all zeroes aren't known to be a valid value of it",
+    label = "not binary-safe",
+    note = "`String`, `Vec`, `&str` and other types owning or borrowing memory hold a pointer, \
+            which can't be null; use a fixed size `[u8; N]` instead"
+)]
+pub unsafe trait Zeroable: Sized {
+    /// Returns a value with all of its bytes set to zero.
+    fn zeroed() -> Self {
+        // SAFETY: implementors guarantee all zeroes are a valid Self.
+        unsafe { MaybeUninit::zeroed().assume_init() }
+    }
+}
+
+macro_rules! zeroable {
+    ($($ty: ty),*) => {
+        $(
+            // SAFETY: zero is a valid value of the type.
+            unsafe impl Zeroable for $ty {}
+        )*
+    };
+}
+
+zeroable!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, (), std::marker::PhantomPinned,
+    Option<NonZeroU8>, Option<NonZeroU16>, Option<NonZeroU32>, Option<NonZeroU64>,
+    Option<NonZeroU128>, Option<NonZeroUsize>, Option<NonZeroI8>, Option<NonZeroI16>,
+    Option<NonZeroI32>, Option<NonZeroI64>, Option<NonZeroI128>, Option<NonZeroIsize>
+);
+
+// SAFETY: the array is valid if all its elements are.
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+
+// SAFETY: PhantomData has no bytes.
+unsafe impl<T: ?Sized> Zeroable for std::marker::PhantomData<T> {}
@@ -0,0 +1,4 @@
+fn main() {
+    let bytes = binext::to_array::<u64, 8>(&7);
+    assert_eq!(binext::from_array::<u64, 8>(bytes).unwrap(), 7);
+}
@@ -0,0 +1,12 @@
+use binext::assert_offset;
+
+#[repr(C)]
+struct Packet {
+    magic: u64,
+    len: u64,
+    flags: u32,
+}
+
+assert_offset!(Packet, flags, 12);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use binext::binary_layout;
+
+#[repr(C)]
+struct Record {
+    id: u64,
+    value: u32,
+    extra: u64,
+}
+
+binary_layout!(Record, size = 16, align = 8);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use binext::{validate, Checksum};
+
+#[repr(C)]
+struct Chunk {
+    data: [u8; 8],
+    crc: u16,
+}
+
+validate!(Chunk {
+    data: [u8; 8],
+    #[checksum = Checksum::Crc32.of(..8)]
+    crc: u16,
+});
+
+fn main() {}
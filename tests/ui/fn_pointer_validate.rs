@@ -0,0 +1,14 @@
+use binext::validate;
+
+#[repr(C)]
+struct Plugin {
+    id: u32,
+    init: extern "C" fn(),
+}
+
+validate!(Plugin {
+    id: u32,
+    init: extern "C" fn(),
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use binext::zeroable;
+
+#[repr(C)]
+struct Plugin {
+    id: u32,
+    init: Option<extern "C" fn()>,
+}
+
+zeroable!(Plugin {
+    id: u32,
+    init: Option<extern "C" fn()>,
+});
+
+fn main() {}
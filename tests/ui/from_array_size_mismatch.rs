@@ -0,0 +1,3 @@
+fn main() {
+    let _point = binext::from_array::<u64, 4>([0; 4]);
+}
@@ -0,0 +1,10 @@
+use binext::zeroable;
+use std::num::NonZeroU32;
+
+struct Record {
+    id: NonZeroU32,
+}
+
+zeroable!(Record { id: NonZeroU32 });
+
+fn main() {}
@@ -0,0 +1,8 @@
+use binext::zeroable;
+use std::num::NonZeroU32;
+
+struct Id(u16, NonZeroU32);
+
+zeroable!(Id(u16, NonZeroU32));
+
+fn main() {}
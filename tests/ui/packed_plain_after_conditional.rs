@@ -0,0 +1,16 @@
+use binext::packed;
+
+struct Frame {
+    flags: u8,
+    checksum: Option<u32>,
+    len: u16,
+}
+
+packed!(Frame {
+    flags: u8,
+    #[present_if = |frame| frame.flags & 0x01 != 0]
+    checksum: Option<u32>,
+    len: u16,
+});
+
+fn main() {}
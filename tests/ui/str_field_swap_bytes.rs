@@ -0,0 +1,14 @@
+use binext::swap_bytes;
+
+#[repr(C)]
+struct Label {
+    id: u32,
+    text: &'static str,
+}
+
+swap_bytes!(Label {
+    id: u32,
+    text: &'static str,
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use binext::validate;
+
+#[repr(C)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+validate!(User {
+    id: u32,
+    name: String,
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use binext::zeroable;
+
+#[repr(C)]
+struct Batch {
+    len: u32,
+    items: Vec<u32>,
+}
+
+zeroable!(Batch {
+    len: u32,
+    items: Vec<u32>,
+});
+
+fn main() {}